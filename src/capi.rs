@@ -0,0 +1,311 @@
+//! C ABI for linking from C/C++ microscopy software.
+//!
+//! Requires the `capi` feature. Build a shared or static library with
+//!
+//! ```text
+//! cargo rustc --release --features capi --crate-type cdylib
+//! cargo rustc --release --features capi --crate-type staticlib
+//! ```
+//!
+//! and declare the functions below in a C header. The ABI is stable: all
+//! functions are `extern "C"`, handles are opaque pointers, and every
+//! fallible call returns one of the `MRC_*` status codes (`MRC_OK == 0`).
+//!
+//! ```c
+//! typedef struct MrcHandle MrcHandle;
+//!
+//! int   mrc_open(const char *path, MrcHandle **out);
+//! void  mrc_close(MrcHandle *handle);
+//! int   mrc_header_get_nx(const MrcHandle *handle);
+//! int   mrc_header_get_ny(const MrcHandle *handle);
+//! int   mrc_header_get_nz(const MrcHandle *handle);
+//! int   mrc_header_get_mode(const MrcHandle *handle);
+//! int   mrc_header_get_voxel_size(const MrcHandle *handle, float out[3]);
+//! int   mrc_read_section(const MrcHandle *handle, size_t z, float *out, size_t len);
+//! const char *mrc_status_message(int status);
+//! ```
+//!
+//! Handles are not thread-safe for concurrent `mrc_close`; concurrent reads
+//! through the same handle are fine.
+//!
+//! A panic never unwinds into C: every function catches it and returns
+//! [`MRC_ERR_PANIC`] (the header getters return `-1`). This relies on the
+//! library being built with `panic = "unwind"`, the default.
+
+use crate::{Error, Reader};
+
+use std::ffi::{CStr, c_char, c_int};
+
+// ============================================================================
+// Status codes
+// ============================================================================
+
+/// Success.
+pub const MRC_OK: c_int = 0;
+/// A required pointer argument was null or a path was not valid UTF-8.
+pub const MRC_ERR_INVALID_ARGUMENT: c_int = 1;
+/// An I/O operation failed ([`Error::Io`], [`Error::Mmap`]).
+pub const MRC_ERR_IO: c_int = 2;
+/// The header is malformed ([`Error::InvalidHeader`], [`Error::InvalidHeaderDetailed`]).
+pub const MRC_ERR_INVALID_HEADER: c_int = 3;
+/// The file's mode or float format is not supported
+/// ([`Error::UnsupportedMode`], [`Error::UnsupportedFloatFormat`]).
+pub const MRC_ERR_UNSUPPORTED_MODE: c_int = 4;
+/// A requested region lies outside the volume ([`Error::BoundsError`]).
+pub const MRC_ERR_BOUNDS: c_int = 5;
+/// A caller buffer or voxel type does not match the data
+/// ([`Error::TypeMismatch`], [`Error::BlockShapeMismatch`], [`Error::ModeMismatch`]).
+pub const MRC_ERR_MISMATCH: c_int = 6;
+/// The file length disagrees with the header ([`Error::FileSizeMismatch`]).
+pub const MRC_ERR_FILE_SIZE: c_int = 7;
+/// Header statistics disagree with the data ([`Error::StatsMismatch`]).
+pub const MRC_ERR_STATS: c_int = 8;
+/// The file is not a volume stack ([`Error::NotAVolumeStack`]).
+pub const MRC_ERR_NOT_A_VOLUME_STACK: c_int = 9;
+/// A value does not fit the target type ([`Error::ValueOutOfRange`]).
+pub const MRC_ERR_VALUE_RANGE: c_int = 10;
+/// The data exceeds the address space ([`Error::TooLargeForPlatform`]).
+pub const MRC_ERR_TOO_LARGE: c_int = 11;
+/// The operation was cancelled ([`Error::Cancelled`]).
+pub const MRC_ERR_CANCELLED: c_int = 12;
+/// The call panicked inside the library; the handle may be left in an
+/// unspecified state and should be closed. Not an
+/// [`ErrorKind`](crate::ErrorKind).
+pub const MRC_ERR_PANIC: c_int = 98;
+/// Any other error.
+pub const MRC_ERR_OTHER: c_int = 99;
+
+/// Map an [`Error`] to its stable C status code, the
+/// [`ErrorKind::code`](crate::ErrorKind::code) of its kind.
+pub fn status_code(err: &Error) -> c_int {
+    err.code()
+}
+
+/// Static, NUL-terminated description of a status code.
+///
+/// The returned pointer is valid for the lifetime of the program and must
+/// not be freed.
+#[unsafe(no_mangle)]
+pub extern "C" fn mrc_status_message(status: c_int) -> *const c_char {
+    guard(c"unknown error".as_ptr(), || {
+        status_message(status).as_ptr()
+    })
+}
+
+/// Description behind [`mrc_status_message`].
+fn status_message(status: c_int) -> &'static CStr {
+    match status {
+        MRC_OK => c"ok",
+        MRC_ERR_INVALID_ARGUMENT => c"invalid argument",
+        MRC_ERR_IO => c"I/O error",
+        MRC_ERR_INVALID_HEADER => c"invalid MRC header",
+        MRC_ERR_UNSUPPORTED_MODE => c"unsupported mode",
+        MRC_ERR_BOUNDS => c"out of bounds",
+        MRC_ERR_MISMATCH => c"type or shape mismatch",
+        MRC_ERR_FILE_SIZE => c"file size does not match header",
+        MRC_ERR_STATS => c"header statistics do not match data",
+        MRC_ERR_NOT_A_VOLUME_STACK => c"not a volume stack",
+        MRC_ERR_VALUE_RANGE => c"value out of range",
+        MRC_ERR_TOO_LARGE => c"data too large for this platform",
+        MRC_ERR_CANCELLED => c"operation cancelled",
+        MRC_ERR_PANIC => c"internal panic",
+        _ => c"unknown error",
+    }
+}
+
+/// Run `f`, returning `on_panic` instead of unwinding across the C boundary.
+#[inline]
+fn guard<T>(on_panic: T, f: impl FnOnce() -> T) -> T {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).unwrap_or(on_panic)
+}
+
+// ============================================================================
+// Handle lifecycle
+// ============================================================================
+
+/// Opaque reader handle passed across the C boundary.
+pub struct MrcHandle {
+    reader: Reader,
+}
+
+/// Open an MRC file (auto-detecting compression) and store a new handle in `*out`.
+///
+/// On failure `*out` is set to null and a non-zero status is returned.
+///
+/// # Safety
+///
+/// `path` must be a valid NUL-terminated string and `out` a valid pointer
+/// to writable storage for one handle pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mrc_open(path: *const c_char, out: *mut *mut MrcHandle) -> c_int {
+    guard(MRC_ERR_PANIC, || {
+        if path.is_null() || out.is_null() {
+            return MRC_ERR_INVALID_ARGUMENT;
+        }
+        // SAFETY: `out` is non-null and the caller guarantees it is writable.
+        unsafe { *out = core::ptr::null_mut() };
+        // SAFETY: `path` is non-null and the caller guarantees NUL termination.
+        let Ok(path) = unsafe { CStr::from_ptr(path) }.to_str() else {
+            return MRC_ERR_INVALID_ARGUMENT;
+        };
+        match Reader::open(path) {
+            Ok(reader) => {
+                // SAFETY: as above.
+                unsafe { *out = Box::into_raw(Box::new(MrcHandle { reader })) };
+                MRC_OK
+            }
+            Err(e) => status_code(&e),
+        }
+    })
+}
+
+/// Release a handle returned by [`mrc_open`]. Passing null is a no-op.
+///
+/// # Safety
+///
+/// `handle` must be null or a pointer obtained from [`mrc_open`] that has
+/// not already been closed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mrc_close(handle: *mut MrcHandle) {
+    guard((), || {
+        if !handle.is_null() {
+            // SAFETY: the caller guarantees `handle` came from `Box::into_raw`
+            // in `mrc_open` and is closed at most once.
+            drop(unsafe { Box::from_raw(handle) });
+        }
+    });
+}
+
+// ============================================================================
+// Header accessors
+// ============================================================================
+
+/// Borrow the reader behind a handle, or `None` for null.
+///
+/// # Safety
+///
+/// `handle` must be null or a live handle from [`mrc_open`].
+#[inline]
+unsafe fn reader<'a>(handle: *const MrcHandle) -> Option<&'a Reader> {
+    // SAFETY: forwarded from the caller.
+    unsafe { handle.as_ref() }.map(|h| &h.reader)
+}
+
+macro_rules! header_getter {
+    ($(#[$doc:meta] $name:ident => $field:ident),* $(,)?) => {$(
+        #[$doc]
+        ///
+        /// Returns `-1` if `handle` is null or the call panicked.
+        ///
+        /// # Safety
+        ///
+        /// `handle` must be null or a live handle from [`mrc_open`].
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn $name(handle: *const MrcHandle) -> c_int {
+            guard(-1, || {
+                // SAFETY: forwarded from the caller.
+                match unsafe { reader(handle) } {
+                    Some(r) => r.header().$field,
+                    None => -1,
+                }
+            })
+        }
+    )*};
+}
+
+header_getter! {
+    /// Number of columns (`nx`).
+    mrc_header_get_nx => nx,
+    /// Number of rows (`ny`).
+    mrc_header_get_ny => ny,
+    /// Number of sections (`nz`).
+    mrc_header_get_nz => nz,
+    /// Raw MRC mode number.
+    mrc_header_get_mode => mode,
+}
+
+/// Write the voxel size in Å (`[x, y, z]`) into `out`.
+///
+/// # Safety
+///
+/// `handle` must be null or a live handle from [`mrc_open`], and `out`
+/// must be null or point to at least three writable `float`s.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mrc_header_get_voxel_size(
+    handle: *const MrcHandle,
+    out: *mut f32,
+) -> c_int {
+    guard(MRC_ERR_PANIC, || {
+        // SAFETY: forwarded from the caller.
+        let Some(r) = (unsafe { reader(handle) }) else {
+            return MRC_ERR_INVALID_ARGUMENT;
+        };
+        if out.is_null() {
+            return MRC_ERR_INVALID_ARGUMENT;
+        }
+        let v = r.header().voxel_size();
+        // SAFETY: the caller guarantees three writable floats at `out`.
+        unsafe { core::ptr::copy_nonoverlapping(v.as_ptr(), out, 3) };
+        MRC_OK
+    })
+}
+
+// ============================================================================
+// Data access
+// ============================================================================
+
+/// Read section `z` converted to `float` into `out`.
+///
+/// `len` is the capacity of `out` in elements and must equal `nx * ny`.
+/// Complex modes yield the real part; mode 0 is read as signed.
+///
+/// # Safety
+///
+/// `handle` must be null or a live handle from [`mrc_open`], and `out`
+/// must be null or point to `len` writable `float`s.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mrc_read_section(
+    handle: *const MrcHandle,
+    z: usize,
+    out: *mut f32,
+    len: usize,
+) -> c_int {
+    guard(MRC_ERR_PANIC, || {
+        // SAFETY: forwarded from the caller.
+        let Some(r) = (unsafe { reader(handle) }) else {
+            return MRC_ERR_INVALID_ARGUMENT;
+        };
+        if out.is_null() {
+            return MRC_ERR_INVALID_ARGUMENT;
+        }
+        let s = r.shape();
+        if len != s.nx * s.ny {
+            return MRC_ERR_MISMATCH;
+        }
+        match r.convert::<f32>().subregion([0, 0, z], [s.nx, s.ny, 1]) {
+            Ok(block) => {
+                // SAFETY: `block.data.len() == nx * ny == len` and the caller
+                // guarantees `len` writable floats at `out`.
+                unsafe { core::ptr::copy_nonoverlapping(block.data.as_ptr(), out, len) };
+                MRC_OK
+            }
+            Err(e) => status_code(&e),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guard_catches_panic() {
+        let prev = std::panic::take_hook();
+        std::panic::set_hook(Box::new(|_| {}));
+        let status = guard(MRC_ERR_PANIC, || -> c_int { panic!("boom") });
+        std::panic::set_hook(prev);
+        assert_eq!(status, MRC_ERR_PANIC);
+        assert_eq!(guard(MRC_ERR_PANIC, || MRC_OK), MRC_OK);
+        assert_eq!(status_message(MRC_ERR_PANIC), c"internal panic");
+    }
+}
@@ -0,0 +1,308 @@
+//! Cache-line-aligned byte buffers.
+//!
+//! A `Vec<u8>` is only guaranteed byte alignment, so whether its contents
+//! can be viewed as `&[f32]` — or loaded with aligned SIMD instructions —
+//! depends on the allocator. [`AlignedBuf`] always starts on a 64-byte
+//! boundary, which is what in-memory readers store voxel data in, so
+//! zero-copy typed access to them never falls back to a copy.
+//!
+//! [`AlignedBuf::zeroed_pages`] goes further and starts and ends the buffer
+//! on page boundaries, as page-locking calls such as `cudaHostRegister`
+//! require. A buffer never reallocates, so the address from
+//! [`as_raw_parts`](AlignedBuf::as_raw_parts) stays valid until it is dropped.
+
+use core::alloc::Layout;
+use core::ptr::NonNull;
+
+/// Alignment of every [`AlignedBuf`], in bytes (one cache line).
+pub const BUF_ALIGN: usize = 64;
+
+/// Alignment of [`AlignedBuf::zeroed_pages`] buffers: the largest base page
+/// size of the target (16 KiB on Apple silicon, 4 KiB elsewhere).
+#[cfg(all(target_vendor = "apple", target_arch = "aarch64"))]
+pub const PAGE_ALIGN: usize = 16 * 1024;
+/// Alignment of [`AlignedBuf::zeroed_pages`] buffers: the largest base page
+/// size of the target (16 KiB on Apple silicon, 4 KiB elsewhere).
+#[cfg(not(all(target_vendor = "apple", target_arch = "aarch64")))]
+pub const PAGE_ALIGN: usize = 4096;
+
+#[repr(C, align(64))]
+struct CacheLine([u8; BUF_ALIGN]);
+
+/// A fixed-length, heap-allocated byte buffer aligned to [`BUF_ALIGN`].
+///
+/// Dereferences to `[u8]`.
+///
+/// # Examples
+///
+/// ```
+/// use mrc::AlignedBuf;
+///
+/// let buf = AlignedBuf::from(&[1u8, 2, 3][..]);
+/// assert_eq!(&buf[..], &[1, 2, 3]);
+/// assert_eq!(buf.as_ptr() as usize % mrc::BUF_ALIGN, 0);
+/// ```
+pub struct AlignedBuf {
+    ptr: NonNull<u8>,
+    len: usize,
+    /// Size of the allocation; 0 when nothing is allocated.
+    cap: usize,
+    align: usize,
+}
+
+// SAFETY: `AlignedBuf` uniquely owns its allocation, like `Vec<u8>`.
+unsafe impl Send for AlignedBuf {}
+// SAFETY: shared access only hands out `&[u8]`.
+unsafe impl Sync for AlignedBuf {}
+
+impl AlignedBuf {
+    /// A zero-filled buffer of `len` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` overflows `isize` when rounded up to the alignment,
+    /// and aborts on allocation failure, like `vec![0u8; len]`.
+    pub fn zeroed(len: usize) -> Self {
+        Self::zeroed_with(len, BUF_ALIGN)
+    }
+
+    /// A zero-filled buffer of `len` bytes aligned to [`PAGE_ALIGN`], whose
+    /// allocation is padded with zeros to a whole number of pages
+    /// ([`capacity`](Self::capacity)), for pinning or page-locking.
+    ///
+    /// # Panics
+    ///
+    /// As [`zeroed`](Self::zeroed).
+    pub fn zeroed_pages(len: usize) -> Self {
+        Self::zeroed_with(len, PAGE_ALIGN)
+    }
+
+    fn zeroed_with(len: usize, align: usize) -> Self {
+        if len == 0 {
+            // No allocation, so an empty page buffer is only
+            // cache-line aligned.
+            return Self {
+                ptr: NonNull::<CacheLine>::dangling().cast(),
+                len,
+                cap: 0,
+                align,
+            };
+        }
+        let layout = Self::layout(len, align);
+        // SAFETY: `layout` has non-zero size.
+        let raw = unsafe { std::alloc::alloc_zeroed(layout) };
+        match NonNull::new(raw) {
+            Some(ptr) => Self {
+                ptr,
+                len,
+                cap: layout.size(),
+                align,
+            },
+            None => std::alloc::handle_alloc_error(layout),
+        }
+    }
+
+    fn layout(len: usize, align: usize) -> Layout {
+        let size = len.checked_next_multiple_of(align).unwrap_or(usize::MAX);
+        match Layout::from_size_align(size, align) {
+            Ok(layout) => layout,
+            Err(_) => panic!("AlignedBuf of {len} bytes exceeds isize::MAX"),
+        }
+    }
+
+    /// Length in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Bytes allocated: at least [`len`](Self::len) rounded up to the
+    /// [`alignment`](Self::alignment), and more for a buffer reused from a
+    /// [`BufferPool`](crate::BufferPool). The bytes past `len` are zero.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    /// Change the length to `len` within the existing allocation, keeping
+    /// the bytes past the new length zero. Returns `false`, leaving the
+    /// buffer unchanged, if `len` exceeds the [`capacity`](Self::capacity).
+    pub(crate) fn set_len_within(&mut self, len: usize) -> bool {
+        if len > self.cap {
+            return false;
+        }
+        if len < self.len {
+            self.as_mut_slice()[len..].fill(0);
+        }
+        self.len = len;
+        true
+    }
+
+    /// Alignment of the start of the buffer: [`BUF_ALIGN`], or
+    /// [`PAGE_ALIGN`] for [`zeroed_pages`](Self::zeroed_pages) buffers.
+    #[inline]
+    pub fn alignment(&self) -> usize {
+        self.align
+    }
+
+    /// `(pointer, len)` of the contents, for handing to foreign code.
+    ///
+    /// The pointer is valid for reads of `len` bytes until the buffer is
+    /// dropped; it never moves, since the buffer cannot grow. It is
+    /// dangling when `len` is 0.
+    #[inline]
+    pub fn as_raw_parts(&self) -> (*const u8, usize) {
+        (self.ptr.as_ptr(), self.len)
+    }
+
+    /// `(pointer, len)` of the contents for writing, as
+    /// [`as_raw_parts`](Self::as_raw_parts).
+    #[inline]
+    pub fn as_raw_parts_mut(&mut self) -> (*mut u8, usize) {
+        (self.ptr.as_ptr(), self.len)
+    }
+
+    /// `true` if the buffer holds no bytes.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The contents as a byte slice.
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        // SAFETY: `ptr` is valid for `len` initialised bytes (or dangling
+        // and aligned with `len == 0`).
+        unsafe { core::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// The contents as a mutable byte slice.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        // SAFETY: as in `as_slice`, and `&mut self` guarantees uniqueness.
+        unsafe { core::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl Drop for AlignedBuf {
+    fn drop(&mut self) {
+        if self.cap != 0 {
+            // SAFETY: allocated in `zeroed_with` with this exact layout.
+            unsafe { std::alloc::dealloc(self.ptr.as_ptr(), Self::layout(self.cap, self.align)) }
+        }
+    }
+}
+
+impl core::ops::Deref for AlignedBuf {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl core::ops::DerefMut for AlignedBuf {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.as_mut_slice()
+    }
+}
+
+impl Clone for AlignedBuf {
+    fn clone(&self) -> Self {
+        let mut buf = Self::zeroed_with(self.len, self.align);
+        buf.copy_from_slice(self);
+        buf
+    }
+}
+
+impl core::fmt::Debug for AlignedBuf {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AlignedBuf")
+            .field("len", &self.len)
+            .field("align", &self.align)
+            .finish()
+    }
+}
+
+impl PartialEq for AlignedBuf {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for AlignedBuf {}
+
+impl From<&[u8]> for AlignedBuf {
+    fn from(bytes: &[u8]) -> Self {
+        let mut buf = Self::zeroed(bytes.len());
+        buf.copy_from_slice(bytes);
+        buf
+    }
+}
+
+/// Copies the bytes; a `Vec`'s allocation cannot be re-aligned in place.
+impl From<Vec<u8>> for AlignedBuf {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::from(bytes.as_slice())
+    }
+}
+
+impl AsRef<[u8]> for AlignedBuf {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aligned_for_every_length() {
+        for len in [0, 1, 63, 64, 65, 4097] {
+            let buf = AlignedBuf::zeroed(len);
+            assert_eq!(buf.len(), len);
+            assert_eq!(buf.as_ptr() as usize % BUF_ALIGN, 0);
+            assert!(buf.iter().all(|&b| b == 0));
+        }
+    }
+
+    #[test]
+    fn copies_and_clones_contents() {
+        let src: Vec<u8> = (0..=200).collect();
+        let mut buf = AlignedBuf::from(src.clone());
+        assert_eq!(&buf[..], &src[..]);
+        buf[0] = 9;
+        let copy = buf.clone();
+        assert_eq!(copy, buf);
+        assert_ne!(copy.as_ptr(), buf.as_ptr());
+        assert_eq!(copy[0], 9);
+    }
+
+    #[test]
+    fn page_buffers_fill_whole_pages() {
+        let mut buf = AlignedBuf::zeroed_pages(PAGE_ALIGN + 1);
+        let (ptr, len) = buf.as_raw_parts();
+        assert_eq!((ptr as usize % PAGE_ALIGN, len), (0, PAGE_ALIGN + 1));
+        assert_eq!(buf.capacity(), 2 * PAGE_ALIGN);
+        buf[PAGE_ALIGN] = 7;
+        let copy = buf.clone();
+        assert_eq!(copy.alignment(), PAGE_ALIGN);
+        assert_eq!(copy.as_ptr() as usize % PAGE_ALIGN, 0);
+        assert_eq!(copy[PAGE_ALIGN], 7);
+        assert_eq!(AlignedBuf::zeroed_pages(0).capacity(), 0);
+    }
+
+    #[test]
+    fn length_changes_stay_within_allocation() {
+        let mut buf = AlignedBuf::from(&[1u8; 100][..]);
+        let cap = buf.capacity();
+        assert!(buf.set_len_within(10));
+        assert!(buf.set_len_within(cap));
+        assert_eq!(buf.len(), cap);
+        assert!(buf[10..].iter().all(|&b| b == 0));
+        assert!(!buf.set_len_within(cap + 1));
+    }
+}
@@ -2,11 +2,36 @@
 //!
 //! [`VolumeShape`] describes the dimensions of an MRC volume, and
 //! [`VoxelBlock`] is the universal container for a contiguous chunk of
-//! voxel data with a known 3D offset and shape.
+//! voxel data with a known 3D offset and shape. [`Axis`] names one of the
+//! three storage axes.
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+/// One of the three storage axes of a volume.
+///
+/// These are the column, row and section axes of the data as stored, which
+/// are the spatial X, Y and Z axes unless the header's `mapc/mapr/maps`
+/// says otherwise.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis {
+    /// Columns, the fastest-changing axis (`nx`).
+    X,
+    /// Rows (`ny`).
+    Y,
+    /// Sections, the slowest-changing axis (`nz`).
+    Z,
+}
+
+impl Axis {
+    /// Index into `[x, y, z]` arrays: 0, 1 or 2.
+    #[inline]
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
 /// Volume geometry in voxels.
 ///
 /// # Examples
@@ -261,6 +286,205 @@ impl<T> VoxelBlock<T> {
         self.offset == [0, 0, 0]
             && self.shape == [volume_shape.nx, volume_shape.ny, volume_shape.nz]
     }
+
+    /// Linear index of block-local voxel `(x, y, z)`, or `None` if outside.
+    #[inline]
+    fn local_index(&self, x: usize, y: usize, z: usize) -> Option<usize> {
+        let [sx, sy, sz] = self.shape;
+        (x < sx && y < sy && z < sz).then(|| (z * sy + y) * sx + x)
+    }
+
+    /// Voxel at block-local coordinates `(x, y, z)`, or `None` if out of bounds.
+    ///
+    /// Coordinates are relative to [`offset`](Self::offset). Indexing with
+    /// `block[(x, y, z)]` is the panicking equivalent.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mrc::VoxelBlock;
+    /// let block = VoxelBlock::new([0, 0, 0], [2, 2, 1], vec![1, 2, 3, 4]).unwrap();
+    /// assert_eq!(block.get(1, 1, 0), Some(&4));
+    /// assert_eq!(block.get(2, 0, 0), None);
+    /// assert_eq!(block[(0, 1, 0)], 3);
+    /// ```
+    #[inline]
+    pub fn get(&self, x: usize, y: usize, z: usize) -> Option<&T> {
+        self.data.get(self.local_index(x, y, z)?)
+    }
+
+    /// Mutable voxel at block-local coordinates `(x, y, z)`, or `None` if
+    /// out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, x: usize, y: usize, z: usize) -> Option<&mut T> {
+        let i = self.local_index(x, y, z)?;
+        self.data.get_mut(i)
+    }
+
+    /// Voxel at block-local `(x, y, z)` without bounds checks.
+    ///
+    /// # Safety
+    ///
+    /// `(x, y, z)` must be inside [`shape`](Self::shape) and `data` must
+    /// hold `sx * sy * sz` voxels, as [`new`](Self::new) ensures.
+    #[inline]
+    pub unsafe fn get_unchecked(&self, x: usize, y: usize, z: usize) -> &T {
+        let [sx, sy, _] = self.shape;
+        // SAFETY: upheld by the caller.
+        unsafe { self.data.get_unchecked((z * sy + y) * sx + x) }
+    }
+
+    /// Mutable voxel at block-local `(x, y, z)` without bounds checks.
+    ///
+    /// # Safety
+    ///
+    /// As for [`get_unchecked`](Self::get_unchecked).
+    #[inline]
+    pub unsafe fn get_unchecked_mut(&mut self, x: usize, y: usize, z: usize) -> &mut T {
+        let [sx, sy, _] = self.shape;
+        // SAFETY: upheld by the caller.
+        unsafe { self.data.get_unchecked_mut((z * sy + y) * sx + x) }
+    }
+
+    /// Store `value` at block-local coordinates `(x, y, z)`.
+    ///
+    /// # Errors
+    /// Returns [`crate::Error::BoundsError`] if the coordinates are outside
+    /// the block.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mrc::VoxelBlock;
+    /// let mut block = VoxelBlock::new([0, 0, 0], [2, 1, 1], vec![0u8; 2]).unwrap();
+    /// block.set(1, 0, 0, 7).unwrap();
+    /// assert_eq!(block.data, [0, 7]);
+    /// assert!(block.set(0, 1, 0, 7).is_err());
+    /// ```
+    pub fn set(&mut self, x: usize, y: usize, z: usize, value: T) -> Result<(), crate::Error> {
+        let shape = self.shape;
+        let slot = self.get_mut(x, y, z).ok_or(crate::Error::BoundsError {
+            offset: Some([x, y, z]),
+            shape: Some([1, 1, 1]),
+            volume: Some(shape),
+        })?;
+        *slot = value;
+        Ok(())
+    }
+
+    /// Block-local Z-section `z` as a row-major `sx * sy` slice, or `None`
+    /// if out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mrc::VoxelBlock;
+    /// let block = VoxelBlock::new([0, 0, 4], [2, 1, 2], vec![1, 2, 3, 4]).unwrap();
+    /// assert_eq!(block.section(1), Some(&[3, 4][..]));
+    /// assert_eq!(block.sections().count(), 2);
+    /// ```
+    #[inline]
+    pub fn section(&self, z: usize) -> Option<&[T]> {
+        let n = self.shape[0] * self.shape[1];
+        if z < self.shape[2] {
+            self.data.get(z * n..(z + 1) * n)
+        } else {
+            None
+        }
+    }
+
+    /// Mutable block-local Z-section `z`, or `None` if out of bounds.
+    #[inline]
+    pub fn section_mut(&mut self, z: usize) -> Option<&mut [T]> {
+        let n = self.shape[0] * self.shape[1];
+        if z < self.shape[2] {
+            self.data.get_mut(z * n..(z + 1) * n)
+        } else {
+            None
+        }
+    }
+
+    /// Iterate over the block's Z-sections, lowest `z` first.
+    pub fn sections(&self) -> impl ExactSizeIterator<Item = &[T]> + '_ {
+        let n = (self.shape[0] * self.shape[1]).max(1);
+        self.data.chunks_exact(n)
+    }
+
+    /// Iterate mutably over the block's Z-sections, lowest `z` first.
+    pub fn sections_mut(&mut self) -> impl ExactSizeIterator<Item = &mut [T]> + '_ {
+        let n = (self.shape[0] * self.shape[1]).max(1);
+        self.data.chunks_exact_mut(n)
+    }
+
+    #[cold]
+    #[track_caller]
+    fn index_oob(&self, (x, y, z): (usize, usize, usize)) -> ! {
+        panic!(
+            "voxel index ({x}, {y}, {z}) out of bounds for block of shape {:?}",
+            self.shape
+        )
+    }
+}
+
+impl<T: Copy + Into<f64>> VoxelBlock<T> {
+    /// Exact percentile `q` (`0.0..=100.0`) of the block's values.
+    ///
+    /// Same rule as [`Volume::percentile`](crate::Volume::percentile):
+    /// linear interpolation between ranks, NaN skipped, `None` for an
+    /// out-of-range `q` or no values.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mrc::VoxelBlock;
+    /// let block = VoxelBlock::new([0, 0, 0], [4, 1, 1], vec![3i16, 1, 4, 2]).unwrap();
+    /// assert_eq!(block.percentile(50.0), Some(2.5));
+    /// ```
+    pub fn percentile(&self, q: f64) -> Option<f64> {
+        crate::engine::stats::percentile(self.data.iter().copied(), q)
+    }
+}
+
+#[cfg(feature = "f16")]
+impl VoxelBlock<crate::f16> {
+    /// Iterate over the block's voxels widened to `f32`, without copying
+    /// the block.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mrc::{VoxelBlock, f16};
+    /// let block = VoxelBlock::new([0, 0, 0], [2, 1, 1], vec![f16::from_f32(0.5), f16::ONE]).unwrap();
+    /// assert_eq!(block.iter_as_f32().collect::<Vec<_>>(), [0.5, 1.0]);
+    /// ```
+    pub fn iter_as_f32(&self) -> impl ExactSizeIterator<Item = f32> + '_ {
+        self.data.iter().map(|v| v.to_f32())
+    }
+}
+
+/// Block-local `(x, y, z)` indexing; panics when out of bounds, like slices.
+impl<T> core::ops::Index<(usize, usize, usize)> for VoxelBlock<T> {
+    type Output = T;
+
+    #[inline]
+    #[track_caller]
+    fn index(&self, (x, y, z): (usize, usize, usize)) -> &T {
+        match self.local_index(x, y, z) {
+            Some(i) => &self.data[i],
+            None => self.index_oob((x, y, z)),
+        }
+    }
+}
+
+impl<T> core::ops::IndexMut<(usize, usize, usize)> for VoxelBlock<T> {
+    #[inline]
+    #[track_caller]
+    fn index_mut(&mut self, (x, y, z): (usize, usize, usize)) -> &mut T {
+        match self.local_index(x, y, z) {
+            Some(i) => &mut self.data[i],
+            None => self.index_oob((x, y, z)),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -346,4 +570,21 @@ mod tests {
         let offset_block = VoxelBlock::new([1, 0, 0], [3, 4, 4], vec![0.0f32; 48]).unwrap();
         assert!(!offset_block.is_full_volume(&vs));
     }
+
+    #[test]
+    fn voxel_block_tuple_indexing() {
+        let mut block = VoxelBlock::new([5, 5, 5], [3, 2, 2], (0..12).collect()).unwrap();
+        assert_eq!(block[(2, 1, 1)], 11);
+        assert_eq!(block[(1, 0, 1)], 7);
+        block[(0, 1, 0)] = 100;
+        assert_eq!(block.data[3], 100);
+        assert_eq!(block.get(3, 0, 0), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn voxel_block_tuple_index_out_of_bounds_panics() {
+        let block = VoxelBlock::new([0, 0, 0], [2, 2, 2], vec![0u8; 8]).unwrap();
+        let _ = block[(0, 0, 2)];
+    }
 }
@@ -28,31 +28,23 @@ use crate::mode::{Float32Complex, Int16Complex};
 ///
 /// let value: i16 = 0x1234;
 /// let mut bytes = [0u8; 2];
-/// value.encode(&mut bytes, 0, FileEndian::LittleEndian);
-/// let decoded = i16::decode(&bytes, 0, FileEndian::LittleEndian);
+/// value.to_bytes(&mut bytes, 0, FileEndian::LittleEndian);
+/// let decoded = i16::from_bytes(&bytes, 0, FileEndian::LittleEndian);
 /// assert_eq!(value, decoded);
 /// ```
 pub trait EndianCodec: Sized {
     /// Size in bytes for one value of this type
     const BYTE_SIZE: usize;
 
+    /// Size in bytes of each independently byte-swapped unit: the whole
+    /// value for scalars, one component for complex types.
+    const SWAP_WIDTH: usize = Self::BYTE_SIZE;
+
     /// Decode: bytes → value (read from bytes at offset)
     fn from_bytes(bytes: &[u8], offset: usize, endian: FileEndian) -> Self;
 
     /// Encode: value → bytes (write to bytes at offset)
     fn to_bytes(&self, bytes: &mut [u8], offset: usize, endian: FileEndian);
-
-    /// Decode alias: bytes → value
-    #[inline]
-    fn decode(bytes: &[u8], offset: usize, endian: FileEndian) -> Self {
-        Self::from_bytes(bytes, offset, endian)
-    }
-
-    /// Encode alias: value → bytes
-    #[inline]
-    fn encode(&self, bytes: &mut [u8], offset: usize, endian: FileEndian) {
-        self.to_bytes(bytes, offset, endian)
-    }
 }
 
 // ============================================================================
@@ -112,6 +104,7 @@ impl EndianCodec for i8 {
 
 impl EndianCodec for Int16Complex {
     const BYTE_SIZE: usize = 4;
+    const SWAP_WIDTH: usize = 2;
 
     #[inline]
     fn from_bytes(bytes: &[u8], offset: usize, endian: FileEndian) -> Self {
@@ -130,6 +123,7 @@ impl EndianCodec for Int16Complex {
 
 impl EndianCodec for Float32Complex {
     const BYTE_SIZE: usize = 8;
+    const SWAP_WIDTH: usize = 4;
 
     #[inline]
     fn from_bytes(bytes: &[u8], offset: usize, endian: FileEndian) -> Self {
@@ -171,6 +165,70 @@ impl EndianCodec for crate::f16 {
     }
 }
 
+// ============================================================================
+// Public per-value codec traits
+// ============================================================================
+
+/// Read one value from the start of a byte slice in a file's byte order.
+///
+/// Implemented for every voxel scalar — `i8`, `i16`, `u16`, `f32`, `f16`
+/// (feature `f16`) — and the complex types, so streaming code can decode
+/// values it pulled out of a file itself. A value takes
+/// `size_of::<Self>()` bytes.
+///
+/// # Examples
+///
+/// ```
+/// use mrc::{DecodeFromFile, FileEndian};
+///
+/// assert_eq!(i16::decode(FileEndian::BigEndian, &[0x01, 0x02]), 0x0102);
+/// assert_eq!(f32::decode(FileEndian::LittleEndian, &1.5f32.to_le_bytes()), 1.5);
+/// ```
+pub trait DecodeFromFile: Sized {
+    /// Decode a value from the first bytes of `bytes`, stored in `endian`
+    /// order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is shorter than one value.
+    fn decode(endian: FileEndian, bytes: &[u8]) -> Self;
+}
+
+/// Write one value to the start of a byte slice in a file's byte order;
+/// the counterpart of [`DecodeFromFile`].
+///
+/// # Examples
+///
+/// ```
+/// use mrc::{EncodeToFile, FileEndian};
+///
+/// let mut out = [0u8; 2];
+/// 0x0102u16.encode(FileEndian::BigEndian, &mut out);
+/// assert_eq!(out, [0x01, 0x02]);
+/// ```
+pub trait EncodeToFile {
+    /// Encode `self` into the first bytes of `bytes` in `endian` order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is shorter than one value.
+    fn encode(&self, endian: FileEndian, bytes: &mut [u8]);
+}
+
+impl<T: crate::Voxel> DecodeFromFile for T {
+    #[inline]
+    fn decode(endian: FileEndian, bytes: &[u8]) -> Self {
+        T::from_bytes(bytes, 0, endian)
+    }
+}
+
+impl<T: crate::Voxel> EncodeToFile for T {
+    #[inline]
+    fn encode(&self, endian: FileEndian, bytes: &mut [u8]) {
+        self.to_bytes(bytes, 0, endian)
+    }
+}
+
 // ============================================================================
 // Slice Operations - Decode
 // ============================================================================
@@ -230,7 +288,7 @@ pub fn decode_into<T: EndianCodec + Copy>(
     // Non-native endian: byte-swap raw bytes into the output slice.
     #[cfg(feature = "simd")]
     {
-        match T::BYTE_SIZE {
+        match T::SWAP_WIDTH {
             2 => crate::engine::simd::swap_2byte_simd(bytes, unsafe {
                 core::slice::from_raw_parts_mut(values.as_mut_ptr() as *mut u8, bytes.len())
             }),
@@ -296,7 +354,7 @@ pub fn decode_slice<T: EndianCodec + Send + Copy>(
     {
         let dst_bytes =
             unsafe { std::slice::from_raw_parts_mut(result.as_mut_ptr() as *mut u8, bytes.len()) };
-        match T::BYTE_SIZE {
+        match T::SWAP_WIDTH {
             2 => crate::engine::simd::swap_2byte_simd(bytes, dst_bytes),
             4 => crate::engine::simd::swap_4byte_simd(bytes, dst_bytes),
             8 => crate::engine::simd::swap_8byte_simd(bytes, dst_bytes),
@@ -355,49 +413,20 @@ pub fn encode_slice<T: EndianCodec + Sync>(
         return Ok(());
     }
 
-    // Non-native endian: memcpy native bytes, then byte-swap in-place.
-    // SAFETY: same invariants as the native path — sizes match, buffers
-    // are non-overlapping; the swap functions read and write within bounds.
-    unsafe {
-        core::ptr::copy_nonoverlapping(
-            values.as_ptr() as *const u8,
-            bytes.as_mut_ptr(),
-            bytes.len(),
-        );
-    }
+    // Non-native endian: byte-swap straight from the values' native bytes.
+    // The swap kernels take distinct source and destination slices; swapping
+    // `bytes` in place through aliased slices corrupts the scalar tail, which
+    // reads bytes it has already overwritten.
     #[cfg(feature = "simd")]
     {
-        match T::BYTE_SIZE {
-            2 => {
-                let (src, dst) = (bytes.as_ptr(), bytes.as_mut_ptr());
-                let len = bytes.len();
-                crate::engine::simd::swap_2byte_simd(
-                    // SAFETY: `src`/`dst` point into `bytes`, which is a valid
-                    // mutable slice of known length. The SIMD function respects
-                    // bounds and writes every byte of the output.
-                    unsafe { std::slice::from_raw_parts(src, len) },
-                    unsafe { std::slice::from_raw_parts_mut(dst, len) },
-                );
-            }
-            4 => {
-                let (src, dst) = (bytes.as_ptr(), bytes.as_mut_ptr());
-                let len = bytes.len();
-                crate::engine::simd::swap_4byte_simd(
-                    unsafe { std::slice::from_raw_parts(src, len) },
-                    unsafe { std::slice::from_raw_parts_mut(dst, len) },
-                );
-            }
-            8 => {
-                let (src, dst) = (bytes.as_ptr(), bytes.as_mut_ptr());
-                let len = bytes.len();
-                crate::engine::simd::swap_8byte_simd(
-                    unsafe { std::slice::from_raw_parts(src, len) },
-                    unsafe { std::slice::from_raw_parts_mut(dst, len) },
-                );
-            }
-            _ => {
-                per_element_encode::<T>(values, bytes, endian);
-            }
+        // SAFETY: `values` is a live slice of exactly `bytes.len()` bytes
+        // (checked above) and does not overlap the mutable `bytes`.
+        let src = unsafe { std::slice::from_raw_parts(values.as_ptr() as *const u8, bytes.len()) };
+        match T::SWAP_WIDTH {
+            2 => crate::engine::simd::swap_2byte_simd(src, bytes),
+            4 => crate::engine::simd::swap_4byte_simd(src, bytes),
+            8 => crate::engine::simd::swap_8byte_simd(src, bytes),
+            _ => per_element_encode::<T>(values, bytes, endian),
         }
     }
     #[cfg(not(feature = "simd"))]
@@ -485,10 +514,11 @@ fn per_element_encode<T: EndianCodec + Sync>(values: &[T], bytes: &mut [u8], end
 ///
 /// Converts a slice of voxels from one endianness to the other by byte-swapping
 /// each element's bytes in-place.  When `from == FileEndian::native()` this is
-/// a no-op — the data is already in host order.
+/// a no-op — the data is already in host order. Complex voxels swap each
+/// component on its own, so real and imaginary parts stay in place.
 ///
-/// Uses SIMD acceleration (AVX2 / NEON) when the `simd` feature is enabled
-/// and the required ISA is detected at runtime.
+/// The swap runs over whole `u16`/`u32` lanes, which the compiler turns into
+/// vector byte shuffles (`pshufb`, `rev`) without needing the `simd` feature.
 ///
 /// # Example
 ///
@@ -505,16 +535,29 @@ pub fn swap_bytes_in_place<T: crate::Voxel>(data: &mut [T], from: FileEndian) {
     if from == FileEndian::native() || data.is_empty() {
         return;
     }
-    // Reinterpret as raw bytes and reverse each element's bytes in-place.
-    // Using slice::reverse on each element avoids the SIMD src≠dst constraint.
     let byte_len = data.len() * T::BYTE_SIZE;
     // SAFETY: the byte_len calculation is exact; the pointer casts produce
     // a valid mutable byte slice of the same length.
     let bytes = unsafe { core::slice::from_raw_parts_mut(data.as_mut_ptr() as *mut u8, byte_len) };
-    // Reverse bytes within each element: for BYTE_SIZE=2, [a,b]→[b,a];
-    // for BYTE_SIZE=4, [a,b,c,d]→[d,c,b,a].
-    for chunk in bytes.chunks_exact_mut(T::BYTE_SIZE) {
-        chunk.reverse();
+    match T::SWAP_WIDTH {
+        2 => swap_lanes(bytes, u16::swap_bytes),
+        4 => swap_lanes(bytes, u32::swap_bytes),
+        8 => swap_lanes(bytes, u64::swap_bytes),
+        _ => {}
+    }
+}
+
+/// Apply `swap` to every `L`-sized lane of `bytes`.
+///
+/// Voxel components are aligned to their own size, so the cast succeeds for
+/// any slice that came from typed data; a misaligned slice still gets the
+/// right answer through the byte-wise path.
+fn swap_lanes<L: bytemuck::Pod>(bytes: &mut [u8], swap: fn(L) -> L) {
+    match bytemuck::try_cast_slice_mut::<u8, L>(bytes) {
+        Ok(lanes) => lanes.iter_mut().for_each(|l| *l = swap(*l)),
+        Err(_) => bytes
+            .chunks_exact_mut(core::mem::size_of::<L>())
+            .for_each(|chunk| chunk.reverse()),
     }
 }
 
@@ -203,13 +203,7 @@ pub(crate) fn pack_u8_to_u4_bytes(src: &[u8], nx: usize, ny: usize) -> Vec<u8> {
 /// Reinterpret Mode 0 (8-bit) data as signed or unsigned and convert to `f32`.
 pub fn reinterpret_m0(data: &[u8], interp: M0Interpretation) -> Vec<f32> {
     match interp {
-        M0Interpretation::Signed => {
-            // SAFETY: `u8` and `i8` have the same size and alignment; the byte
-            // pattern is valid for `i8` because every bit pattern is valid for `i8`.
-            let src: &[i8] =
-                unsafe { core::slice::from_raw_parts(data.as_ptr() as *const i8, data.len()) };
-            convert_i8_slice_to_f32(src)
-        }
+        M0Interpretation::Signed => convert_i8_slice_to_f32(bytemuck::cast_slice(data)),
         M0Interpretation::Unsigned => convert_u8_slice_to_f32(data),
     }
 }
@@ -2,7 +2,8 @@
 //!
 //! MRC files encode byte order via the 4-byte MACHST machine stamp.
 //! This module detects the stamp, provides the [`FileEndian`] enum, and
-//! defines the [`MachstInfo`] metadata type.
+//! defines the [`MachstInfo`] metadata type and the [`MachineStamp`]
+//! classification of legacy stamps.
 
 /// Endianness of MRC file data.
 ///
@@ -148,6 +149,70 @@ impl FileEndian {
     }
 }
 
+/// Classification of a MACHST machine stamp, including pre-2014 variants.
+///
+/// The stamp follows the CCP4 convention: the high nibble of byte 0 names
+/// the floating-point format and the high nibble of byte 1 the integer
+/// byte order, with `1` meaning big-endian IEEE, `4` little-endian IEEE,
+/// and `2`, `3`, `5` the non-IEEE VAX, Cray and Convex formats. Writers
+/// have filled the low nibbles and bytes 2–3 inconsistently, so only the
+/// high nibbles are checked.
+///
+/// # Examples
+///
+/// ```rust
+/// use mrc::{FileEndian, MachineStamp};
+///
+/// assert_eq!(MachineStamp::parse([0x44, 0x41, 0, 0])?, MachineStamp::LittleEndian);
+/// let old_sgi = MachineStamp::parse([0x11, 0x11, 0, 0])?;
+/// assert_eq!(old_sgi.endian(), Some(FileEndian::BigEndian));
+/// // VAX floats cannot be decoded as IEEE.
+/// assert!(matches!(
+///     MachineStamp::parse([0x22, 0x41, 0, 0]),
+///     Err(mrc::Error::UnsupportedFloatFormat { .. })
+/// ));
+/// # Ok::<(), mrc::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MachineStamp {
+    /// IEEE little-endian (`0x44 0x44`, or CCP4's `0x44 0x41`).
+    LittleEndian,
+    /// IEEE big-endian (`0x11 0x11` and variants).
+    BigEndian,
+    /// All zero: written by software that predates the stamp.
+    Unset,
+    /// Anything else; readers fall back to little-endian.
+    Unknown([u8; 4]),
+}
+
+impl MachineStamp {
+    /// Classify a raw stamp.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedFloatFormat`](crate::Error::UnsupportedFloatFormat)
+    /// for VAX, Cray and Convex stamps, whose floating-point values would
+    /// be silently wrong if decoded as IEEE.
+    pub fn parse(machst: [u8; 4]) -> Result<Self, crate::Error> {
+        match (machst[0] >> 4, machst[1] >> 4) {
+            (2 | 3 | 5, _) => Err(crate::Error::UnsupportedFloatFormat { machst }),
+            (4, 4) => Ok(Self::LittleEndian),
+            (1, 1) => Ok(Self::BigEndian),
+            _ if machst == [0; 4] => Ok(Self::Unset),
+            _ => Ok(Self::Unknown(machst)),
+        }
+    }
+
+    /// Byte order the stamp declares, or `None` if unset or unrecognized.
+    pub fn endian(self) -> Option<FileEndian> {
+        match self {
+            Self::LittleEndian => Some(FileEndian::LittleEndian),
+            Self::BigEndian => Some(FileEndian::BigEndian),
+            Self::Unset | Self::Unknown(_) => None,
+        }
+    }
+}
+
 /// Metadata about a MACHST machine stamp.
 #[doc(hidden)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -189,6 +254,33 @@ mod tests {
         assert!(!info.is_standard);
     }
 
+    #[test]
+    fn test_machine_stamp_legacy_variants() {
+        assert_eq!(
+            MachineStamp::parse([0x44, 0x44, 0x00, 0x00]).unwrap(),
+            MachineStamp::LittleEndian
+        );
+        assert_eq!(
+            MachineStamp::parse([0x11, 0x11, 0x00, 0x00]).unwrap(),
+            MachineStamp::BigEndian
+        );
+        assert_eq!(
+            MachineStamp::parse([0x11, 0x10, 0x00, 0x00]).unwrap(),
+            MachineStamp::BigEndian
+        );
+        assert_eq!(MachineStamp::parse([0; 4]).unwrap(), MachineStamp::Unset);
+        assert_eq!(
+            MachineStamp::parse([0xAB, 0xCD, 0, 0]).unwrap(),
+            MachineStamp::Unknown([0xAB, 0xCD, 0, 0])
+        );
+        for vax_like in [[0x22, 0x41, 0, 0], [0x33, 0x11, 0, 0], [0x55, 0x11, 0, 0]] {
+            assert!(matches!(
+                MachineStamp::parse(vax_like),
+                Err(crate::Error::UnsupportedFloatFormat { machst }) if machst == vax_like
+            ));
+        }
+    }
+
     #[test]
     fn test_opposite() {
         assert_eq!(FileEndian::LittleEndian.opposite(), FileEndian::BigEndian);
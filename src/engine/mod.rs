@@ -8,18 +8,28 @@
 //!
 //! Submodules provide:
 //!
+//! * [`aligned`] – 64-byte-aligned byte buffers for in-memory data.
 //! * [`block`] – volume geometry and voxel block types.
 //! * [`codec`] – bidirectional endian codec for primitive types.
 //! * [`convert`] – common type conversions (e.g. `i16` → `f32`).
 //! * [`endian`] – endianness detection and the [`FileEndian`](endian::FileEndian) enum.
+//! * [`raw_block`] – encoded voxel bytes tagged with mode and byte order.
+//! * [`roi`] – strided sub-box views of a [`Volume`](crate::Volume).
+//! * [`sketch`] – bounded-memory quantile estimates.
 //! * [`stats`] – statistics computation for header validation.
+//! * [`volume`] – statically typed whole-volume access.
 //! * [`simd`] – SIMD-accelerated conversion kernels (optional `simd` feature).
 
+pub mod aligned;
 pub mod block;
 pub mod codec;
 pub mod convert;
 pub mod endian;
+pub mod raw_block;
+pub mod roi;
+pub mod sketch;
 pub mod stats;
+pub mod volume;
 
 #[cfg(feature = "simd")]
 #[doc(hidden)]
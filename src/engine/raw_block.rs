@@ -0,0 +1,174 @@
+//! Encoded voxel bytes tagged with their mode and byte order.
+//!
+//! [`DataBlock`](crate::DataBlock) and [`VoxelBlock`](crate::VoxelBlock)
+//! hold data already decoded to native values. Code that moves voxels
+//! between files, sockets and caches often wants to keep them as stored
+//! and decode only at the edges; [`RawBlock`] carries the bytes together
+//! with the two facts needed to read them.
+
+use super::codec::{decode_slice, encode_slice};
+use super::endian::FileEndian;
+use crate::{Error, Mode, Voxel};
+
+/// Voxel bytes in file encoding, with the [`Mode`] and [`FileEndian`] that
+/// say how to read them.
+///
+/// [`decode`](Self::decode) turns the bytes into a `Vec<T>` and
+/// [`encode`](Self::encode) writes a slice back over them; the `as_*` and
+/// `set_*` methods are the same for each real scalar type. Mode 101 bytes
+/// have no [`Voxel`] type and are reached through [`bytes`](Self::bytes).
+///
+/// # Examples
+///
+/// ```
+/// use mrc::{FileEndian, Mode, RawBlock};
+///
+/// let mut block = RawBlock::new(vec![0u8; 8], Mode::Int16, FileEndian::BigEndian)?;
+/// block.set_i16(&[1, -2, 300, 4])?;
+/// assert_eq!(&block.bytes()[..2], &[0x00, 0x01]);
+/// assert_eq!(block.as_i16()?, [1, -2, 300, 4]);
+/// # Ok::<(), mrc::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawBlock {
+    bytes: Vec<u8>,
+    mode: Mode,
+    endian: FileEndian,
+}
+
+macro_rules! typed_access {
+    ($($(#[$attr:meta])* $ty:ty => $get:ident, $set:ident;)*) => {$(
+        $(#[$attr])*
+        #[doc = concat!("[`decode::<", stringify!($ty), ">()`](Self::decode).")]
+        ///
+        /// # Errors
+        ///
+        /// As [`decode`](Self::decode).
+        #[inline]
+        pub fn $get(&self) -> Result<Vec<$ty>, Error> {
+            self.decode()
+        }
+
+        $(#[$attr])*
+        #[doc = concat!("[`encode::<", stringify!($ty), ">(values)`](Self::encode).")]
+        ///
+        /// # Errors
+        ///
+        /// As [`encode`](Self::encode).
+        #[inline]
+        pub fn $set(&mut self, values: &[$ty]) -> Result<(), Error> {
+            self.encode(values)
+        }
+    )*};
+}
+
+impl RawBlock {
+    /// Wrap `bytes` holding `mode` voxels in `endian` byte order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TypeMismatch`] if `bytes` is not a whole number of
+    /// voxels.
+    pub fn new(bytes: Vec<u8>, mode: Mode, endian: FileEndian) -> Result<Self, Error> {
+        let size = mode.byte_size();
+        if mode != Mode::Packed4Bit && bytes.len() % size != 0 {
+            return Err(Error::TypeMismatch {
+                expected: size,
+                actual: bytes.len(),
+            });
+        }
+        Ok(Self {
+            bytes,
+            mode,
+            endian,
+        })
+    }
+
+    /// The encoded bytes.
+    #[inline]
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Take the encoded bytes.
+    #[inline]
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// The mode the bytes encode.
+    #[inline]
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// The byte order the bytes are in.
+    #[inline]
+    pub fn endian(&self) -> FileEndian {
+        self.endian
+    }
+
+    /// Number of voxels; two per byte for Mode 101.
+    #[inline]
+    pub fn len(&self) -> usize {
+        match self.mode {
+            Mode::Packed4Bit => self.bytes.len() * 2,
+            mode => self.bytes.len() / mode.byte_size(),
+        }
+    }
+
+    /// `true` if the block holds no voxels.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Decode every voxel into native `T` values.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ModeMismatch`] if the block's mode is not `T::MODE`.
+    pub fn decode<T: Voxel>(&self) -> Result<Vec<T>, Error> {
+        self.check_mode::<T>()?;
+        decode_slice(&self.bytes, self.endian)
+    }
+
+    /// Encode `values` over the block's bytes, in its byte order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ModeMismatch`] if the block's mode is not `T::MODE`,
+    /// and [`Error::BlockShapeMismatch`] if `values` is not
+    /// [`len`](Self::len) voxels long.
+    pub fn encode<T: Voxel>(&mut self, values: &[T]) -> Result<(), Error> {
+        self.check_mode::<T>()?;
+        if values.len() != self.len() {
+            return Err(Error::BlockShapeMismatch {
+                expected: self.len(),
+                actual: values.len(),
+            });
+        }
+        encode_slice(values, &mut self.bytes, self.endian)
+    }
+
+    typed_access! {
+        i8 => as_i8, set_i8;
+        i16 => as_i16, set_i16;
+        u16 => as_u16, set_u16;
+        f32 => as_f32, set_f32;
+        #[cfg(feature = "f16")]
+        crate::f16 => as_f16, set_f16;
+    }
+
+    fn check_mode<T: Voxel>(&self) -> Result<(), Error> {
+        if self.mode == T::MODE {
+            Ok(())
+        } else {
+            Err(Error::ModeMismatch {
+                file_mode: self.mode,
+                requested_mode: T::MODE,
+                offset: None,
+            })
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! Rectangular windows into a typed volume.
+//!
+//! A viewer panning over a 4k³ map needs a few hundred rows at a time, not
+//! a copy of the box they come from. [`RoiView`] describes a sub-box of a
+//! [`Volume`] by offset and shape and hands out each of its rows as a
+//! slice of the volume's own data, stepping by the volume's row and
+//! section strides.
+
+use crate::{Error, Volume, Voxel, VoxelBlock};
+
+/// A sub-box of a [`Volume`], borrowed without copying.
+///
+/// Coordinates passed to its accessors are local to the box. Each row is
+/// contiguous in the volume, so [`row`](Self::row) and
+/// [`rows`](Self::rows) return plain slices; consecutive rows are `nx`
+/// voxels apart and consecutive sections `nx * ny`.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> Result<(), mrc::Error> {
+/// let reader = mrc::Reader::open("huge_map.mrc")?;
+/// let vol = reader.volume::<f32>()?;
+/// let window = vol.roi([1024, 2048, 700], [512, 256, 1])?;
+/// for (y, row) in window.rows().enumerate() {
+///     // draw `row` (512 voxels) at screen line y
+///     let _ = (y, row);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct RoiView<'v, T> {
+    data: &'v [T],
+    strides: [usize; 2],
+    offset: [usize; 3],
+    shape: [usize; 3],
+}
+
+impl<'v, T: Voxel> RoiView<'v, T> {
+    /// Corner of the box within the volume, `[x, y, z]`.
+    #[inline]
+    pub fn offset(&self) -> [usize; 3] {
+        self.offset
+    }
+
+    /// Extent of the box, `[sx, sy, sz]`.
+    #[inline]
+    pub fn shape(&self) -> [usize; 3] {
+        self.shape
+    }
+
+    /// Distance in voxels between the starts of consecutive rows and of
+    /// consecutive sections.
+    #[inline]
+    pub fn strides(&self) -> [usize; 2] {
+        self.strides
+    }
+
+    /// Index into the volume's data of box-local `(x, y, z)`.
+    #[inline]
+    fn index_of(&self, x: usize, y: usize, z: usize) -> usize {
+        let [ox, oy, oz] = self.offset;
+        (oz + z) * self.strides[1] + (oy + y) * self.strides[0] + ox + x
+    }
+
+    /// Voxel at box-local `(x, y, z)`, or `None` if outside the box.
+    #[inline]
+    pub fn get(&self, x: usize, y: usize, z: usize) -> Option<&'v T> {
+        let [sx, sy, sz] = self.shape;
+        if x < sx && y < sy && z < sz {
+            self.data.get(self.index_of(x, y, z))
+        } else {
+            None
+        }
+    }
+
+    /// Row `y` of section `z` (box-local), `sx` voxels, or `None` if
+    /// outside the box.
+    #[inline]
+    pub fn row(&self, y: usize, z: usize) -> Option<&'v [T]> {
+        if y < self.shape[1] && z < self.shape[2] {
+            let start = self.index_of(0, y, z);
+            self.data.get(start..start + self.shape[0])
+        } else {
+            None
+        }
+    }
+
+    /// Every row of the box, `y` fastest then `z`.
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &'v [T]> + 'v {
+        let view = *self;
+        let [_, sy, sz] = self.shape;
+        (0..sy * sz).map(move |i| {
+            let start = view.index_of(0, i % sy, i / sy);
+            &view.data[start..start + view.shape[0]]
+        })
+    }
+
+    /// A sub-box of this one, at `offset` relative to this box.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::BoundsError`] if the sub-box does not fit inside
+    /// this one.
+    pub fn roi(&self, offset: [usize; 3], shape: [usize; 3]) -> Result<Self, Error> {
+        check_fits(offset, shape, self.shape)?;
+        Ok(Self {
+            offset: std::array::from_fn(|i| self.offset[i] + offset[i]),
+            shape,
+            ..*self
+        })
+    }
+
+    /// Copy the box into a [`VoxelBlock`] positioned at its volume offset.
+    pub fn to_block(&self) -> VoxelBlock<T> {
+        VoxelBlock {
+            offset: self.offset,
+            shape: self.shape,
+            data: self.rows().flatten().copied().collect(),
+        }
+    }
+}
+
+/// Box-local `(x, y, z)` indexing; panics when outside the box.
+impl<T: Voxel> core::ops::Index<(usize, usize, usize)> for RoiView<'_, T> {
+    type Output = T;
+
+    #[inline]
+    #[track_caller]
+    fn index(&self, (x, y, z): (usize, usize, usize)) -> &T {
+        match self.get(x, y, z) {
+            Some(v) => v,
+            None => panic!(
+                "voxel index ({x}, {y}, {z}) out of bounds for region of shape {:?}",
+                self.shape
+            ),
+        }
+    }
+}
+
+fn check_fits(offset: [usize; 3], shape: [usize; 3], within: [usize; 3]) -> Result<(), Error> {
+    let fits = (0..3).all(|i| {
+        offset[i]
+            .checked_add(shape[i])
+            .is_some_and(|end| end <= within[i])
+    });
+    if fits {
+        Ok(())
+    } else {
+        Err(Error::BoundsError {
+            offset: Some(offset),
+            shape: Some(shape),
+            volume: Some(within),
+        })
+    }
+}
+
+impl<T: Voxel> Volume<'_, T> {
+    /// A window onto the box at `offset` with extent `shape`, borrowing
+    /// this volume's data.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::BoundsError`] if the box extends past the volume.
+    pub fn roi(&self, offset: [usize; 3], shape: [usize; 3]) -> Result<RoiView<'_, T>, Error> {
+        let s = self.shape();
+        check_fits(offset, shape, [s.nx, s.ny, s.nz])?;
+        Ok(RoiView {
+            data: self.as_slice(),
+            strides: [s.nx, s.nx * s.ny],
+            offset,
+            shape,
+        })
+    }
+}
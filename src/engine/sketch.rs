@@ -0,0 +1,286 @@
+//! Bounded-memory quantile estimates.
+//!
+//! [`QuantileSketch`] is a merging t-digest: values are summarised by a few
+//! hundred weighted centroids, kept small near the tails where contrast
+//! limits and thresholds live. Feed it one section at a time from any reader
+//! and ask for quantiles at the end; memory stays constant however large
+//! the file is. [`Reader::quantile_sketch`](crate::Reader::quantile_sketch)
+//! does the feeding for a whole file.
+
+use std::f64::consts::PI;
+
+/// Compression used by [`QuantileSketch::default`].
+const DEFAULT_COMPRESSION: f64 = 200.0;
+
+/// Approximate quantiles of a stream of values, in bounded memory.
+///
+/// Accuracy is best near the extremes: with the default compression, the
+/// 0.5th and 99.5th percentiles of a million voxels are typically within a
+/// few hundredths of a percent in rank. NaN and infinite values are
+/// ignored, so [`min`](Self::min) and [`max`](Self::max) are the finite
+/// extremes.
+///
+/// # Examples
+///
+/// ```rust
+/// use mrc::QuantileSketch;
+/// let mut sketch = QuantileSketch::default();
+/// for section in 0..100 {
+///     let values: Vec<f32> = (0..1000).map(|i| (section * 1000 + i) as f32).collect();
+///     sketch.extend(&values);
+/// }
+/// let median = sketch.quantile(0.5).unwrap();
+/// assert!((median - 49_999.5).abs() < 100.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct QuantileSketch {
+    compression: f64,
+    /// `(mean, weight)`, sorted by mean.
+    centroids: Vec<(f64, f64)>,
+    buffer: Vec<f64>,
+    count: u64,
+    min: f64,
+    max: f64,
+}
+
+impl Default for QuantileSketch {
+    fn default() -> Self {
+        Self::new(DEFAULT_COMPRESSION)
+    }
+}
+
+impl QuantileSketch {
+    /// Empty sketch. `compression` bounds the number of centroids (about
+    /// `compression / 2` are kept); larger is more accurate and slower.
+    /// Values below 20 are raised to 20.
+    pub fn new(compression: f64) -> Self {
+        let compression = if compression.is_nan() {
+            DEFAULT_COMPRESSION
+        } else {
+            compression.max(20.0)
+        };
+        Self {
+            compression,
+            centroids: Vec::new(),
+            buffer: Vec::new(),
+            count: 0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    /// Add one value. NaN and ±infinity are ignored.
+    pub fn push(&mut self, value: f64) {
+        if !value.is_finite() {
+            return;
+        }
+        self.count += 1;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.buffer.push(value);
+        if self.buffer.len() >= self.buffer_limit() {
+            self.compress();
+        }
+    }
+
+    /// Add every value of `values`, for example one Z-section.
+    pub fn extend<T: Copy + Into<f64>>(&mut self, values: &[T]) {
+        for &v in values {
+            self.push(v.into());
+        }
+    }
+
+    /// Fold another sketch into this one, e.g. one built by another thread.
+    pub fn merge(&mut self, other: &QuantileSketch) {
+        self.count += other.count;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.buffer.extend_from_slice(&other.buffer);
+        self.centroids.extend_from_slice(&other.centroids);
+        self.compress();
+    }
+
+    /// Number of values added.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Smallest value added, or `None` if empty.
+    pub fn min(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.min)
+    }
+
+    /// Largest value added, or `None` if empty.
+    pub fn max(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.max)
+    }
+
+    /// Estimated value below which a fraction `q` (`0.0..=1.0`) of the
+    /// values fall. Returns `None` when empty or `q` is out of range.
+    pub fn quantile(&mut self, q: f64) -> Option<f64> {
+        if self.count == 0 || !(0.0..=1.0).contains(&q) {
+            return None;
+        }
+        self.compress();
+        let total = self.count as f64;
+        let target = q * total;
+        let c = &self.centroids;
+
+        // Interpolate between centroid centres, with min and max as the
+        // outermost points.
+        let (mut prev_pos, mut prev_val) = (0.0, self.min);
+        let mut cum = 0.0;
+        for &(mean, weight) in c {
+            let pos = cum + weight / 2.0;
+            if target <= pos {
+                return Some(interpolate(prev_pos, prev_val, pos, mean, target));
+            }
+            (prev_pos, prev_val) = (pos, mean);
+            cum += weight;
+        }
+        Some(interpolate(prev_pos, prev_val, total, self.max, target))
+    }
+
+    fn buffer_limit(&self) -> usize {
+        (self.compression * 5.0) as usize
+    }
+
+    /// Merge buffered values into the centroids.
+    fn compress(&mut self) {
+        if self.buffer.is_empty() && self.centroids.len() <= self.compression as usize {
+            return;
+        }
+        let mut all = std::mem::take(&mut self.centroids);
+        all.extend(self.buffer.drain(..).map(|v| (v, 1.0)));
+        all.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let total: f64 = all.iter().map(|c| c.1).sum();
+        let delta = self.compression;
+        // Scale function k1: centroids near q = 0 and 1 stay small.
+        let k = |q: f64| delta / (2.0 * PI) * (2.0 * q - 1.0).asin();
+        let k_inv = |k: f64| ((2.0 * PI * k / delta).min(PI / 2.0).sin() + 1.0) / 2.0;
+
+        let mut merged = Vec::with_capacity(delta as usize);
+        let mut iter = all.into_iter();
+        let Some(mut cur) = iter.next() else {
+            return;
+        };
+        let mut before = 0.0;
+        let mut limit = k_inv(k(0.0) + 1.0);
+        for next in iter {
+            if (before + cur.1 + next.1) / total <= limit {
+                let w = cur.1 + next.1;
+                cur = (cur.0 + (next.0 - cur.0) * next.1 / w, w);
+            } else {
+                before += cur.1;
+                merged.push(cur);
+                limit = k_inv(k(before / total) + 1.0);
+                cur = next;
+            }
+        }
+        merged.push(cur);
+        self.centroids = merged;
+    }
+}
+
+fn interpolate(x0: f64, y0: f64, x1: f64, y1: f64, x: f64) -> f64 {
+    if x1 <= x0 {
+        return y1;
+    }
+    y0 + (y1 - y0) * ((x - x0) / (x1 - x0)).clamp(0.0, 1.0)
+}
+
+impl crate::Reader {
+    /// Build a [`QuantileSketch`] of the whole volume in one pass.
+    ///
+    /// Sections are read one at a time through
+    /// [`convert::<f32>()`](Self::convert), so memory use is one section
+    /// plus the sketch, whatever the file size. NaN and infinite voxels are
+    /// skipped, as in [`histogram`](Self::histogram). Useful for contrast levels
+    /// and thresholds on files too large for
+    /// [`Volume::percentile`](crate::Volume::percentile).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let reader = mrc::Reader::open("tomogram.mrc")?;
+    /// let mut sketch = reader.quantile_sketch()?;
+    /// let (black, white) = (sketch.quantile(0.005), sketch.quantile(0.995));
+    /// println!("display {black:?} .. {white:?}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedMode`](crate::Error::UnsupportedMode) for
+    /// complex modes and propagates any error from reading the volume.
+    pub fn quantile_sketch(&self) -> Result<QuantileSketch, crate::Error> {
+        if self.mode().is_complex() {
+            return Err(crate::Error::UnsupportedMode);
+        }
+        let mut sketch = QuantileSketch::default();
+        for section in self.convert::<f32>().slices() {
+            sketch.extend(&section?.data);
+        }
+        Ok(sketch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sketch_tracks_uniform_quantiles() {
+        let mut s = QuantileSketch::default();
+        // A scrambled 0..100_000 so insertion order is not sorted.
+        for i in 0..100_000u64 {
+            s.push(((i * 7919) % 100_000) as f64);
+        }
+        assert!(s.centroids.len() < 400);
+        assert_eq!(s.quantile(0.0), Some(0.0));
+        assert_eq!(s.quantile(1.0), Some(99_999.0));
+        for q in [0.001, 0.01, 0.25, 0.5, 0.75, 0.99, 0.999] {
+            let est = s.quantile(q).unwrap();
+            assert!((est - q * 100_000.0).abs() < 100.0, "q={q}: {est}");
+        }
+    }
+
+    #[test]
+    fn sketch_merge_matches_single_stream() {
+        let (mut a, mut b) = (QuantileSketch::new(100.0), QuantileSketch::new(100.0));
+        a.extend(&(0..5000).map(|i| i as f32).collect::<Vec<_>>());
+        b.extend(&(5000..10_000).map(|i| i as f32).collect::<Vec<_>>());
+        a.merge(&b);
+        assert_eq!(a.count(), 10_000);
+        let est = a.quantile(0.9).unwrap();
+        assert!((est - 9000.0).abs() < 50.0, "{est}");
+    }
+
+    #[test]
+    fn sketch_ignores_nan_and_handles_empty() {
+        let mut s = QuantileSketch::default();
+        assert_eq!(s.quantile(0.5), None);
+        s.push(f64::NAN);
+        s.push(f64::INFINITY);
+        s.push(f64::NEG_INFINITY);
+        assert_eq!(s.count(), 0);
+        s.push(3.0);
+        assert_eq!(s.quantile(0.5), Some(3.0));
+        assert_eq!(s.quantile(1.5), None);
+    }
+
+    #[test]
+    fn sketch_skips_infinities_among_many_values() {
+        let mut s = QuantileSketch::default();
+        s.extend(&(0..2000).map(|i| i as f32).collect::<Vec<_>>());
+        s.extend(&[f32::INFINITY, f32::NEG_INFINITY]);
+        assert_eq!(s.count(), 2000);
+        assert_eq!(s.quantile(0.0), Some(0.0));
+        assert_eq!(s.quantile(1.0), Some(1999.0));
+        let high = s.quantile(0.999).unwrap();
+        assert!((high - 1997.0).abs() < 5.0, "{high}");
+    }
+}
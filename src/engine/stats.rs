@@ -13,7 +13,7 @@
 use crate::Error;
 use crate::engine::codec::decode_slice;
 use crate::engine::endian::FileEndian;
-use crate::mode::{Float32Complex, Int16Complex, Mode};
+use crate::mode::{DataView, Float32Complex, Int16Complex, Mode};
 
 /// Compute (dmin, dmax, dmean, rms) from raw data bytes.
 ///
@@ -74,11 +74,19 @@ pub(crate) fn compute_stats(
 }
 
 fn stats_real<T>(data: &[T]) -> (f32, f32, f32, f32)
+where
+    T: Copy + Into<f64> + 'static,
+{
+    running_real(data).finalize()
+}
+
+/// [`RunningStats`] of real data, taking the SIMD path for `f32`.
+fn running_real<T>(data: &[T]) -> RunningStats
 where
     T: Copy + Into<f64> + 'static,
 {
     if data.is_empty() {
-        return (0.0, -1.0, -2.0, -1.0);
+        return RunningStats::new();
     }
 
     // Specialized SIMD path for f32 (most common case).
@@ -89,34 +97,91 @@ where
         if core::any::TypeId::of::<T>() == core::any::TypeId::of::<f32>() {
             let f32_data: &[f32] =
                 unsafe { core::slice::from_raw_parts(data.as_ptr() as *const f32, data.len()) };
-            return stats_f32_simd_inner(f32_data);
+            let (min, max, mean, rms) = stats_f32_simd_inner(f32_data);
+            return RunningStats::from_summary(
+                data.len(),
+                min.into(),
+                max.into(),
+                mean.into(),
+                rms.into(),
+            );
         }
     }
 
-    // Generic single-pass scalar using Welford's online algorithm.
-    let len = data.len();
-    let mut min = f64::INFINITY;
-    let mut max = f64::NEG_INFINITY;
-    let mut n = 0u64;
-    let mut mean = 0.0f64;
-    let mut m2 = 0.0f64;
+    running_values(data.iter().map(|&v| v.into()))
+}
 
-    for &v in data {
-        let x = v.into();
-        n += 1;
-        if x < min {
-            min = x;
-        }
-        if x > max {
-            max = x;
+/// Generic single-pass scalar statistics using Welford's online algorithm.
+fn running_values(values: impl Iterator<Item = f64>) -> RunningStats {
+    let mut stats = RunningStats::new();
+    stats.extend(values);
+    stats
+}
+
+/// `(dmin, dmax, dmean, rms)` of already-decoded data, with complex values
+/// taken as their magnitudes. `nx` is the row length used to skip the
+/// padding nibble of odd-width [`DataView::Packed4Bit`] rows.
+pub(crate) fn view_stats(view: DataView<'_>, nx: usize) -> (f32, f32, f32, f32) {
+    let magnitude = |re: f64, im: f64| re.hypot(im);
+    let stats = match view {
+        DataView::Int8(v) => chunked(v, 1, running_real),
+        DataView::Int16(v) => chunked(v, 1, running_real),
+        DataView::Float32(v) => chunked(v, 1, running_real),
+        DataView::Int16Complex(v) => chunked(v, 1, |c| {
+            running_values(c.iter().map(|c| magnitude(c.real.into(), c.imag.into())))
+        }),
+        DataView::Float32Complex(v) => chunked(v, 1, |c| {
+            running_values(c.iter().map(|c| magnitude(c.real.into(), c.imag.into())))
+        }),
+        DataView::Uint16(v) => chunked(v, 1, running_real),
+        #[cfg(feature = "f16")]
+        DataView::Float16(v) => chunked(v, 1, |c| running_values(c.iter().map(|h| h.to_f64()))),
+        DataView::Packed4Bit(v) => {
+            let row = nx.div_ceil(2).max(1);
+            chunked(v, row, |c| {
+                running_values(c.chunks(row).flat_map(|bytes| {
+                    (0..nx.min(bytes.len() * 2)).map(move |x| {
+                        let b = bytes[x / 2];
+                        f64::from(if x % 2 == 0 { b & 0x0F } else { b >> 4 })
+                    })
+                }))
+            })
         }
-        let delta = x - mean;
-        mean += delta / n as f64;
-        m2 += delta * (x - mean);
+    };
+    stats.finalize()
+}
+
+/// Voxels per chunk when statistics are split across threads: 1 MiB of
+/// `f32`, small enough to stay in L2 and large enough that merging is
+/// noise even for a 10 GB tomogram.
+#[cfg(feature = "parallel")]
+const PAR_CHUNK_VOXELS: usize = 262_144;
+
+/// `stats` of `data`, computed per chunk on the rayon pool and merged.
+/// Chunks are whole multiples of `unit` elements, so Packed4Bit rows are
+/// never split.
+#[cfg(feature = "parallel")]
+fn chunked<T: Sync>(
+    data: &[T],
+    unit: usize,
+    stats: impl Fn(&[T]) -> RunningStats + Sync,
+) -> RunningStats {
+    use rayon::prelude::*;
+    let chunk = PAR_CHUNK_VOXELS.div_ceil(unit) * unit;
+    if data.len() <= chunk {
+        return stats(data);
     }
+    data.par_chunks(chunk)
+        .map(&stats)
+        .reduce(RunningStats::new, |mut a, b| {
+            a.merge(&b);
+            a
+        })
+}
 
-    let rms = (m2 / len as f64).sqrt();
-    (min as f32, max as f32, mean as f32, rms as f32)
+#[cfg(not(feature = "parallel"))]
+fn chunked<T>(data: &[T], _unit: usize, stats: impl Fn(&[T]) -> RunningStats) -> RunningStats {
+    stats(data)
 }
 
 /// SIMD-accelerated single-pass statistics for f32 data.
@@ -247,10 +312,54 @@ pub(crate) fn validate_header_stats(
     Ok(())
 }
 
+/// Percentile `q` (`0.0..=100.0`) of `values`, interpolating linearly
+/// between the two nearest ranks as NumPy's default `percentile` does.
+///
+/// NaN values are skipped. The values are copied and partially ordered with
+/// `select_nth_unstable`, so this is O(n) rather than a full sort. Returns
+/// `None` for a `q` outside `0.0..=100.0` or when no values remain.
+pub(crate) fn percentile<T: Copy + Into<f64>>(
+    values: impl IntoIterator<Item = T>,
+    q: f64,
+) -> Option<f64> {
+    if !(0.0..=100.0).contains(&q) {
+        return None;
+    }
+    let mut v: Vec<f64> = values
+        .into_iter()
+        .map(Into::into)
+        .filter(|x: &f64| !x.is_nan())
+        .collect();
+    if v.is_empty() {
+        return None;
+    }
+    let rank = q / 100.0 * (v.len() - 1) as f64;
+    let lo = rank.floor() as usize;
+    let frac = rank - lo as f64;
+    let (_, &mut a, above) = v.select_nth_unstable_by(lo, f64::total_cmp);
+    if frac == 0.0 {
+        return Some(a);
+    }
+    let b = above.iter().copied().fold(f64::INFINITY, f64::min);
+    Some(a + (b - a) * frac)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn percentile_interpolates_between_ranks() {
+        let data = [4.0f32, 1.0, f32::NAN, 3.0, 2.0];
+        assert_eq!(percentile(data, 0.0), Some(1.0));
+        assert_eq!(percentile(data, 50.0), Some(2.5));
+        assert_eq!(percentile(data, 100.0), Some(4.0));
+        assert_eq!(percentile(data, 25.0), Some(1.75));
+        assert_eq!(percentile([7i16], 99.5), Some(7.0));
+        assert_eq!(percentile([f32::NAN], 50.0), None);
+        assert_eq!(percentile(data, 100.5), None);
+    }
+
     #[test]
     fn test_stats_real_basic() {
         let data = [1.0f32, 2.0, 3.0, 4.0];
@@ -347,11 +456,10 @@ mod tests {
 }
 
 // ============================================================================
-// RunningStats — online Welford accumulator (test-only; unused in production)
+// RunningStats — online Welford accumulator
 // ============================================================================
 
 /// Online single-pass statistics accumulator using Welford's algorithm.
-#[cfg(test)]
 #[derive(Debug, Clone)]
 pub(crate) struct RunningStats {
     n: u64,
@@ -361,7 +469,6 @@ pub(crate) struct RunningStats {
     m2: f64,
 }
 
-#[cfg(test)]
 impl RunningStats {
     pub fn new() -> Self {
         Self {
@@ -373,9 +480,26 @@ impl RunningStats {
         }
     }
 
+    /// Accumulator equivalent to having seen `n` values with these
+    /// statistics (`rms` being the population standard deviation).
+    fn from_summary(n: usize, min: f64, max: f64, mean: f64, rms: f64) -> Self {
+        Self {
+            n: n as u64,
+            min,
+            max,
+            mean,
+            m2: rms * rms * n as f64,
+        }
+    }
+
+    #[cfg(test)]
     pub fn update(&mut self, data: &[f32]) {
-        for &v in data {
-            let x = v as f64;
+        self.extend(data.iter().map(|&v| f64::from(v)));
+    }
+
+    /// Fold `values` in one at a time.
+    fn extend(&mut self, values: impl Iterator<Item = f64>) {
+        for x in values {
             self.n += 1;
             if x < self.min {
                 self.min = x;
@@ -425,6 +549,101 @@ impl RunningStats {
     }
 }
 
+/// Chunk-at-a-time version of [`compute_stats`].
+///
+/// Each [`push`](Self::push) decodes one chunk of raw data and folds its
+/// statistics into the running totals, so only one decoded chunk is ever
+/// resident.
+pub(crate) struct StatsAccumulator {
+    mode: Mode,
+    endian: FileEndian,
+    nx: usize,
+    /// Real values, or the real parts of complex values.
+    real: RunningStats,
+    /// Imaginary parts of complex values.
+    imag: RunningStats,
+}
+
+impl StatsAccumulator {
+    pub fn new(mode: Mode, endian: FileEndian, nx: usize) -> Self {
+        Self {
+            mode,
+            endian,
+            nx,
+            real: RunningStats::new(),
+            imag: RunningStats::new(),
+        }
+    }
+
+    /// Fold in a chunk of raw bytes. Chunks must hold whole voxels — whole
+    /// rows for [`Mode::Packed4Bit`].
+    pub fn push(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        match self.mode {
+            Mode::Float32Complex => {
+                let data = decode_slice::<Float32Complex>(bytes, self.endian)?;
+                self.push_complex(&data);
+            }
+            Mode::Int16Complex => {
+                let data = decode_slice::<Int16Complex>(bytes, self.endian)?;
+                self.push_complex(&data);
+            }
+            Mode::Packed4Bit => {
+                let rows = bytes.len() / self.nx.div_ceil(2).max(1);
+                let unpacked = crate::engine::convert::unpack_u4_bytes_to_u8(bytes, self.nx, rows);
+                self.push_real(unpacked.len(), stats_real(&unpacked));
+            }
+            mode => {
+                let n = bytes.len() / mode.byte_size();
+                let stats = compute_stats(bytes, mode, self.endian, self.nx, 0)?;
+                self.push_real(n, stats);
+            }
+        }
+        Ok(())
+    }
+
+    fn push_real(&mut self, n: usize, (min, max, mean, rms): (f32, f32, f32, f32)) {
+        if n > 0 {
+            let chunk =
+                RunningStats::from_summary(n, min as f64, max as f64, mean as f64, rms as f64);
+            self.real.merge(&chunk);
+        }
+    }
+
+    fn push_complex<T: ComplexLike>(&mut self, data: &[T]) {
+        if data.is_empty() {
+            return;
+        }
+        let n = data.len() as f64;
+        let (sum_r, sum_i) = data
+            .iter()
+            .fold((0.0, 0.0), |(r, i), c| (r + c.real_f64(), i + c.imag_f64()));
+        let (mean_r, mean_i) = (sum_r / n, sum_i / n);
+        let (m2_r, m2_i) = data.iter().fold((0.0, 0.0), |(r, i), c| {
+            let (dr, di) = (c.real_f64() - mean_r, c.imag_f64() - mean_i);
+            (r + dr * dr, i + di * di)
+        });
+        let len = data.len();
+        let summary =
+            |mean: f64, m2: f64| RunningStats::from_summary(len, 0.0, 0.0, mean, (m2 / n).sqrt());
+        self.real.merge(&summary(mean_r, m2_r));
+        self.imag.merge(&summary(mean_i, m2_i));
+    }
+
+    /// `(dmin, dmax, dmean, rms)`, with the same sentinels as [`compute_stats`].
+    pub fn finish(&self) -> (f32, f32, f32, f32) {
+        let r = &self.real;
+        if r.n == 0 {
+            return (0.0, -1.0, -2.0, -1.0);
+        }
+        if self.mode.is_complex() {
+            let rms = ((r.m2 + self.imag.m2) / r.n as f64).sqrt();
+            return (0.0, -1.0, -2.0, rms as f32);
+        }
+        let rms = (r.m2 / r.n as f64).sqrt();
+        (r.min as f32, r.max as f32, r.mean as f32, rms as f32)
+    }
+}
+
 #[cfg(test)]
 mod running_stats_tests {
     use super::*;
@@ -482,4 +701,44 @@ mod running_stats_tests {
         assert_eq!(max, 6.0);
         assert!((mean - 3.5).abs() < 1e-6);
     }
+
+    fn accumulate(bytes: &[u8], mode: Mode, nx: usize, chunk: usize) -> (f32, f32, f32, f32) {
+        let mut acc = StatsAccumulator::new(mode, FileEndian::LittleEndian, nx);
+        for part in bytes.chunks(chunk) {
+            acc.push(part).unwrap();
+        }
+        acc.finish()
+    }
+
+    fn assert_stats_close(a: (f32, f32, f32, f32), b: (f32, f32, f32, f32)) {
+        for (x, y) in [(a.0, b.0), (a.1, b.1), (a.2, b.2), (a.3, b.3)] {
+            assert!((x - y).abs() <= 1e-4 * x.abs().max(1.0), "{a:?} vs {b:?}");
+        }
+    }
+
+    #[test]
+    fn accumulator_matches_whole_buffer_stats() {
+        let le = FileEndian::LittleEndian;
+        let f: Vec<u8> = (0..97)
+            .flat_map(|i| ((i * 37 % 101) as f32 - 40.5).to_le_bytes())
+            .collect();
+        let whole = compute_stats(&f, Mode::Float32, le, 97, 1).unwrap();
+        assert_stats_close(accumulate(&f, Mode::Float32, 97, 12), whole);
+        assert_stats_close(accumulate(&f, Mode::Float32, 97, 4), whole);
+
+        let c: Vec<u8> = (0..40i16)
+            .flat_map(|i| [i * 3, 7 - i].into_iter().flat_map(i16::to_le_bytes))
+            .collect();
+        let whole = compute_stats(&c, Mode::Int16Complex, le, 40, 1).unwrap();
+        assert_stats_close(accumulate(&c, Mode::Int16Complex, 40, 12), whole);
+
+        let packed: Vec<u8> = (0..30u8).map(|i| i.wrapping_mul(29)).collect();
+        let whole = compute_stats(&packed, Mode::Packed4Bit, le, 6, 10).unwrap();
+        assert_stats_close(accumulate(&packed, Mode::Packed4Bit, 6, 9), whole);
+
+        assert_eq!(
+            accumulate(&[], Mode::Float32, 1, 4),
+            (0.0, -1.0, -2.0, -1.0)
+        );
+    }
 }
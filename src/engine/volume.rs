@@ -0,0 +1,373 @@
+//! Statically typed whole-volume access.
+//!
+//! [`Volume<T>`] is obtained once from [`Reader::volume`](crate::Reader::volume),
+//! which checks the file's mode against `T` and decodes the data if needed.
+//! Every accessor afterwards is a plain slice operation with no per-call mode
+//! dispatch. Native-endian files borrow the reader's buffer (zero-copy);
+//! byte-swapped files are decoded into an owned buffer once.
+
+use crate::{DataBlock, Error, VolumeShape, Voxel, VoxelBlock};
+
+use std::borrow::Cow;
+
+/// A whole MRC volume typed as `T`, indexed in `[x, y, z]` voxel order.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> Result<(), mrc::Error> {
+/// let reader = mrc::Reader::open("density.mrc")?;
+/// let vol = reader.volume::<f32>()?;
+/// let centre = vol[(vol.shape().nx / 2, vol.shape().ny / 2, vol.shape().nz / 2)];
+/// for (z, section) in vol.sections().enumerate() {
+///     let max = section.iter().copied().fold(f32::MIN, f32::max);
+///     println!("z={z}: max {max}, centre {centre}");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Volume<'a, T: Voxel> {
+    shape: VolumeShape,
+    data: Cow<'a, [T]>,
+    /// Spatial axis (0 = X, 1 = Y, 2 = Z) stored along columns, rows and
+    /// sections: `mapc - 1`, `mapr - 1`, `maps - 1`.
+    axes: [usize; 3],
+}
+
+impl<'a, T: Voxel> Volume<'a, T> {
+    /// Volume dimensions.
+    #[inline]
+    pub fn shape(&self) -> VolumeShape {
+        self.shape
+    }
+
+    /// All voxels in C-order (X fastest, Z slowest).
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    /// `true` if the data borrows the reader's buffer rather than a decoded copy.
+    #[inline]
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self.data, Cow::Borrowed(_))
+    }
+
+    /// Linear index of voxel `(x, y, z)`, or `None` if outside.
+    #[inline]
+    fn index_of(&self, x: usize, y: usize, z: usize) -> Option<usize> {
+        let s = self.shape;
+        (x < s.nx && y < s.ny && z < s.nz).then(|| (z * s.ny + y) * s.nx + x)
+    }
+
+    /// Voxel at `(x, y, z)`, or `None` if out of bounds.
+    #[inline]
+    pub fn get(&self, x: usize, y: usize, z: usize) -> Option<&T> {
+        self.data.get(self.index_of(x, y, z)?)
+    }
+
+    /// The spatial axis (0 = X, 1 = Y, 2 = Z) stored along columns, rows
+    /// and sections, from the header's `mapc`, `mapr` and `maps`.
+    ///
+    /// `[0, 1, 2]` for the usual X-fastest layout, and also when the header's
+    /// mapping is not a permutation of 1, 2, 3.
+    #[inline]
+    pub fn axis_order(&self) -> [usize; 3] {
+        self.axes
+    }
+
+    /// Extent along X, Y and Z, whatever order they are stored in.
+    #[inline]
+    pub fn logical_shape(&self) -> [usize; 3] {
+        let mut out = [0; 3];
+        let s = self.shape;
+        for (axis, n) in self.axes.into_iter().zip([s.nx, s.ny, s.nz]) {
+            out[axis] = n;
+        }
+        out
+    }
+
+    /// Voxel at spatial position `(x, y, z)`, or `None` if out of bounds.
+    ///
+    /// Unlike [`get`](Self::get), which takes storage coordinates (column,
+    /// row, section), this honours the header's axis mapping, so a file
+    /// stored with `mapc/mapr/maps = 3/1/2` (Z fastest) is addressed the
+    /// same way as an X-fastest one. The two agree for the usual `1/2/3`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let reader = mrc::Reader::open("permuted.mrc")?;
+    /// let vol = reader.volume::<f32>()?;
+    /// let [nx, ny, nz] = vol.logical_shape();
+    /// let centre = vol.get_logical(nx / 2, ny / 2, nz / 2);
+    /// println!("centre voxel: {centre:?}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn get_logical(&self, x: usize, y: usize, z: usize) -> Option<&T> {
+        let p = [x, y, z];
+        self.get(p[self.axes[0]], p[self.axes[1]], p[self.axes[2]])
+    }
+
+    /// Mutable voxel at `(x, y, z)`, or `None` if out of bounds.
+    ///
+    /// A volume that still borrows the reader's buffer is copied into an
+    /// owned one first (see [`is_borrowed`](Self::is_borrowed)); the file
+    /// itself is never modified.
+    #[inline]
+    pub fn get_mut(&mut self, x: usize, y: usize, z: usize) -> Option<&mut T> {
+        let i = self.index_of(x, y, z)?;
+        self.data.to_mut().get_mut(i)
+    }
+
+    /// Voxel at `(x, y, z)` without bounds checks, for inner loops whose
+    /// ranges are already known to be inside the volume.
+    ///
+    /// # Safety
+    ///
+    /// `x < nx`, `y < ny` and `z < nz` must hold; anything else is
+    /// undefined behaviour.
+    #[inline]
+    pub unsafe fn get_unchecked(&self, x: usize, y: usize, z: usize) -> &T {
+        let s = self.shape;
+        // SAFETY: the caller guarantees the index is inside the volume, and
+        // `data` holds exactly `nx * ny * nz` voxels.
+        unsafe { self.data.get_unchecked((z * s.ny + y) * s.nx + x) }
+    }
+
+    /// Z-section `z` as a row-major `nx * ny` slice, or `None` if out of bounds.
+    #[inline]
+    pub fn section(&self, z: usize) -> Option<&[T]> {
+        let n = self.shape.nx * self.shape.ny;
+        if z < self.shape.nz {
+            self.data.get(z * n..(z + 1) * n)
+        } else {
+            None
+        }
+    }
+
+    /// Copy the plane perpendicular to `axis` at `index` into a
+    /// [`VoxelBlock`] one voxel thick along `axis`.
+    ///
+    /// The block keeps its place in the volume: an X plane at `x = 10` has
+    /// offset `[10, 0, 0]` and shape `[1, ny, nz]`, so its data runs Y
+    /// fastest, then Z. A Z plane is the same as [`section`](Self::section)
+    /// copied.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// use mrc::Axis;
+    /// let reader = mrc::Reader::open("tomogram.mrc")?;
+    /// let vol = reader.volume::<f32>()?;
+    /// let s = vol.shape();
+    /// let xy = vol.plane(Axis::Z, s.nz / 2)?;
+    /// let xz = vol.plane(Axis::Y, s.ny / 2)?;
+    /// let yz = vol.plane(Axis::X, s.nx / 2)?;
+    /// println!("{:?} {:?} {:?}", xy.shape, xz.shape, yz.shape);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::BoundsError`] if `index` is past the end of `axis`.
+    pub fn plane(&self, axis: crate::Axis, index: usize) -> Result<VoxelBlock<T>, Error> {
+        let s = self.shape;
+        let dims = [s.nx, s.ny, s.nz];
+        let mut offset = [0; 3];
+        let mut shape = dims;
+        offset[axis.index()] = index;
+        shape[axis.index()] = 1;
+        if index >= dims[axis.index()] {
+            return Err(Error::BoundsError {
+                offset: Some(offset),
+                shape: Some(shape),
+                volume: Some(dims),
+            });
+        }
+        let data = match axis {
+            crate::Axis::X => self.data[index..].iter().step_by(s.nx).copied().collect(),
+            crate::Axis::Y => {
+                let mut out = Vec::with_capacity(s.nx * s.nz);
+                for section in self.sections() {
+                    out.extend_from_slice(&section[index * s.nx..(index + 1) * s.nx]);
+                }
+                out
+            }
+            crate::Axis::Z => self.section(index).unwrap_or_default().to_vec(),
+        };
+        Ok(VoxelBlock {
+            offset,
+            shape,
+            data,
+        })
+    }
+
+    /// Iterate over Z-sections.
+    pub fn sections(&self) -> impl ExactSizeIterator<Item = &[T]> + '_ {
+        let n = (self.shape.nx * self.shape.ny).max(1);
+        self.data.chunks_exact(n)
+    }
+
+    /// Iterate over all voxels in C-order.
+    #[inline]
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    /// Take the data, copying only if it is still borrowed.
+    pub fn into_vec(self) -> Vec<T> {
+        self.data.into_owned()
+    }
+
+    /// Convert into a full-volume [`VoxelBlock`].
+    pub fn into_block(self) -> VoxelBlock<T> {
+        let s = self.shape;
+        VoxelBlock {
+            offset: [0, 0, 0],
+            shape: [s.nx, s.ny, s.nz],
+            data: self.data.into_owned(),
+        }
+    }
+}
+
+impl<T: Voxel + Into<f64>> Volume<'_, T> {
+    /// Exact percentile `q` (`0.0..=100.0`) of the voxel values.
+    ///
+    /// Interpolates linearly between the two nearest ranks, like NumPy's
+    /// `percentile`, and skips NaN. Works on a copy of the whole volume as
+    /// `f64`; see [`percentile_sampled`](Self::percentile_sampled) for large
+    /// volumes. Returns `None` for a `q` outside `0.0..=100.0` or a volume
+    /// with no non-NaN voxels.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let reader = mrc::Reader::open("map.mrc")?;
+    /// let level = reader.volume::<f32>()?.percentile(99.5);
+    /// println!("isosurface level: {level:?}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn percentile(&self, q: f64) -> Option<f64> {
+        crate::engine::stats::percentile(self.data.iter().copied(), q)
+    }
+
+    /// Percentile `q` of an evenly strided sample of at most `max_samples`
+    /// voxels.
+    ///
+    /// The same as [`percentile`](Self::percentile) when the volume has no
+    /// more than `max_samples` voxels; otherwise every `k`-th voxel is used,
+    /// which bounds the copy for volumes too large to duplicate. A
+    /// `max_samples` of 0 is treated as 1.
+    pub fn percentile_sampled(&self, q: f64, max_samples: usize) -> Option<f64> {
+        let step = self.data.len().div_ceil(max_samples.max(1)).max(1);
+        crate::engine::stats::percentile(self.data.iter().step_by(step).copied(), q)
+    }
+}
+
+#[cfg(feature = "f16")]
+impl Volume<'_, crate::f16> {
+    /// Iterate over all voxels in C-order, widened to `f32` one at a time.
+    ///
+    /// The blessed way to consume Mode 12 data: no `f32` copy of the volume
+    /// is made, and every `f16` value is exactly representable as `f32`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let reader = mrc::Reader::open("half.mrc")?;
+    /// let vol = reader.volume::<mrc::f16>()?;
+    /// let sum: f64 = vol.iter_as_f32().map(f64::from).sum();
+    /// println!("mean {}", sum / vol.as_slice().len() as f64);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn iter_as_f32(&self) -> impl ExactSizeIterator<Item = f32> + '_ {
+        self.data.iter().map(|v| v.to_f32())
+    }
+}
+
+/// `(x, y, z)` indexing; panics when out of bounds, like slices.
+impl<T: Voxel> core::ops::Index<(usize, usize, usize)> for Volume<'_, T> {
+    type Output = T;
+
+    #[inline]
+    #[track_caller]
+    fn index(&self, (x, y, z): (usize, usize, usize)) -> &T {
+        match self.get(x, y, z) {
+            Some(v) => v,
+            None => panic!(
+                "voxel index ({x}, {y}, {z}) out of bounds for volume of shape {:?}",
+                [self.shape.nx, self.shape.ny, self.shape.nz]
+            ),
+        }
+    }
+}
+
+/// Mutable `(x, y, z)` indexing, copying borrowed data first as
+/// [`get_mut`](Volume::get_mut) does; panics when out of bounds.
+impl<T: Voxel> core::ops::IndexMut<(usize, usize, usize)> for Volume<'_, T> {
+    #[inline]
+    #[track_caller]
+    fn index_mut(&mut self, (x, y, z): (usize, usize, usize)) -> &mut T {
+        let shape = [self.shape.nx, self.shape.ny, self.shape.nz];
+        match self.get_mut(x, y, z) {
+            Some(v) => v,
+            None => {
+                panic!("voxel index ({x}, {y}, {z}) out of bounds for volume of shape {shape:?}")
+            }
+        }
+    }
+}
+
+impl<'a, T: Voxel> IntoIterator for &'a Volume<'_, T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl crate::Reader {
+    /// View the whole volume as `T`, checking the mode once.
+    ///
+    /// Native-endian data is borrowed without copying; otherwise it is
+    /// decoded once into an owned buffer. Use [`convert`](Self::convert)
+    /// instead when the file's mode differs from the type you want.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ModeMismatch`] if the file's mode is not `T::MODE`,
+    /// and propagates any read error.
+    pub fn volume<T: Voxel>(&self) -> Result<Volume<'_, T>, Error> {
+        if self.mode() != T::MODE {
+            return Err(Error::ModeMismatch {
+                file_mode: self.mode(),
+                requested_mode: T::MODE,
+                offset: None,
+            });
+        }
+        let data = match self.read_volume()? {
+            DataBlock::Borrowed { data, .. } => T::from_view(data).map(Cow::Borrowed),
+            DataBlock::Owned { data, .. } => T::from_owned(data).map(Cow::Owned),
+        };
+        Ok(Volume {
+            axes: self.header().storage_axes(),
+            shape: self.shape(),
+            data: data.ok_or(Error::TypeMismatch {
+                expected: T::BYTE_SIZE,
+                actual: self.mode().byte_size(),
+            })?,
+        })
+    }
+}
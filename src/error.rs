@@ -115,7 +115,10 @@ pub enum Error {
         offset: Option<[usize; 3]>,
     },
     /// Detailed header validation failed.
-    #[error("Invalid header: {0}")]
+    ///
+    /// The message ends with the field at fault and its byte offset; see
+    /// [`HeaderValidationError::field`].
+    #[error("Invalid header: {0} ({field} at byte {offset})", field = .0.field().0, offset = .0.field().1)]
     InvalidHeaderDetailed(#[from] HeaderValidationError),
     /// Header statistics do not match the actual data.
     #[error(
@@ -144,7 +147,14 @@ pub enum Error {
     #[error("Memory mapping error")]
     Mmap,
     /// The file size does not match the header's declared data size.
-    #[error("File size mismatch: expected {expected} bytes, got {actual} bytes")]
+    ///
+    /// The message also states how many bytes are missing or extra. When a
+    /// file is opened permissively the same message is returned as a
+    /// warning instead.
+    #[error(
+        "File size mismatch: expected {expected} bytes, got {actual} bytes ({})",
+        size_gap(*expected, *actual)
+    )]
     FileSizeMismatch {
         /// Expected file size in bytes (header + extended header + data).
         expected: usize,
@@ -159,6 +169,81 @@ pub enum Error {
         /// The MZ (sampling along Z) value from the header.
         mz: i32,
     },
+    /// The file does not start like an MRC file: the MAP field is not
+    /// `"MAP "` or the machine stamp is not a recognized little- or
+    /// big-endian stamp.
+    ///
+    /// Only raised when opening in strict mode; permissive opening accepts
+    /// the legacy variants (`"MAP\0"`, zeroed fields) with a warning.
+    #[error(
+        "Not an MRC file: MAP field {map:02X?} at byte 208, machine stamp {machst:02X?} at byte 212"
+    )]
+    NotAnMrcFile {
+        /// The raw MAP bytes (word 53).
+        map: [u8; 4],
+        /// The raw MACHST bytes (word 54).
+        machst: [u8; 4],
+    },
+    /// `nsymbt`, the extended header size, is impossible for this file.
+    ///
+    /// Raised on open when `nsymbt` reaches past the end of the file, and in
+    /// strict mode also when it is negative or not a whole number of records
+    /// of the declared EXTTYP (see [`ExtHeaderType::record_size`](crate::ExtHeaderType::record_size)).
+    #[error("Invalid extended header size: NSYMBT at byte 92 is {nsymbt} ({reason})")]
+    InvalidExtendedHeaderSize {
+        /// The NSYMBT value from the header.
+        nsymbt: i32,
+        /// What is wrong with it.
+        reason: String,
+    },
+    /// The data is larger than this target can address.
+    ///
+    /// Only possible where `usize` is narrower than `u64`, such as 32-bit
+    /// and WASM targets, when a file or block would have to be held in
+    /// memory or mapped whole.
+    #[error(
+        "Data too large for this platform: {bytes} bytes exceeds usize::MAX ({})",
+        usize::MAX
+    )]
+    TooLargeForPlatform {
+        /// Number of bytes that would have been needed.
+        bytes: u64,
+    },
+    /// An extended-header record is malformed.
+    ///
+    /// Returned by [`Reader::try_parse_extended_header`](crate::Reader::try_parse_extended_header)
+    /// with the absolute file offset of the bad bytes.
+    #[error(
+        "Invalid {} extended header: record {record}, {field} at byte {offset}: {reason}",
+        String::from_utf8_lossy(exttyp)
+    )]
+    InvalidExtendedHeader {
+        /// The EXTTYP of the extended header.
+        exttyp: [u8; 4],
+        /// Index of the record at fault.
+        record: usize,
+        /// The field being parsed.
+        field: String,
+        /// Absolute byte offset of that field in the file.
+        offset: u64,
+        /// What is wrong with it.
+        reason: String,
+    },
+    /// The machine stamp declares a non-IEEE floating-point format (VAX,
+    /// Cray or Convex), which this crate cannot decode.
+    ///
+    /// See [`MachineStamp::parse`](crate::MachineStamp::parse).
+    #[error(
+        "Unsupported floating-point format: machine stamp {machst:02X?} at byte 212 is not IEEE"
+    )]
+    UnsupportedFloatFormat {
+        /// The raw MACHST bytes.
+        machst: [u8; 4],
+    },
+    /// The operation was stopped through a
+    /// [`CancellationToken`](crate::CancellationToken).
+    #[error("Operation cancelled")]
+    Cancelled,
     /// A value exceeds the representable range of the target type.
     ///
     /// Raised by [`convert_u16_slice_to_u8`](crate::convert_u16_slice_to_u8)
@@ -173,7 +258,60 @@ pub enum Error {
     },
 }
 
+/// "N bytes missing" or "N extra bytes" for a [`Error::FileSizeMismatch`].
+fn size_gap(expected: usize, actual: usize) -> String {
+    if actual < expected {
+        format!("{} bytes missing", expected - actual)
+    } else {
+        format!("{} extra bytes", actual - expected)
+    }
+}
+
 impl Error {
+    /// The category of this error, stable across releases.
+    ///
+    /// Variants gain fields over time; the kind does not change, so match on
+    /// it where only the category matters.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mrc::{Error, ErrorKind};
+    ///
+    /// let err = Error::NotAnMrcFile { map: [0; 4], machst: [0; 4] };
+    /// assert_eq!(err.kind(), ErrorKind::InvalidHeader);
+    /// assert_eq!(err.code(), 3);
+    /// ```
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Io(_) => ErrorKind::Io,
+            #[cfg(feature = "mmap")]
+            Self::Mmap => ErrorKind::Io,
+            Self::InvalidHeader
+            | Self::InvalidHeaderDetailed(_)
+            | Self::NotAnMrcFile { .. }
+            | Self::InvalidExtendedHeaderSize { .. }
+            | Self::InvalidExtendedHeader { .. } => ErrorKind::InvalidHeader,
+            Self::UnsupportedMode | Self::UnsupportedFloatFormat { .. } => ErrorKind::Unsupported,
+            Self::BoundsError { .. } => ErrorKind::OutOfBounds,
+            Self::TypeMismatch { .. }
+            | Self::BlockShapeMismatch { .. }
+            | Self::ModeMismatch { .. } => ErrorKind::Mismatch,
+            Self::FileSizeMismatch { .. } => ErrorKind::FileSize,
+            Self::StatsMismatch { .. } => ErrorKind::StatsMismatch,
+            Self::NotAVolumeStack { .. } => ErrorKind::NotAVolumeStack,
+            Self::ValueOutOfRange { .. } => ErrorKind::ValueOutOfRange,
+            Self::TooLargeForPlatform { .. } => ErrorKind::TooLarge,
+            Self::Cancelled => ErrorKind::Cancelled,
+        }
+    }
+
+    /// Stable numeric code of this error's [`kind`](Self::kind); see
+    /// [`ErrorKind::code`].
+    pub fn code(&self) -> i32 {
+        self.kind().code()
+    }
+
     /// Create a bounds error without detailed context.
     ///
     /// Use this in cold error paths where the offset/shape/volume are not
@@ -187,6 +325,121 @@ impl Error {
             volume: None,
         }
     }
+
+    /// An [`Io`](Self::Io) error of kind
+    /// [`InvalidInput`](std::io::ErrorKind::InvalidInput), for arguments
+    /// the caller got wrong.
+    #[cold]
+    pub(crate) fn invalid_input(msg: impl Into<String>) -> Self {
+        Self::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            msg.into(),
+        ))
+    }
+
+    /// Convert a byte count to `usize`, or fail with
+    /// [`TooLargeForPlatform`](Self::TooLargeForPlatform) if the target's
+    /// address space cannot hold it.
+    pub(crate) fn addressable(bytes: u64) -> Result<usize, Self> {
+        usize::try_from(bytes).map_err(|_| Self::TooLargeForPlatform { bytes })
+    }
+}
+
+/// Category of an [`Error`], for FFI, logging and metrics.
+///
+/// Each kind has a fixed numeric [`code`](Self::code) that will not be
+/// reused; the C API returns the same numbers as its `MRC_ERR_*` status
+/// codes. New kinds may be added in minor releases.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+#[repr(i32)]
+pub enum ErrorKind {
+    /// Reading, writing or mapping the file failed.
+    Io = 2,
+    /// The header is malformed or the file is not an MRC file.
+    InvalidHeader = 3,
+    /// The mode or floating-point format is not supported.
+    Unsupported = 4,
+    /// A region lies outside the volume.
+    OutOfBounds = 5,
+    /// A voxel type, buffer length or block shape does not match.
+    Mismatch = 6,
+    /// The file length disagrees with the header.
+    FileSize = 7,
+    /// Header statistics disagree with the data.
+    StatsMismatch = 8,
+    /// A volume-stack operation on a file that is not one.
+    NotAVolumeStack = 9,
+    /// A value does not fit the target type.
+    ValueOutOfRange = 10,
+    /// The data exceeds the target's address space.
+    TooLarge = 11,
+    /// The operation was cancelled.
+    Cancelled = 12,
+}
+
+impl ErrorKind {
+    /// The stable numeric code, never 0 (which C callers use for success).
+    pub fn code(self) -> i32 {
+        self as i32
+    }
+}
+
+impl HeaderValidationError {
+    /// The header field at fault and its byte offset in the file, for
+    /// inspecting the raw bytes in a hex viewer.
+    ///
+    /// Where several fields are involved the first offending one is named,
+    /// e.g. `NY` for dimensions `4 × 0 × 0`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mrc::HeaderValidationError;
+    ///
+    /// let err = HeaderValidationError::InvalidNversion(7);
+    /// assert_eq!(err.field(), ("NVERSION", 108));
+    /// ```
+    pub fn field(&self) -> (&'static str, u64) {
+        use crate::header::*;
+        let (name, offset) = match *self {
+            Self::InvalidDimensions { nx, ny, .. } => match (nx > 0, ny > 0) {
+                (false, _) => ("NX", OFFSET_NX),
+                (true, false) => ("NY", OFFSET_NY),
+                (true, true) => ("NZ", OFFSET_NZ),
+            },
+            Self::UnsupportedMode(_) => ("MODE", OFFSET_MODE),
+            Self::InvalidMap(_) => ("MAP", OFFSET_MAP),
+            Self::InvalidIspg(_) => ("ISPG", OFFSET_ISPG),
+            Self::InvalidAxisMapping { .. } => ("MAPC", OFFSET_MAPC),
+            Self::InvalidNsymbt(_) => ("NSYMBT", OFFSET_NSYMBT),
+            Self::InvalidNlabl(_) | Self::LabelCountMismatch { .. } => ("NLABL", OFFSET_NLABL),
+            Self::InvalidNversion(_) => ("NVERSION", OFFSET_NVERSION),
+            Self::InvalidVolumeStack { .. } => ("MZ", OFFSET_MZ),
+            Self::InvalidSampling { mx, my, .. } => match (mx > 0, my > 0) {
+                (false, _) => ("MX", OFFSET_MX),
+                (true, false) => ("MY", OFFSET_MY),
+                (true, true) => ("MZ", OFFSET_MZ),
+            },
+            Self::InvalidCellLengths { xlen, ylen, .. } => {
+                match (
+                    xlen.is_finite() && xlen > 0.0,
+                    ylen.is_finite() && ylen > 0.0,
+                ) {
+                    (false, _) => ("XLEN", OFFSET_XLEN),
+                    (true, false) => ("YLEN", OFFSET_YLEN),
+                    (true, true) => ("ZLEN", OFFSET_ZLEN),
+                }
+            }
+            Self::SamplingMismatch { axis: 'X', .. } => ("MX", OFFSET_MX),
+            Self::SamplingMismatch { .. } => ("MY", OFFSET_MY),
+            Self::EmptyLabelBeforeFilled { index } => {
+                ("LABEL", OFFSET_LABEL + 80 * index.max(0) as usize)
+            }
+        };
+        (name, offset as u64)
+    }
 }
 
 impl From<Error> for std::io::Error {
@@ -276,6 +529,32 @@ pub enum HeaderValidationError {
         /// Sampling along Z.
         mz: i32,
     },
+    /// A unit-cell length is zero, negative or not finite while the others
+    /// are set, which gives a 0 Å (or nonsensical) pixel size. An all-zero
+    /// cell means "unknown" and is accepted.
+    #[error("Invalid cell lengths: xlen={xlen}, ylen={ylen}, zlen={zlen} (must all be positive)")]
+    InvalidCellLengths {
+        /// Cell length along X in Å.
+        xlen: f32,
+        /// Cell length along Y in Å.
+        ylen: f32,
+        /// Cell length along Z in Å.
+        zlen: f32,
+    },
+    /// The sampling along X or Y is wildly out of proportion to the number
+    /// of voxels on that axis, so `len / m` is not a plausible pixel size.
+    #[error(
+        "Sampling mismatch along {axis}: {n} voxels but sampling {m} ({ratio}× or more apart)",
+        ratio = crate::header::MAX_SAMPLING_RATIO
+    )]
+    SamplingMismatch {
+        /// The axis, `'X'` or `'Y'`.
+        axis: char,
+        /// Number of voxels along the axis (`nx` or `ny`).
+        n: i32,
+        /// Sampling along the axis (`mx` or `my`).
+        m: i32,
+    },
     /// The declared label count does not match the actual non-empty labels.
     #[error("Label count mismatch: nlabl={nlabl} but {actual} non-empty labels found")]
     LabelCountMismatch {
@@ -0,0 +1,178 @@
+//! [`arbitrary`] support for fuzzing and property tests.
+//!
+//! Three generators, from hostile to well-formed:
+//!
+//! * [`Header`] — any 1024 bytes decoded as a header. Most are invalid;
+//!   this is the input for fuzzing header parsing and validation.
+//! * [`ValidHeader`] — a header that passes [`Header::validate`], with
+//!   small dimensions, any supported mode, any axis order and a
+//!   consistent cell.
+//! * [`SmallVolume`] — a [`ValidHeader`] plus exactly the right number of
+//!   data bytes, ready for [`Reader::from_bytes`](crate::Reader::from_bytes)
+//!   via [`SmallVolume::to_file_bytes`], for fuzzing readers and converters.
+//!
+//! ```no_run
+//! use arbitrary::{Arbitrary, Unstructured};
+//! use mrc::{Reader, SmallVolume};
+//!
+//! fn fuzz_target(data: &[u8]) {
+//!     let mut u = Unstructured::new(data);
+//!     if let Ok(vol) = SmallVolume::arbitrary(&mut u) {
+//!         let reader = Reader::from_bytes(vol.to_file_bytes()).expect("valid volume");
+//!         let _ = reader.convert::<f32>().read_volume();
+//!     }
+//! }
+//! ```
+//!
+//! Requires the `arbitrary` feature (disabled by default).
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{Header, Mode};
+
+/// Largest dimension [`ValidHeader`] generates along any axis.
+const MAX_DIM: i32 = 16;
+
+impl<'a> Arbitrary<'a> for Header {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let bytes: [u8; 1024] = u.arbitrary()?;
+        Ok(Header::decode_from_bytes(&bytes))
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (1024, Some(1024))
+    }
+}
+
+/// A header that passes [`Header::validate`], for fuzzing past validation.
+///
+/// Dimensions are 1–16 per axis with `m = n`, the cell is `n` times a
+/// pixel size of 0.25–64 Å, `nsymbt` is 0, `ispg` is 0 or 1, and the mode,
+/// axis order and origin are arbitrary. Density statistics are the "not
+/// computed" values. The file is little-endian.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValidHeader(pub Header);
+
+impl<'a> Arbitrary<'a> for ValidHeader {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        const MODES: [Mode; 8] = [
+            Mode::Int8,
+            Mode::Int16,
+            Mode::Float32,
+            Mode::Int16Complex,
+            Mode::Float32Complex,
+            Mode::Uint16,
+            Mode::Float16,
+            Mode::Packed4Bit,
+        ];
+        const AXES: [[i32; 3]; 6] = [
+            [1, 2, 3],
+            [1, 3, 2],
+            [2, 1, 3],
+            [2, 3, 1],
+            [3, 1, 2],
+            [3, 2, 1],
+        ];
+
+        let mut h = Header::new();
+        h.mode = u.choose(&MODES)?.as_i32();
+        let n: [i32; 3] = [
+            u.int_in_range(1..=MAX_DIM)?,
+            u.int_in_range(1..=MAX_DIM)?,
+            u.int_in_range(1..=MAX_DIM)?,
+        ];
+        (h.nx, h.ny, h.nz) = (n[0], n[1], n[2]);
+        (h.mx, h.my, h.mz) = (n[0], n[1], n[2]);
+        let apix = f32::from(u.int_in_range(1u16..=256)?) / 4.0;
+        (h.xlen, h.ylen, h.zlen) = (n[0] as f32 * apix, n[1] as f32 * apix, n[2] as f32 * apix);
+        [h.mapc, h.mapr, h.maps] = *u.choose(&AXES)?;
+        h.ispg = i32::from(u.arbitrary::<bool>()?);
+        let start: [i16; 3] = u.arbitrary()?;
+        [h.nxstart, h.nystart, h.nzstart] = start.map(i32::from);
+        h.origin = u.arbitrary::<[i16; 3]>()?.map(f32::from);
+        Ok(Self(h))
+    }
+}
+
+/// A valid header with matching voxel bytes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SmallVolume {
+    /// The header, from [`ValidHeader`].
+    pub header: Header,
+    /// Exactly [`Header::data_size`] bytes of voxel data, in file order.
+    /// Any bit pattern, so float modes include NaN and infinities.
+    pub data: Vec<u8>,
+}
+
+impl SmallVolume {
+    /// The complete file image: 1024-byte header followed by the data.
+    pub fn to_file_bytes(&self) -> Vec<u8> {
+        let mut raw = [0u8; 1024];
+        self.header.encode_to_bytes(&mut raw);
+        let mut bytes = Vec::with_capacity(1024 + self.data.len());
+        bytes.extend_from_slice(&raw);
+        bytes.extend_from_slice(&self.data);
+        bytes
+    }
+}
+
+impl<'a> Arbitrary<'a> for SmallVolume {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let ValidHeader(header) = u.arbitrary()?;
+        let len = header.data_size().unwrap_or(0);
+        // Pad with zeros once the input runs out rather than failing.
+        let available = u.bytes(len.min(u.len()))?;
+        let mut data = available.to_vec();
+        data.resize(len, 0);
+        Ok(Self { header, data })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random input for `Unstructured`.
+    fn noise(seed: u64, len: usize) -> Vec<u8> {
+        let mut x = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+        (0..len)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 7;
+                x ^= x << 17;
+                x as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn valid_headers_validate() {
+        for seed in 0..200 {
+            let bytes = noise(seed, 64);
+            let ValidHeader(h) = ValidHeader::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+            assert!(h.validate(), "seed {seed}: {:?}", h.validate_detailed());
+        }
+    }
+
+    #[test]
+    fn small_volumes_open() {
+        for seed in 0..200 {
+            let bytes = noise(seed, 4096);
+            let vol = SmallVolume::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+            let reader = crate::Reader::from_bytes(vol.to_file_bytes()).unwrap();
+            assert_eq!(reader.header().mode, vol.header.mode);
+            let _ = reader.read_volume().unwrap();
+        }
+    }
+
+    #[test]
+    fn adversarial_headers_do_not_panic() {
+        for seed in 0..500 {
+            let bytes = noise(seed, 1100);
+            let h = Header::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+            let _ = h.validate_detailed();
+            let _ = h.validate_permissive();
+            let _ = crate::Reader::from_bytes(bytes);
+        }
+    }
+}
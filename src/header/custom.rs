@@ -0,0 +1,151 @@
+// ============================================================================
+// Custom extended-header formats
+// ============================================================================
+
+//! Extended-header layouts defined outside this crate.
+//!
+//! Camera vendors and facilities write their own fixed-size records under
+//! their own EXTTYP. Implement [`ExtHeaderFormat`] for such a layout to
+//! parse and write it like the built-in ones, and
+//! [`register_ext_header_format`] it so that
+//! [`Reader::ext_header_typed`](crate::Reader::ext_header_typed) finds it
+//! by the file's EXTTYP.
+
+use crate::Error;
+
+use std::any::Any;
+use std::sync::{PoisonError, RwLock};
+
+/// A fixed-size-record extended header layout.
+///
+/// # Examples
+///
+/// ```
+/// use mrc::{Error, ExtHeaderFormat};
+///
+/// /// One 8-byte record per frame: exposure (s) and dose (e⁻/Å²).
+/// struct AcmeCam;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct AcmeFrame {
+///     exposure: f32,
+///     dose: f32,
+/// }
+///
+/// impl ExtHeaderFormat for AcmeCam {
+///     type Record = AcmeFrame;
+///     const EXTTYP: [u8; 4] = *b"ACME";
+///     const RECORD_SIZE: usize = 8;
+///
+///     fn parse_record(b: &[u8]) -> Result<AcmeFrame, Error> {
+///         let f = |i: usize| f32::from_le_bytes([b[i], b[i + 1], b[i + 2], b[i + 3]]);
+///         Ok(AcmeFrame { exposure: f(0), dose: f(4) })
+///     }
+///
+///     fn serialize_record(r: &AcmeFrame, out: &mut [u8]) -> Result<(), Error> {
+///         out[..4].copy_from_slice(&r.exposure.to_le_bytes());
+///         out[4..].copy_from_slice(&r.dose.to_le_bytes());
+///         Ok(())
+///     }
+/// }
+///
+/// let frames = [AcmeFrame { exposure: 0.1, dose: 1.5 }];
+/// let bytes = AcmeCam::serialize(&frames)?;
+/// assert_eq!(AcmeCam::parse(&bytes)?, frames);
+/// # Ok::<(), Error>(())
+/// ```
+pub trait ExtHeaderFormat: 'static {
+    /// The parsed form of one record.
+    type Record: Send + Sync + 'static;
+
+    /// EXTTYP identifier written to header bytes 104–107.
+    const EXTTYP: [u8; 4];
+
+    /// Size of one record in bytes; must be non-zero.
+    const RECORD_SIZE: usize;
+
+    /// Parse one record from exactly [`RECORD_SIZE`](Self::RECORD_SIZE)
+    /// bytes.
+    ///
+    /// # Errors
+    ///
+    /// Any error describing why the record is invalid.
+    fn parse_record(bytes: &[u8]) -> Result<Self::Record, Error>;
+
+    /// Encode `record` into `out`, which is
+    /// [`RECORD_SIZE`](Self::RECORD_SIZE) zeroed bytes.
+    ///
+    /// # Errors
+    ///
+    /// Any error describing why the record cannot be encoded.
+    fn serialize_record(record: &Self::Record, out: &mut [u8]) -> Result<(), Error>;
+
+    /// Parse every record in `bytes`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidExtendedHeader`] if `bytes` ends in a partial
+    /// record, and the first error from
+    /// [`parse_record`](Self::parse_record).
+    fn parse(bytes: &[u8]) -> Result<Vec<Self::Record>, Error> {
+        let size = Self::RECORD_SIZE.max(1);
+        let whole = bytes.len() - bytes.len() % size;
+        if whole != bytes.len() {
+            return Err(Error::InvalidExtendedHeader {
+                exttyp: Self::EXTTYP,
+                record: whole / size,
+                field: "record".to_owned(),
+                offset: 1024 + whole as u64,
+                reason: format!("{} bytes left, a record is {size}", bytes.len() - whole),
+            });
+        }
+        bytes.chunks_exact(size).map(Self::parse_record).collect()
+    }
+
+    /// Encode `records` back to back, ready for
+    /// [`WriterBuilder::extended_header`](crate::WriterBuilder::extended_header).
+    ///
+    /// # Errors
+    ///
+    /// The first error from [`serialize_record`](Self::serialize_record).
+    fn serialize(records: &[Self::Record]) -> Result<Vec<u8>, Error> {
+        let mut out = vec![0u8; records.len() * Self::RECORD_SIZE];
+        for (record, chunk) in records.iter().zip(out.chunks_exact_mut(Self::RECORD_SIZE)) {
+            Self::serialize_record(record, chunk)?;
+        }
+        Ok(out)
+    }
+}
+
+/// Type-erased parser stored in the registry.
+type ErasedParse = fn(&[u8]) -> Result<Box<dyn Any + Send + Sync>, Error>;
+
+fn parse_erased<F: ExtHeaderFormat>(bytes: &[u8]) -> Result<Box<dyn Any + Send + Sync>, Error> {
+    Ok(Box::new(F::parse(bytes)?))
+}
+
+static REGISTRY: RwLock<Vec<([u8; 4], ErasedParse)>> = RwLock::new(Vec::new());
+
+/// Make `F` the format used for files whose EXTTYP is `F::EXTTYP`,
+/// replacing any format registered for it before.
+///
+/// Registration is process-wide. Built-in types (`FEI1`, `SERI`, …) are
+/// still parsed by [`Reader::parse_extended_header`](crate::Reader::parse_extended_header);
+/// registering one of their EXTTYPs only affects
+/// [`Reader::ext_header_typed`](crate::Reader::ext_header_typed).
+pub fn register_ext_header_format<F: ExtHeaderFormat>() {
+    let mut registry = REGISTRY.write().unwrap_or_else(PoisonError::into_inner);
+    registry.retain(|(exttyp, _)| *exttyp != F::EXTTYP);
+    registry.push((F::EXTTYP, parse_erased::<F>));
+}
+
+/// Parse `bytes` with the format registered for `exttyp`, or `None` if
+/// there is none.
+pub(crate) fn parse_registered(
+    exttyp: [u8; 4],
+    bytes: &[u8],
+) -> Option<Result<Box<dyn Any + Send + Sync>, Error>> {
+    let registry = REGISTRY.read().unwrap_or_else(PoisonError::into_inner);
+    let (_, parse) = registry.iter().find(|(t, _)| *t == exttyp)?;
+    Some(parse(bytes))
+}
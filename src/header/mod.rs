@@ -42,12 +42,14 @@
 
 pub mod agar;
 pub mod ccp4;
+pub mod custom;
 pub mod fei;
 pub mod mrco;
 pub mod seri;
 
 pub use agar::{AGAR_RECORD_SIZE, AgarRecord, parse_agar_records};
 pub use ccp4::{CCP4_RECORD_SIZE, Ccp4Record, parse_ccp4_records};
+pub use custom::{ExtHeaderFormat, register_ext_header_format};
 pub use fei::{
     FEI1_RECORD_SIZE, FEI2_RECORD_SIZE, Fei1Metadata, Fei2Metadata, parse_fei1_records,
     parse_fei2_records,
@@ -152,6 +154,28 @@ impl ExtHeaderType {
     pub fn from_header(header: &Header) -> Self {
         Self::from_exttyp(header.exttyp())
     }
+
+    /// Size in bytes of one record of this type, or `None` for HDF5 and
+    /// unknown types, which have no fixed record size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrc::ExtHeaderType;
+    /// assert_eq!(ExtHeaderType::Fei1.record_size(), Some(768));
+    /// assert_eq!(ExtHeaderType::Hdf5.record_size(), None);
+    /// ```
+    pub fn record_size(self) -> Option<usize> {
+        match self {
+            Self::Ccp4 => Some(CCP4_RECORD_SIZE),
+            Self::Mrco => Some(MRCO_RECORD_SIZE),
+            Self::Seri => Some(SERI_RECORD_SIZE),
+            Self::Agar => Some(AGAR_RECORD_SIZE),
+            Self::Fei1 => Some(FEI1_RECORD_SIZE),
+            Self::Fei2 => Some(FEI2_RECORD_SIZE),
+            Self::Hdf5 | Self::Unknown(_) => None,
+        }
+    }
 }
 
 /// Parsed extended header data, dispatched by [`ExtHeaderType`].
@@ -235,39 +259,39 @@ impl ExtHeaderData {
 }
 
 // Header field offsets (MRC2014 format)
-const OFFSET_NX: usize = 0;
-const OFFSET_NY: usize = 4;
-const OFFSET_NZ: usize = 8;
-const OFFSET_MODE: usize = 12;
-const OFFSET_NXSTART: usize = 16;
-const OFFSET_NYSTART: usize = 20;
-const OFFSET_NZSTART: usize = 24;
-const OFFSET_MX: usize = 28;
-const OFFSET_MY: usize = 32;
-const OFFSET_MZ: usize = 36;
-const OFFSET_XLEN: usize = 40;
-const OFFSET_YLEN: usize = 44;
-const OFFSET_ZLEN: usize = 48;
-const OFFSET_ALPHA: usize = 52;
-const OFFSET_BETA: usize = 56;
-const OFFSET_GAMMA: usize = 60;
-const OFFSET_MAPC: usize = 64;
-const OFFSET_MAPR: usize = 68;
-const OFFSET_MAPS: usize = 72;
-const OFFSET_DMIN: usize = 76;
-const OFFSET_DMAX: usize = 80;
-const OFFSET_DMEAN: usize = 84;
-const OFFSET_ISPG: usize = 88;
-const OFFSET_NSYMBT: usize = 92;
-const OFFSET_EXTRA: usize = 96;
-const OFFSET_EXTTYP: usize = 104; // extra[8..12]
-const OFFSET_NVERSION: usize = 108; // extra[12..16]
-const OFFSET_ORIGIN: usize = 196;
-const OFFSET_MAP: usize = 208;
-const OFFSET_MACHST: usize = 212;
-const OFFSET_RMS: usize = 216;
-const OFFSET_NLABL: usize = 220;
-const OFFSET_LABEL: usize = 224;
+pub(crate) const OFFSET_NX: usize = 0;
+pub(crate) const OFFSET_NY: usize = 4;
+pub(crate) const OFFSET_NZ: usize = 8;
+pub(crate) const OFFSET_MODE: usize = 12;
+pub(crate) const OFFSET_NXSTART: usize = 16;
+pub(crate) const OFFSET_NYSTART: usize = 20;
+pub(crate) const OFFSET_NZSTART: usize = 24;
+pub(crate) const OFFSET_MX: usize = 28;
+pub(crate) const OFFSET_MY: usize = 32;
+pub(crate) const OFFSET_MZ: usize = 36;
+pub(crate) const OFFSET_XLEN: usize = 40;
+pub(crate) const OFFSET_YLEN: usize = 44;
+pub(crate) const OFFSET_ZLEN: usize = 48;
+pub(crate) const OFFSET_ALPHA: usize = 52;
+pub(crate) const OFFSET_BETA: usize = 56;
+pub(crate) const OFFSET_GAMMA: usize = 60;
+pub(crate) const OFFSET_MAPC: usize = 64;
+pub(crate) const OFFSET_MAPR: usize = 68;
+pub(crate) const OFFSET_MAPS: usize = 72;
+pub(crate) const OFFSET_DMIN: usize = 76;
+pub(crate) const OFFSET_DMAX: usize = 80;
+pub(crate) const OFFSET_DMEAN: usize = 84;
+pub(crate) const OFFSET_ISPG: usize = 88;
+pub(crate) const OFFSET_NSYMBT: usize = 92;
+pub(crate) const OFFSET_EXTRA: usize = 96;
+pub(crate) const OFFSET_EXTTYP: usize = 104; // extra[8..12]
+pub(crate) const OFFSET_NVERSION: usize = 108; // extra[12..16]
+pub(crate) const OFFSET_ORIGIN: usize = 196;
+pub(crate) const OFFSET_MAP: usize = 208;
+pub(crate) const OFFSET_MACHST: usize = 212;
+pub(crate) const OFFSET_RMS: usize = 216;
+pub(crate) const OFFSET_NLABL: usize = 220;
+pub(crate) const OFFSET_LABEL: usize = 224;
 
 /// Default `extra` bytes with NVERSION=20141 encoded in little-endian.
 const DEFAULT_EXTRA: [u8; 100] = {
@@ -279,6 +303,13 @@ const DEFAULT_EXTRA: [u8; 100] = {
     e[15] = 0x00;
     e
 };
+
+/// Ratio between `nx` and `mx` (or `ny` and `my`) at which validation treats
+/// the sampling as inconsistent with the dimensions. Crystallographic maps
+/// often cover a fraction or a few multiples of the unit cell, so only
+/// mismatches far beyond that are rejected.
+pub(crate) const MAX_SAMPLING_RATIO: i32 = 100;
+
 /// Mirror of the 1024-byte MRC-2014 fixed header.
 ///
 /// Every field is a typed public member — dimensions, cell parameters,
@@ -443,7 +474,8 @@ impl Header {
     /// Size, in bytes, of the voxel data block.
     ///
     /// Returns `None` if the dimensions are so large that the calculation
-    /// overflows `usize`.
+    /// overflows `usize`, which on 32-bit targets means 4 GiB; see
+    /// [`data_size_u64`](Self::data_size_u64).
     ///
     /// ```
     /// use mrc::Header;
@@ -453,9 +485,27 @@ impl Header {
     /// assert_eq!(h.data_size(), Some(64 * 64 * 32 * 4));
     /// ```
     pub fn data_size(&self) -> Option<usize> {
-        let nx = self.nx.max(0) as usize;
-        let ny = self.ny.max(0) as usize;
-        let nz = self.nz.max(0) as usize;
+        usize::try_from(self.data_size_u64()?).ok()
+    }
+
+    #[inline]
+    /// Size, in bytes, of the voxel data block, independent of the target's
+    /// pointer width.
+    ///
+    /// Returns `None` for an unsupported mode or if the calculation
+    /// overflows `u64`.
+    ///
+    /// ```
+    /// use mrc::Header;
+    /// let mut h = Header::new();
+    /// h.nx = 4096; h.ny = 4096; h.nz = 1024;
+    /// h.mode = 2;
+    /// assert_eq!(h.data_size_u64(), Some(64 << 30));
+    /// ```
+    pub fn data_size_u64(&self) -> Option<u64> {
+        let nx = self.nx.max(0) as u64;
+        let ny = self.ny.max(0) as u64;
+        let nz = self.nz.max(0) as u64;
         match Mode::from_i32(self.mode) {
             Some(mode) => {
                 match mode {
@@ -468,7 +518,7 @@ impl Header {
                     _ => nx
                         .checked_mul(ny)?
                         .checked_mul(nz)?
-                        .checked_mul(mode.byte_size()),
+                        .checked_mul(mode.byte_size() as u64),
                 }
             }
             None => None, // unknown/unsupported mode
@@ -575,6 +625,10 @@ impl Header {
             });
         }
 
+        if let Some(e) = self.cell_error() {
+            return Err(e);
+        }
+
         if self.ispg >= 400 && self.ispg <= 630 && self.mz != 0 && self.nz % self.mz != 0 {
             return Err(HeaderValidationError::InvalidVolumeStack {
                 nz: self.nz,
@@ -674,6 +728,10 @@ impl Header {
             ));
         }
 
+        if let Some(e) = self.cell_error() {
+            warnings.push(e.to_string());
+        }
+
         if self.ispg >= 400 && self.ispg <= 630 && self.mz != 0 && self.nz % self.mz != 0 {
             warnings.push(format!(
                 "Volume stack: nz ({}) is not divisible by mz ({}) for ispg={}",
@@ -684,6 +742,34 @@ impl Header {
         Ok(warnings)
     }
 
+    /// Pixel-size checks shared by strict and permissive validation: cell
+    /// lengths must be positive, and `mx`/`my` within
+    /// [`MAX_SAMPLING_RATIO`] of `nx`/`ny`. Z is not compared, since image
+    /// and volume stacks legitimately use an `mz` unrelated to `nz`.
+    ///
+    /// An all-zero cell is accepted: it is how `mrcfile` and many camera
+    /// programs record an unknown pixel size. A cell with only some lengths
+    /// zero is the broken case behind "0 Å pixel size" reports.
+    fn cell_error(&self) -> Option<crate::HeaderValidationError> {
+        use crate::HeaderValidationError;
+
+        let lengths = [self.xlen, self.ylen, self.zlen];
+        let unset = lengths == [0.0; 3];
+        if !unset && !lengths.iter().all(|v| v.is_finite() && *v > 0.0) {
+            return Some(HeaderValidationError::InvalidCellLengths {
+                xlen: self.xlen,
+                ylen: self.ylen,
+                zlen: self.zlen,
+            });
+        }
+        for (axis, n, m) in [('X', self.nx, self.mx), ('Y', self.ny, self.my)] {
+            if n > 0 && m > 0 && n.max(m) / n.min(m) >= MAX_SAMPLING_RATIO {
+                return Some(HeaderValidationError::SamplingMismatch { axis, n, m });
+            }
+        }
+        None
+    }
+
     #[inline]
     /// Validate the MAP field, allowing for legacy variants.
     ///
@@ -805,7 +891,7 @@ impl Header {
         use crate::engine::codec::EndianCodec;
         let file_endian = self.detect_endian();
         let start = OFFSET_NVERSION - OFFSET_EXTRA;
-        i32::decode(&self.extra[start..start + 4], 0, file_endian)
+        i32::from_bytes(&self.extra[start..start + 4], 0, file_endian)
     }
 
     #[inline]
@@ -825,7 +911,7 @@ impl Header {
         use crate::engine::codec::EndianCodec;
         let file_endian = self.detect_endian();
         let start = OFFSET_NVERSION - OFFSET_EXTRA;
-        value.encode(&mut self.extra[start..start + 4], 0, file_endian);
+        value.to_bytes(&mut self.extra[start..start + 4], 0, file_endian);
     }
 
     /// Get the list of non-empty text labels.
@@ -861,7 +947,7 @@ impl Header {
     }
 
     /// Count how many of the 10 label slots contain non-empty text.
-    fn count_non_empty_labels(&self) -> usize {
+    pub(crate) fn count_non_empty_labels(&self) -> usize {
         (0..10).filter(|&i| !self.label_is_empty(i)).count()
     }
 
@@ -1008,6 +1094,43 @@ impl Header {
         (400..=630).contains(&self.ispg)
     }
 
+    /// Heuristic: is this a Fourier-space half-transform?
+    ///
+    /// Real-to-complex FFTs keep only `N/2 + 1` columns of an `N`-wide
+    /// transform; the rest follow from Hermitian symmetry. A complex-mode
+    /// (3 or 4) file is taken to be a half-transform when `nx` is
+    /// `mx / 2 + 1` (the sampling still describes the real-space grid) or,
+    /// failing that, `ny / 2 + 1` (the transform of a square image or cube).
+    /// Full complex transforms and other complex data give `false`, except
+    /// where their shape happens to match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrc::Header;
+    /// let mut h = Header::new();
+    /// h.mode = 4;
+    /// (h.nx, h.ny, h.nz) = (33, 64, 64);
+    /// assert!(h.is_half_transform());
+    /// h.mode = 2;
+    /// assert!(!h.is_half_transform());
+    /// ```
+    pub fn is_half_transform(&self) -> bool {
+        self.half_transform_width().is_some()
+    }
+
+    /// Width `N` of the full transform when [`is_half_transform`](Self::is_half_transform)
+    /// holds.
+    pub(crate) fn half_transform_width(&self) -> Option<usize> {
+        if !matches!(self.mode, 3 | 4) || self.nx <= 0 {
+            return None;
+        }
+        [self.mx, self.ny]
+            .into_iter()
+            .find(|&n| n > self.nx && n / 2 + 1 == self.nx)
+            .map(|n| n as usize)
+    }
+
     /// Configure the header as an image stack.
     ///
     /// Sets `ispg = 0` and `mz = 1`.
@@ -1256,6 +1379,93 @@ impl Header {
         Some(trimmed)
     }
 
+    /// Parse `key=value` pairs stored in the text labels.
+    ///
+    /// Each label is split on whitespace; a word containing `=` starts a
+    /// pair (split at its first `=`), and the words after it up to the next
+    /// pair make up the value, so `defocus=1.2 um tilt=30` yields
+    /// `defocus → "1.2 um"` and `tilt → "30"`. Text before the first pair
+    /// is ignored. When a key appears more than once, the newest label wins.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrc::Header;
+    /// let mut h = Header::new();
+    /// h.add_label("Warp export  pixel=0.83 defocus=1.2 um");
+    /// h.add_label("pixel=1.66");
+    /// let kv = h.labels_kv();
+    /// assert_eq!(kv["pixel"], "1.66");
+    /// assert_eq!(kv["defocus"], "1.2 um");
+    /// ```
+    pub fn labels_kv(&self) -> std::collections::BTreeMap<String, String> {
+        let mut map = std::collections::BTreeMap::new();
+        for i in 0..10 {
+            if let Some(label) = self.label_at(i) {
+                for (k, v) in parse_label_pairs(label) {
+                    map.insert(k.to_string(), v);
+                }
+            }
+        }
+        map
+    }
+
+    /// Store `key=value` in the labels, in the normalized one-pair-per-label
+    /// form that [`labels_kv`](Self::labels_kv) reads back.
+    ///
+    /// If the newest label mentioning `key` holds only that pair, it is
+    /// rewritten in place; otherwise a new label is appended with
+    /// [`add_label`](Self::add_label), dropping the oldest label when all
+    /// ten are in use.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `key` is empty or contains whitespace or `=`, if `value`
+    /// contains `=`, if either is not printable ASCII, or if the pair does
+    /// not fit in one 80-character label.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrc::Header;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut h = Header::new();
+    /// h.set_label_kv("dose", "1.5")?;
+    /// h.set_label_kv("dose", "2.0")?;
+    /// assert_eq!(h.nlabl, 1);
+    /// assert_eq!(h.label_at(0), Some("dose=2.0"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_label_kv(&mut self, key: &str, value: &str) -> Result<(), &'static str> {
+        if key.is_empty() || key.contains(|c: char| c == '=' || !c.is_ascii_graphic()) {
+            return Err("label key must be non-empty printable ASCII without spaces or '='");
+        }
+        if value.contains(|c: char| c == '=' || !(c.is_ascii_graphic() || c == ' ')) {
+            return Err("label value must be printable ASCII without '='");
+        }
+        let text = format!("{key}={}", value.trim());
+        if text.len() > 80 {
+            return Err("key=value pair exceeds the 80-character label size");
+        }
+        let newest = (0..10).rev().find_map(|i| {
+            let pairs = parse_label_pairs(self.label_at(i)?);
+            pairs
+                .iter()
+                .any(|(k, _)| *k == key)
+                .then_some((i, pairs.len() == 1))
+        });
+        match newest {
+            Some((slot, true)) => {
+                let start = slot * 80;
+                self.label[start..start + 80].fill(b' ');
+                self.label[start..start + text.len()].copy_from_slice(text.as_bytes());
+            }
+            _ => self.add_label(&text),
+        }
+        Ok(())
+    }
+
     /// Compute the unit cell volume in cubic ångströms.
     ///
     /// Uses the general formula for a triclinic cell:
@@ -1357,12 +1567,12 @@ macro_rules! header_numeric_fields {
     ( $( ($field:ident, $offset:ident, $ty:ty) ),+ $(,)? ) => {
         macro_rules! decode_numeric_fields {
             ($_h:ident, $_b:expr, $_e:expr) => {
-                $( $_h.$field = <$ty>::decode($_b, $offset, $_e); )+
+                $( $_h.$field = <$ty>::from_bytes($_b, $offset, $_e); )+
             }
         }
         macro_rules! encode_numeric_fields {
             ($_h:expr, $_o:expr, $_e:expr) => {
-                $( $_h.$field.encode($_o, $offset, $_e); )+
+                $( $_h.$field.to_bytes($_o, $offset, $_e); )+
             }
         }
     }
@@ -1450,6 +1660,40 @@ impl Header {
         (header, None)
     }
 
+    /// Decode a header and report the byte order its fields were read in.
+    ///
+    /// Detection follows [`decode_from_bytes_with_info`](Self::decode_from_bytes_with_info):
+    /// the MACHST stamp is tried first, and the opposite order is used when
+    /// MODE only makes sense that way. The returned [`FileEndian`](crate::FileEndian)
+    /// is the order actually used — which may differ from what
+    /// [`detect_endian`](Self::detect_endian) reports for a mis-stamped file —
+    /// so it is the one to decode the data block with. Works on in-memory
+    /// bytes only, so it is available without the `mmap` feature and on
+    /// WASM targets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrc::{FileEndian, Header};
+    /// let mut raw = [0u8; 1024];
+    /// raw[0..4].copy_from_slice(&(64i32).to_be_bytes());
+    /// raw[12..16].copy_from_slice(&(2i32).to_be_bytes());
+    /// raw[208..212].copy_from_slice(b"MAP ");
+    /// // Stamp left blank by the producing program.
+    /// let (h, endian) = Header::from_bytes_with_detection(&raw);
+    /// assert_eq!(endian, FileEndian::BigEndian);
+    /// assert_eq!(h.nx, 64);
+    /// ```
+    pub fn from_bytes_with_detection(bytes: &[u8; 1024]) -> (Self, crate::FileEndian) {
+        let (header, warning) = Self::decode_from_bytes_with_info(bytes);
+        let endian = match warning {
+            Some(EndianFallbackWarning::MachstLeDataBe) => crate::FileEndian::BigEndian,
+            Some(EndianFallbackWarning::MachstBeDataLe) => crate::FileEndian::LittleEndian,
+            None => header.detect_endian(),
+        };
+        (header, endian)
+    }
+
     fn decode_with_endian(bytes: &[u8; 1024], file_endian: crate::FileEndian) -> Self {
         use crate::engine::codec::EndianCodec;
 
@@ -1461,9 +1705,9 @@ impl Header {
             .extra
             .copy_from_slice(&bytes[OFFSET_EXTRA..OFFSET_ORIGIN]);
 
-        header.origin[0] = f32::decode(bytes, OFFSET_ORIGIN, file_endian);
-        header.origin[1] = f32::decode(bytes, OFFSET_ORIGIN + 4, file_endian);
-        header.origin[2] = f32::decode(bytes, OFFSET_ORIGIN + 8, file_endian);
+        header.origin[0] = f32::from_bytes(bytes, OFFSET_ORIGIN, file_endian);
+        header.origin[1] = f32::from_bytes(bytes, OFFSET_ORIGIN + 4, file_endian);
+        header.origin[2] = f32::from_bytes(bytes, OFFSET_ORIGIN + 8, file_endian);
 
         header
             .map
@@ -1472,8 +1716,8 @@ impl Header {
             .machst
             .copy_from_slice(&bytes[OFFSET_MACHST..OFFSET_RMS]);
 
-        header.rms = f32::decode(bytes, OFFSET_RMS, file_endian);
-        header.nlabl = i32::decode(bytes, OFFSET_NLABL, file_endian);
+        header.rms = f32::from_bytes(bytes, OFFSET_RMS, file_endian);
+        header.nlabl = i32::from_bytes(bytes, OFFSET_NLABL, file_endian);
         header.label.copy_from_slice(&bytes[OFFSET_LABEL..1024]);
 
         header
@@ -1504,9 +1748,9 @@ impl Header {
         out[OFFSET_EXTRA..OFFSET_ORIGIN].copy_from_slice(&self.extra);
 
         // Write origin coordinates
-        self.origin[0].encode(out, OFFSET_ORIGIN, file_endian);
-        self.origin[1].encode(out, OFFSET_ORIGIN + 4, file_endian);
-        self.origin[2].encode(out, OFFSET_ORIGIN + 8, file_endian);
+        self.origin[0].to_bytes(out, OFFSET_ORIGIN, file_endian);
+        self.origin[1].to_bytes(out, OFFSET_ORIGIN + 4, file_endian);
+        self.origin[2].to_bytes(out, OFFSET_ORIGIN + 8, file_endian);
 
         // Write MAP identifier - ASCII, no endian conversion
         out[OFFSET_MAP..OFFSET_MACHST].copy_from_slice(&self.map);
@@ -1515,10 +1759,10 @@ impl Header {
         out[OFFSET_MACHST..OFFSET_RMS].copy_from_slice(&self.machst);
 
         // Write RMS
-        self.rms.encode(out, OFFSET_RMS, file_endian);
+        self.rms.to_bytes(out, OFFSET_RMS, file_endian);
 
         // Write label count
-        self.nlabl.encode(out, OFFSET_NLABL, file_endian);
+        self.nlabl.to_bytes(out, OFFSET_NLABL, file_endian);
 
         // Write labels - ASCII, no endian conversion
         out[OFFSET_LABEL..1024].copy_from_slice(&self.label);
@@ -1582,6 +1826,18 @@ impl Header {
     pub fn is_y_inverted(&self) -> bool {
         self.mapr == -2
     }
+
+    /// The spatial axis (0 = X, 1 = Y, 2 = Z) stored along columns, rows
+    /// and sections; `[0, 1, 2]` when `mapc/mapr/maps` is not a permutation
+    /// of 1, 2, 3.
+    pub(crate) fn storage_axes(&self) -> [usize; 3] {
+        let axes = [self.mapc, self.mapr, self.maps].map(|m| (m - 1) as usize);
+        if axes.contains(&0) && axes.contains(&1) && axes.contains(&2) {
+            axes
+        } else {
+            [0, 1, 2]
+        }
+    }
 }
 
 /// IMOD image type classification from the `idtype` field.
@@ -2118,6 +2374,22 @@ impl Default for HeaderBuilder {
     }
 }
 
+/// Split a label into `key=value` pairs (see [`Header::labels_kv`]).
+fn parse_label_pairs(label: &str) -> Vec<(&str, String)> {
+    let mut pairs: Vec<(&str, String)> = Vec::new();
+    for word in label.split_whitespace() {
+        if let Some((k, v)) = word.split_once('=').filter(|(k, _)| !k.is_empty()) {
+            pairs.push((k, v.to_string()));
+        } else if let Some((_, v)) = pairs.last_mut() {
+            if !v.is_empty() {
+                v.push(' ');
+            }
+            v.push_str(word);
+        }
+    }
+    pairs
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2264,4 +2536,60 @@ mod tests {
         assert_eq!(h.nversion(), 0);
         assert!(h.validate(), "NVERSION=0 should pass strict validation");
     }
+
+    #[test]
+    fn test_pixel_size_validation() {
+        use crate::HeaderValidationError;
+
+        let mut h = Header::new();
+        (h.nx, h.ny, h.nz) = (64, 64, 10);
+        (h.mx, h.my, h.mz) = (64, 64, 10);
+        (h.xlen, h.ylen, h.zlen) = (0.0, 0.0, 0.0);
+        assert!(h.validate(), "all-zero cell means unknown pixel size");
+
+        h.zlen = 10.0;
+        assert!(matches!(
+            h.validate_detailed(),
+            Err(HeaderValidationError::InvalidCellLengths { xlen: 0.0, .. })
+        ));
+        assert_eq!(h.validate_permissive().unwrap().len(), 1);
+
+        (h.xlen, h.ylen) = (64.0, 64.0);
+        (h.ny, h.my) = (1000, 1);
+        assert_eq!(
+            h.validate_detailed(),
+            Err(HeaderValidationError::SamplingMismatch {
+                axis: 'Y',
+                n: 1000,
+                m: 1
+            })
+        );
+        h.my = 500;
+        assert!(h.validate());
+    }
+
+    #[test]
+    fn test_label_kv_roundtrip() {
+        let mut h = Header::new();
+        h.add_label("RELION  run=12 note=two words");
+        h.set_label_kv("apix", "0.83").unwrap();
+        // Key shared with a multi-pair label: appended, newest wins.
+        h.set_label_kv("run", "13").unwrap();
+        // Lone pair: rewritten in place.
+        h.set_label_kv("apix", "1.66").unwrap();
+        assert_eq!(
+            h.get_labels(),
+            ["RELION  run=12 note=two words", "apix=1.66", "run=13"]
+        );
+        let kv = h.labels_kv();
+        assert_eq!(kv.len(), 3);
+        assert_eq!(kv["run"], "13");
+        assert_eq!(kv["note"], "two words");
+
+        assert!(h.set_label_kv("", "x").is_err());
+        assert!(h.set_label_kv("a b", "x").is_err());
+        assert!(h.set_label_kv("a", "x=y").is_err());
+        assert!(h.set_label_kv("a", &"x".repeat(79)).is_err());
+        assert_eq!(h.nlabl, 3);
+    }
 }
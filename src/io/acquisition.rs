@@ -0,0 +1,157 @@
+//! Acquisition metadata in one vendor-neutral shape.
+//!
+//! FEI/Thermo Fisher and SerialEM files record pixel size, dose, defocus and
+//! timing in different places and units. [`Reader::acquisition`] gathers
+//! what the extended header holds for one section into an [`Acquisition`]
+//! with fixed units, so callers need not match on
+//! [`ExtHeaderData`](crate::ExtHeaderData) themselves.
+//!
+//! FEI records are written in SI units by the Thermo Fisher specification
+//! (metres, e⁻/m²) but in Å, µm and e⁻/Å² by some converters. Values are
+//! converted by magnitude, since the two encodings are many orders apart: a
+//! pixel size below 10⁻⁶ or a defocus below 10⁻³ can only be in metres, and
+//! a dose above 10¹⁰ only in e⁻/m².
+//!
+//! [`Reader::acquisition`]: crate::Reader::acquisition
+
+use crate::{ExtHeaderData, Fei1Metadata};
+
+/// Days between the OLE Automation epoch (1899-12-30) and the Unix epoch.
+const OLE_TO_UNIX_DAYS: f64 = 25_569.0;
+
+/// Acquisition parameters of one section, in fixed units.
+///
+/// Fields are `None` when the file does not record them.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Acquisition {
+    /// Pixel size in Å.
+    pub pixel_size: Option<f64>,
+    /// Electron dose on this section, in e⁻/Å².
+    pub dose: Option<f64>,
+    /// Objective defocus in µm, with the vendor's sign convention.
+    pub defocus: Option<f64>,
+    /// Stage alpha tilt in degrees.
+    pub tilt_angle: Option<f64>,
+    /// Acquisition time in seconds since the Unix epoch.
+    pub time: Option<f64>,
+}
+
+impl Acquisition {
+    fn from_fei(r: &Fei1Metadata) -> Self {
+        let pixel_size = match r.pixel_size_x {
+            v if !(v.is_finite() && v > 0.0) => None,
+            v if v < 1e-6 => Some(v * 1e10),
+            v => Some(v),
+        };
+        let dose = match r.dose {
+            v if !v.is_finite() => None,
+            v if v > 1e10 => Some(v * 1e-20),
+            v => Some(v),
+        };
+        let defocus = match r.defocus {
+            v if !v.is_finite() => None,
+            v if v != 0.0 && v.abs() < 1e-3 => Some(v * 1e6),
+            v => Some(v),
+        };
+        // Unix seconds, or an OLE Automation date (days since 1899-12-30).
+        let time = match r.timestamp {
+            v if !(v.is_finite() && v > 0.0) => None,
+            v if v < 1e6 => Some((v - OLE_TO_UNIX_DAYS) * 86_400.0),
+            v => Some(v),
+        };
+        Self {
+            pixel_size,
+            dose,
+            defocus,
+            tilt_angle: r.alpha_tilt.is_finite().then_some(r.alpha_tilt),
+            time,
+        }
+    }
+}
+
+impl crate::Reader {
+    /// Acquisition parameters of section `z` from the extended header.
+    ///
+    /// FEI1 and FEI2 records supply all fields (FEI2's nanosecond
+    /// timestamp is preferred when set); SerialEM records supply the tilt
+    /// angle. A missing pixel size is filled from the header's voxel size.
+    /// Returns `None` when there is no FEI or SerialEM record for `z`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let reader = mrc::Reader::open("tilt_series.mrc")?;
+    /// for z in 0..reader.shape().nz {
+    ///     if let Some(a) = reader.acquisition(z) {
+    ///         println!("{z}: tilt {:?}°, defocus {:?} µm", a.tilt_angle, a.defocus);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn acquisition(&self, z: usize) -> Option<Acquisition> {
+        self.acquisitions().into_iter().nth(z)
+    }
+
+    /// Pixel size in Å: from the first FEI record when present, else the
+    /// header's X voxel size. `None` when neither is positive.
+    pub fn pixel_size_angstrom(&self) -> Option<f64> {
+        self.acquisition(0)
+            .and_then(|a| a.pixel_size)
+            .or_else(|| self.header_pixel_size())
+    }
+
+    /// Sum of the per-section doses, in e⁻/Å², or `None` when no section
+    /// records a dose.
+    pub fn total_dose(&self) -> Option<f64> {
+        let doses: Vec<f64> = self.acquisitions().iter().filter_map(|a| a.dose).collect();
+        (!doses.is_empty()).then(|| doses.iter().sum())
+    }
+
+    /// Defocus of section `z` in µm; see [`acquisition`](Self::acquisition).
+    pub fn defocus(&self, z: usize) -> Option<f64> {
+        self.acquisition(z)?.defocus
+    }
+
+    /// Acquisition time of section `z` in seconds since the Unix epoch; see
+    /// [`acquisition`](Self::acquisition).
+    pub fn acquisition_time(&self, z: usize) -> Option<f64> {
+        self.acquisition(z)?.time
+    }
+
+    /// One [`Acquisition`] per extended-header record.
+    fn acquisitions(&self) -> Vec<Acquisition> {
+        let mut all: Vec<Acquisition> = match self.parse_extended_header() {
+            ExtHeaderData::Fei1(records) => records.iter().map(Acquisition::from_fei).collect(),
+            ExtHeaderData::Fei2(records) => records
+                .iter()
+                .map(|r| {
+                    let mut a = Acquisition::from_fei(&r.fei1);
+                    if r.acquisition_time_stamp > 0 {
+                        a.time = Some(r.acquisition_time_stamp as f64 * 1e-9);
+                    }
+                    a
+                })
+                .collect(),
+            ExtHeaderData::Seri(records) => records
+                .iter()
+                .map(|r| Acquisition {
+                    tilt_angle: Some(f64::from(r.alpha_tilt)),
+                    ..Acquisition::default()
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        let fallback = self.header_pixel_size();
+        for a in &mut all {
+            a.pixel_size = a.pixel_size.or(fallback);
+        }
+        all
+    }
+
+    fn header_pixel_size(&self) -> Option<f64> {
+        let apix = f64::from(self.header().voxel_size()[0]);
+        (apix.is_finite() && apix > 0.0).then_some(apix)
+    }
+}
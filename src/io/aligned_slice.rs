@@ -0,0 +1,116 @@
+//! Whole-volume slices with a guaranteed cache-line alignment.
+//!
+//! SIMD kernels and GPU upload paths often want their input on a 64-byte
+//! boundary. Memory-mapped voxel data starts wherever the header and
+//! extended header end, so it is only sometimes aligned that well.
+//! [`Reader::data_aligned_or_copy`] borrows the data when it is, and
+//! otherwise copies it once into an [`AlignedBuf`].
+
+use crate::iter::RegionIter;
+use crate::{AlignedBuf, BUF_ALIGN, Error, FileEndian, Reader, Voxel};
+
+use std::borrow::Cow;
+
+/// The voxel data as `&[T]`, starting on a [`BUF_ALIGN`]-byte boundary.
+///
+/// Dereferences to `[T]`. Either borrows the reader's buffer or owns an
+/// aligned copy; [`is_borrowed`](Self::is_borrowed) tells which.
+#[derive(Debug)]
+pub struct AlignedSlice<'a, T> {
+    data: Storage<'a, T>,
+}
+
+#[derive(Debug)]
+enum Storage<'a, T> {
+    Borrowed(&'a [T]),
+    Copied(AlignedBuf),
+}
+
+impl<T: Voxel> AlignedSlice<'_, T> {
+    /// `true` if the data borrows the reader's buffer rather than a copy.
+    #[inline]
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self.data, Storage::Borrowed(_))
+    }
+}
+
+impl<T: Voxel> core::ops::Deref for AlignedSlice<'_, T> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &[T] {
+        match &self.data {
+            Storage::Borrowed(v) => v,
+            // The buffer holds native-endian `T`s and is aligned for any
+            // voxel type, so the view always succeeds.
+            Storage::Copied(buf) => RegionIter::<()>::try_zero_copy(buf, T::MODE)
+                .and_then(T::from_view)
+                .unwrap_or(&[]),
+        }
+    }
+}
+
+impl<T: Voxel> AsRef<[T]> for AlignedSlice<'_, T> {
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl Reader {
+    /// The whole volume as `&[T]` starting on a 64-byte boundary.
+    ///
+    /// Borrows the reader's buffer when the file is native-endian and the
+    /// data already starts on such a boundary; otherwise decodes it once
+    /// into a cache-line-aligned [`AlignedBuf`]. Compare
+    /// [`volume`](Self::volume), which borrows whenever `T`'s own
+    /// alignment is met.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let reader = mrc::Reader::open("map.mrc")?;
+    /// let data = reader.data_aligned_or_copy::<f32>()?;
+    /// assert_eq!(data.as_ptr() as usize % mrc::BUF_ALIGN, 0);
+    /// println!("{} voxels, copied: {}", data.len(), !data.is_borrowed());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ModeMismatch`] if the file's mode is not
+    /// `T::MODE`, and propagates any read error.
+    pub fn data_aligned_or_copy<T: Voxel>(&self) -> Result<AlignedSlice<'_, T>, Error> {
+        if self.mode() != T::MODE {
+            return Err(Error::ModeMismatch {
+                file_mode: self.mode(),
+                requested_mode: T::MODE,
+                offset: None,
+            });
+        }
+        let s = self.shape();
+        let bytes = self.read_block_bytes_cow([0, 0, 0], [s.nx, s.ny, s.nz])?;
+        let native = self.endian().is_native();
+        if let Cow::Borrowed(b) = &bytes {
+            if native && b.as_ptr() as usize % BUF_ALIGN == 0 {
+                if let Some(v) = RegionIter::<()>::try_zero_copy(b, T::MODE).and_then(T::from_view)
+                {
+                    return Ok(AlignedSlice {
+                        data: Storage::Borrowed(v),
+                    });
+                }
+            }
+        }
+        let mut buf = AlignedBuf::zeroed(bytes.len());
+        if native {
+            buf.copy_from_slice(&bytes);
+        } else {
+            let values = crate::engine::codec::decode_slice::<T>(&bytes, self.endian())?;
+            crate::engine::codec::encode_slice(&values, &mut buf, FileEndian::native())?;
+        }
+        Ok(AlignedSlice {
+            data: Storage::Copied(buf),
+        })
+    }
+}
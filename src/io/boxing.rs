@@ -0,0 +1,218 @@
+//! Coordinate-driven particle extraction ("boxing").
+//!
+//! [`Reader::extract_particles`](crate::Reader::extract_particles) cuts a
+//! fixed-size box around each picked coordinate of a micrograph or tomogram
+//! and collects the boxes into a [`ParticleStack`], which can be written out
+//! as an MRC image stack (2D boxes) or volume stack (3D boxes).
+//!
+//! Boxes that cross the volume edge are handled by an [`EdgePolicy`]; the
+//! corner of every box in source voxel coordinates is kept so picks can be
+//! mapped back to the original data.
+
+use crate::{Error, VolumeShape};
+
+use std::path::Path;
+
+/// How to fill the part of a box that lies outside the source volume.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EdgePolicy {
+    /// Drop particles whose box is not entirely inside the volume.
+    Skip,
+    /// Fill outside voxels with a constant.
+    Constant(f32),
+    /// Fill outside voxels with the mean of the in-bounds part of the box.
+    Mean,
+    /// Repeat the nearest edge voxel.
+    Replicate,
+}
+
+/// Boxed particles extracted by
+/// [`Reader::extract_particles`](crate::Reader::extract_particles).
+#[derive(Debug, Clone)]
+pub struct ParticleStack {
+    /// Box size in voxels `[x, y, z]`.
+    pub box_shape: [usize; 3],
+    /// All boxes back to back in C-order, one box after another.
+    pub data: Vec<f32>,
+    /// Index into the input coordinate list of each extracted particle.
+    pub indices: Vec<usize>,
+    /// Corner of each box in source voxel coordinates. Negative or
+    /// past-the-end values mean the box was padded.
+    pub corners: Vec<[i64; 3]>,
+    /// Voxel size of the source in Å.
+    pub voxel_size: [f32; 3],
+    /// Header origin of the source in Å.
+    pub source_origin: [f32; 3],
+}
+
+impl ParticleStack {
+    /// Number of extracted particles.
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// `true` if no particle was extracted.
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Voxels of particle `i`, or `None` if out of range.
+    pub fn particle(&self, i: usize) -> Option<&[f32]> {
+        let n = self.box_shape.iter().product::<usize>();
+        self.data.get(i * n..(i + 1) * n)
+    }
+
+    /// Origin of box `i` in Å in the source frame: the source origin plus
+    /// the box corner times the voxel size.
+    pub fn origin(&self, i: usize) -> Option<[f32; 3]> {
+        let c = self.corners.get(i)?;
+        Some(core::array::from_fn(|k| {
+            self.source_origin[k] + c[k] as f32 * self.voxel_size[k]
+        }))
+    }
+
+    /// Write the boxes as a `float32` MRC stack.
+    ///
+    /// 2D boxes (`box_shape[2] == 1`) become an image stack with one section
+    /// per particle; 3D boxes become a volume stack with `mz` equal to the
+    /// box depth. The cell keeps the source voxel size.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidHeaderDetailed`] if the stack is empty and
+    /// [`Error::Io`] on write failure.
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let [bx, by, bz] = self.box_shape;
+        let nz = bz * self.len();
+        let [vx, vy, vz] = self.voxel_size;
+        let builder = crate::create(path)
+            .shape([bx, by, nz])
+            .mode::<f32>()
+            .cell_lengths(vx * bx as f32, vy * by as f32, vz * nz as f32);
+        let builder = if bz == 1 {
+            builder.image_stack()
+        } else {
+            builder.volume_stack(bz as i32)
+        };
+        let mut writer = builder.add_label("Boxed particles").finish()?;
+        writer.set_data(&self.data)?;
+        writer.finalize()
+    }
+}
+
+impl crate::Reader {
+    /// Extract a box of `box_shape` voxels around each center.
+    ///
+    /// `centers` are `[x, y, z]` voxel coordinates (rounded to the nearest
+    /// voxel); use `z = 0` and a box depth of 1 for a 2D micrograph. The box
+    /// corner is `center - box_shape / 2`. Boxes that do not overlap the
+    /// volume at all are always skipped; partially outside boxes follow
+    /// `edge`. Every mode is read through the default `f32` conversion.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// use mrc::EdgePolicy;
+    ///
+    /// let reader = mrc::Reader::open("micrograph.mrc")?;
+    /// let picks = [[512.0, 300.0, 0.0], [40.0, 1000.0, 0.0]];
+    /// let stack = reader.extract_particles(&picks, [128, 128, 1], EdgePolicy::Mean)?;
+    /// stack.write("particles.mrcs")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Propagates any error from reading the source.
+    pub fn extract_particles(
+        &self,
+        centers: &[[f32; 3]],
+        box_shape: [usize; 3],
+        edge: EdgePolicy,
+    ) -> Result<ParticleStack, Error> {
+        let box_shape = box_shape.map(|b| b.max(1));
+        let shape = self.shape();
+        let dims = [shape.nx, shape.ny, shape.nz];
+        let reader = self.convert::<f32>();
+
+        let mut stack = ParticleStack {
+            box_shape,
+            data: Vec::new(),
+            indices: Vec::new(),
+            corners: Vec::new(),
+            voxel_size: self.header.voxel_size(),
+            source_origin: self.header.origin,
+        };
+        for (i, center) in centers.iter().enumerate() {
+            let corner: [i64; 3] =
+                core::array::from_fn(|k| center[k].round() as i64 - (box_shape[k] / 2) as i64);
+            let lo: [usize; 3] = core::array::from_fn(|k| corner[k].max(0) as usize);
+            let hi: [usize; 3] = core::array::from_fn(|k| {
+                (corner[k] + box_shape[k] as i64).clamp(0, dims[k] as i64) as usize
+            });
+            if (0..3).any(|k| lo[k] >= hi[k]) {
+                continue;
+            }
+            let inside = (0..3).all(|k| hi[k] - lo[k] == box_shape[k]);
+            if !inside && edge == EdgePolicy::Skip {
+                continue;
+            }
+
+            let extent = [hi[0] - lo[0], hi[1] - lo[1], hi[2] - lo[2]];
+            let block = reader.subregion(lo, extent)?.data;
+            if inside {
+                stack.data.extend_from_slice(&block);
+            } else {
+                pad_box(&block, extent, lo, corner, box_shape, edge, &mut stack.data);
+            }
+            stack.indices.push(i);
+            stack.corners.push(corner);
+        }
+        Ok(stack)
+    }
+}
+
+/// Append one box, filling voxels outside the in-bounds `block` (which has
+/// `extent` and starts at source voxel `lo`) according to `edge`.
+fn pad_box(
+    block: &[f32],
+    extent: [usize; 3],
+    lo: [usize; 3],
+    corner: [i64; 3],
+    box_shape: [usize; 3],
+    edge: EdgePolicy,
+    out: &mut Vec<f32>,
+) {
+    let s = VolumeShape::new(extent[0], extent[1], extent[2]);
+    let fill = match edge {
+        EdgePolicy::Constant(v) => v,
+        EdgePolicy::Mean => block.iter().sum::<f32>() / block.len() as f32,
+        EdgePolicy::Skip | EdgePolicy::Replicate => 0.0,
+    };
+    // Position along axis `k` relative to the block, if inside it.
+    let local = |k: usize, b: usize| -> Result<usize, usize> {
+        let src = corner[k] + b as i64 - lo[k] as i64;
+        if (0..extent[k] as i64).contains(&src) {
+            Ok(src as usize)
+        } else {
+            Err(src.clamp(0, extent[k] as i64 - 1) as usize)
+        }
+    };
+    for bz in 0..box_shape[2] {
+        for by in 0..box_shape[1] {
+            for bx in 0..box_shape[0] {
+                let v = match (local(0, bx), local(1, by), local(2, bz)) {
+                    (Ok(x), Ok(y), Ok(z)) => block[(z * s.ny + y) * s.nx + x],
+                    (x, y, z) if edge == EdgePolicy::Replicate => {
+                        let [x, y, z] = [x, y, z].map(|r| r.unwrap_or_else(|c| c));
+                        block[(z * s.ny + y) * s.nx + x]
+                    }
+                    _ => fill,
+                };
+                out.push(v);
+            }
+        }
+    }
+}
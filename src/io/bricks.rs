@@ -0,0 +1,167 @@
+//! Brick-by-brick traversal with a reused buffer.
+//!
+//! Filters, morphology and other neighbourhood operations touch voxels in
+//! all three directions, so walking a volume x-fastest streams whole
+//! sections through the cache for every neighbour in Z. Processing it in
+//! small cubic bricks (32³ or 64³) keeps each brick's working set in cache.
+//! [`Reader::bricks`] decodes each brick straight from the file's bytes
+//! into one buffer that is reused for the whole traversal, so a pass over
+//! the volume allocates once.
+//!
+//! [`Reader::bricks`]: crate::Reader::bricks
+
+use crate::engine::codec::decode_into;
+use crate::iter::{Stepper, TileStepper};
+use crate::{Error, Reader, Voxel};
+
+/// One brick from [`Bricks::next_brick`], borrowing the shared buffer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Brick<'b, T> {
+    /// `[x, y, z]` of the brick's first voxel.
+    pub offset: [usize; 3],
+    /// `[sx, sy, sz]`; smaller than the requested brick shape at the upper
+    /// edges of the volume.
+    pub shape: [usize; 3],
+    /// `sx · sy · sz` voxels, x fastest.
+    pub data: &'b [T],
+}
+
+/// Traversal of a volume in 3D bricks, returned by [`Reader::bricks`].
+///
+/// Bricks are visited x fastest, then y, then z. Each one is decoded into
+/// the same buffer, so this is a lending iterator: call
+/// [`next_brick`](Self::next_brick) in a `while let` loop, and copy out
+/// anything that must outlive the next call.
+#[derive(Debug)]
+pub struct Bricks<'a, T> {
+    reader: &'a Reader,
+    stepper: TileStepper,
+    buf: Vec<T>,
+}
+
+impl<T: Voxel> Bricks<'_, T> {
+    /// Decode the next brick into the buffer, or `None` once the volume is
+    /// covered.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::OutOfBounds`] when the brick lies past the end of a
+    /// truncated file.
+    pub fn next_brick(&mut self) -> Option<Result<Brick<'_, T>, Error>> {
+        let s = self.reader.shape();
+        let (offset, shape) = self.stepper.next(s)?;
+        if let Err(e) = self.reader.check_cancelled() {
+            return Some(Err(e));
+        }
+        let data = self.reader._source_data();
+        if let Err(e) =
+            crate::io::reader_common::validate_block_bounds(s, T::MODE, data.len(), offset, shape)
+        {
+            return Some(Err(e));
+        }
+
+        let [x0, y0, z0] = offset;
+        let [sx, sy, sz] = shape;
+        self.buf.resize(sx * sy * sz, T::default());
+        let endian = self.reader.endian();
+        let row = sx * T::BYTE_SIZE;
+        for (i, dst) in self.buf.chunks_exact_mut(sx.max(1)).enumerate() {
+            let (y, z) = (y0 + i % sy, z0 + i / sy);
+            let start = ((z * s.ny + y) * s.nx + x0) * T::BYTE_SIZE;
+            if let Err(e) = decode_into(&data[start..start + row], dst, endian) {
+                return Some(Err(e));
+            }
+        }
+        Some(Ok(Brick {
+            offset,
+            shape,
+            data: &self.buf,
+        }))
+    }
+}
+
+impl Reader {
+    /// Walk the volume in bricks of `brick_shape`, decoding each into one
+    /// reused buffer of `T`.
+    ///
+    /// Unlike [`tiles`](Self::tiles), which yields a separately owned or
+    /// borrowed block per tile, every brick here is a contiguous `&[T]`
+    /// written into the same allocation, with no per-brick allocation or
+    /// mode dispatch.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let reader = mrc::Reader::open("tomogram.mrc")?;
+    /// let mut bricks = reader.bricks::<f32>([64, 64, 64])?;
+    /// let mut peak = f32::MIN;
+    /// while let Some(brick) = bricks.next_brick() {
+    ///     let brick = brick?;
+    ///     peak = brick.data.iter().copied().fold(peak, f32::max);
+    /// }
+    /// println!("max {peak}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ModeMismatch`] if the file's mode is not `T::MODE`
+    /// (use [`convert`](Self::convert) and its `tiles` for other types), and
+    /// [`Error::OutOfBounds`] if any dimension of `brick_shape` is 0.
+    pub fn bricks<T: Voxel>(&self, brick_shape: [usize; 3]) -> Result<Bricks<'_, T>, Error> {
+        if self.mode() != T::MODE {
+            return Err(Error::ModeMismatch {
+                file_mode: self.mode(),
+                requested_mode: T::MODE,
+                offset: None,
+            });
+        }
+        Ok(Bricks {
+            reader: self,
+            stepper: TileStepper::new(brick_shape)?,
+            buf: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bricks_cover_volume_with_edge_remainders() {
+        let mut h = crate::Header::new();
+        (h.nx, h.ny, h.nz) = (5, 4, 3);
+        (h.mx, h.my, h.mz) = (5, 4, 3);
+        h.mode = crate::Mode::Int16.as_i32();
+        let mut file = vec![0u8; 1024];
+        h.encode_to_bytes((&mut file[..]).try_into().unwrap());
+        for v in 0..60i16 {
+            file.extend_from_slice(&v.to_le_bytes());
+        }
+        let reader = Reader::from_bytes(file).unwrap();
+
+        let mut bricks = reader.bricks::<i16>([2, 3, 2]).unwrap();
+        let mut seen = [false; 60];
+        let mut count = 0;
+        while let Some(brick) = bricks.next_brick() {
+            let b = brick.unwrap();
+            let [sx, sy, _] = b.shape;
+            for (i, &v) in b.data.iter().enumerate() {
+                let (x, y, z) = (i % sx, i / sx % sy, i / (sx * sy));
+                let [ox, oy, oz] = b.offset;
+                assert_eq!(v as usize, ((oz + z) * 4 + oy + y) * 5 + ox + x);
+                seen[v as usize] = true;
+            }
+            count += 1;
+        }
+        assert_eq!(count, 3 * 2 * 2);
+        assert!(seen.iter().all(|&s| s));
+        assert!(matches!(
+            reader.bricks::<f32>([2, 2, 2]),
+            Err(Error::ModeMismatch { .. })
+        ));
+    }
+}
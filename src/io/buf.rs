@@ -0,0 +1,547 @@
+//! An owned MRC volume built up in memory.
+//!
+//! [`Reader`] and [`Writer`](crate::Writer) cover files that already exist
+//! or are written straight to disk. [`MrcBuf`] is for the in-between case:
+//! a header, extended header and typed voxel data owned together, which
+//! can start empty, grow section by section, be edited in place, and be
+//! serialized when done.
+
+use crate::engine::codec::{EndianCodec, encode_slice};
+use crate::engine::convert::decode_block_to_any;
+use crate::{DataView, Error, FileEndian, Header, Mode, OwnedData, Reader, VolumeShape, Voxel};
+
+/// Voxels widened to `f32` at a time by `map_voxels_f32`: 16 KiB of
+/// `f32`, which stays in L1 alongside the slice being rewritten.
+pub(crate) const MAP_CHUNK: usize = 4096;
+
+/// Rewrites a typed slice through `f32`, converting with `to` and `from`.
+pub(crate) trait MapF32 {
+    fn map<T: Copy + Send>(
+        &mut self,
+        v: &mut [T],
+        to: impl Fn(T) -> f32 + Sync,
+        from: impl Fn(f32) -> T + Sync,
+    );
+}
+
+/// A header plus owned voxel data, independent of any file.
+///
+/// The data is kept decoded in native byte order, so typed access is a
+/// plain slice borrow; serialization encodes it little-endian.
+///
+/// # Examples
+///
+/// ```
+/// use mrc::{Header, Mode, MrcBuf};
+///
+/// let mut header = Header::new();
+/// (header.nx, header.ny, header.nz) = (4, 4, 0);
+/// (header.mx, header.my, header.mz) = (4, 4, 0);
+/// header.mode = Mode::Float32.as_i32();
+///
+/// let mut buf = MrcBuf::new(header)?;
+/// for z in 0..3 {
+///     buf.push_section(&[z as f32; 16])?;
+/// }
+/// buf.data_as_mut::<f32>()?[0] = -1.0;
+/// assert_eq!(buf.shape().nz, 3);
+///
+/// let reader = mrc::Reader::from_bytes(buf.to_bytes()?)?;
+/// assert_eq!(reader.volume::<f32>()?[(0, 0, 0)], -1.0);
+/// # Ok::<(), mrc::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct MrcBuf {
+    header: Header,
+    ext_header: Vec<u8>,
+    data: OwnedData,
+}
+
+impl MrcBuf {
+    /// A zero-filled volume laid out as `header` describes, with a zeroed
+    /// extended header of `header.nsymbt` bytes.
+    ///
+    /// `nz` (and with it `mz`) may be 0 for a buffer to be filled with
+    /// [`push_section`](Self::push_section). The header is switched to
+    /// little-endian, the byte order [`to_bytes`](Self::to_bytes) writes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidHeaderDetailed`] if `header` fails
+    /// validation (other than for `nz == 0`), and
+    /// [`Error::UnsupportedMode`] for Mode 12 without the `f16` feature.
+    pub fn new(mut header: Header) -> Result<Self, Error> {
+        let mut check = header;
+        if check.nz == 0 {
+            check.nz = 1;
+            if check.mz == 0 {
+                check.mz = 1;
+            }
+        }
+        check
+            .validate_detailed()
+            .map_err(Error::InvalidHeaderDetailed)?;
+        header.set_file_endian(FileEndian::LittleEndian);
+        let mode = Mode::from_i32(header.mode).ok_or(Error::UnsupportedMode)?;
+        let size = header.data_size_u64().ok_or(Error::InvalidHeader)?;
+        let size = usize::try_from(size).map_err(|_| Error::InvalidHeader)?;
+        let shape = VolumeShape::from_header(&header)?;
+        let data = decode_block_to_any(
+            &vec![0u8; size],
+            mode,
+            FileEndian::native(),
+            [shape.nx, shape.ny, shape.nz],
+        )?;
+        Ok(Self {
+            ext_header: vec![0; header.nsymbt.max(0) as usize],
+            header,
+            data,
+        })
+    }
+
+    /// Copy a reader's header, extended header and voxel data.
+    ///
+    /// # Errors
+    ///
+    /// Propagates any error from reading the data.
+    pub fn from_reader(reader: &Reader) -> Result<Self, Error> {
+        let data = match reader.read_volume()? {
+            crate::DataBlock::Borrowed { data, .. } => data.into(),
+            crate::DataBlock::Owned { data, .. } => data,
+        };
+        let mut header = *reader.header();
+        header.set_file_endian(FileEndian::LittleEndian);
+        Ok(Self {
+            header,
+            ext_header: reader.ext_header_bytes().to_vec(),
+            data,
+        })
+    }
+
+    /// Assemble a buffer whose parts are already known to agree.
+    pub(crate) fn from_parts(header: Header, ext_header: Vec<u8>, data: OwnedData) -> Self {
+        Self {
+            header,
+            ext_header,
+            data,
+        }
+    }
+
+    /// The data itself, for crate code that handles every mode.
+    pub(crate) fn owned_data(&self) -> &OwnedData {
+        &self.data
+    }
+
+    /// View of the whole Z-sections `z`; the range must be in bounds.
+    pub(crate) fn sections(&self, z: core::ops::Range<usize>) -> DataView<'_> {
+        let s = self.shape();
+        let per = match self.data {
+            OwnedData::Packed4Bit(_) => s.nx.div_ceil(2) * s.ny,
+            _ => s.nx * s.ny,
+        };
+        let r = z.start * per..z.end * per;
+        match &self.data {
+            OwnedData::Int8(v) => DataView::Int8(&v[r]),
+            OwnedData::Int16(v) => DataView::Int16(&v[r]),
+            OwnedData::Float32(v) => DataView::Float32(&v[r]),
+            OwnedData::Int16Complex(v) => DataView::Int16Complex(&v[r]),
+            OwnedData::Float32Complex(v) => DataView::Float32Complex(&v[r]),
+            OwnedData::Uint16(v) => DataView::Uint16(&v[r]),
+            #[cfg(feature = "f16")]
+            OwnedData::Float16(v) => DataView::Float16(&v[r]),
+            OwnedData::Packed4Bit(v) => DataView::Packed4Bit(&v[r]),
+        }
+    }
+
+    /// The header. Dimensions and `nsymbt` always match the data held.
+    #[inline]
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Volume dimensions.
+    #[inline]
+    pub fn shape(&self) -> VolumeShape {
+        VolumeShape {
+            nx: self.header.nx as usize,
+            ny: self.header.ny as usize,
+            nz: self.header.nz as usize,
+        }
+    }
+
+    /// The voxel mode.
+    #[inline]
+    pub fn mode(&self) -> Mode {
+        DataView::from(&self.data).mode()
+    }
+
+    /// The extended header bytes.
+    #[inline]
+    pub fn ext_header(&self) -> &[u8] {
+        &self.ext_header
+    }
+
+    /// Replace the extended header, updating `nsymbt` to its length.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] of kind `InvalidInput` if `bytes` is longer
+    /// than `i32::MAX`.
+    pub fn set_ext_header(&mut self, bytes: Vec<u8>) -> Result<(), Error> {
+        self.header.nsymbt = i32::try_from(bytes.len())
+            .map_err(|_| Error::invalid_input("extended header longer than i32::MAX bytes"))?;
+        self.ext_header = bytes;
+        Ok(())
+    }
+
+    /// The voxel data, typed by mode.
+    #[inline]
+    pub fn data(&self) -> DataView<'_> {
+        (&self.data).into()
+    }
+
+    /// The voxel data as `&[T]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ModeMismatch`] if the mode is not `T::MODE`.
+    pub fn data_as<T: Voxel>(&self) -> Result<&[T], Error> {
+        self.data().as_slice()
+    }
+
+    /// The voxel data as `&mut [T]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ModeMismatch`] if the mode is not `T::MODE`.
+    pub fn data_as_mut<T: Voxel>(&mut self) -> Result<&mut [T], Error> {
+        self.data.as_mut_slice()
+    }
+
+    /// `(dmin, dmax, dmean, rms)` of the data, as [`DataView::stats`] but
+    /// without the padding nibbles of odd-width Mode 101 rows.
+    pub fn stats(&self) -> (f32, f32, f32, f32) {
+        crate::engine::stats::view_stats(self.data(), self.shape().nx)
+    }
+
+    /// Set the header's `dmin`, `dmax`, `dmean` and `rms` from
+    /// [`stats`](Self::stats), after editing voxels through
+    /// [`data_as_mut`](Self::data_as_mut) or [`map_voxels`](Self::map_voxels).
+    ///
+    /// Edits never touch the header on their own, so until this is called
+    /// it describes the data as it was.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrc::{Header, MrcBuf};
+    ///
+    /// let mut header = Header::new();
+    /// (header.nx, header.ny, header.nz) = (2, 1, 1);
+    /// (header.mx, header.my, header.mz) = (2, 1, 1);
+    /// let mut buf = MrcBuf::new(header)?;
+    /// buf.data_as_mut::<f32>()?.copy_from_slice(&[1.0, 3.0]);
+    ///
+    /// buf.update_header_stats();
+    /// let h = buf.header();
+    /// assert_eq!((h.dmin, h.dmax, h.dmean, h.rms), (1.0, 3.0, 2.0, 1.0));
+    /// # Ok::<(), mrc::Error>(())
+    /// ```
+    pub fn update_header_stats(&mut self) {
+        let stats = self.stats();
+        let h = &mut self.header;
+        (h.dmin, h.dmax, h.dmean, h.rms) = stats;
+    }
+
+    /// Set every voxel to `value`, converted to the mode as by
+    /// [`OwnedData::fill`].
+    pub fn fill(&mut self, value: f32) {
+        self.data.fill(value);
+    }
+
+    /// Set every voxel of Z-section `z` to `value`, converted to the mode
+    /// as by [`OwnedData::fill`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::BoundsError`] if `z` is not below `nz`.
+    pub fn fill_section(&mut self, z: usize, value: f32) -> Result<(), Error> {
+        let s = self.shape();
+        if z >= s.nz {
+            return Err(Error::BoundsError {
+                offset: Some([0, 0, z]),
+                shape: Some([s.nx, s.ny, 1]),
+                volume: Some([s.nx, s.ny, s.nz]),
+            });
+        }
+        let section = match self.mode() {
+            Mode::Packed4Bit => s.ny * s.nx.div_ceil(2),
+            _ => s.nx * s.ny,
+        };
+        self.data.fill_range(z * section..(z + 1) * section, value);
+        Ok(())
+    }
+
+    /// Set every voxel to zero.
+    pub fn zero(&mut self) {
+        self.fill(0.0);
+    }
+
+    /// Overwrite every NaN and infinite voxel with `value`, returning how
+    /// many were changed; see [`OwnedData::replace_non_finite`].
+    ///
+    /// [`DataView::find_non_finite`] on [`data`](Self::data) lists them
+    /// first. The header statistics are not updated.
+    pub fn replace_non_finite(&mut self, value: f32) -> usize {
+        self.data.replace_non_finite(value)
+    }
+
+    /// Replace every voxel `v` with `f(v)`, in the data's own type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mrc::{Header, MrcBuf};
+    /// # let mut header = Header::new();
+    /// # (header.nx, header.ny, header.nz) = (2, 1, 1);
+    /// # (header.mx, header.my, header.mz) = (2, 1, 1);
+    /// # header.mode = mrc::Mode::Int16.as_i32();
+    /// let mut buf = MrcBuf::new(header)?;
+    /// buf.map_voxels(|v: i16| v + 7)?;
+    /// assert_eq!(buf.data_as::<i16>()?, &[7, 7]);
+    /// # Ok::<(), mrc::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ModeMismatch`] if the mode is not `T::MODE`.
+    pub fn map_voxels<T: Voxel>(&mut self, mut f: impl FnMut(T) -> T) -> Result<(), Error> {
+        for v in self.data_as_mut::<T>()? {
+            *v = f(*v);
+        }
+        Ok(())
+    }
+
+    /// Replace every voxel `v` with `f(v)`, working in `f32` whatever the
+    /// mode — for gains, offsets, log transforms and the like.
+    ///
+    /// Values are widened to `f32` a cache-sized chunk at a time and
+    /// written back rounded and saturated for integer modes; IMOD's
+    /// unsigned Mode 0 bytes are seen as `0..=255`, and Mode 101 nibbles as
+    /// `0..=15`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedMode`] for complex modes, where a real
+    /// function has no single meaning; use
+    /// [`map_voxels`](Self::map_voxels) with the complex type instead.
+    pub fn map_voxels_f32(&mut self, f: impl FnMut(f32) -> f32) -> Result<(), Error> {
+        struct Serial<F>(F);
+        impl<F: FnMut(f32) -> f32> MapF32 for Serial<F> {
+            fn map<T: Copy + Send>(
+                &mut self,
+                v: &mut [T],
+                to: impl Fn(T) -> f32 + Sync,
+                from: impl Fn(f32) -> T + Sync,
+            ) {
+                let mut buf = Vec::with_capacity(MAP_CHUNK.min(v.len()));
+                for chunk in v.chunks_mut(MAP_CHUNK) {
+                    buf.clear();
+                    buf.extend(chunk.iter().map(|&x| (self.0)(to(x))));
+                    for (d, &x) in chunk.iter_mut().zip(&buf) {
+                        *d = from(x);
+                    }
+                }
+            }
+        }
+        self.map_f32_with(&mut Serial(f))
+    }
+
+    /// Run `m` over the voxels with the `f32` conversions for this mode;
+    /// shared by the serial and parallel `map_voxels_f32`.
+    pub(crate) fn map_f32_with(&mut self, m: &mut impl MapF32) -> Result<(), Error> {
+        let unsigned_m0 = self
+            .header
+            .detect_imod()
+            .is_some_and(|imod| !imod.bytes_are_signed);
+        let s = self.shape();
+        match &mut self.data {
+            OwnedData::Int8(v) if unsigned_m0 => {
+                m.map(v, |x| f32::from(x as u8), |x| x.round() as u8 as i8)
+            }
+            OwnedData::Int8(v) => m.map(v, f32::from, |x| x.round() as i8),
+            OwnedData::Int16(v) => m.map(v, f32::from, |x| x.round() as i16),
+            OwnedData::Float32(v) => m.map(v, |x| x, |x| x),
+            OwnedData::Uint16(v) => m.map(v, f32::from, |x| x.round() as u16),
+            #[cfg(feature = "f16")]
+            OwnedData::Float16(v) => m.map(v, |x| x.to_f32(), crate::f16::from_f32),
+            OwnedData::Packed4Bit(v) => {
+                let rows = s.ny * s.nz;
+                let mut nibbles = crate::engine::convert::unpack_u4_bytes_to_u8(v, s.nx, rows);
+                m.map(&mut nibbles, f32::from, |x| {
+                    x.round().clamp(0.0, 15.0) as u8
+                });
+                *v = crate::engine::convert::pack_u8_to_u4_bytes(&nibbles, s.nx, rows);
+            }
+            OwnedData::Int16Complex(_) | OwnedData::Float32Complex(_) => {
+                return Err(Error::UnsupportedMode);
+            }
+        }
+        Ok(())
+    }
+
+    /// Append one Z-section of `nx * ny` voxels, incrementing `nz`.
+    ///
+    /// For a single volume `mz` grows with `nz` when the two were equal;
+    /// image and volume stacks keep their `mz` (1 and the per-volume depth).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ModeMismatch`] if the mode is not `T::MODE`, and
+    /// [`Error::BlockShapeMismatch`] if `section` is not `nx * ny` voxels
+    /// long.
+    pub fn push_section<T: Voxel>(&mut self, section: &[T]) -> Result<(), Error> {
+        let s = self.shape();
+        let file_mode = self.mode();
+        let data = T::from_owned_mut(&mut self.data).ok_or(Error::ModeMismatch {
+            file_mode,
+            requested_mode: T::MODE,
+            offset: None,
+        })?;
+        if section.len() != s.nx * s.ny {
+            return Err(Error::BlockShapeMismatch {
+                expected: s.nx * s.ny,
+                actual: section.len(),
+            });
+        }
+        data.extend_from_slice(section);
+        if self.header.is_volume() && self.header.mz == self.header.nz {
+            self.header.mz += 1;
+        }
+        self.header.nz += 1;
+        Ok(())
+    }
+
+    /// Encode the whole file: header, extended header and little-endian
+    /// voxel data.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidHeaderDetailed`] if the header does not
+    /// validate, for instance while `nz` is still 0.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let size = self.header.data_size_u64().unwrap_or(0) as usize;
+        let mut out = Vec::with_capacity(1024 + self.ext_header.len() + size);
+        self.write_into(&mut out)?;
+        Ok(out)
+    }
+
+    /// Stream [`to_bytes`](Self::to_bytes) into `w` — a socket, an archive
+    /// entry, a `Vec<u8>` — encoding the voxels a chunk at a time rather
+    /// than building the whole file image first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrc::{Header, MrcBuf, Reader};
+    ///
+    /// let mut header = Header::new();
+    /// (header.nx, header.ny, header.nz) = (2, 2, 1);
+    /// (header.mx, header.my, header.mz) = (2, 2, 1);
+    /// let buf = MrcBuf::new(header)?;
+    ///
+    /// let mut out = std::io::Cursor::new(Vec::new());
+    /// buf.write_into(&mut out)?;
+    /// let reader = Reader::from_bytes(out.into_inner())?;
+    /// assert_eq!(reader.shape().nx, 2);
+    /// # Ok::<(), mrc::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// As [`to_bytes`](Self::to_bytes), plus [`Error::Io`] from `w`. Nothing
+    /// is written when the header does not validate.
+    pub fn write_into<W: std::io::Write>(&self, w: &mut W) -> Result<(), Error> {
+        self.validate()?;
+        let mut raw = [0u8; 1024];
+        self.header.encode_to_bytes(&mut raw);
+        w.write_all(&raw)?;
+        w.write_all(&self.ext_header)?;
+        match self.data() {
+            DataView::Int8(v) => write_le(w, v),
+            DataView::Int16(v) => write_le(w, v),
+            DataView::Float32(v) => write_le(w, v),
+            DataView::Int16Complex(v) => write_le(w, v),
+            DataView::Float32Complex(v) => write_le(w, v),
+            DataView::Uint16(v) => write_le(w, v),
+            #[cfg(feature = "f16")]
+            DataView::Float16(v) => write_le(w, v),
+            DataView::Packed4Bit(v) => Ok(w.write_all(v)?),
+        }
+    }
+
+    /// Write [`to_bytes`](Self::to_bytes) to `path`, replacing any file
+    /// there.
+    ///
+    /// # Errors
+    ///
+    /// As [`to_bytes`](Self::to_bytes), plus [`Error::Io`] if the file
+    /// cannot be written.
+    pub fn write_to<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), Error> {
+        // Check before creating, so an invalid buffer leaves any existing
+        // file untouched.
+        self.validate()?;
+        let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        self.write_into(&mut file)?;
+        std::io::Write::flush(&mut file)?;
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        self.header
+            .validate_detailed()
+            .map_err(Error::InvalidHeaderDetailed)
+    }
+}
+
+/// Encode `values` little-endian into `w`, one bounded chunk at a time.
+fn write_le<T: EndianCodec + Sync>(w: &mut impl std::io::Write, values: &[T]) -> Result<(), Error> {
+    const CHUNK_VOXELS: usize = 262_144;
+    let mut buf = vec![0u8; CHUNK_VOXELS.min(values.len()) * T::BYTE_SIZE];
+    for chunk in values.chunks(CHUNK_VOXELS) {
+        let dst = &mut buf[..chunk.len() * T::BYTE_SIZE];
+        encode_slice(chunk, dst, FileEndian::LittleEndian)?;
+        w.write_all(dst)?;
+    }
+    Ok(())
+}
+
+impl Reader {
+    /// An owned, editable copy of the volume in native byte order.
+    ///
+    /// A memory-mapped big-endian file can be read but not swapped where it
+    /// lies, since the mapping is read-only. This decodes the voxels from
+    /// the order the machine stamp names into a fresh [`MrcBuf`], so typed
+    /// slices such as [`MrcBuf::data_as`] work whatever the file's origin.
+    /// Native-endian files are copied as they are. The copy's header is
+    /// stamped little-endian, the order [`MrcBuf::to_bytes`] writes.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let reader = mrc::Reader::open("big_endian_map.mrc")?;
+    /// let buf = reader.to_native_endian()?;
+    /// let data: &[f32] = buf.data_as()?;
+    /// println!("first voxel: {}", data[0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Propagates any error from reading the data.
+    pub fn to_native_endian(&self) -> Result<MrcBuf, Error> {
+        MrcBuf::from_reader(self)
+    }
+}
@@ -0,0 +1,71 @@
+//! Cooperative cancellation of long-running reads.
+//!
+//! Statistics, conversions and comparisons over a large volume can run for
+//! minutes. A [`CancellationToken`] attached to a reader with
+//! [`Reader::set_cancellation`](crate::Reader::set_cancellation) (or
+//! [`OpenOptions::cancellation`](crate::OpenOptions::cancellation)) is
+//! checked before each section, slab, tile or chunk is read; once another
+//! thread calls [`cancel`](CancellationToken::cancel), the operation stops
+//! at the next check and returns [`Error::Cancelled`](crate::Error::Cancelled).
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A shared flag that asks readers to stop.
+///
+/// Clones share the flag, so keep one in the UI thread and hand another to
+/// the reader. Cancellation cannot be undone; use a new token for the next
+/// operation.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> Result<(), mrc::Error> {
+/// use mrc::CancellationToken;
+///
+/// let token = CancellationToken::new();
+/// let mut reader = mrc::Reader::open("tomogram.mrc")?;
+/// reader.set_cancellation(Some(token.clone()));
+/// let worker = std::thread::spawn(move || reader.histogram(256));
+///
+/// token.cancel(); // e.g. the user pressed "Stop"
+/// match worker.join().expect("worker panicked") {
+///     Err(mrc::Error::Cancelled) => println!("stopped"),
+///     other => println!("{other:?}"),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// A token that has not been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask every operation watching this token to stop.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// `true` once [`cancel`](Self::cancel) has been called on any clone.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// `Err(Error::Cancelled)` once cancelled, for use with `?` in loops
+    /// of your own.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Cancelled`](crate::Error::Cancelled) once cancelled.
+    pub fn check(&self) -> Result<(), crate::Error> {
+        if self.is_cancelled() {
+            Err(crate::Error::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,241 @@
+//! A writer fed by any number of producer threads over a channel.
+//!
+//! Reconstruction code that splits a volume across worker threads finishes
+//! sections in whatever order the workers happen to run. [`ChannelWriter`]
+//! owns the output file on a background thread; workers send it
+//! [`Section`]s through cloned [`SectionSender`]s, and it writes them in Z order,
+//! holding early arrivals until the sections before them come in. Writing
+//! in order lets the density statistics be accumulated along the way, so
+//! the header is complete without reading the data back.
+
+use crate::io::double_buffer::worker_gone;
+use crate::{Error, FileEndian, Header, Voxel, VoxelBlock, Writer};
+
+use std::collections::BTreeMap;
+use std::sync::mpsc::{Receiver, SendError, SyncSender, channel, sync_channel};
+use std::thread::JoinHandle;
+
+/// Sections queued between the producers and the background thread
+/// before [`SectionSender::send`] blocks.
+const QUEUE_DEPTH: usize = 16;
+
+/// Channel message: a section, or `None` from [`ChannelWriter::finish`].
+type Msg<T> = Option<Section<T>>;
+
+/// One complete Z-section sent to a [`ChannelWriter`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section<T> {
+    /// Index of the section, `0..nz`.
+    pub z: usize,
+    /// `nx · ny` voxels, x fastest.
+    pub data: Vec<T>,
+}
+
+/// The producer end of a [`ChannelWriter`]; clone one per thread.
+#[derive(Debug)]
+pub struct SectionSender<T> {
+    tx: SyncSender<Msg<T>>,
+}
+
+impl<T> Clone for SectionSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            tx: self.tx.clone(),
+        }
+    }
+}
+
+impl<T> SectionSender<T> {
+    /// Queue `section` for writing, blocking while the queue is full.
+    ///
+    /// # Errors
+    ///
+    /// Hands `section` back once the writer has finished or its background
+    /// thread has stopped on an error, which
+    /// [`finish`](ChannelWriter::finish) then reports.
+    pub fn send(&self, section: Section<T>) -> Result<(), SendError<Section<T>>> {
+        match self.tx.send(Some(section)) {
+            Err(SendError(Some(section))) => Err(SendError(section)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A background writer that collects sections from many threads.
+///
+/// Hand each producer a [`sender`](Self::sender), and call
+/// [`finish`](Self::finish) once they are done. The queue between them is
+/// bounded, so producers that outrun the disk block in
+/// [`send`](SectionSender::send) instead of piling sections up in memory.
+///
+/// `finish` does not wait for outstanding senders to be dropped: it writes
+/// what was sent before it was called and then finalizes, so a sender kept
+/// on the finishing thread cannot hold it up. Sections sent afterwards are
+/// rejected.
+///
+/// Sections that arrive ahead of the next one due are kept in memory until
+/// it arrives, so producers should work roughly in Z order; a single very
+/// late section makes every section after it wait in RAM.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> Result<(), mrc::Error> {
+/// use mrc::{ChannelWriter, Header, Section};
+///
+/// let mut header = Header::new();
+/// (header.nx, header.ny, header.nz) = (256, 256, 64);
+/// (header.mx, header.my, header.mz) = (256, 256, 64);
+/// header.mode = mrc::Mode::Float32.as_i32();
+///
+/// let writer = ChannelWriter::<f32>::spawn("recon.mrc", header)?;
+/// std::thread::scope(|scope| {
+///     for worker in 0..4 {
+///         let tx = writer.sender();
+///         scope.spawn(move || {
+///             for z in (worker..64).step_by(4) {
+///                 let data = vec![z as f32; 256 * 256]; // reconstruct section z
+///                 tx.send(Section { z, data }).ok();
+///             }
+///         });
+///     }
+/// });
+/// writer.finish()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ChannelWriter<T: Voxel> {
+    sender: SyncSender<Msg<T>>,
+    worker: Option<JoinHandle<Result<(), Error>>>,
+}
+
+impl<T: Voxel> ChannelWriter<T> {
+    /// Create the file at `path` from `header` and start the background
+    /// thread that writes sections to it.
+    ///
+    /// The file is little-endian, with a zeroed extended header of
+    /// `header.nsymbt` bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ModeMismatch`] if `header.mode` is not `T::MODE`,
+    /// and the errors of [`WriterBuilder::finish`](crate::WriterBuilder::finish)
+    /// for an invalid header or a file that cannot be created.
+    pub fn spawn<P: AsRef<std::path::Path>>(path: P, header: Header) -> Result<Self, Error> {
+        // An invalid mode is left for header validation to report.
+        match crate::Mode::from_i32(header.mode) {
+            Some(mode) if mode != T::MODE => {
+                return Err(Error::ModeMismatch {
+                    file_mode: mode,
+                    requested_mode: T::MODE,
+                    offset: None,
+                });
+            }
+            _ => {}
+        }
+        let path = path.as_ref().to_path_buf();
+        let (tx, rx) = sync_channel::<Msg<T>>(QUEUE_DEPTH);
+        let (ready_tx, ready_rx) = channel::<Result<(), Error>>();
+        let worker = std::thread::spawn(move || {
+            match Writer::create(path, header, &[], FileEndian::LittleEndian) {
+                Ok(w) => {
+                    let _ = ready_tx.send(Ok(()));
+                    run(w, rx)
+                }
+                Err(e) => {
+                    let _ = ready_tx.send(Err(e));
+                    Ok(())
+                }
+            }
+        });
+        ready_rx.recv().map_err(|_| worker_gone())??;
+        Ok(Self {
+            sender: tx,
+            worker: Some(worker),
+        })
+    }
+
+    /// A sender for one producer; clone it freely.
+    pub fn sender(&self) -> SectionSender<T> {
+        SectionSender {
+            tx: self.sender.clone(),
+        }
+    }
+
+    /// Write every section sent so far, then finalize the file.
+    ///
+    /// Call this once the producers are done: it does not wait for their
+    /// senders to be dropped, and sections sent after it are rejected.
+    ///
+    /// # Errors
+    ///
+    /// Returns the background thread's first error: a section with the
+    /// wrong length, a `z` past the end or sent twice, a failed write, or
+    /// — after the file has been finalized with zeros in their place —
+    /// sections that never arrived.
+    pub fn finish(mut self) -> Result<(), Error> {
+        self.join()
+    }
+
+    fn join(&mut self) -> Result<(), Error> {
+        // Fails only if the thread has already stopped on an error.
+        let _ = self.sender.send(None);
+        match self.worker.take() {
+            Some(worker) => worker.join().unwrap_or_else(|_| Err(worker_gone())),
+            None => Err(worker_gone()),
+        }
+    }
+}
+
+impl<T: Voxel> Drop for ChannelWriter<T> {
+    /// Waits like [`finish`](Self::finish), discarding any error.
+    fn drop(&mut self) {
+        if self.worker.is_some() {
+            let _ = self.join();
+        }
+    }
+}
+
+/// Body of the background thread: write sections in Z order as they
+/// become contiguous, then finalize once told to finish.
+fn run<T: Voxel>(mut writer: Writer, rx: Receiver<Msg<T>>) -> Result<(), Error> {
+    let s = writer.shape();
+    let mut next_z = 0;
+    let mut pending = BTreeMap::new();
+    while let Ok(Some(section)) = rx.recv() {
+        if section.z >= s.nz || section.z < next_z || pending.contains_key(&section.z) {
+            return Err(Error::invalid_input(format!(
+                "section {} is out of range or was already sent",
+                section.z
+            )));
+        }
+        if section.data.len() != s.nx * s.ny {
+            return Err(Error::BlockShapeMismatch {
+                expected: s.nx * s.ny,
+                actual: section.data.len(),
+            });
+        }
+        pending.insert(section.z, section.data);
+        while let Some(data) = pending.remove(&next_z) {
+            writer.write_block(&VoxelBlock::new([0, 0, next_z], [s.nx, s.ny, 1], data)?)?;
+            next_z += 1;
+        }
+    }
+    if next_z == s.nz {
+        writer.update_header_stats()?;
+        return writer.finalize();
+    }
+    // Keep what did arrive, zero-fill the gaps, and say what is missing.
+    let missing = next_z;
+    for z in next_z..s.nz {
+        let data = pending
+            .remove(&z)
+            .unwrap_or_else(|| vec![T::default(); s.nx * s.ny]);
+        writer.write_block(&VoxelBlock::new([0, 0, z], [s.nx, s.ny, 1], data)?)?;
+    }
+    writer.finalize()?;
+    Err(Error::invalid_input(format!(
+        "section {missing} was never sent"
+    )))
+}
@@ -0,0 +1,125 @@
+//! Out-of-core processing of whole volumes, one Z-slab at a time.
+//!
+//! A file far larger than RAM can still be filtered, rescaled or
+//! thresholded voxel by voxel if only a slab of it is in memory at once.
+//! [`ChunkedProcessor`] drives that loop: it reads slabs sized by a
+//! [`StreamingConfig`] through [`Reader::convert`], hands each to a closure,
+//! and either writes what the closure returns to a [`Writer`] at the same
+//! position ([`map`](ChunkedProcessor::map)) or folds it into an
+//! accumulator ([`reduce`](ChunkedProcessor::reduce)).
+
+use crate::engine::convert::ConvertFrom;
+use crate::{Error, Reader, StreamingConfig, Voxel, VoxelBlock, Writer};
+
+/// Slab-at-a-time map and reduce over a [`Reader`], in bounded memory.
+///
+/// Each step holds one slab of the source, its converted copy and the
+/// closure's output, so resident memory stays near
+/// [`max_resident_bytes`](StreamingConfig::max_resident_bytes) plus one
+/// output slab, whatever the file size.
+///
+/// # Examples
+///
+/// ```no_run
+/// use mrc::{ChunkedProcessor, StreamingConfig};
+///
+/// # fn main() -> Result<(), mrc::Error> {
+/// let reader = mrc::Reader::open("tomogram.mrc")?;
+/// let s = reader.shape();
+/// let mut writer = mrc::create("thresholded.mrc")
+///     .shape([s.nx, s.ny, s.nz])
+///     .mode::<i8>()
+///     .finish()?;
+/// let config = StreamingConfig { chunk_bytes: 16 << 20, max_resident_bytes: 64 << 20 };
+/// ChunkedProcessor::new(&reader)
+///     .config(config)
+///     .map(&mut writer, |slab: mrc::VoxelBlock<f32>| {
+///         Ok(slab.data.iter().map(|&v| i8::from(v > 0.5)).collect::<Vec<i8>>())
+///     })?;
+/// writer.finalize()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ChunkedProcessor<'a> {
+    reader: &'a Reader,
+    config: StreamingConfig,
+}
+
+impl<'a> ChunkedProcessor<'a> {
+    /// A processor over `reader` with the default [`StreamingConfig`].
+    pub fn new(reader: &'a Reader) -> Self {
+        Self {
+            reader,
+            config: StreamingConfig::default(),
+        }
+    }
+
+    /// Size slabs by `config` instead of the default.
+    #[must_use]
+    pub fn config(mut self, config: StreamingConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Pass every slab, converted to `T`, through `f` and write the voxels
+    /// it returns to `writer` at the slab's position.
+    ///
+    /// `f` receives the slab by value, so an in-place transform can return
+    /// `slab.data` without allocating. The writer must have the reader's
+    /// shape and mode `U`; it is not finalized.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with
+    /// [`InvalidInput`](std::io::ErrorKind::InvalidInput) if the writer's
+    /// shape differs from the reader's, [`Error::ModeMismatch`] if its mode
+    /// is not `U`, [`Error::BlockShapeMismatch`] if `f` returns the wrong
+    /// number of voxels, and the first error from reading, from `f` or from
+    /// writing.
+    pub fn map<T, U, F>(&self, writer: &mut Writer, mut f: F) -> Result<(), Error>
+    where
+        T: Voxel + ConvertFrom<f32>,
+        U: Voxel,
+        F: FnMut(VoxelBlock<T>) -> Result<Vec<U>, Error>,
+    {
+        let (rs, ws) = (self.reader.shape(), writer.shape());
+        if rs != ws {
+            return Err(Error::invalid_input(format!(
+                "writer is {}×{}×{}, reader is {}×{}×{}",
+                ws.nx, ws.ny, ws.nz, rs.nx, rs.ny, rs.nz
+            )));
+        }
+        if writer.mode() != U::MODE {
+            return Err(Error::ModeMismatch {
+                file_mode: writer.mode(),
+                requested_mode: U::MODE,
+                offset: None,
+            });
+        }
+        for slab in self.reader.convert::<T>().chunks(&self.config) {
+            let slab = slab?;
+            let (offset, shape) = (slab.offset, slab.shape);
+            let out = VoxelBlock::new(offset, shape, f(slab)?)?;
+            writer.write_block(&out)?;
+        }
+        Ok(())
+    }
+
+    /// Fold every slab, converted to `T`, into an accumulator.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from reading or from `f`.
+    pub fn reduce<T, A, F>(&self, init: A, mut f: F) -> Result<A, Error>
+    where
+        T: Voxel + ConvertFrom<f32>,
+        F: FnMut(A, VoxelBlock<T>) -> Result<A, Error>,
+    {
+        let mut acc = init;
+        for slab in self.reader.convert::<T>().chunks(&self.config) {
+            acc = f(acc, slab?)?;
+        }
+        Ok(acc)
+    }
+}
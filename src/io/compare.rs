@@ -0,0 +1,205 @@
+//! Voxel-by-voxel comparison of two volumes.
+//!
+//! [`Reader::approx_eq`](crate::Reader::approx_eq) checks that two files
+//! hold the same values within a tolerance, whatever their modes, one
+//! Z-section at a time. It is meant for round-trip and regression tests of
+//! converters and writers. [`difference`] subtracts one volume from another
+//! for inspecting what changed, such as between refinement iterations or
+//! half-maps.
+
+use crate::{Error, Header, Reader, VoxelBlock};
+
+/// Outcome of [`Reader::approx_eq`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComparisonReport {
+    /// Number of voxels compared.
+    pub voxels: usize,
+    /// Number of voxels outside the tolerance.
+    pub mismatches: usize,
+    /// Largest absolute difference. NaN pairs are not counted.
+    pub max_abs_diff: f64,
+    /// Mean absolute difference. NaN pairs are not counted.
+    pub mean_abs_diff: f64,
+    /// `[x, y, z]` of the first voxel outside the tolerance, in file order.
+    pub first_mismatch: Option<[usize; 3]>,
+}
+
+impl ComparisonReport {
+    /// `true` when every voxel is within the tolerance.
+    pub fn is_match(&self) -> bool {
+        self.mismatches == 0
+    }
+}
+
+impl Reader {
+    /// Compare this volume with `other`, voxel by voxel.
+    ///
+    /// Both are read through [`convert::<f32>()`](Self::convert), so files
+    /// of different modes can be compared. Voxels `a` (from `self`) and `b`
+    /// match when `|a − b| ≤ abs_tol + rel_tol · |b|`, as in NumPy's
+    /// `isclose`; two NaNs match each other, and infinities match only
+    /// themselves.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let original = mrc::Reader::open("map.mrc")?;
+    /// let converted = mrc::Reader::open("map_f16.mrc")?;
+    /// let report = original.approx_eq(&converted, 1e-3, 1e-3)?;
+    /// assert!(report.is_match(), "first difference at {:?}", report.first_mismatch);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with
+    /// [`InvalidInput`](std::io::ErrorKind::InvalidInput) if the two volumes
+    /// differ in shape, and propagates any error from reading either one.
+    pub fn approx_eq(
+        &self,
+        other: &Reader,
+        abs_tol: f64,
+        rel_tol: f64,
+    ) -> Result<ComparisonReport, Error> {
+        check_same_shape(self, other)?;
+        let s = self.shape();
+        let mut report = ComparisonReport {
+            voxels: 0,
+            mismatches: 0,
+            max_abs_diff: 0.0,
+            mean_abs_diff: 0.0,
+            first_mismatch: None,
+        };
+        let mut sum = 0.0;
+        let mut counted = 0usize;
+        let (ca, cb) = (self.convert::<f32>(), other.convert::<f32>());
+        let pairs = ca.slices().zip(cb.slices());
+        for (z, (sa, sb)) in pairs.enumerate() {
+            let (sa, sb) = (sa?, sb?);
+            for (i, (&a, &b)) in sa.data.iter().zip(&sb.data).enumerate() {
+                let (a, b) = (f64::from(a), f64::from(b));
+                let diff = (a - b).abs();
+                let close = if a.is_nan() || b.is_nan() {
+                    a.is_nan() && b.is_nan()
+                } else if a.is_infinite() || b.is_infinite() {
+                    a == b
+                } else {
+                    diff <= abs_tol + rel_tol * b.abs()
+                };
+                if !close {
+                    report.mismatches += 1;
+                    report.first_mismatch.get_or_insert([i % s.nx, i / s.nx, z]);
+                }
+                if !diff.is_nan() {
+                    report.max_abs_diff = report.max_abs_diff.max(diff);
+                    sum += diff;
+                    counted += 1;
+                }
+            }
+            report.voxels += sa.data.len();
+        }
+        if counted > 0 {
+            report.mean_abs_diff = sum / counted as f64;
+        }
+        Ok(report)
+    }
+}
+
+/// Reject a pair of volumes whose dimensions differ.
+pub(crate) fn check_same_shape(a: &Reader, b: &Reader) -> Result<(), Error> {
+    let (sa, sb) = (a.shape(), b.shape());
+    if sa == sb {
+        return Ok(());
+    }
+    Err(Error::invalid_input(format!(
+        "volumes differ in shape: {}×{}×{} vs {}×{}×{}",
+        sa.nx, sa.ny, sa.nz, sb.nx, sb.ny, sb.nz
+    )))
+}
+
+/// A voxel-wise difference volume, returned by [`difference`].
+#[derive(Debug, Clone)]
+pub struct DifferenceMap {
+    /// Header to write the map with: the first volume's header with mode 2,
+    /// no extended header, and `dmin`/`dmax`/`dmean`/`rms` describing the
+    /// difference (see [`Header::density_stats`]).
+    pub header: Header,
+    /// `a − b` for every voxel.
+    pub data: VoxelBlock<f32>,
+}
+
+/// Subtract volume `b` from volume `a`, voxel by voxel.
+///
+/// Both volumes are read as `f32` through [`Reader::convert`], one section
+/// at a time, and must have the same dimensions and the same voxel size
+/// (to 0.1%). Origin, labels and the rest of the header are taken from `a`.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> Result<(), mrc::Error> {
+/// let half1 = mrc::Reader::open("half_map_1.mrc")?;
+/// let half2 = mrc::Reader::open("half_map_2.mrc")?;
+/// let diff = mrc::difference(&half1, &half2)?;
+/// let (dmin, dmax, dmean, rms) = diff.header.density_stats();
+/// println!("difference in [{dmin}, {dmax}], mean {dmean}, rms {rms}");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns [`Error::Io`] with [`InvalidInput`](std::io::ErrorKind::InvalidInput)
+/// if the dimensions or voxel sizes differ, and propagates any error from
+/// reading either volume.
+pub fn difference(a: &Reader, b: &Reader) -> Result<DifferenceMap, Error> {
+    check_same_shape(a, b)?;
+    let (va, vb) = (a.header().voxel_size(), b.header().voxel_size());
+    let same = va
+        .iter()
+        .zip(&vb)
+        .all(|(&p, &q)| (p - q).abs() <= 1e-3 * p.abs().max(q.abs()));
+    if !same {
+        return Err(Error::invalid_input(format!(
+            "voxel sizes differ: {va:?} Å vs {vb:?} Å"
+        )));
+    }
+
+    let s = a.shape();
+    let mut data = Vec::with_capacity(s.nx * s.ny * s.nz);
+    let (ca, cb) = (a.convert::<f32>(), b.convert::<f32>());
+    for (sa, sb) in ca.slices().zip(cb.slices()) {
+        let (sa, sb) = (sa?, sb?);
+        data.extend(sa.data.iter().zip(&sb.data).map(|(&x, &y)| x - y));
+    }
+
+    let mut header = *a.header();
+    header.mode = crate::Mode::Float32.as_i32();
+    header.nsymbt = 0;
+    (header.dmin, header.dmax, header.dmean, header.rms) = summary(&data);
+    let data = VoxelBlock::new([0, 0, 0], [s.nx, s.ny, s.nz], data)?;
+    Ok(DifferenceMap { header, data })
+}
+
+/// `(min, max, mean, rms deviation from the mean)`, accumulated in `f64`.
+pub(crate) fn summary(data: &[f32]) -> (f32, f32, f32, f32) {
+    if data.is_empty() {
+        return (0.0, -1.0, -2.0, -1.0);
+    }
+    let n = data.len() as f64;
+    let (mut min, mut max, mut sum) = (f32::INFINITY, f32::NEG_INFINITY, 0f64);
+    for &v in data {
+        min = min.min(v);
+        max = max.max(v);
+        sum += f64::from(v);
+    }
+    let mean = sum / n;
+    let var = data
+        .iter()
+        .map(|&v| (f64::from(v) - mean).powi(2))
+        .sum::<f64>()
+        / n;
+    (min, max, mean as f32, var.sqrt() as f32)
+}
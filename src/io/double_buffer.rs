@@ -0,0 +1,228 @@
+//! Section-by-section writing that overlaps computation with disk I/O.
+//!
+//! Simulation and reconstruction codes typically produce a volume one
+//! Z-section at a time and wait on every write. [`DoubleBufferedWriter`]
+//! keeps two section buffers: while the caller fills one, a background
+//! thread encodes and writes the other. When the disk falls behind,
+//! [`submit`](DoubleBufferedWriter::submit) blocks until the previous
+//! section is on its way, so no more than two sections are ever resident.
+
+use crate::{Error, Voxel, VoxelBlock, Writer, WriterBuilder};
+
+use std::sync::mpsc::{Receiver, Sender, SyncSender, channel, sync_channel};
+use std::thread::JoinHandle;
+
+/// A writer that accepts whole Z-sections and writes them on a background
+/// thread, created by [`WriterBuilder::finish_double_buffered`].
+///
+/// Fill [`buffer`](Self::buffer) with section `next_z()`, then call
+/// [`submit`](Self::submit); repeat for every section and end with
+/// [`finish`](Self::finish), which waits for the last write, stores the
+/// density statistics gathered along the way and finalizes the file.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> Result<(), mrc::Error> {
+/// let mut writer = mrc::create("simulated.mrc")
+///     .shape([512, 512, 200])
+///     .mode::<f32>()
+///     .finish_double_buffered::<f32>()?;
+/// while writer.next_z() < 200 {
+///     let z = writer.next_z() as f32;
+///     for (i, v) in writer.buffer().iter_mut().enumerate() {
+///         *v = (i as f32 + z).sin(); // expensive per-section work
+///     }
+///     writer.submit()?;
+/// }
+/// writer.finish()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct DoubleBufferedWriter<T: Voxel> {
+    shape: [usize; 3],
+    next_z: usize,
+    current: Vec<T>,
+    filled: Option<SyncSender<Vec<T>>>,
+    recycled: Receiver<Vec<T>>,
+    worker: Option<JoinHandle<Result<(), Error>>>,
+}
+
+impl<T: Voxel> DoubleBufferedWriter<T> {
+    /// Index of the section the next [`submit`](Self::submit) will write.
+    pub fn next_z(&self) -> usize {
+        self.next_z
+    }
+
+    /// `[nx, ny, nz]` of the volume being written.
+    pub fn shape(&self) -> [usize; 3] {
+        self.shape
+    }
+
+    /// The section buffer to fill, `nx · ny` voxels, x fastest.
+    ///
+    /// It holds whatever was written two sections earlier (zeros at first),
+    /// so every voxel should be overwritten before submitting.
+    pub fn buffer(&mut self) -> &mut [T] {
+        &mut self.current
+    }
+
+    /// Hand the filled buffer to the background thread as section
+    /// [`next_z`](Self::next_z) and take the other buffer for the next one.
+    ///
+    /// Blocks while the section before is still being written.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with
+    /// [`InvalidInput`](std::io::ErrorKind::InvalidInput) once all `nz`
+    /// sections have been submitted, and the background thread's error if
+    /// an earlier write failed; after that the writer accepts nothing more.
+    pub fn submit(&mut self) -> Result<(), Error> {
+        if self.next_z == self.shape[2] {
+            return Err(Error::invalid_input(format!(
+                "all {} sections have been submitted",
+                self.shape[2]
+            )));
+        }
+        let Some(filled) = &self.filled else {
+            return Err(worker_gone());
+        };
+        let section = std::mem::take(&mut self.current);
+        let next = filled
+            .send(section)
+            .ok()
+            .and_then(|()| self.recycled.recv().ok());
+        match next {
+            Some(buf) => {
+                self.current = buf;
+                self.next_z += 1;
+                Ok(())
+            }
+            None => Err(self.join().err().unwrap_or_else(worker_gone)),
+        }
+    }
+
+    /// Wait for every submitted section to be written, then finalize the
+    /// file.
+    ///
+    /// When all `nz` sections were submitted the header's `dmin`, `dmax`,
+    /// `dmean` and `rms` are set from statistics accumulated while writing,
+    /// without reading the data back. Submitting fewer sections leaves the
+    /// rest of the data block zero and the statistics untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error the background thread hit while writing or
+    /// finalizing.
+    pub fn finish(mut self) -> Result<(), Error> {
+        self.join()
+    }
+
+    /// Close the section channel and wait for the background thread.
+    fn join(&mut self) -> Result<(), Error> {
+        self.filled = None;
+        match self.worker.take() {
+            Some(worker) => worker.join().unwrap_or_else(|_| Err(worker_gone())),
+            None => Err(worker_gone()),
+        }
+    }
+}
+
+pub(crate) fn worker_gone() -> Error {
+    Error::Io(std::io::Error::other(
+        "background writer thread has stopped",
+    ))
+}
+
+/// Body of the background thread: write each section as it arrives, hand
+/// its buffer back, and finalize once the channel closes.
+fn run<T: Voxel>(
+    mut writer: Writer,
+    filled: Receiver<Vec<T>>,
+    recycled: Sender<Vec<T>>,
+) -> Result<(), Error> {
+    let s = writer.shape();
+    let mut z = 0;
+    while let Ok(section) = filled.recv() {
+        let block = VoxelBlock::new([0, 0, z], [s.nx, s.ny, 1], section)?;
+        writer.write_block(&block)?;
+        z += 1;
+        // The caller may already be finishing; a closed channel is fine.
+        let _ = recycled.send(block.data);
+    }
+    if z == s.nz {
+        writer.update_header_stats()?;
+    }
+    for z in z..s.nz {
+        let zeros = vec![T::default(); s.nx * s.ny];
+        writer.write_block(&VoxelBlock::new([0, 0, z], [s.nx, s.ny, 1], zeros)?)?;
+    }
+    writer.finalize()
+}
+
+impl<T: Voxel> Drop for DoubleBufferedWriter<T> {
+    /// Dropping without [`finish`](Self::finish) still waits for the
+    /// submitted sections and finalizes the file, discarding any error.
+    fn drop(&mut self) {
+        if self.worker.is_some() {
+            let _ = self.join();
+        }
+    }
+}
+
+impl WriterBuilder {
+    /// Build a [`DoubleBufferedWriter`] that writes whole `T` sections on a
+    /// background thread while the caller computes the next one.
+    ///
+    /// The file is created as by [`finish`](Self::finish) before this
+    /// returns, so errors opening it are reported here.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ModeMismatch`] if the builder's mode is not
+    /// `T::MODE`, and the errors of [`finish`](Self::finish).
+    pub fn finish_double_buffered<T: Voxel>(self) -> Result<DoubleBufferedWriter<T>, Error> {
+        // An invalid mode is left for `finish` to report.
+        match crate::Mode::from_i32(self.header.mode) {
+            Some(mode) if mode != T::MODE => {
+                return Err(Error::ModeMismatch {
+                    file_mode: mode,
+                    requested_mode: T::MODE,
+                    offset: None,
+                });
+            }
+            _ => {}
+        }
+        let (filled_tx, filled_rx) = sync_channel::<Vec<T>>(1);
+        let (recycled_tx, recycled_rx) = channel::<Vec<T>>();
+        let (ready_tx, ready_rx) = channel::<Result<[usize; 3], Error>>();
+
+        // `Writer` is not `Send` (it may wrap any `Read + Write + Seek`), so
+        // it is created on the thread that will own it.
+        let worker = std::thread::spawn(move || match self.finish() {
+            Ok(w) => {
+                let s = w.shape();
+                // The second buffer starts out queued for reuse.
+                let _ = recycled_tx.send(vec![T::default(); s.nx * s.ny]);
+                let _ = ready_tx.send(Ok([s.nx, s.ny, s.nz]));
+                run(w, filled_rx, recycled_tx)
+            }
+            Err(e) => {
+                let _ = ready_tx.send(Err(e));
+                Ok(())
+            }
+        });
+        let shape = ready_rx.recv().map_err(|_| worker_gone())??;
+
+        Ok(DoubleBufferedWriter {
+            shape,
+            next_z: 0,
+            current: vec![T::default(); shape[0] * shape[1]],
+            filled: Some(filled_tx),
+            recycled: recycled_rx,
+            worker: Some(worker),
+        })
+    }
+}
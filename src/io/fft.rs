@@ -0,0 +1,155 @@
+//! Forward and inverse 3D FFTs between real and Fourier-space volumes.
+//!
+//! [`Reader::fft3`](crate::Reader::fft3) transforms a real volume into a
+//! mode 4 half-transform and [`Reader::ifft3`](crate::Reader::ifft3) takes
+//! a half-transform back to a real mode 2 volume. Both return the header to
+//! write the result with, so a round trip through files preserves the
+//! real-space grid and pixel size.
+//!
+//! Conventions follow NumPy's `rfftn`/`irfftn`: the forward transform is
+//! unnormalised, the inverse divides by the voxel count, and the stored
+//! layout is the unshifted one described in [`fourier`](super::fourier).
+//! The half-transform header records the real-space width in `mx`, which is
+//! what lets [`Header::is_half_transform`](crate::Header::is_half_transform)
+//! recognise it and lets odd widths round-trip.
+//!
+//! Requires the `fft` feature (disabled by default).
+
+use crate::{Error, Float32Complex, Header, Mode, VoxelBlock};
+
+use rustfft::FftPlanner;
+use rustfft::num_complex::Complex32;
+
+impl crate::Reader {
+    /// Fourier transform of a real volume, as a half-transform.
+    ///
+    /// Any real mode is read through [`convert::<f32>()`](Self::convert).
+    /// The result is `(nx/2 + 1) × ny × nz`. The returned header has mode 4,
+    /// that shape, `mx` set to the real-space `nx` (with `xlen` adjusted so
+    /// the pixel size is unchanged), `nsymbt` 0 and the density statistics
+    /// reset; everything else is copied from this file.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let reader = mrc::Reader::open("map.mrc")?;
+    /// let (header, transform) = reader.fft3()?;
+    /// let mut writer = mrc::Writer::from_writer(
+    ///     std::fs::File::options().read(true).write(true).create(true).open("map_fft.mrc")?,
+    ///     header,
+    ///     &[],
+    /// )?;
+    /// writer.write_block(&transform)?;
+    /// writer.finalize()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedMode`] for complex-mode files and
+    /// propagates any error from reading the volume.
+    pub fn fft3(&self) -> Result<(Header, VoxelBlock<Float32Complex>), Error> {
+        if self.mode().is_complex() {
+            return Err(Error::UnsupportedMode);
+        }
+        let s = self.shape();
+        let shape = [s.nx, s.ny, s.nz];
+        let real = self.convert::<f32>().read_volume()?;
+        let mut data: Vec<Complex32> = real.data.iter().map(|&v| Complex32::new(v, 0.0)).collect();
+        fft3_in_place(&mut data, shape, false);
+
+        let half_nx = s.nx / 2 + 1;
+        let half = data
+            .chunks_exact(s.nx)
+            .flat_map(|row| &row[..half_nx])
+            .map(|c| Float32Complex {
+                real: c.re,
+                imag: c.im,
+            })
+            .collect();
+        let header = transformed_header(self.header(), Mode::Float32Complex, half_nx, s.nx);
+        Ok((
+            header,
+            VoxelBlock::new([0, 0, 0], [half_nx, s.ny, s.nz], half)?,
+        ))
+    }
+
+    /// Inverse Fourier transform of a half-transform, as a real volume.
+    ///
+    /// The file must pass [`is_half_transform`](Self::is_half_transform),
+    /// which also fixes the real-space width `N`. The result is
+    /// `N × ny × nz`, divided by the voxel count so that the inverse of
+    /// [`fft3`](Self::fft3) recovers the original up to rounding. The returned header has mode 2, `nx = mx = N`
+    /// (pixel size unchanged), `nsymbt` 0 and the density statistics reset.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedMode`] if the file is not a
+    /// half-transform and propagates any error from reading the volume.
+    pub fn ifft3(&self) -> Result<(Header, VoxelBlock<f32>), Error> {
+        let full = self.expand_half_transform()?;
+        let shape = full.shape;
+        let mut data: Vec<Complex32> = full
+            .data
+            .iter()
+            .map(|c| Complex32::new(c.real, c.imag))
+            .collect();
+        fft3_in_place(&mut data, shape, true);
+
+        let norm = 1.0 / data.len() as f32;
+        let real = data.iter().map(|c| c.re * norm).collect();
+        let header = transformed_header(self.header(), Mode::Float32, shape[0], shape[0]);
+        Ok((header, VoxelBlock::new([0, 0, 0], shape, real)?))
+    }
+}
+
+/// Header for the other side of a transform: `nx` and `mode` replaced, `mx`
+/// set to the real-space width with the X pixel size kept, and the fields
+/// that no longer describe the data reset.
+fn transformed_header(source: &Header, mode: Mode, nx: usize, real_nx: usize) -> Header {
+    let pixel = source.voxel_size()[0];
+    let mut h = *source;
+    h.mode = mode.as_i32();
+    h.nx = nx as i32;
+    h.mx = real_nx as i32;
+    h.xlen = pixel * real_nx as f32;
+    h.nsymbt = 0;
+    (h.dmin, h.dmax, h.dmean, h.rms) = (0.0, -1.0, -2.0, -1.0);
+    h
+}
+
+/// Unnormalised 3D complex FFT of a row-major `nx × ny × nz` volume, one
+/// axis at a time. Y and Z lines are gathered into a scratch buffer.
+fn fft3_in_place(data: &mut [Complex32], [nx, ny, nz]: [usize; 3], inverse: bool) {
+    let mut planner = FftPlanner::new();
+    let mut plan = |n| {
+        if inverse {
+            planner.plan_fft_inverse(n)
+        } else {
+            planner.plan_fft_forward(n)
+        }
+    };
+
+    plan(nx).process(data);
+
+    for (n, stride) in [(ny, nx), (nz, nx * ny)] {
+        if n < 2 {
+            continue;
+        }
+        let fft = plan(n);
+        let mut line = vec![Complex32::default(); n];
+        for outer in (0..data.len()).step_by(stride * n) {
+            for start in outer..outer + stride {
+                for (i, c) in line.iter_mut().enumerate() {
+                    *c = data[start + i * stride];
+                }
+                fft.process(&mut line);
+                for (i, &c) in line.iter().enumerate() {
+                    data[start + i * stride] = c;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+//! Fourier-space volumes stored as half-transforms.
+//!
+//! The transform of real data is Hermitian, `F(-k) = conj(F(k))`, so FFT
+//! programs usually store only the `N/2 + 1` non-redundant columns along X.
+//! [`Reader::expand_half_transform`](crate::Reader::expand_half_transform)
+//! rebuilds the full `N`-column volume from them.
+//!
+//! The stored layout is taken to be the one real-to-complex FFTs produce:
+//! column `x` holds frequency `x`, and rows and sections run from frequency
+//! 0 upwards with negative frequencies in the upper half (not centred).
+
+use crate::{DataView, Error, Float32Complex, VoxelBlock};
+
+impl crate::Reader {
+    /// Heuristic check for a half-transform; see
+    /// [`Header::is_half_transform`](crate::Header::is_half_transform).
+    pub fn is_half_transform(&self) -> bool {
+        self.header().is_half_transform()
+    }
+
+    /// Reconstruct the full complex volume of a half-transform.
+    ///
+    /// The result is `N × ny × nz`, where `N` is the real-space width the
+    /// heuristic inferred (`mx` when the header records it, else `ny`).
+    /// Stored columns are copied as they are; column `x ≥ nx` is filled from
+    /// Hermitian symmetry as `conj(F[N − x, −y, −z])`. Mode 3 values are
+    /// widened to `f32`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let reader = mrc::Reader::open("map_fft.mrc")?;
+    /// if reader.is_half_transform() {
+    ///     let full = reader.expand_half_transform()?;
+    ///     println!("full transform: {:?}", full.shape);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedMode`] if the file is not a half-transform
+    /// by [`is_half_transform`](Self::is_half_transform), and propagates any
+    /// error from reading the volume.
+    pub fn expand_half_transform(&self) -> Result<VoxelBlock<Float32Complex>, Error> {
+        let full_nx = self
+            .header()
+            .half_transform_width()
+            .ok_or(Error::UnsupportedMode)?;
+        let half = self.complex_volume()?;
+        let s = self.shape();
+        let full = expand_hermitian(&half, [s.nx, s.ny, s.nz], full_nx);
+        VoxelBlock::new([0, 0, 0], [full_nx, s.ny, s.nz], full)
+    }
+
+    /// The whole volume of a complex-mode file as `Float32Complex`.
+    pub(crate) fn complex_volume(&self) -> Result<Vec<Float32Complex>, Error> {
+        let block = self.read_volume()?;
+        Ok(match block.data() {
+            DataView::Float32Complex(v) => v.to_vec(),
+            DataView::Int16Complex(v) => v
+                .iter()
+                .map(|c| Float32Complex {
+                    real: f32::from(c.real),
+                    imag: f32::from(c.imag),
+                })
+                .collect(),
+            _ => return Err(Error::UnsupportedMode),
+        })
+    }
+}
+
+/// Rebuild the `full_nx`-column transform from the `shape[0]` stored
+/// columns of a half-transform.
+pub(crate) fn expand_hermitian(
+    half: &[Float32Complex],
+    shape: [usize; 3],
+    full_nx: usize,
+) -> Vec<Float32Complex> {
+    let [nx, ny, nz] = shape;
+    let mut full = Vec::with_capacity(full_nx * ny * nz);
+    for z in 0..nz {
+        let mirror_z = (nz - z) % nz;
+        for y in 0..ny {
+            let mirror_y = (ny - y) % ny;
+            let row = &half[(z * ny + y) * nx..][..nx];
+            full.extend_from_slice(row);
+            let mirror_row = &half[(mirror_z * ny + mirror_y) * nx..][..nx];
+            for x in nx..full_nx {
+                let c = mirror_row[full_nx - x];
+                full.push(Float32Complex {
+                    real: c.real,
+                    imag: -c.imag,
+                });
+            }
+        }
+    }
+    full
+}
@@ -0,0 +1,85 @@
+//! Weighted summation of movie frames.
+//!
+//! A movie is stored as an image stack whose Z-sections are the camera
+//! frames in acquisition order. [`Reader::sum_frames_weighted`] adds the
+//! frames into one image with caller-supplied per-frame weights, which is
+//! the final step of most motion-correction and exposure-weighting schemes.
+//!
+//! [`Reader::sum_frames_weighted`]: crate::Reader::sum_frames_weighted
+
+use crate::{DataView, Error, Mode, VoxelBlock};
+
+impl crate::Reader {
+    /// Sum the frames (Z-sections) of a movie, scaling frame `i` by
+    /// `weights[i]`.
+    ///
+    /// Frames are accumulated straight from the section views returned by
+    /// [`subregion`](Self::subregion) — zero-copy for native-endian data —
+    /// instead of being converted to an `f32` copy first. Mode 0, complex
+    /// and 4-bit frames go through [`convert::<f32>()`](Self::convert) so
+    /// they get the same interpretation as every other `f32` read.
+    /// Frames with a weight of exactly zero are not read at all.
+    ///
+    /// Pass `1.0 / nz` for every frame to get the plain average.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let movie = mrc::Reader::open("aligned_frames.mrc")?;
+    /// // Drop the first two frames, which carry the most beam-induced motion.
+    /// let weights: Vec<f32> = (0..movie.shape().nz)
+    ///     .map(|i| if i < 2 { 0.0 } else { 1.0 })
+    ///     .collect();
+    /// let image = movie.sum_frames_weighted(&weights)?;
+    /// # let _ = image;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::BlockShapeMismatch`] if `weights.len()` is not the
+    /// number of sections, and propagates any error from reading a frame.
+    pub fn sum_frames_weighted(&self, weights: &[f32]) -> Result<VoxelBlock<f32>, Error> {
+        let s = self.shape();
+        if weights.len() != s.nz {
+            return Err(Error::BlockShapeMismatch {
+                expected: s.nz,
+                actual: weights.len(),
+            });
+        }
+        let frame_shape = [s.nx, s.ny, 1];
+        let mut sum = vec![0f32; s.nx * s.ny];
+        for (z, &w) in weights.iter().enumerate() {
+            if w == 0.0 {
+                continue;
+            }
+            let offset = [0, 0, z];
+            if matches!(
+                self.mode(),
+                Mode::Int8 | Mode::Int16Complex | Mode::Float32Complex | Mode::Packed4Bit
+            ) {
+                let frame = self.convert::<f32>().subregion(offset, frame_shape)?;
+                add_scaled(&mut sum, &frame.data, w);
+                continue;
+            }
+            let frame = self.subregion(offset, frame_shape)?;
+            match frame.data() {
+                DataView::Int16(v) => add_scaled(&mut sum, v, w),
+                DataView::Uint16(v) => add_scaled(&mut sum, v, w),
+                DataView::Float32(v) => add_scaled(&mut sum, v, w),
+                #[cfg(feature = "f16")]
+                DataView::Float16(v) => add_scaled(&mut sum, v, w),
+                _ => return Err(Error::UnsupportedMode),
+            }
+        }
+        VoxelBlock::new([0, 0, 0], frame_shape, sum)
+    }
+}
+
+fn add_scaled<T: Copy + Into<f32>>(sum: &mut [f32], frame: &[T], w: f32) {
+    for (acc, &v) in sum.iter_mut().zip(frame) {
+        *acc += w * v.into();
+    }
+}
@@ -0,0 +1,318 @@
+//! Uploading volumes to the GPU with [`wgpu`].
+//!
+//! Volume renderers need the same three decisions for every MRC file: which
+//! texture format holds the mode, how large the 3D texture is, and how to
+//! pad rows to the 256-byte `bytes_per_row` alignment that buffer-to-texture
+//! copies require. [`texture_format`] and [`TextureLayout`] make them, and
+//! [`Volume::create_texture`] and [`Volume::create_staging_buffer`] do the
+//! upload.
+//!
+//! | Mode | Texture format |
+//! |------|----------------|
+//! | 0 (`i8`) | `R8Sint` |
+//! | 1 (`i16`) | `R16Sint` |
+//! | 2 (`f32`) | `R32Float` |
+//! | 3 (`Int16Complex`) | `Rg16Sint` |
+//! | 4 (`Float32Complex`) | `Rg32Float` |
+//! | 6 (`u16`) | `R16Uint` |
+//! | 12 (`f16`) | `R16Float` |
+//! | 101 (packed 4-bit) | none; read through [`Reader::convert`](crate::Reader::convert) |
+//!
+//! Values keep their stored type, so shaders see the same numbers as the
+//! file. Integer formats cannot be filtered, and `R32Float` only with
+//! [`wgpu::Features::FLOAT32_FILTERABLE`]; convert to `f16` first for
+//! hardware trilinear sampling.
+//!
+//! Requires the `wgpu` feature (disabled by default).
+
+use crate::{Error, FileEndian, Mode, Volume, Voxel};
+
+use wgpu::util::DeviceExt;
+
+/// The texture format holding voxels of `mode`, or `None` for modes with
+/// no single-texel equivalent (packed 4-bit).
+pub fn texture_format(mode: Mode) -> Option<wgpu::TextureFormat> {
+    use wgpu::TextureFormat as F;
+    match mode {
+        Mode::Int8 => Some(F::R8Sint),
+        Mode::Int16 => Some(F::R16Sint),
+        Mode::Float32 => Some(F::R32Float),
+        Mode::Int16Complex => Some(F::Rg16Sint),
+        Mode::Float32Complex => Some(F::Rg32Float),
+        Mode::Uint16 => Some(F::R16Uint),
+        Mode::Float16 => Some(F::R16Float),
+        Mode::Packed4Bit => None,
+    }
+}
+
+/// Format, extent and row pitch of a volume uploaded as a 3D texture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextureLayout {
+    /// Texel format; see [`texture_format`].
+    pub format: wgpu::TextureFormat,
+    /// `nx × ny × nz` as a texture extent.
+    pub size: wgpu::Extent3d,
+    /// Bytes in one row of voxels.
+    pub bytes_per_row: u32,
+    /// [`bytes_per_row`](Self::bytes_per_row) rounded up to
+    /// [`wgpu::COPY_BYTES_PER_ROW_ALIGNMENT`], the row pitch of
+    /// [`Volume::padded_bytes`] and of staging buffers.
+    pub padded_bytes_per_row: u32,
+}
+
+impl TextureLayout {
+    /// Layout for a volume of `mode` and shape `[nx, ny, nz]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedMode`] for packed 4-bit data and
+    /// [`Error::Io`] with [`InvalidInput`](std::io::ErrorKind::InvalidInput)
+    /// if a dimension or the row pitch does not fit in a `u32`.
+    pub fn new(mode: Mode, shape: [usize; 3]) -> Result<Self, Error> {
+        let format = texture_format(mode).ok_or(Error::UnsupportedMode)?;
+        let too_large = || {
+            Error::invalid_input(format!(
+                "{}×{}×{} volume is too large for a texture",
+                shape[0], shape[1], shape[2]
+            ))
+        };
+        let [w, h, d] = shape.map(u32::try_from);
+        let (w, h, d) = (
+            w.map_err(|_| too_large())?,
+            h.map_err(|_| too_large())?,
+            d.map_err(|_| too_large())?,
+        );
+        let bytes_per_row = w
+            .checked_mul(mode.byte_size() as u32)
+            .ok_or_else(too_large)?;
+        let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let padded_bytes_per_row = bytes_per_row
+            .checked_next_multiple_of(align)
+            .ok_or_else(too_large)?;
+        Ok(Self {
+            format,
+            size: wgpu::Extent3d {
+                width: w,
+                height: h,
+                depth_or_array_layers: d,
+            },
+            bytes_per_row,
+            padded_bytes_per_row,
+        })
+    }
+
+    /// Descriptor for a 3D texture of this layout, without mipmaps.
+    pub fn texture_descriptor<'a>(
+        &self,
+        label: Option<&'a str>,
+        usage: wgpu::TextureUsages,
+    ) -> wgpu::TextureDescriptor<'a> {
+        wgpu::TextureDescriptor {
+            label,
+            size: self.size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D3,
+            format: self.format,
+            usage,
+            view_formats: &[],
+        }
+    }
+
+    /// Source layout for copying a staging buffer of this layout into a
+    /// texture with [`wgpu::CommandEncoder::copy_buffer_to_texture`].
+    pub fn buffer_layout(&self) -> wgpu::TexelCopyBufferLayout {
+        wgpu::TexelCopyBufferLayout {
+            offset: 0,
+            bytes_per_row: Some(self.padded_bytes_per_row),
+            rows_per_image: Some(self.size.height),
+        }
+    }
+
+    /// Size of the padded image: `padded_bytes_per_row × ny × nz`.
+    pub fn padded_size(&self) -> u64 {
+        u64::from(self.padded_bytes_per_row)
+            * u64::from(self.size.height)
+            * u64::from(self.size.depth_or_array_layers)
+    }
+
+    /// Reject a layout that exceeds the device's 3D texture limit.
+    fn check_limits(&self, device: &wgpu::Device) -> Result<(), Error> {
+        let max = device.limits().max_texture_dimension_3d;
+        let s = self.size;
+        if s.width.max(s.height).max(s.depth_or_array_layers) <= max {
+            return Ok(());
+        }
+        Err(Error::invalid_input(format!(
+            "{}×{}×{} volume exceeds the device's 3D texture limit of {max}",
+            s.width, s.height, s.depth_or_array_layers
+        )))
+    }
+}
+
+impl<T: Voxel> Volume<'_, T> {
+    /// [`TextureLayout`] of this volume.
+    ///
+    /// # Errors
+    ///
+    /// See [`TextureLayout::new`].
+    pub fn texture_layout(&self) -> Result<TextureLayout, Error> {
+        let s = self.shape();
+        TextureLayout::new(T::MODE, [s.nx, s.ny, s.nz])
+    }
+
+    /// Voxels as native-endian texel bytes, each row padded with zeros to
+    /// [`padded_bytes_per_row`](TextureLayout::padded_bytes_per_row).
+    ///
+    /// # Errors
+    ///
+    /// See [`TextureLayout::new`]; also [`Error::TooLargeForPlatform`] if
+    /// the padded image does not fit in memory.
+    pub fn padded_bytes(&self) -> Result<Vec<u8>, Error> {
+        let layout = self.texture_layout()?;
+        let mut out = vec![0u8; Error::addressable(layout.padded_size())?];
+        self.write_padded(&layout, &mut out);
+        Ok(out)
+    }
+
+    /// Create a 3D texture holding this volume and upload it through `queue`.
+    ///
+    /// `usage` gains [`COPY_DST`](wgpu::TextureUsages::COPY_DST). The data
+    /// is written with [`wgpu::Queue::write_texture`], which takes unpadded
+    /// rows, so no padded copy is made.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn upload(device: &wgpu::Device, queue: &wgpu::Queue) -> Result<(), mrc::Error> {
+    /// let reader = mrc::Reader::open("tomogram.mrc")?;
+    /// let texture = reader
+    ///     .volume::<f32>()?
+    ///     .create_texture(device, queue, Some("tomogram"), wgpu::TextureUsages::TEXTURE_BINDING)?;
+    /// let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    /// # let _ = view;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// See [`TextureLayout::new`]; also [`Error::Io`] with
+    /// [`InvalidInput`](std::io::ErrorKind::InvalidInput) if a dimension
+    /// exceeds the device's `max_texture_dimension_3d`.
+    pub fn create_texture(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        label: Option<&str>,
+        usage: wgpu::TextureUsages,
+    ) -> Result<wgpu::Texture, Error> {
+        let layout = self.texture_layout()?;
+        layout.check_limits(device)?;
+        let usage = usage | wgpu::TextureUsages::COPY_DST;
+        let texture = device.create_texture(&layout.texture_descriptor(label, usage));
+        let bytes = self.native_bytes();
+        queue.write_texture(
+            texture.as_image_copy(),
+            &bytes,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(layout.bytes_per_row),
+                rows_per_image: Some(layout.size.height),
+            },
+            layout.size,
+        );
+        Ok(texture)
+    }
+
+    /// Create a [`COPY_SRC`](wgpu::BufferUsages::COPY_SRC) buffer holding
+    /// [`padded_bytes`](Self::padded_bytes), for recording the upload
+    /// yourself with [`wgpu::CommandEncoder::copy_buffer_to_texture`] and
+    /// [`TextureLayout::buffer_layout`].
+    ///
+    /// # Errors
+    ///
+    /// See [`padded_bytes`](Self::padded_bytes).
+    pub fn create_staging_buffer(
+        &self,
+        device: &wgpu::Device,
+        label: Option<&str>,
+    ) -> Result<(wgpu::Buffer, TextureLayout), Error> {
+        let layout = self.texture_layout()?;
+        let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label,
+            contents: &self.padded_bytes()?,
+            usage: wgpu::BufferUsages::COPY_SRC,
+        });
+        Ok((buffer, layout))
+    }
+
+    fn native_bytes(&self) -> Vec<u8> {
+        let data = self.as_slice();
+        let mut out = vec![0u8; data.len() * T::BYTE_SIZE];
+        for (i, v) in data.iter().enumerate() {
+            v.to_bytes(&mut out, i * T::BYTE_SIZE, FileEndian::native());
+        }
+        out
+    }
+
+    fn write_padded(&self, layout: &TextureLayout, out: &mut [u8]) {
+        let row = layout.bytes_per_row as usize;
+        let pitch = layout.padded_bytes_per_row as usize;
+        let nx = layout.size.width as usize;
+        if nx == 0 {
+            return;
+        }
+        for (r, voxels) in self.as_slice().chunks_exact(nx).enumerate() {
+            let dst = &mut out[r * pitch..r * pitch + row];
+            for (i, v) in voxels.iter().enumerate() {
+                v.to_bytes(dst, i * T::BYTE_SIZE, FileEndian::native());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout_pads_rows_to_copy_alignment() {
+        let l = TextureLayout::new(Mode::Int16, [100, 3, 2]).unwrap();
+        assert_eq!(l.format, wgpu::TextureFormat::R16Sint);
+        assert_eq!((l.bytes_per_row, l.padded_bytes_per_row), (200, 256));
+        assert_eq!(l.padded_size(), 256 * 3 * 2);
+        let l = TextureLayout::new(Mode::Float32Complex, [32, 1, 1]).unwrap();
+        assert_eq!((l.bytes_per_row, l.padded_bytes_per_row), (256, 256));
+        assert!(matches!(
+            TextureLayout::new(Mode::Packed4Bit, [4, 4, 4]),
+            Err(Error::UnsupportedMode)
+        ));
+    }
+
+    #[test]
+    fn padded_bytes_keep_voxels_in_place() {
+        let mut h = crate::Header::new();
+        (h.nx, h.ny, h.nz) = (3, 2, 2);
+        (h.mx, h.my, h.mz) = (3, 2, 2);
+        h.mode = Mode::Int16.as_i32();
+        let mut file = vec![0u8; 1024];
+        h.encode_to_bytes((&mut file[..]).try_into().unwrap());
+        for v in 0..12i16 {
+            file.extend_from_slice(&v.to_le_bytes());
+        }
+        let reader = crate::Reader::from_bytes(file).unwrap();
+        let bytes = reader.volume::<i16>().unwrap().padded_bytes().unwrap();
+        assert_eq!(bytes.len(), 256 * 4);
+        for row in 0..4 {
+            let r = &bytes[row * 256..(row + 1) * 256];
+            let got: Vec<i16> = r[..6]
+                .chunks_exact(2)
+                .map(|b| i16::from_ne_bytes([b[0], b[1]]))
+                .collect();
+            let first = row as i16 * 3;
+            assert_eq!(got, [first, first + 1, first + 2]);
+            assert!(r[6..].iter().all(|&b| b == 0));
+        }
+    }
+}
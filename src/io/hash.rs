@@ -0,0 +1,119 @@
+//! Content hashes for deduplication and transfer checks.
+//!
+//! Two copies of a map should hash the same even if one was written
+//! big-endian or re-stamped with a new processing label. A
+//! [`ContentHash`] is a BLAKE3 digest of the file's content with those
+//! differences normalized away: the header is hashed as it would be
+//! encoded little-endian (labels optionally blanked), followed by the
+//! extended header and the voxel data converted to little-endian.
+//!
+//! Requires the `hash` feature (disabled by default).
+
+use crate::{Error, FileEndian, Reader};
+
+use std::io::Read;
+
+/// Bytes of voxel data hashed between cancellation checks.
+const CHUNK: usize = 16 << 20;
+
+/// What [`Reader::content_hash_with`] includes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HashOptions {
+    /// Hash `NLABL` and the 800-byte label block. Off by default, since
+    /// labels record who touched the file and when, not what it contains.
+    pub include_labels: bool,
+}
+
+/// A 32-byte BLAKE3 digest of a volume's normalized content.
+///
+/// Displays as 64 lowercase hex digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ContentHash(pub [u8; 32]);
+
+impl ContentHash {
+    /// The raw digest.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for ContentHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.iter().try_for_each(|b| write!(f, "{b:02x}"))
+    }
+}
+
+impl Reader {
+    /// Hash the volume's content, ignoring byte order and labels.
+    ///
+    /// Equivalent to [`content_hash_with`](Self::content_hash_with) with
+    /// default [`HashOptions`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let a = mrc::Reader::open("upload/map.mrc")?.content_hash()?;
+    /// let b = mrc::Reader::open("archive/map_be.mrc")?.content_hash()?;
+    /// if a == b {
+    ///     println!("duplicate of {a}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// As [`content_hash_with`](Self::content_hash_with).
+    pub fn content_hash(&self) -> Result<ContentHash, Error> {
+        self.content_hash_with(&HashOptions::default())
+    }
+
+    /// Hash the header (re-encoded little-endian, labels blanked unless
+    /// [`include_labels`](HashOptions::include_labels) is set), the
+    /// extended header as stored, and the voxel data in little-endian
+    /// order.
+    ///
+    /// A truncated file hashes only the data it has.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Cancelled`] if the reader's
+    /// [`CancellationToken`](crate::CancellationToken) is cancelled while
+    /// the data is being hashed.
+    pub fn content_hash_with(&self, options: &HashOptions) -> Result<ContentHash, Error> {
+        let mut header = self.header;
+        header.set_file_endian(FileEndian::LittleEndian);
+        if !options.include_labels {
+            header.nlabl = 0;
+            header.label = [0; 800];
+        }
+        let mut raw = [0u8; 1024];
+        header.encode_to_bytes(&mut raw);
+
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&raw);
+        hasher.update(self.ext_header_bytes());
+
+        let data = self._source_data();
+        if self.endian == FileEndian::LittleEndian {
+            for chunk in data.chunks(CHUNK) {
+                self.check_cancelled()?;
+                hasher.update(chunk);
+            }
+        } else {
+            let mut stream = self.encoded(FileEndian::LittleEndian);
+            let mut buf = vec![0u8; CHUNK.min(stream.byte_len())];
+            // Skip the stream's own copy of the header and extended header.
+            let mut skip = stream.byte_len() - data.len();
+            while stream.remaining() > 0 {
+                self.check_cancelled()?;
+                let n = stream.read(&mut buf)?;
+                let consumed = n.min(skip);
+                skip -= consumed;
+                hasher.update(&buf[consumed..n]);
+            }
+        }
+        Ok(ContentHash(*hasher.finalize().as_bytes()))
+    }
+}
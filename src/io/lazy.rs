@@ -0,0 +1,144 @@
+//! Decode-on-access voxel reads.
+//!
+//! Looking up a handful of voxels in a big-endian map should not require
+//! decoding the whole volume first. A [`VoxelAccessor`] keeps the raw bytes
+//! and the byte order from the file's machine stamp, and decodes only the
+//! voxel asked for.
+
+use crate::engine::codec::EndianCodec;
+use crate::{
+    ComplexToRealStrategy, FileEndian, Float32Complex, Int16Complex, Mode, Reader, VolumeShape,
+};
+
+/// Random access to voxel values as `f32`, decoded one at a time.
+///
+/// Values follow [`Reader::convert::<f32>`](Reader::convert): complex modes
+/// give the magnitude, and Mode 0 is read as unsigned when IMOD marks the
+/// bytes so. Indices are linear in storage order (X fastest); voxels past
+/// the end of a truncated file read as `None`.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> Result<(), mrc::Error> {
+/// let reader = mrc::Reader::open("big_endian_map.mrc")?;
+/// let voxels = reader.voxel_accessor();
+/// let s = voxels.shape();
+/// println!("centre: {:?}", voxels.get_f32_at(s.nx / 2, s.ny / 2, s.nz / 2));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct VoxelAccessor<'a> {
+    data: &'a [u8],
+    mode: Mode,
+    endian: FileEndian,
+    shape: VolumeShape,
+    unsigned_m0: bool,
+}
+
+impl VoxelAccessor<'_> {
+    /// Volume dimensions.
+    #[inline]
+    pub fn shape(&self) -> VolumeShape {
+        self.shape
+    }
+
+    /// The file's mode.
+    #[inline]
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// The byte order values are decoded from.
+    #[inline]
+    pub fn endian(&self) -> FileEndian {
+        self.endian
+    }
+
+    /// Number of voxels, `nx * ny * nz`.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.shape.nx * self.shape.ny * self.shape.nz
+    }
+
+    /// `true` if the volume has no voxels.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Voxel `i` in storage order as `f32`, or `None` if out of range.
+    pub fn get_f32(&self, i: usize) -> Option<f32> {
+        if i >= self.len() {
+            return None;
+        }
+        if self.mode == Mode::Packed4Bit {
+            let (nx, x) = (self.shape.nx, i % self.shape.nx);
+            let byte = *self.data.get((i / nx) * nx.div_ceil(2) + x / 2)?;
+            let nibble = if x % 2 == 0 { byte & 0x0F } else { byte >> 4 };
+            return Some(nibble as f32);
+        }
+        let size = self.mode.byte_size();
+        let bytes = self.data.get(i * size..(i + 1) * size)?;
+        let e = self.endian;
+        Some(match self.mode {
+            Mode::Int8 if self.unsigned_m0 => bytes[0] as f32,
+            Mode::Int8 => bytes[0] as i8 as f32,
+            Mode::Int16 => i16::from_bytes(bytes, 0, e) as f32,
+            Mode::Float32 => f32::from_bytes(bytes, 0, e),
+            Mode::Int16Complex => {
+                Int16Complex::from_bytes(bytes, 0, e).to_real(ComplexToRealStrategy::Magnitude)
+            }
+            Mode::Float32Complex => {
+                Float32Complex::from_bytes(bytes, 0, e).to_real(ComplexToRealStrategy::Magnitude)
+            }
+            Mode::Uint16 => u16::from_bytes(bytes, 0, e) as f32,
+            #[cfg(feature = "f16")]
+            Mode::Float16 => crate::f16::from_bytes(bytes, 0, e).to_f32(),
+            #[cfg(not(feature = "f16"))]
+            Mode::Float16 => return None,
+            Mode::Packed4Bit => return None,
+        })
+    }
+
+    /// Voxel `(x, y, z)` as `f32`, or `None` if out of bounds.
+    #[inline]
+    pub fn get_f32_at(&self, x: usize, y: usize, z: usize) -> Option<f32> {
+        let s = self.shape;
+        if x < s.nx && y < s.ny && z < s.nz {
+            self.get_f32((z * s.ny + y) * s.nx + x)
+        } else {
+            None
+        }
+    }
+
+    /// Every voxel as `f32`, in storage order, stopping early at the end
+    /// of a truncated file.
+    pub fn iter_f32(&self) -> impl Iterator<Item = f32> + '_ {
+        (0..self.len()).map_while(|i| self.get_f32(i))
+    }
+}
+
+impl Reader {
+    /// A [`VoxelAccessor`] over this reader's data, decoding each value
+    /// from the file's byte order when it is read.
+    ///
+    /// Nothing is copied or swapped up front, so this is the cheap way to
+    /// sample a few voxels of a byte-swapped file; use
+    /// [`convert`](Self::convert) or [`volume`](Self::volume) to read many.
+    pub fn voxel_accessor(&self) -> VoxelAccessor<'_> {
+        let unsigned_m0 = self.mode() == Mode::Int8
+            && self
+                .header()
+                .detect_imod()
+                .is_some_and(|imod| !imod.bytes_are_signed);
+        VoxelAccessor {
+            data: self._source_data(),
+            mode: self.mode(),
+            endian: self.endian(),
+            shape: self.shape(),
+            unsigned_m0,
+        }
+    }
+}
@@ -0,0 +1,541 @@
+//! Histograms and display levels.
+//!
+//! The `dmin`/`dmax` of a micrograph or tomogram are often set by a handful
+//! of hot pixels or gold fiducials, so mapping them to black and white
+//! leaves the rest of the image a flat grey. [`Reader::display_range`]
+//! picks the black and white points from percentiles of the histogram
+//! instead, the way IMOD and most viewers auto-contrast, and
+//! [`Reader::percentile`] reads off any other percentile the same way.
+//! [`Reader::quick_statistics`] is the cheap alternative when the header
+//! can be trusted, and [`Reader::verify_statistics`] tells whether it can.
+//! [`Reader::section_stats`] breaks the statistics down by Z-section.
+//!
+//! [`Reader::display_range`]: crate::Reader::display_range
+//! [`Reader::percentile`]: crate::Reader::percentile
+//! [`Reader::quick_statistics`]: crate::Reader::quick_statistics
+//! [`Reader::verify_statistics`]: crate::Reader::verify_statistics
+//! [`Reader::section_stats`]: crate::Reader::section_stats
+
+use crate::engine::stats::StatsAccumulator;
+use crate::{Error, Mode, Reader};
+
+/// Number of bins [`Reader::display_range`] uses.
+const DISPLAY_BINS: usize = 4096;
+
+/// Voxel counts in equal-width bins spanning `[min, max]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    /// Smallest finite value.
+    pub min: f32,
+    /// Largest finite value.
+    pub max: f32,
+    /// Count per bin. Bin `i` covers `min + i·w .. min + (i+1)·w` with
+    /// `w = (max − min) / counts.len()`; the last bin includes `max`.
+    pub counts: Vec<u64>,
+}
+
+impl Histogram {
+    /// Total number of values counted (NaN and infinities are not).
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// Value below which a fraction `q` (`0.0..=1.0`) of the counts fall.
+    ///
+    /// Values are taken to be spread evenly within a bin, so the result is
+    /// accurate to about one bin width. Returns `None` for an empty
+    /// histogram or a `q` outside `0.0..=1.0`.
+    pub fn quantile(&self, q: f64) -> Option<f32> {
+        let total = self.total();
+        if total == 0 || !(0.0..=1.0).contains(&q) {
+            return None;
+        }
+        Some(self.value_at(q * total as f64))
+    }
+
+    fn width(&self) -> f64 {
+        (f64::from(self.max) - f64::from(self.min)) / self.counts.len() as f64
+    }
+
+    fn bin_of(&self, v: f32) -> usize {
+        let span = (f64::from(self.max) - f64::from(self.min)).max(f64::MIN_POSITIVE);
+        let bin = ((f64::from(v) - f64::from(self.min)) * self.counts.len() as f64 / span) as usize;
+        bin.min(self.counts.len() - 1)
+    }
+
+    /// The bin holding the `target`-th count, and the number of counts in
+    /// the bins before it.
+    fn locate(&self, target: f64) -> (usize, u64) {
+        let mut below = 0u64;
+        for (i, &c) in self.counts.iter().enumerate() {
+            if c > 0 && (below + c) as f64 >= target {
+                return (i, below);
+            }
+            below += c;
+        }
+        (self.counts.len() - 1, below)
+    }
+
+    /// Interpolated value of the `target`-th count.
+    fn value_at(&self, target: f64) -> f32 {
+        let (i, below) = self.locate(target);
+        let c = self.counts[i].max(1) as f64;
+        let within = ((target - below as f64) / c).clamp(0.0, 1.0);
+        let v = f64::from(self.min) + (i as f64 + within) * self.width();
+        (v as f32).clamp(self.min, self.max)
+    }
+
+    /// An empty histogram with the same bin count over bin `i` alone.
+    fn zoom(&self, i: usize) -> Histogram {
+        let w = self.width();
+        let lo = f64::from(self.min) + i as f64 * w;
+        Histogram {
+            min: (lo as f32).max(self.min),
+            max: ((lo + w) as f32).min(self.max),
+            counts: vec![0; self.counts.len()],
+        }
+    }
+}
+
+/// Where the values of a [`QuickStatistics`] came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsSource {
+    /// The header's `dmin`/`dmax`/`dmean`/`rms`; no voxels were read.
+    Metadata,
+    /// Computed from the voxel data.
+    Data,
+}
+
+/// Density statistics from [`Reader::quick_statistics`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuickStatistics {
+    /// Minimum density.
+    pub dmin: f32,
+    /// Maximum density.
+    pub dmax: f32,
+    /// Mean density.
+    pub dmean: f32,
+    /// Standard deviation from the mean.
+    pub rms: f32,
+    /// Whether the values were read from the header or computed.
+    pub source: StatsSource,
+}
+
+/// Density statistics of one Z-section, from [`Reader::section_stats`].
+///
+/// Complex modes report only `rms`, with the same `dmin`/`dmax`/`dmean`
+/// sentinels as [`Reader::data_stats_with`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SectionStatistics {
+    /// Minimum density.
+    pub dmin: f32,
+    /// Maximum density.
+    pub dmax: f32,
+    /// Mean density.
+    pub dmean: f32,
+    /// Standard deviation from the mean.
+    pub rms: f32,
+}
+
+/// How one header statistic compares with the data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatVerdict {
+    /// Within tolerance of the computed value.
+    Matches,
+    /// Outside tolerance: the header is stale or wrong.
+    Differs,
+    /// The header marks it as not determined (`dmin > dmax`, `rms < 0`),
+    /// or it has no meaning for a complex mode.
+    Unset,
+}
+
+/// One statistic from a [`StatisticsReport`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StatCheck {
+    /// Value in the header.
+    pub claimed: f32,
+    /// Value computed from the voxel data.
+    pub actual: f32,
+    /// Outcome of the comparison.
+    pub verdict: StatVerdict,
+}
+
+/// Header statistics compared with the data, from
+/// [`Reader::verify_statistics`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StatisticsReport {
+    /// Minimum density.
+    pub dmin: StatCheck,
+    /// Maximum density.
+    pub dmax: StatCheck,
+    /// Mean density.
+    pub dmean: StatCheck,
+    /// Standard deviation from the mean.
+    pub rms: StatCheck,
+    /// Relative tolerance the comparison used.
+    pub rtol: f32,
+}
+
+impl StatisticsReport {
+    /// `true` unless some statistic [`Differs`](StatVerdict::Differs).
+    /// Unset statistics count as consistent.
+    pub fn is_consistent(&self) -> bool {
+        self.checks()
+            .iter()
+            .all(|(_, c)| c.verdict != StatVerdict::Differs)
+    }
+
+    /// The four checks with their header field names, in header order.
+    pub fn checks(&self) -> [(&'static str, StatCheck); 4] {
+        [
+            ("dmin", self.dmin),
+            ("dmax", self.dmax),
+            ("dmean", self.dmean),
+            ("rms", self.rms),
+        ]
+    }
+}
+
+impl Reader {
+    /// Density statistics, from the header when it has them.
+    ///
+    /// When the header's `dmin`/`dmax`/`dmean`/`rms` are finite and
+    /// consistent (`dmin ≤ dmean ≤ dmax`, `rms ≥ 0`, not all zero) they are
+    /// returned as they are, without reading any voxels. Otherwise — unset,
+    /// sentinel or complex-mode values — they are computed from the data as
+    /// by [`data_stats_with`](Self::data_stats_with) with the default
+    /// [`StreamingConfig`](crate::StreamingConfig).
+    ///
+    /// Header values are only as good as the program that wrote them; use
+    /// [`validate_header_stats`](Self::validate_header_stats) when that
+    /// matters. FEI1/FEI2 extended headers carry no per-image statistics in
+    /// the Thermo Fisher specification, so they are not consulted.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let reader = mrc::Reader::open("movie.mrc")?;
+    /// let stats = reader.quick_statistics()?;
+    /// println!("{} .. {} ({:?})", stats.dmin, stats.dmax, stats.source);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Only when computing from the data; see
+    /// [`data_stats_with`](Self::data_stats_with).
+    pub fn quick_statistics(&self) -> Result<QuickStatistics, Error> {
+        let (dmin, dmax, dmean, rms) = self.header().density_stats();
+        let plausible = [dmin, dmax, dmean, rms].iter().all(|v| v.is_finite())
+            && dmin <= dmean
+            && dmean <= dmax
+            && rms >= 0.0
+            && (dmin, dmax, dmean, rms) != (0.0, 0.0, 0.0, 0.0);
+        let ((dmin, dmax, dmean, rms), source) = if plausible && !self.mode().is_complex() {
+            ((dmin, dmax, dmean, rms), StatsSource::Metadata)
+        } else {
+            let config = crate::StreamingConfig::default();
+            (self.data_stats_with(&config)?, StatsSource::Data)
+        };
+        Ok(QuickStatistics {
+            dmin,
+            dmax,
+            dmean,
+            rms,
+            source,
+        })
+    }
+
+    /// `dmin`/`dmax`/`dmean`/`rms` of each Z-section, in one pass over the
+    /// data.
+    ///
+    /// These are the per-section numbers SerialEM and IMOD's `header`/
+    /// `alterheader` report; a frame whose mean drops or whose maximum
+    /// jumps stands out at a glance. Sections past the end of a truncated
+    /// file are left out.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let reader = mrc::Reader::open("movie.mrc")?;
+    /// for (z, s) in reader.section_stats()?.iter().enumerate() {
+    ///     println!("{z}: mean {} rms {}", s.dmean, s.rms);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// As [`data_stats_with`](Self::data_stats_with).
+    pub fn section_stats(&self) -> Result<Vec<SectionStatistics>, Error> {
+        let s = self.shape();
+        let section = match self.mode() {
+            Mode::Packed4Bit => s.ny * s.nx.div_ceil(2),
+            mode => s.nx * s.ny * mode.byte_size(),
+        };
+        let mut out = Vec::with_capacity(s.nz);
+        for bytes in self._source_data().chunks_exact(section.max(1)).take(s.nz) {
+            self.check_cancelled()?;
+            let mut acc = StatsAccumulator::new(self.mode(), self.endian(), s.nx);
+            acc.push(bytes)?;
+            let (dmin, dmax, dmean, rms) = acc.finish();
+            out.push(SectionStatistics {
+                dmin,
+                dmax,
+                dmean,
+                rms,
+            });
+        }
+        Ok(out)
+    }
+
+    /// Compare the header's `dmin`/`dmax`/`dmean`/`rms` with values
+    /// recomputed from the data, to a relative tolerance of 1% as in
+    /// Python `mrcfile`.
+    ///
+    /// Unlike [`validate_header_stats`](Self::validate_header_stats), which
+    /// fails on the first disagreement, this reports every statistic, so
+    /// callers can decide what to trust and what to recompute.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let reader = mrc::Reader::open("map.mrc")?;
+    /// let report = reader.verify_statistics()?;
+    /// for (name, check) in report.checks() {
+    ///     if check.verdict == mrc::StatVerdict::Differs {
+    ///         println!("{name}: header {} but data {}", check.claimed, check.actual);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// As [`data_stats_with`](Self::data_stats_with).
+    pub fn verify_statistics(&self) -> Result<StatisticsReport, Error> {
+        self.verify_statistics_with(0.01)
+    }
+
+    /// [`verify_statistics`](Self::verify_statistics) with relative
+    /// tolerance `rtol`: a statistic matches when
+    /// `|claimed − actual| ≤ rtol · max(|claimed|, |actual|)`.
+    ///
+    /// # Errors
+    ///
+    /// As [`data_stats_with`](Self::data_stats_with).
+    pub fn verify_statistics_with(&self, rtol: f32) -> Result<StatisticsReport, Error> {
+        let (dmin, dmax, dmean, rms) = self.header().density_stats();
+        let config = crate::StreamingConfig::default();
+        let actual = self.data_stats_with(&config)?;
+        let density_unset = dmin > dmax || self.mode().is_complex();
+        let check = |claimed: f32, actual: f32, unset: bool| StatCheck {
+            claimed,
+            actual,
+            verdict: if unset {
+                StatVerdict::Unset
+            } else if crate::engine::stats::is_close(claimed, actual, rtol) {
+                StatVerdict::Matches
+            } else {
+                StatVerdict::Differs
+            },
+        };
+        Ok(StatisticsReport {
+            dmin: check(dmin, actual.0, density_unset),
+            dmax: check(dmax, actual.1, density_unset),
+            dmean: check(dmean, actual.2, density_unset),
+            rms: check(rms, actual.3, rms < 0.0),
+            rtol,
+        })
+    }
+
+    /// Histogram of the volume with `bins` equal-width bins.
+    ///
+    /// The volume is read twice through [`convert::<f32>()`](Self::convert),
+    /// one section at a time: once for the range and once to count.
+    /// NaN and infinite voxels are skipped. The header's `dmin`/`dmax` are
+    /// not used, since they are often stale or missing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with
+    /// [`InvalidInput`](std::io::ErrorKind::InvalidInput) if `bins` is 0,
+    /// [`Error::UnsupportedMode`] for complex modes, and propagates any error
+    /// from reading the volume.
+    pub fn histogram(&self, bins: usize) -> Result<Histogram, Error> {
+        if bins == 0 {
+            return Err(Error::invalid_input("histogram needs at least one bin"));
+        }
+        if self.mode().is_complex() {
+            return Err(Error::UnsupportedMode);
+        }
+        let reader = self.convert::<f32>();
+        let (mut min, mut max) = (f32::INFINITY, f32::NEG_INFINITY);
+        for section in reader.slices() {
+            for &v in section?.data.iter().filter(|v| v.is_finite()) {
+                min = min.min(v);
+                max = max.max(v);
+            }
+        }
+        let mut hist = Histogram {
+            min: 0.0,
+            max: 0.0,
+            counts: vec![0; bins],
+        };
+        if min > max {
+            return Ok(hist);
+        }
+        (hist.min, hist.max) = (min, max);
+        for section in reader.slices() {
+            for &v in section?.data.iter().filter(|v| v.is_finite()) {
+                let bin = hist.bin_of(v);
+                hist.counts[bin] += 1;
+            }
+        }
+        Ok(hist)
+    }
+
+    /// Black and white points for display, from histogram percentiles.
+    ///
+    /// Returns the values at the `percentile_low` and `percentile_high`
+    /// percentiles (`0.0..=100.0`), for example `(0.5, 99.5)` to saturate
+    /// the darkest and brightest 0.5% of voxels. A 4096-bin
+    /// [`histogram`](Self::histogram) locates each percentile, and one more
+    /// pass re-bins the bin it falls in, so a few extreme outliers widening
+    /// the range do not cost precision.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let reader = mrc::Reader::open("tomogram.mrc")?;
+    /// let (black, white) = reader.display_range(0.5, 99.5)?;
+    /// println!("display {black} .. {white}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with
+    /// [`InvalidInput`](std::io::ErrorKind::InvalidInput) if the percentiles
+    /// are outside `0.0..=100.0`, `percentile_low > percentile_high`, or the
+    /// volume has no finite voxels, and otherwise the errors of
+    /// [`histogram`](Self::histogram).
+    pub fn display_range(
+        &self,
+        percentile_low: f64,
+        percentile_high: f64,
+    ) -> Result<(f32, f32), Error> {
+        let valid = (0.0..=100.0).contains(&percentile_low)
+            && (0.0..=100.0).contains(&percentile_high)
+            && percentile_low <= percentile_high;
+        if !valid {
+            return Err(Error::invalid_input(format!(
+                "display percentiles must satisfy 0 <= low <= high <= 100, \
+                 got {percentile_low} and {percentile_high}"
+            )));
+        }
+        let [lo, hi] = self.percentiles([percentile_low, percentile_high])?;
+        Ok((lo, hi))
+    }
+
+    /// Value at percentile `p` (`0.0..=100.0`) of the finite voxels.
+    ///
+    /// Located as [`display_range`](Self::display_range) locates its black
+    /// and white points: a 4096-bin histogram and one re-binning pass,
+    /// three reads of the volume in all, without sorting or holding it in
+    /// memory. The result is accurate to a 4096th of a coarse bin; use
+    /// [`Volume::percentile`](crate::Volume::percentile) for the exact
+    /// value of a volume that fits in memory.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let reader = mrc::Reader::open("map.mrc")?;
+    /// println!("median density {}", reader.percentile(50.0)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with
+    /// [`InvalidInput`](std::io::ErrorKind::InvalidInput) if `p` is outside
+    /// `0.0..=100.0` or the volume has no finite voxels, and otherwise the
+    /// errors of [`histogram`](Self::histogram).
+    pub fn percentile(&self, p: f64) -> Result<f32, Error> {
+        if !(0.0..=100.0).contains(&p) {
+            return Err(Error::invalid_input(format!(
+                "percentile must be within 0..=100, got {p}"
+            )));
+        }
+        let [v] = self.percentiles([p])?;
+        Ok(v)
+    }
+
+    /// An initial isosurface threshold, `dmean + sigma · rms`.
+    ///
+    /// Viewers commonly open a map at one to three standard deviations
+    /// above the mean. The statistics come from
+    /// [`quick_statistics`](Self::quick_statistics), so plausible header
+    /// values are used without reading the data.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let reader = mrc::Reader::open("emd_1234.map")?;
+    /// let level = reader.suggest_contour_level(1.5)?;
+    /// println!("contour at {level}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedMode`] for complex modes, and otherwise
+    /// the errors of [`quick_statistics`](Self::quick_statistics).
+    pub fn suggest_contour_level(&self, sigma: f32) -> Result<f32, Error> {
+        if self.mode().is_complex() {
+            return Err(Error::UnsupportedMode);
+        }
+        let stats = self.quick_statistics()?;
+        Ok(stats.dmean + sigma * stats.rms)
+    }
+
+    /// Values at each of the percentiles `ps`, already checked to be
+    /// within `0.0..=100.0`.
+    fn percentiles<const N: usize>(&self, ps: [f64; N]) -> Result<[f32; N], Error> {
+        let coarse = self.histogram(DISPLAY_BINS)?;
+        let total = coarse.total();
+        if total == 0 {
+            return Err(Error::invalid_input("volume has no finite voxels"));
+        }
+
+        let targets = ps.map(|p| p / 100.0 * total as f64);
+        let located = targets.map(|t| coarse.locate(t));
+        let mut fine = located.map(|(bin, _)| coarse.zoom(bin));
+        for section in self.convert::<f32>().slices() {
+            for &v in section?.data.iter().filter(|v| v.is_finite()) {
+                let bin = coarse.bin_of(v);
+                for (h, &(target_bin, _)) in fine.iter_mut().zip(&located) {
+                    if bin == target_bin {
+                        let i = h.bin_of(v);
+                        h.counts[i] += 1;
+                    }
+                }
+            }
+        }
+        Ok(std::array::from_fn(|k| {
+            fine[k].value_at(targets[k] - located[k].1 as f64)
+        }))
+    }
+}
@@ -17,9 +17,37 @@
 //!
 //! * [`WriterBuilder`] / [`crate::create`] — configure and create a writer.
 //!   Use `.finish()` for files, `.finish_gzip()` for compressed output.
+//! * [`MrcBuf`](crate::MrcBuf) — build a volume in memory, then serialize it.
 
+pub mod acquisition;
+pub mod aligned_slice;
+pub mod boxing;
+pub mod bricks;
+pub mod buf;
+pub mod cancel;
+pub mod channel;
+pub mod chunked;
+pub mod compare;
+pub mod double_buffer;
+pub mod fourier;
+pub mod frames;
+pub mod lazy;
+pub mod levels;
+pub mod options;
+#[cfg(feature = "parallel")]
+pub mod par;
+pub mod pool;
+pub mod projection;
 pub mod reader;
 pub mod reader_common;
+pub mod resample;
+pub mod reshape;
+pub mod source;
+pub mod stack;
+pub mod stream;
+pub mod streaming;
+pub mod structure;
+pub mod transcode;
 pub mod writer;
 
 #[cfg(feature = "gzip")]
@@ -27,3 +55,27 @@ pub mod gzip;
 
 #[cfg(feature = "bzip2")]
 pub mod bzip2;
+
+#[cfg(feature = "tiff")]
+pub mod tiff;
+
+#[cfg(feature = "zarr")]
+pub mod zarr;
+
+#[cfg(feature = "fft")]
+pub mod dose;
+
+#[cfg(feature = "fft")]
+pub mod fft;
+
+#[cfg(feature = "notify")]
+pub mod watch;
+
+#[cfg(feature = "wgpu")]
+pub mod gpu;
+
+#[cfg(all(feature = "shm", target_os = "linux"))]
+pub mod shm;
+
+#[cfg(feature = "hash")]
+pub mod hash;
@@ -0,0 +1,239 @@
+//! Configurable file opening.
+//!
+//! [`OpenOptions`] gathers the choices spread across the `Reader::open_*`
+//! constructors — validation strictness, memory mapping versus an eager
+//! in-memory read, the decompression limit — and adds a byte-order policy,
+//! in the style of [`std::fs::OpenOptions`].
+//!
+//! ```no_run
+//! use mrc::{EndianPolicy, OpenOptions};
+//!
+//! let (reader, warnings) = OpenOptions::new()
+//!     .permissive(true)
+//!     .eager(true)
+//!     .endian_policy(EndianPolicy::Preserve)
+//!     .open_with_warnings("legacy.mrc")?;
+//! # Ok::<_, mrc::Error>(())
+//! ```
+//!
+//! Readers are always read-only; new files are created with
+//! [`WriterBuilder`](crate::WriterBuilder).
+
+use crate::{Error, FileEndian, Reader};
+
+use std::path::Path;
+
+/// What to do when a file's byte order differs from the host's.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EndianPolicy {
+    /// Byte-swap voxel data on access (the default behaviour of
+    /// [`Reader::open`]).
+    #[default]
+    AutoSwap,
+    /// Refuse to open non-native files, for pipelines that require
+    /// zero-copy access.
+    Error,
+    /// Ignore the machine stamp and interpret voxel data in host byte
+    /// order. Intended for files whose stamp is known to be wrong; the
+    /// reader's header is re-stamped to match.
+    Preserve,
+}
+
+/// Options controlling how [`Reader`]s are opened.
+///
+/// Compression is always auto-detected from the file's magic bytes.
+#[derive(Debug, Clone)]
+pub struct OpenOptions {
+    pub(crate) permissive: bool,
+    pub(crate) mmap: bool,
+    pub(crate) eager: bool,
+    pub(crate) endian: EndianPolicy,
+    pub(crate) max_decompressed_bytes: u64,
+    pub(crate) read_threads: usize,
+    pub(crate) prefault: bool,
+    pub(crate) salvage_truncated: bool,
+    pub(crate) page_aligned: bool,
+    pub(crate) cancel: Option<crate::CancellationToken>,
+    pub(crate) pool: Option<crate::BufferPool>,
+}
+
+impl Default for OpenOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OpenOptions {
+    /// Options matching [`Reader::open`]: strict validation, memory-mapped
+    /// when possible, automatic byte swapping, and the
+    /// [`DEFAULT_MAX_DECOMPRESSED_BYTES`](crate::DEFAULT_MAX_DECOMPRESSED_BYTES)
+    /// limit.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            permissive: false,
+            mmap: true,
+            eager: false,
+            endian: EndianPolicy::AutoSwap,
+            max_decompressed_bytes: crate::DEFAULT_MAX_DECOMPRESSED_BYTES,
+            read_threads: 1,
+            prefault: false,
+            salvage_truncated: false,
+            page_aligned: false,
+            cancel: None,
+            pool: None,
+        }
+    }
+
+    /// Collect non-fatal header problems as warnings instead of failing
+    /// (see [`Reader::open_permissive`]). Default: `false`.
+    #[must_use]
+    pub fn permissive(mut self, permissive: bool) -> Self {
+        self.permissive = permissive;
+        self
+    }
+
+    /// Prefer a memory map for plain files. Without the `mmap` feature, or
+    /// if mapping fails, buffered I/O is used instead. Default: `true`.
+    #[must_use]
+    pub fn mmap(mut self, mmap: bool) -> Self {
+        self.mmap = mmap;
+        self
+    }
+
+    /// Read all voxel data into memory at open time rather than paging it
+    /// in lazily through a memory map. Implies `mmap(false)`. Compressed
+    /// files are always read eagerly. Default: `false`.
+    #[must_use]
+    pub fn eager(mut self, eager: bool) -> Self {
+        self.eager = eager;
+        self
+    }
+
+    /// Byte-order handling. Default: [`EndianPolicy::AutoSwap`].
+    #[must_use]
+    pub fn endian_policy(mut self, policy: EndianPolicy) -> Self {
+        self.endian = policy;
+        self
+    }
+
+    /// Cap on the decompressed size of gzip/bzip2 files.
+    #[must_use]
+    pub fn max_decompressed_bytes(mut self, max: u64) -> Self {
+        self.max_decompressed_bytes = max;
+        self
+    }
+
+    /// Read the voxel data of eagerly loaded plain files with `threads`
+    /// concurrent positional reads into one preallocated buffer. On NVMe
+    /// and parallel filesystems this is several times faster than a single
+    /// sequential read. Only applies when the data is not memory-mapped
+    /// (see [`eager`](Self::eager)); each thread reads at least 1 MiB.
+    /// Default: `1`.
+    #[must_use]
+    pub fn read_threads(mut self, threads: usize) -> Self {
+        self.read_threads = threads.max(1);
+        self
+    }
+
+    /// Fault in every page of the voxel data while opening (see
+    /// [`Reader::prefault`]), trading a slower open for no first-access
+    /// latency spikes later. Default: `false`.
+    #[must_use]
+    pub fn prefault(mut self, prefault: bool) -> Self {
+        self.prefault = prefault;
+        self
+    }
+
+    /// Open files that end early, exposing only the Z-sections whose data
+    /// is complete.
+    ///
+    /// A file cut short by an interrupted transfer or acquisition is
+    /// otherwise rejected with [`Error::FileSizeMismatch`] (or, when
+    /// permissive, opened with sections that fail to read). With this
+    /// option the reader's `nz` is reduced to
+    /// [`complete_sections`](Reader::complete_sections) — whole volumes
+    /// for a volume stack — and a warning records how many of the declared
+    /// sections were kept. [`is_truncated`](Reader::is_truncated) still
+    /// reports `true`. A file that ends inside its first section (or first
+    /// volume) still fails to open, with an [`Error::Io`] of kind
+    /// `UnexpectedEof`. Header validation keeps the strictness set by
+    /// [`permissive`](Self::permissive). Default: `false`.
+    #[must_use]
+    pub fn salvage_truncated(mut self, salvage: bool) -> Self {
+        self.salvage_truncated = salvage;
+        self
+    }
+
+    /// Read the voxel data into a page-aligned buffer whose size is a whole
+    /// number of pages (see [`AlignedBuf::zeroed_pages`](crate::AlignedBuf::zeroed_pages)),
+    /// for registering with `cudaHostRegister` or `hipHostRegister` and
+    /// transferring without a staging copy. The address from
+    /// [`Reader::as_raw_parts`] is then page-aligned and stays fixed for as
+    /// long as the reader or any of its clones is alive. Implies
+    /// `eager(true)`; compressed files are decompressed and then copied
+    /// once into such a buffer. Default: `false`.
+    #[must_use]
+    pub fn page_aligned(mut self, page_aligned: bool) -> Self {
+        self.page_aligned = page_aligned;
+        self
+    }
+
+    /// Attach `token` to the reader; see [`Reader::set_cancellation`].
+    /// Opening itself is not interrupted. Default: none.
+    #[must_use]
+    pub fn cancellation(mut self, token: crate::CancellationToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Read the voxel data of plain files loaded into memory (see
+    /// [`eager`](Self::eager) and [`page_aligned`](Self::page_aligned))
+    /// into a buffer from `pool`, and give the buffer back to it when the
+    /// reader and all of its clones are dropped. Memory-mapped and
+    /// compressed files do not use the pool. Default: none.
+    #[must_use]
+    pub fn buffer_pool(mut self, pool: crate::BufferPool) -> Self {
+        self.pool = Some(pool);
+        self
+    }
+
+    /// Open `path` with these options.
+    ///
+    /// # Errors
+    ///
+    /// Any error [`Reader::open`] can return, plus [`Error::Io`] with
+    /// [`InvalidData`](std::io::ErrorKind::InvalidData) when the file is
+    /// not native-endian under [`EndianPolicy::Error`].
+    pub fn open<P: AsRef<Path>>(&self, path: P) -> Result<Reader, Error> {
+        self.open_with_warnings(path).map(|(r, _)| r)
+    }
+
+    /// Open `path` and also return the warnings gathered in permissive mode.
+    ///
+    /// # Errors
+    ///
+    /// See [`open`](Self::open).
+    pub fn open_with_warnings<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<(Reader, Vec<String>), Error> {
+        let (mut reader, warnings) = Reader::_open_with(path.as_ref(), self)?;
+        let host = FileEndian::native();
+        match self.endian {
+            EndianPolicy::AutoSwap => {}
+            EndianPolicy::Error if reader.endian != host => {
+                return Err(Error::Io(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("file is {:?}, host is {host:?}", reader.endian),
+                )));
+            }
+            EndianPolicy::Error => {}
+            EndianPolicy::Preserve => {
+                reader.endian = host;
+                reader.header.set_file_endian(host);
+            }
+        }
+        Ok((reader, warnings))
+    }
+}
@@ -0,0 +1,122 @@
+//! Parallel section access and voxel maps (feature `parallel`).
+//!
+//! [`Reader`](crate::Reader) is `Send + Sync` and every read takes `&self`,
+//! so sections of a mapped or buffered volume can be decoded on many threads
+//! at once without locking. [`MrcBuf`] owns its data, so its voxels can be
+//! split into disjoint chunks and rewritten on every core.
+
+use crate::io::buf::{MAP_CHUNK, MapF32};
+use crate::{DataBlock, Error, MrcBuf, Voxel};
+
+use rayon::prelude::*;
+
+impl crate::Reader {
+    /// Iterate over Z-sections in parallel with rayon.
+    ///
+    /// Each item is the same [`DataBlock`] that [`slices`](Self::slices)
+    /// yields for that section — zero-copy for native-endian data — and
+    /// the iterator is indexed, so `enumerate` gives the section number.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// use rayon::prelude::*;
+    ///
+    /// let reader = mrc::Reader::open("tomogram.mrc")?;
+    /// let lens: Vec<usize> = reader
+    ///     .par_sections()
+    ///     .map(|section| section.map(|b| b.data().len()))
+    ///     .collect::<Result<_, _>>()?;
+    /// # let _ = lens;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn par_sections(
+        &self,
+    ) -> impl IndexedParallelIterator<Item = Result<DataBlock<'_>, Error>> + '_ {
+        let s = self.shape();
+        (0..s.nz)
+            .into_par_iter()
+            .map(move |z| self.subregion([0, 0, z], [s.nx, s.ny, 1]))
+    }
+}
+
+/// Voxels handed to each rayon task by the `par_map_voxels` methods: 1 MiB
+/// of `f32`, enough work per task that scheduling cost vanishes on a
+/// many-core node, and small enough that a multi-GB tomogram splits into
+/// thousands of tasks to balance.
+const PAR_CHUNK_VOXELS: usize = 262_144;
+
+impl MrcBuf {
+    /// [`map_voxels`](Self::map_voxels) spread across the rayon pool.
+    ///
+    /// `f` is called from many threads at once and in no particular order,
+    /// so it must be `Fn + Sync` rather than `FnMut`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrc::{Header, Mode, MrcBuf};
+    ///
+    /// let mut header = Header::new();
+    /// (header.nx, header.ny, header.nz) = (3, 1, 1);
+    /// (header.mx, header.my, header.mz) = (3, 1, 1);
+    /// header.mode = Mode::Int16.as_i32();
+    /// let mut buf = MrcBuf::new(header)?;
+    /// buf.data_as_mut::<i16>()?.copy_from_slice(&[1, 2, 3]);
+    ///
+    /// buf.par_map_voxels(|v: i16| v * 10)?;
+    /// assert_eq!(buf.data_as::<i16>()?, &[10, 20, 30]);
+    /// # Ok::<(), mrc::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ModeMismatch`] if the mode is not `T::MODE`.
+    pub fn par_map_voxels<T: Voxel + Send>(
+        &mut self,
+        f: impl Fn(T) -> T + Sync + Send,
+    ) -> Result<(), Error> {
+        self.data_as_mut::<T>()?
+            .par_chunks_mut(PAR_CHUNK_VOXELS)
+            .for_each(|chunk| chunk.iter_mut().for_each(|v| *v = f(*v)));
+        Ok(())
+    }
+
+    /// [`map_voxels_f32`](Self::map_voxels_f32) spread across the rayon
+    /// pool, with the same conversions for each mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedMode`] for complex modes.
+    pub fn par_map_voxels_f32(
+        &mut self,
+        f: impl Fn(f32) -> f32 + Sync + Send,
+    ) -> Result<(), Error> {
+        struct Parallel<F>(F);
+        impl<F: Fn(f32) -> f32 + Sync + Send> MapF32 for Parallel<F> {
+            fn map<T: Copy + Send>(
+                &mut self,
+                v: &mut [T],
+                to: impl Fn(T) -> f32 + Sync,
+                from: impl Fn(f32) -> T + Sync,
+            ) {
+                let f = &self.0;
+                v.par_chunks_mut(PAR_CHUNK_VOXELS).for_each_init(
+                    || Vec::with_capacity(MAP_CHUNK),
+                    |buf, task| {
+                        for chunk in task.chunks_mut(MAP_CHUNK) {
+                            buf.clear();
+                            buf.extend(chunk.iter().map(|&x| f(to(x))));
+                            for (d, &x) in chunk.iter_mut().zip(buf.iter()) {
+                                *d = from(x);
+                            }
+                        }
+                    },
+                );
+            }
+        }
+        self.map_f32_with(&mut Parallel(f))
+    }
+}
@@ -0,0 +1,130 @@
+//! Reusing voxel buffers across many files.
+//!
+//! A batch job that loads thousands of same-sized files into memory one
+//! after another spends much of its time allocating, page-faulting and
+//! freeing the same few gigabytes. A [`BufferPool`] keeps freed
+//! [`AlignedBuf`]s for the next load: readers opened with
+//! [`OpenOptions::buffer_pool`](crate::OpenOptions::buffer_pool) read their
+//! voxel data into a pooled buffer and give it back when the last handle to
+//! it is dropped, and [`take`](BufferPool::take) and
+//! [`give`](BufferPool::give) let callers pool their own section buffers.
+
+use crate::AlignedBuf;
+
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// A shared, bounded set of free [`AlignedBuf`]s.
+///
+/// Clones share the same buffers, so one pool can serve reader threads
+/// working in parallel. A request takes the smallest free buffer that is
+/// large enough and has the requested alignment, or allocates a new one.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> Result<(), mrc::Error> {
+/// use mrc::{BufferPool, OpenOptions};
+///
+/// let pool = BufferPool::new(2);
+/// let options = OpenOptions::new().eager(true).buffer_pool(pool.clone());
+/// for path in ["a.mrc", "b.mrc", "c.mrc"] {
+///     let reader = options.open(path)?;
+///     println!("{path}: {:?}", reader.quick_statistics()?);
+/// } // each reader hands its buffer to the next
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct BufferPool {
+    inner: Arc<Mutex<Vec<AlignedBuf>>>,
+    max_buffers: usize,
+}
+
+impl BufferPool {
+    /// A pool that keeps at most `max_buffers` free buffers; further
+    /// buffers given back are freed.
+    pub fn new(max_buffers: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Vec::new())),
+            max_buffers,
+        }
+    }
+
+    /// A cache-line-aligned buffer of `len` bytes, reused when possible.
+    ///
+    /// A reused buffer still holds the data of its previous use, so
+    /// overwrite all of it; a new one is zeroed.
+    pub fn take(&self, len: usize) -> AlignedBuf {
+        self.take_aligned(len, crate::BUF_ALIGN)
+            .unwrap_or_else(|| AlignedBuf::zeroed(len))
+    }
+
+    /// A page-aligned buffer of `len` bytes, as
+    /// [`AlignedBuf::zeroed_pages`], reused when possible.
+    ///
+    /// As with [`take`](Self::take), a reused buffer is not cleared.
+    pub fn take_pages(&self, len: usize) -> AlignedBuf {
+        self.take_aligned(len, crate::PAGE_ALIGN)
+            .unwrap_or_else(|| AlignedBuf::zeroed_pages(len))
+    }
+
+    fn take_aligned(&self, len: usize, align: usize) -> Option<AlignedBuf> {
+        let mut free = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        let best = free
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| b.alignment() == align && b.capacity() >= len)
+            .min_by_key(|(_, b)| b.capacity())
+            .map(|(i, _)| i)?;
+        let mut buf = free.swap_remove(best);
+        buf.set_len_within(len);
+        Some(buf)
+    }
+
+    /// Return `buf` for reuse. Empty buffers, and any beyond the pool's
+    /// limit, are simply dropped.
+    pub fn give(&self, buf: AlignedBuf) {
+        if buf.capacity() == 0 {
+            return;
+        }
+        let mut free = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        if free.len() < self.max_buffers {
+            free.push(buf);
+        }
+    }
+
+    /// Number of free buffers held.
+    pub fn len(&self) -> usize {
+        self.inner
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// `true` if no free buffers are held.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reuses_smallest_buffer_that_fits() {
+        let pool = BufferPool::new(2);
+        let small = pool.take(100);
+        let large = pool.take(10_000);
+        let large_ptr = large.as_ptr();
+        pool.give(small);
+        pool.give(large);
+        pool.give(AlignedBuf::zeroed(5)); // over the limit
+        assert_eq!(pool.len(), 2);
+
+        let buf = pool.take(5_000);
+        assert_eq!((buf.as_ptr(), buf.len()), (large_ptr, 5_000));
+        assert_eq!(pool.take_pages(10).alignment(), crate::PAGE_ALIGN);
+        assert_eq!(pool.len(), 1);
+    }
+}
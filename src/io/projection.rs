@@ -0,0 +1,81 @@
+//! Projections of a volume along one axis.
+//!
+//! Summing a tomogram along Z gives the familiar overview image for quick
+//! QC, and a maximum projection shows where gold and ice contamination
+//! sit; projecting along Y shows the section's thickness profile.
+//! [`Reader::project`](crate::Reader::project) computes these one section
+//! at a time, so the volume never has to fit in memory.
+
+use crate::{Axis, Error, Reader, VoxelBlock};
+
+/// How [`Reader::project`] combines the voxels along the projected axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectionKind {
+    /// Sum of the values.
+    Sum,
+    /// Arithmetic mean of the values.
+    Mean,
+    /// Largest value, ignoring NaN.
+    Max,
+}
+
+impl Reader {
+    /// Project the volume along `axis`, combining each line of voxels
+    /// parallel to it as `kind` says.
+    ///
+    /// The result is laid out like [`Volume::plane`](crate::Volume::plane):
+    /// the volume's shape with `axis` reduced to 1, at offset `[0, 0, 0]`,
+    /// so a Z projection is `nx × ny` and an X projection `ny × nz` with Y
+    /// fastest. Values are read through [`convert::<f32>()`](Self::convert)
+    /// one section at a time.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// use mrc::{Axis, ProjectionKind};
+    /// let reader = mrc::Reader::open("tomogram.mrc")?;
+    /// let overview = reader.project(Axis::Z, ProjectionKind::Sum)?;
+    /// let side = reader.project(Axis::Y, ProjectionKind::Mean)?;
+    /// println!("{:?} {:?}", overview.shape, side.shape);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Propagates any error from reading the volume.
+    pub fn project(&self, axis: Axis, kind: ProjectionKind) -> Result<VoxelBlock<f32>, Error> {
+        let s = self.shape();
+        let a = axis.index();
+        let mut shape = [s.nx, s.ny, s.nz];
+        let n = shape[a];
+        shape[a] = 1;
+        let init = match kind {
+            ProjectionKind::Max => f32::NAN,
+            _ => 0.0,
+        };
+        let mut data = vec![init; shape.iter().product()];
+        for (z, section) in self.convert::<f32>().slices().enumerate() {
+            let section = section?;
+            for (i, &v) in section.data.iter().enumerate() {
+                let mut p = [i % s.nx, i / s.nx, z];
+                p[a] = 0;
+                let out = &mut data[(p[2] * shape[1] + p[1]) * shape[0] + p[0]];
+                *out = match kind {
+                    ProjectionKind::Max => out.max(v),
+                    _ => *out + v,
+                };
+            }
+        }
+        if kind == ProjectionKind::Mean && n > 0 {
+            let n = n as f32;
+            data.iter_mut().for_each(|v| *v /= n);
+        }
+        Ok(VoxelBlock {
+            offset: [0; 3],
+            shape,
+            data,
+        })
+    }
+}
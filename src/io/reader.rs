@@ -40,6 +40,7 @@ pub fn detect_compression_from_bytes(bytes: &[u8]) -> CompressionType {
     if bytes.len() < 2 {
         return CompressionType::Plain;
     }
+    #[cfg_attr(not(any(feature = "gzip", feature = "bzip2")), allow(unused_variables))]
     let magic = [bytes[0], bytes[1]];
     #[cfg(feature = "gzip")]
     if magic == [0x1f, 0x8b] {
@@ -62,20 +63,87 @@ pub fn detect_compression<P: AsRef<Path>>(path: P) -> Result<CompressionType, Er
     Ok(detect_compression_from_bytes(&buf[..n]))
 }
 
+/// Smallest share of a parallel read given to one thread.
+const MIN_BYTES_PER_READ_THREAD: usize = 1 << 20;
+
+/// Fill `buf` from `file` starting at byte `pos`, splitting the work into
+/// up to `threads` concurrent positional reads.
+fn read_exact_parallel(
+    file: &std::fs::File,
+    pos: u64,
+    buf: &mut [u8],
+    threads: usize,
+) -> std::io::Result<()> {
+    let threads = threads.min(buf.len() / MIN_BYTES_PER_READ_THREAD).max(1);
+    let chunk = buf.len().div_ceil(threads).max(1);
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = buf
+            .chunks_mut(chunk)
+            .enumerate()
+            .map(|(i, part)| {
+                let at = pos + (i * chunk) as u64;
+                scope.spawn(move || read_exact_at(file, part, at))
+            })
+            .collect();
+        handles.into_iter().try_for_each(|h| {
+            h.join()
+                .unwrap_or_else(|_| Err(std::io::Error::other("read thread panicked")))
+        })
+    })
+}
+
+#[cfg(unix)]
+fn read_exact_at(file: &std::fs::File, buf: &mut [u8], pos: u64) -> std::io::Result<()> {
+    std::os::unix::fs::FileExt::read_exact_at(file, buf, pos)
+}
+
+#[cfg(windows)]
+fn read_exact_at(file: &std::fs::File, mut buf: &mut [u8], mut pos: u64) -> std::io::Result<()> {
+    use std::os::windows::fs::FileExt;
+    while !buf.is_empty() {
+        match file.seek_read(buf, pos)? {
+            0 => return Err(std::io::ErrorKind::UnexpectedEof.into()),
+            n => {
+                buf = &mut buf[n..];
+                pos += n as u64;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Without positional reads the chunks are read one after another.
+#[cfg(not(any(unix, windows)))]
+fn read_exact_at(file: &std::fs::File, buf: &mut [u8], pos: u64) -> std::io::Result<()> {
+    use std::io::{Read, Seek};
+    static LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+    let _guard = LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let mut file = file;
+    file.seek(std::io::SeekFrom::Start(pos))?;
+    file.read_exact(buf)
+}
+
 // ============================================================================
 // ============================================================================
 // Data source and Reader type
 // ============================================================================
 
-/// How the reader accesses voxel data.
-#[derive(Debug)]
+/// How the reader accesses voxel data. Both backends are reference counted
+/// so [`Reader::try_clone`] can share them.
+#[derive(Debug, Clone)]
 enum DataSource {
     /// Loaded entirely into memory.
-    Buffered { data: Vec<u8>, truncated: bool },
+    Buffered {
+        data: std::sync::Arc<crate::AlignedBuf>,
+        truncated: bool,
+    },
     /// Memory-mapped file (zero-copy).
     #[cfg(feature = "mmap")]
+    #[cfg_attr(target_os = "wasi", allow(dead_code))]
     Mmap {
-        map: memmap2::Mmap,
+        map: std::sync::Arc<memmap2::Mmap>,
         data_offset: usize,
         truncated: bool,
     },
@@ -116,7 +184,9 @@ enum DataSource {
 /// }
 /// # Ok::<_, mrc::Error>(())
 /// ```
-#[derive(Debug)]
+///
+/// `Debug` output shows dimensions, mode, backend and byte counts but never
+/// the voxel data; [`summary`](Self::summary) gives the same as one line.
 pub struct Reader {
     pub(crate) header: Header,
     pub(crate) ext_header: Vec<u8>,
@@ -124,6 +194,54 @@ pub struct Reader {
     pub(crate) mode: Mode,
     pub(crate) shape: VolumeShape,
     source: DataSource,
+    /// Path and options the reader was opened with, for [`Reader::reopen`].
+    pub(crate) origin: Option<(std::path::PathBuf, crate::OpenOptions)>,
+    /// Checked between chunks by long-running reads.
+    cancel: Option<crate::CancellationToken>,
+    /// Receives the in-memory voxel buffer when the last handle drops.
+    pool: Option<crate::BufferPool>,
+}
+
+// Readers and the blocks they hand out are shared across threads (e.g. by
+// `par_sections`); keep that a compile-time guarantee.
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Reader>();
+    assert_send_sync::<crate::DataBlock<'static>>();
+};
+
+impl std::fmt::Debug for Reader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Reader")
+            .field("shape", &[self.shape.nx, self.shape.ny, self.shape.nz])
+            .field("mode", &self.mode)
+            .field("endian", &self.endian)
+            .field("backend", &self.backend_name())
+            .field("ext_header_len", &self.ext_header_bytes().len())
+            .field("data_len", &self.raw_bytes().len())
+            .field("truncated", &self.is_truncated())
+            .finish()
+    }
+}
+
+impl Drop for Reader {
+    /// Hands an in-memory voxel buffer back to the reader's
+    /// [`BufferPool`](crate::BufferPool) once no clone shares it.
+    fn drop(&mut self) {
+        let Some(pool) = &self.pool else {
+            return;
+        };
+        let empty = DataSource::Buffered {
+            data: std::sync::Arc::new(crate::AlignedBuf::zeroed(0)),
+            truncated: false,
+        };
+        #[cfg_attr(not(feature = "mmap"), allow(irrefutable_let_patterns))]
+        if let DataSource::Buffered { data, .. } = std::mem::replace(&mut self.source, empty) {
+            if let Ok(buf) = std::sync::Arc::try_unwrap(data) {
+                pool.give(buf);
+            }
+        }
+    }
 }
 
 // ============================================================================
@@ -134,7 +252,10 @@ impl Reader {
     /// Open an MRC file, auto-detecting gzip/bzip2 compression.
     ///
     /// For plain files, selects memory-mapped I/O when available (the `mmap`
-    /// feature) and falls back to buffered I/O otherwise.
+    /// feature) and falls back to buffered I/O otherwise. When targeting
+    /// WASI the mmap attempt is skipped and the file is read with plain
+    /// `std::fs` calls, so paths resolve through the sandbox's preopened
+    /// directories.
     ///
     /// # Examples
     ///
@@ -261,6 +382,58 @@ impl Reader {
         Self::_read_from_buf(data, false).map(|(r, _)| r)
     }
 
+    /// Build a reader from a header, extended header and voxel data held
+    /// separately, without concatenating them into a file image first.
+    ///
+    /// The header is validated as by [`from_bytes`](Self::from_bytes), and
+    /// `data` is copied into an aligned buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let mut h = mrc::Header::new();
+    /// (h.nx, h.ny, h.nz) = (4, 4, 1);
+    /// (h.mx, h.my, h.mz) = (4, 4, 1);
+    /// let reader = mrc::Reader::from_parts(h, &[], &[0u8; 64])?;
+    /// assert_eq!(reader.shape().nx, 4);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with
+    /// [`InvalidInput`](std::io::ErrorKind::InvalidInput) if
+    /// `ext_header.len()` differs from the header's `nsymbt`,
+    /// [`Error::FileSizeMismatch`] if `data` is not the size the header
+    /// describes, and the header validation errors of
+    /// [`from_bytes`](Self::from_bytes).
+    pub fn from_parts(header: Header, ext_header: &[u8], data: &[u8]) -> Result<Self, Error> {
+        if header.nsymbt.max(0) as usize != ext_header.len() {
+            return Err(Error::invalid_input(format!(
+                "extended header is {} bytes but NSYMBT is {}",
+                ext_header.len(),
+                header.nsymbt
+            )));
+        }
+        let mut header_bytes = [0u8; 1024];
+        header.encode_to_bytes(&mut header_bytes);
+        let len = (1024 + ext_header.len() + data.len()) as u64;
+        let (header, warnings, _endian, _data_size) =
+            crate::io::reader_common::parse_header(&header_bytes, len, false)?;
+        Self::_build(
+            header,
+            ext_header.to_vec(),
+            DataSource::Buffered {
+                data: std::sync::Arc::new(crate::AlignedBuf::from(data)),
+                truncated: false,
+            },
+            warnings,
+        )
+        .map(|(r, _)| r)
+    }
+
     /// Parse an MRC file from an in-memory byte buffer in permissive mode.
     ///
     /// # Examples
@@ -283,15 +456,298 @@ impl Reader {
         Self::_read_from_buf(data, true)
     }
 
+    /// Parse a whole MRC file held in a borrowed byte slice.
+    ///
+    /// Unlike [`from_bytes`](Self::from_bytes), gzip and bzip2 input is
+    /// detected from the magic bytes and decompressed in memory (when the
+    /// corresponding feature is enabled), so a `fetch()`ed `.mrc` or
+    /// `.mrc.gz` can be handed over unchanged. No filesystem or mmap access
+    /// is involved, which makes this the entry point for
+    /// `wasm32-unknown-unknown` builds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// # let mut h = mrc::Header::new();
+    /// # h.nx = 4; h.ny = 4; h.nz = 1;
+    /// # h.mx = 4; h.my = 4; h.mz = 1;
+    /// # let mut raw = [0u8; 1024];
+    /// # h.encode_to_bytes(&mut raw);
+    /// # let buf: Vec<u8> = raw.into_iter().chain(vec![0u8; 64]).collect();
+    /// let reader = mrc::Reader::from_slice(&buf)?;
+    /// assert_eq!(reader.shape().nx, 4);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_slice(data: &[u8]) -> Result<Self, Error> {
+        Self::_from_slice(data, false).map(|(r, _)| r)
+    }
+
+    /// Parse a whole MRC file held in a borrowed byte slice in permissive mode.
+    pub fn from_slice_permissive(data: &[u8]) -> Result<(Self, Vec<String>), Error> {
+        Self::_from_slice(data, true)
+    }
+
+    /// Serialize the whole file image — header, extended header and voxel
+    /// data — into one buffer, the inverse of [`from_bytes`](Self::from_bytes).
+    ///
+    /// Data is copied as stored, in the file's byte order, so the result is
+    /// byte-identical to an uncompressed file written by this crate. For a
+    /// truncated reader only the bytes actually present are emitted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// # let mut h = mrc::Header::new();
+    /// # h.nx = 4; h.ny = 4; h.nz = 1;
+    /// # h.mx = 4; h.my = 4; h.mz = 1;
+    /// # let mut raw = [0u8; 1024];
+    /// # h.encode_to_bytes(&mut raw);
+    /// # let buf: Vec<u8> = raw.into_iter().chain(vec![0u8; 64]).collect();
+    /// let reader = mrc::Reader::from_slice(&buf)?;
+    /// let bytes = reader.to_bytes();
+    /// assert_eq!(bytes, buf);
+    /// let copy = mrc::Reader::from_bytes(bytes)?;
+    /// assert_eq!(copy.shape(), reader.shape());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let ext = self.ext_header_bytes();
+        let data = self._source_data();
+        let mut out = Vec::with_capacity(1024 + ext.len() + data.len());
+        let mut raw = [0u8; 1024];
+        self.header.encode_to_bytes(&mut raw);
+        out.extend_from_slice(&raw);
+        out.extend_from_slice(ext);
+        out.extend_from_slice(data);
+        out
+    }
+
+    /// Stream [`to_bytes`](Self::to_bytes) into `w` without collecting it
+    /// into one buffer first — to answer an HTTP request or fill a tar
+    /// entry straight from a mapped file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if `w` fails.
+    pub fn write_into<W: std::io::Write>(&self, w: &mut W) -> Result<(), Error> {
+        let mut raw = [0u8; 1024];
+        self.header.encode_to_bytes(&mut raw);
+        w.write_all(&raw)?;
+        w.write_all(self.ext_header_bytes())?;
+        w.write_all(self._source_data())?;
+        Ok(())
+    }
+
+    /// Touch every page of the voxel data so later accesses do not fault.
+    ///
+    /// Memory-mapped data is otherwise paged in on first access, which can
+    /// put multi-millisecond stalls in the middle of a timed loop or an
+    /// interactive session. This walks the data once, reading one byte per
+    /// 4 KiB page; for in-memory readers it is a cheap no-op in effect.
+    /// [`OpenOptions::prefault`](crate::OpenOptions::prefault) does the same
+    /// at open time, with `MAP_POPULATE` on Linux.
+    pub fn prefault(&self) {
+        const PAGE: usize = 4096;
+        let data = self._source_data();
+        let mut acc = 0u8;
+        for i in (0..data.len()).step_by(PAGE) {
+            acc ^= data[i];
+        }
+        std::hint::black_box(acc);
+    }
+
+    /// Stop long-running reads on this reader when `token` is cancelled, or
+    /// never when `None`.
+    ///
+    /// The token is checked before each section, slab, tile or brick that
+    /// the iterators read — and therefore between the chunks of
+    /// [`convert`](Self::convert), [`histogram`](Self::histogram),
+    /// [`display_range`](Self::display_range),
+    /// [`quantile_sketch`](Self::quantile_sketch),
+    /// [`approx_eq`](Self::approx_eq) and
+    /// [`ChunkedProcessor`](crate::ChunkedProcessor) — and between the
+    /// chunks of [`data_stats_with`](Self::data_stats_with). These then
+    /// return [`Error::Cancelled`]. Clones made with
+    /// [`try_clone`](Self::try_clone) share the token.
+    pub fn set_cancellation(&mut self, token: Option<crate::CancellationToken>) {
+        self.cancel = token;
+    }
+
+    /// `Err(Error::Cancelled)` once this reader's token is cancelled.
+    pub(crate) fn check_cancelled(&self) -> Result<(), Error> {
+        match &self.cancel {
+            Some(token) => token.check(),
+            None => Ok(()),
+        }
+    }
+
+    /// `(pointer, len)` of the voxel data, for zero-copy handoff to foreign
+    /// code such as CUDA or HIP.
+    ///
+    /// The pointer is valid for reads of `len` bytes while this reader or
+    /// any [`try_clone`](Self::try_clone) of it is alive, and does not move
+    /// in that time. It is page-aligned, and the allocation is padded to a
+    /// whole number of pages, when the reader was opened with
+    /// [`OpenOptions::page_aligned`](crate::OpenOptions::page_aligned);
+    /// otherwise it is cache-line aligned for in-memory readers and has no
+    /// particular alignment for memory maps. The bytes are in file order and
+    /// byte order. Never write through it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let reader = mrc::OpenOptions::new().page_aligned(true).open("movie.mrc")?;
+    /// let (ptr, len) = reader.as_raw_parts();
+    /// assert_eq!(ptr as usize % mrc::PAGE_ALIGN, 0);
+    /// // cudaHostRegister(ptr as *mut _, len, cudaHostRegisterReadOnly) …
+    /// # let _ = len;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_raw_parts(&self) -> (*const u8, usize) {
+        let data = self._source_data();
+        (data.as_ptr(), data.len())
+    }
+
+    /// Re-home the voxel data in a page-aligned buffer unless it is in one.
+    fn move_to_pages(&mut self) {
+        #[cfg_attr(not(feature = "mmap"), allow(irrefutable_let_patterns))]
+        if let DataSource::Buffered { data, .. } = &self.source {
+            if data.alignment() >= crate::PAGE_ALIGN {
+                return;
+            }
+        }
+        let src = self._source_data();
+        let mut data = match &self.pool {
+            Some(pool) => pool.take_pages(src.len()),
+            None => crate::AlignedBuf::zeroed_pages(src.len()),
+        };
+        data.copy_from_slice(src);
+        self.source = DataSource::Buffered {
+            data: std::sync::Arc::new(data),
+            truncated: self.is_truncated(),
+        };
+    }
+
+    /// Create a second, independent handle to the same data.
+    ///
+    /// The memory map or in-memory buffer is shared rather than copied, so
+    /// this is cheap; each handle can be moved to its own thread and read
+    /// without coordinating with the others. Mirrors
+    /// [`File::try_clone`](std::fs::File::try_clone).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let reader = mrc::Reader::open("density.mrc")?;
+    /// let second = reader.try_clone()?;
+    /// let worker = std::thread::spawn(move || second.slices().filter(Result::is_ok).count());
+    /// let corner = reader.subregion([0, 0, 0], [8, 8, 8])?;
+    /// # let _ = (corner, worker.join());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Currently infallible; the `Result` leaves room for backends that own
+    /// an operating-system handle.
+    pub fn try_clone(&self) -> Result<Self, Error> {
+        Ok(Self {
+            header: self.header,
+            ext_header: self.ext_header.clone(),
+            endian: self.endian,
+            mode: self.mode,
+            shape: self.shape,
+            source: self.source.clone(),
+            origin: self.origin.clone(),
+            cancel: self.cancel.clone(),
+            pool: self.pool.clone(),
+        })
+    }
+
+    /// Open the file this reader came from again, with the same options.
+    ///
+    /// Unlike [`try_clone`](Self::try_clone) this creates a fresh mapping or
+    /// buffer, so it picks up changes made to the file since it was opened.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with [`Unsupported`](std::io::ErrorKind::Unsupported)
+    /// for readers not opened from a path (`from_bytes`, `from_reader`, …),
+    /// and otherwise any error opening the file can produce.
+    pub fn reopen(&self) -> Result<Self, Error> {
+        match &self.origin {
+            Some((path, options)) => options.open(path),
+            None => Err(Error::Io(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "reader was not opened from a path",
+            ))),
+        }
+    }
+
     // ── Internal open helpers ──────────────────────────────────────────
 
     /// Detect compression and open. Tries mmap first for plain files.
     fn _open_detect(
         path: &std::path::Path,
         permissive: bool,
+    ) -> Result<(Self, Vec<String>), Error> {
+        Self::_open_with(path, &crate::OpenOptions::new().permissive(permissive))
+    }
+
+    /// Detect compression and open with explicit backend choices, recording
+    /// them so the file can be [reopened](Self::reopen).
+    pub(crate) fn _open_with(
+        path: &std::path::Path,
+        options: &crate::OpenOptions,
+    ) -> Result<(Self, Vec<String>), Error> {
+        let (mut reader, mut warnings) = Self::_open_path(path, options)?;
+        if options.salvage_truncated && reader.is_truncated() {
+            if !options.permissive {
+                reader
+                    .header
+                    .validate_detailed()
+                    .map_err(Error::InvalidHeaderDetailed)?;
+            }
+            let declared = reader.shape.nz;
+            let kept = reader.keep_complete_sections()?;
+            warnings.push(format!(
+                "file truncated: kept {kept} of {declared} sections"
+            ));
+        }
+        reader.pool = options.pool.clone();
+        if options.page_aligned {
+            reader.move_to_pages();
+        }
+        reader.cancel = options.cancel.clone();
+        if options.prefault {
+            reader.prefault();
+        }
+        reader.origin = Some((path.to_path_buf(), options.clone()));
+        Ok((reader, warnings))
+    }
+
+    /// Mapping is a preference: plain files fall back to buffered I/O when
+    /// it is unavailable or fails.
+    fn _open_path(
+        path: &std::path::Path,
+        options: &crate::OpenOptions,
     ) -> Result<(Self, Vec<String>), Error> {
         use std::io::{Read, Seek};
 
+        // Truncated files only open permissively; salvage re-checks the
+        // header strictly afterwards.
+        let permissive = options.permissive || options.salvage_truncated;
+        #[cfg(any(feature = "gzip", feature = "bzip2"))]
+        let max_decompressed_bytes = options.max_decompressed_bytes;
+
         let mut file = std::fs::File::open(path)?;
         let mut magic = [0u8; 2];
         let n = file.read(&mut magic)?;
@@ -303,145 +759,146 @@ impl Reader {
                     // Seek back to start before handing to the gzip decoder.
                     // An error here is benign — the decoder will fail on its own.
                     let _ = file.seek(std::io::SeekFrom::Start(0));
-                    return Self::_open_gzip_file(
-                        file,
-                        permissive,
-                        crate::io::reader_common::DEFAULT_MAX_DECOMPRESSED_BYTES,
-                    );
+                    return Self::_open_gzip_file(file, permissive, max_decompressed_bytes);
                 }
                 #[cfg(feature = "bzip2")]
                 [b'B', b'Z'] => {
                     // Seek back to start before handing to the bzip2 decoder.
                     // An error here is benign — the decoder will fail on its own.
                     let _ = file.seek(std::io::SeekFrom::Start(0));
-                    return Self::_open_bzip2_file(
-                        file,
-                        permissive,
-                        crate::io::reader_common::DEFAULT_MAX_DECOMPRESSED_BYTES,
-                    );
+                    return Self::_open_bzip2_file(file, permissive, max_decompressed_bytes);
                 }
                 _ => {}
             }
         }
 
         // Plain file — try mmap first; fall back to buffered on any error.
-        #[cfg(feature = "mmap")]
-        {
+        #[cfg(all(feature = "mmap", not(target_os = "wasi")))]
+        if options.mmap && !options.eager && !options.page_aligned {
             drop(file);
-            if let Ok(result) = Self::_open_mmap_path(path, permissive) {
+            if let Ok(result) = Self::_open_mmap_path(path, permissive, options.prefault) {
                 return Ok(result);
             }
             // mmap failed — re-open for buffered fallback.
             let file = std::fs::File::open(path)?;
-            Self::_open_plain_file(file, permissive)
+            return Self::_open_plain_file(file, permissive, options);
         }
 
-        #[cfg(not(feature = "mmap"))]
-        {
-            // Seek back to start (file is at offset 2 after reading magic bytes).
-            // An error here is benign — the plain-file reader will fail with
-            // its own I/O error if the file is genuinely unreadable.
-            let _ = file.seek(std::io::SeekFrom::Start(0));
-            Self::_open_plain_file(file, permissive)
-        }
+        // Seek back to start (file is at offset 2 after reading magic bytes).
+        // An error here is benign — the plain-file reader will fail with
+        // its own I/O error if the file is genuinely unreadable.
+        let _ = file.seek(std::io::SeekFrom::Start(0));
+        Self::_open_plain_file(file, permissive, options)
     }
 
     fn _open_plain<P: AsRef<std::path::Path>>(
         path: P,
         permissive: bool,
     ) -> Result<(Self, Vec<String>), Error> {
-        Self::_open_plain_file(std::fs::File::open(path)?, permissive)
+        let options = crate::OpenOptions::new().permissive(permissive).mmap(false);
+        let (mut reader, warnings) =
+            Self::_open_plain_file(std::fs::File::open(path.as_ref())?, permissive, &options)?;
+        reader.origin = Some((path.as_ref().to_path_buf(), options));
+        Ok((reader, warnings))
     }
 
     fn _open_plain_file(
         mut file: std::fs::File,
         permissive: bool,
+        options: &crate::OpenOptions,
     ) -> Result<(Self, Vec<String>), Error> {
         use std::io::Read;
 
         let mut header_bytes = [0u8; 1024];
         file.read_exact(&mut header_bytes)?;
+        let file_len = file.metadata()?.len();
 
         let (header, warnings, _endian, data_size) =
-            crate::io::reader_common::parse_header(&header_bytes, permissive)?;
-
-        let ext_size = header.nsymbt as usize;
-        let mut ext_header = vec![0u8; ext_size];
-        if ext_size > 0 {
-            file.read_exact(&mut ext_header)?;
-        }
+            crate::io::reader_common::parse_header(&header_bytes, file_len, permissive)?;
 
-        let mut data = vec![0u8; data_size];
-        file.read_exact(&mut data)?;
-
-        if !permissive {
-            let file_len = file.metadata()?.len() as usize;
-            let expected_len = header.data_offset() + data_size;
-            if file_len != expected_len {
-                return Err(Error::FileSizeMismatch {
-                    expected: expected_len,
-                    actual: file_len,
-                });
+        // `parse_header` has compared the length with the header before
+        // anything is allocated, so a bogus header cannot trigger a huge
+        // allocation, and has checked that the extended header fits.
+        let data_offset = header.data_offset();
+        let mut ext_header = vec![0u8; data_offset - 1024];
+        file.read_exact(&mut ext_header)?;
+
+        let available = (file_len - data_offset as u64).min(data_size as u64) as usize;
+        let mut data = match (&options.pool, options.page_aligned) {
+            (Some(pool), true) => pool.take_pages(available),
+            (Some(pool), false) => pool.take(available),
+            (None, true) => crate::AlignedBuf::zeroed_pages(available),
+            (None, false) => crate::AlignedBuf::zeroed(available),
+        };
+        let read = if options.read_threads > 1 {
+            read_exact_parallel(&file, data_offset as u64, &mut data, options.read_threads)
+        } else {
+            file.read_exact(&mut data)
+        };
+        if let Err(e) = read {
+            if let Some(pool) = &options.pool {
+                pool.give(data);
             }
+            return Err(e.into());
         }
 
         Self::_build(
             header,
             ext_header,
             DataSource::Buffered {
-                data,
-                truncated: false,
+                data: std::sync::Arc::new(data),
+                truncated: available < data_size,
             },
             warnings,
         )
     }
 
+    fn _from_slice(data: &[u8], permissive: bool) -> Result<(Self, Vec<String>), Error> {
+        #[cfg(any(feature = "gzip", feature = "bzip2"))]
+        let limit = crate::io::reader_common::DEFAULT_MAX_DECOMPRESSED_BYTES;
+        match detect_compression_from_bytes(data) {
+            #[cfg(feature = "gzip")]
+            CompressionType::Gzip => {
+                Self::_from_decompressed(crate::io::reader_common::open_compressed(
+                    flate2::read::GzDecoder::new(data),
+                    permissive,
+                    limit,
+                )?)
+            }
+            #[cfg(feature = "bzip2")]
+            CompressionType::Bzip2 => {
+                Self::_from_decompressed(crate::io::reader_common::open_compressed(
+                    bzip2::read::BzDecoder::new(data),
+                    permissive,
+                    limit,
+                )?)
+            }
+            CompressionType::Plain => Self::_read_from_buf(data.to_vec(), permissive),
+        }
+    }
+
     fn _read_from_buf(data: Vec<u8>, permissive: bool) -> Result<(Self, Vec<String>), Error> {
         if data.len() < 1024 {
             return Err(Error::InvalidHeader);
         }
         let mut header_bytes = [0u8; 1024];
         header_bytes.copy_from_slice(&data[..1024]);
-        let (header, mut warnings, _endian, data_size) =
-            crate::io::reader_common::parse_header(&header_bytes, permissive)?;
-
-        let ext_size = header.nsymbt as usize;
-        let ext_end = (1024 + ext_size).min(data.len());
-        let ext_header = if ext_size > 0 && ext_end > 1024 {
-            if ext_end < 1024 + ext_size {
-                warnings.push(format!(
-                    "Extended header truncated: expected {} bytes, got {}",
-                    ext_size,
-                    ext_end - 1024
-                ));
-            }
-            data[1024..ext_end].to_vec()
-        } else {
-            Vec::new()
-        };
+        let (header, warnings, _endian, data_size) =
+            crate::io::reader_common::parse_header(&header_bytes, data.len() as u64, permissive)?;
 
+        // `parse_header` has checked the length against the header, so the
+        // extended header fits and the data is only short when permissive.
         let data_offset = header.data_offset();
-        let voxel_data = if data_offset < data.len() {
-            let available = data.len() - data_offset;
-            let expected = data_size.min(available);
-            data[data_offset..data_offset + expected].to_vec()
-        } else {
-            Vec::new()
-        };
-
-        if !permissive && voxel_data.len() != data_size {
-            return Err(Error::FileSizeMismatch {
-                expected: header.data_offset() + data_size,
-                actual: data.len(),
-            });
-        }
+        let ext_header = data[1024..data_offset].to_vec();
+        let available = (data.len() - data_offset).min(data_size);
+        let voxel_data = crate::AlignedBuf::from(&data[data_offset..data_offset + available]);
 
         let truncated = voxel_data.len() != data_size;
         Self::_build(
             header,
             ext_header,
             DataSource::Buffered {
-                data: voxel_data,
+                data: std::sync::Arc::new(voxel_data),
                 truncated,
             },
             warnings,
@@ -449,18 +906,21 @@ impl Reader {
     }
 
     #[cfg(feature = "mmap")]
+    #[cfg_attr(target_os = "wasi", allow(dead_code))]
     fn _open_mmap_path(
         path: &std::path::Path,
         permissive: bool,
+        populate: bool,
     ) -> Result<(Self, Vec<String>), Error> {
         use std::fs::File;
 
         let file = File::open(path)?;
-        let mmap = unsafe {
-            memmap2::MmapOptions::new()
-                .map(&file)
-                .map_err(|_| Error::Mmap)?
-        };
+        let mut options = memmap2::MmapOptions::new();
+        if populate {
+            // MAP_POPULATE on Linux; ignored elsewhere.
+            options.populate();
+        }
+        let mmap = unsafe { options.map(&file).map_err(|_| Error::Mmap)? };
         // File is closed here; mmap keeps the mapping alive.
 
         // Read header from mmap (file is already mapped)
@@ -471,35 +931,17 @@ impl Reader {
         header_bytes.copy_from_slice(&mmap[..1024]);
 
         let (header, warnings, _endian, data_size) =
-            crate::io::reader_common::parse_header(&header_bytes, permissive)?;
-
-        let expected_size = header
-            .data_offset()
-            .checked_add(data_size)
-            .ok_or(Error::InvalidHeader)?;
-        let truncated = if !permissive {
-            if mmap.len() != expected_size {
-                return Err(Error::FileSizeMismatch {
-                    expected: expected_size,
-                    actual: mmap.len(),
-                });
-            }
-            false
-        } else if mmap.len() < header.data_offset() {
-            return Err(Error::FileSizeMismatch {
-                expected: header.data_offset(),
-                actual: mmap.len(),
-            });
-        } else {
-            mmap.len() < expected_size
-        };
+            crate::io::reader_common::parse_header(&header_bytes, mmap.len() as u64, permissive)?;
+
+        // `parse_header` has rejected a length mismatch unless permissive.
+        let truncated = mmap.len() < header.data_offset() + data_size;
 
         // IMOD detection is done in _build; warnings passed through
         Self::_build(
             header,
             Vec::new(), // ext_header read from mmap on demand
             DataSource::Mmap {
-                map: mmap,
+                map: std::sync::Arc::new(mmap),
                 data_offset: header.data_offset(),
                 truncated,
             },
@@ -539,6 +981,9 @@ impl Reader {
                 mode,
                 shape,
                 source,
+                origin: None,
+                cancel: None,
+                pool: None,
             },
             warnings,
         ))
@@ -552,7 +997,7 @@ impl Reader {
             d.header,
             d.ext_header,
             DataSource::Buffered {
-                data: d.data,
+                data: std::sync::Arc::new(d.data),
                 truncated: false,
             },
             d.warnings,
@@ -758,6 +1203,111 @@ impl Reader {
         }
     }
 
+    /// Number of Z-sections whose voxel data is entirely present.
+    ///
+    /// Equal to `nz` unless the file is [truncated](Self::is_truncated).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// # let mut h = mrc::Header::new();
+    /// # h.nx = 4; h.ny = 4; h.nz = 2;
+    /// # h.mx = 4; h.my = 4; h.mz = 2;
+    /// # let mut raw = [0u8; 1024];
+    /// # h.encode_to_bytes(&mut raw);
+    /// // One and a half sections of data.
+    /// let buf: Vec<u8> = raw.into_iter().chain(vec![0u8; 96]).collect();
+    /// let (reader, _warnings) = mrc::Reader::from_bytes_permissive(buf)?;
+    /// assert_eq!(reader.complete_sections(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn complete_sections(&self) -> usize {
+        let mut one = self.header;
+        one.nz = 1;
+        match one.data_size() {
+            Some(section) if section > 0 => (self.raw_bytes().len() / section).min(self.shape.nz),
+            _ => self.shape.nz,
+        }
+    }
+
+    /// Shrink the header and shape to the complete sections (whole volumes
+    /// for a volume stack), keeping the Z voxel size. Returns the new `nz`,
+    /// or an `UnexpectedEof` error if not even one section (or volume) is
+    /// complete.
+    fn keep_complete_sections(&mut self) -> Result<usize, Error> {
+        let mut n = self.complete_sections();
+        let h = &mut self.header;
+        let stack = h.is_volume_stack() && h.mz > 0;
+        let unit = if stack { h.mz as usize } else { 1 };
+        n -= n % unit;
+        if n == 0 {
+            let mut first = *h;
+            first.nz = unit as i32;
+            return Err(Error::Io(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                format!(
+                    "file truncated inside the first {}: {} of {} data bytes present",
+                    if stack { "volume" } else { "section" },
+                    self.raw_bytes().len(),
+                    first.data_size().unwrap_or(0)
+                ),
+            )));
+        }
+        if !stack && h.mz == h.nz {
+            h.zlen *= n as f32 / h.mz as f32;
+            h.mz = n as i32;
+        }
+        h.nz = n as i32;
+        self.shape.nz = n;
+        Ok(n)
+    }
+
+    /// One-line description for logs: dimensions, mode, byte order,
+    /// backend, extended-header and data sizes.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let reader = mrc::Reader::open("density.mrc")?;
+    /// // "64×64×32 Float32, little-endian, mmap, ext header 0 B, data 524288 B"
+    /// println!("{}", reader.summary());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn summary(&self) -> String {
+        let s = self.shape;
+        format!(
+            "{}×{}×{} {:?}, {}, {}, ext header {} B, data {} B{}",
+            s.nx,
+            s.ny,
+            s.nz,
+            self.mode,
+            match self.endian {
+                FileEndian::LittleEndian => "little-endian",
+                FileEndian::BigEndian => "big-endian",
+            },
+            self.backend_name(),
+            self.ext_header_bytes().len(),
+            self.raw_bytes().len(),
+            if self.is_truncated() {
+                " (truncated)"
+            } else {
+                ""
+            }
+        )
+    }
+
+    fn backend_name(&self) -> &'static str {
+        match &self.source {
+            DataSource::Buffered { .. } => "buffered",
+            #[cfg(feature = "mmap")]
+            DataSource::Mmap { .. } => "mmap",
+        }
+    }
+
     // ── Volume type queries (delegated to header) ────────────────────
 
     /// Returns `true` if the file represents a single 2D image (`nz == 1`).
@@ -898,6 +1448,45 @@ impl Reader {
     pub fn validate_header_stats(&self) -> Result<(), Error> {
         crate::engine::stats::validate_header_stats(&self.header, self.raw_bytes())
     }
+
+    /// Compute `(dmin, dmax, dmean, rms)` from the voxel data, decoding one
+    /// chunk at a time as configured by `config`.
+    ///
+    /// `rms` is the standard deviation from the mean, as written to MRC
+    /// headers. Complex modes report only `rms`, with `(0, -1, -2)` for the
+    /// others; empty data gives `(0, -1, -2, -1)`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// use mrc::StreamingConfig;
+    ///
+    /// let reader = mrc::Reader::open("tomogram.mrc")?;
+    /// let low_memory = StreamingConfig { chunk_bytes: 4 << 20, max_resident_bytes: 16 << 20 };
+    /// let (dmin, dmax, dmean, rms) = reader.data_stats_with(&low_memory)?;
+    /// # let _ = (dmin, dmax, dmean, rms);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedMode`] for `Float16` data without the
+    /// `f16` feature.
+    pub fn data_stats_with(
+        &self,
+        config: &crate::StreamingConfig,
+    ) -> Result<(f32, f32, f32, f32), Error> {
+        let nx = self.shape.nx;
+        let chunk = crate::io::streaming::stats_chunk_len(config, self.mode, nx);
+        let mut acc = crate::engine::stats::StatsAccumulator::new(self.mode, self.endian, nx);
+        for part in self._source_data().chunks(chunk) {
+            self.check_cancelled()?;
+            acc.push(part)?;
+        }
+        Ok(acc.finish())
+    }
 }
 
 // ============================================================================
@@ -905,6 +1494,56 @@ impl Reader {
 // ============================================================================
 
 impl Reader {
+    /// Copy the raw bytes at `range` of the data region into `buf`.
+    ///
+    /// Offsets are relative to the first voxel byte (after the extended
+    /// header) and the bytes are returned as stored, in the file's byte
+    /// order — the lowest-level read, for access patterns the block and
+    /// section readers do not cover, such as fixed-size byte chunking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// # let mut h = mrc::Header::new();
+    /// # h.nx = 4; h.ny = 4; h.nz = 1;
+    /// # h.mx = 4; h.my = 4; h.mz = 1;
+    /// # let mut raw = [0u8; 1024];
+    /// # h.encode_to_bytes(&mut raw);
+    /// # let buf: Vec<u8> = raw.into_iter().chain((0..64).map(|i| i as u8)).collect();
+    /// # let reader = mrc::Reader::from_bytes(buf)?;
+    /// let mut second_row = [0u8; 16];
+    /// reader.read_bytes(16..32, &mut second_row)?;
+    /// assert_eq!(second_row[0], 16);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::BlockShapeMismatch`] if `buf.len()` differs from the
+    /// range length, and [`Error::BoundsError`] if the range is reversed or
+    /// extends past the data the reader holds.
+    pub fn read_bytes(&self, range: core::ops::Range<u64>, buf: &mut [u8]) -> Result<(), Error> {
+        let data = self._source_data();
+        let len = range
+            .end
+            .checked_sub(range.start)
+            .ok_or_else(Error::bounds_err)?;
+        if len != buf.len() as u64 {
+            return Err(Error::BlockShapeMismatch {
+                expected: usize::try_from(len).unwrap_or(usize::MAX),
+                actual: buf.len(),
+            });
+        }
+        let src = usize::try_from(range.start)
+            .ok()
+            .and_then(|start| data.get(start..start.checked_add(buf.len())?))
+            .ok_or_else(Error::bounds_err)?;
+        buf.copy_from_slice(src);
+        Ok(())
+    }
+
     /// Read a block of raw voxel bytes.
     ///
     /// # Examples
@@ -971,82 +1610,33 @@ impl Reader {
         offset: [usize; 3],
         shape: [usize; 3],
     ) -> Result<Cow<'a, [u8]>, Error> {
-        match &self.source {
-            DataSource::Buffered { data, .. } => {
-                let data_len = data.len();
-                crate::io::reader_common::validate_block_bounds(
-                    self.shape,
-                    self.mode(),
-                    data_len,
-                    offset,
-                    shape,
-                )?;
-
-                let [nx, ny, ..] = [self.shape.nx, self.shape.ny, self.shape.nz];
-                let [ox, oy, oz] = offset;
-                let [sx, sy, sz] = shape;
-
-                if ox == 0 && sx == nx && oy == 0 && sy == ny {
-                    let (start, byte_len) = if self.mode == Mode::Packed4Bit {
-                        let row_bytes = nx.div_ceil(2);
-                        (oz * ny * row_bytes, row_bytes * ny * sz)
-                    } else {
-                        let linear = oz * nx * ny;
-                        let b = self.mode.byte_size();
-                        (linear * b, sx * sy * sz * b)
-                    };
-                    return Ok(Cow::Borrowed(&data[start..start + byte_len]));
-                }
-
-                Ok(Cow::Owned(crate::io::reader_common::gather_block_bytes(
-                    data,
-                    self.shape,
-                    self.mode(),
-                    offset,
-                    shape,
-                )))
-            }
-            #[cfg(feature = "mmap")]
-            DataSource::Mmap {
-                map, data_offset, ..
-            } => {
-                let [nx, ny, ..] = [self.shape.nx, self.shape.ny, self.shape.nz];
-                let [ox, oy, oz] = offset;
-                let [sx, sy, sz] = shape;
-                let data_len = map.len().saturating_sub(*data_offset);
-                crate::io::reader_common::validate_block_bounds(
-                    self.shape,
-                    self.mode(),
-                    data_len,
-                    offset,
-                    shape,
-                )?;
-
-                if ox == 0 && sx == nx && oy == 0 && sy == ny {
-                    let (start_offset, byte_len) = if self.mode == Mode::Packed4Bit {
-                        let row_bytes = nx.div_ceil(2);
-                        (data_offset + oz * ny * row_bytes, row_bytes * ny * sz)
-                    } else {
-                        let linear = oz * nx * ny;
-                        let b = self.mode.byte_size();
-                        (data_offset + linear * b, sx * sy * sz * b)
-                    };
-                    return Ok(Cow::Borrowed(&map[start_offset..start_offset + byte_len]));
-                }
+        let data = self._source_data();
+        crate::io::reader_common::validate_block_bounds(
+            self.shape,
+            self.mode(),
+            data.len(),
+            offset,
+            shape,
+        )?;
 
-                Ok(Cow::Owned(crate::io::reader_common::gather_block_bytes(
-                    &map[*data_offset..],
-                    self.shape,
-                    self.mode(),
-                    offset,
-                    shape,
-                )))
-            }
+        // A block that occupies one contiguous byte range is borrowed
+        // directly; anything else is gathered one run at a time.
+        if let Some(run) =
+            crate::io::reader_common::contiguous_block_range(self.shape, self.mode, offset, shape)
+        {
+            return Ok(Cow::Borrowed(&data[run]));
         }
+        Ok(Cow::Owned(crate::io::reader_common::gather_block_bytes(
+            data,
+            self.shape,
+            self.mode(),
+            offset,
+            shape,
+        )))
     }
 
     /// Internal: return a `&[u8]` to the full data region regardless of backend.
-    fn _source_data(&self) -> &[u8] {
+    pub(crate) fn _source_data(&self) -> &[u8] {
         match &self.source {
             DataSource::Buffered { data, .. } => data,
             #[cfg(feature = "mmap")]
@@ -1533,6 +2123,156 @@ impl Reader {
         crate::ExtHeaderData::from_header(&self.header, self.ext_header_bytes())
     }
 
+    /// Like [`parse_extended_header`](Self::parse_extended_header), but
+    /// reports why parsing fails instead of returning
+    /// [`ExtHeaderData::None`](crate::ExtHeaderData::None).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidExtendedHeader`] naming the record, the field
+    /// and its absolute file offset when the extended header ends in a
+    /// partial record or an FEI record's `metadata_size` is wrong.
+    pub fn try_parse_extended_header(&self) -> Result<crate::ExtHeaderData, Error> {
+        let ext_type = crate::ExtHeaderType::from_header(&self.header);
+        let bytes = self.ext_header_bytes();
+        let invalid =
+            |record: usize, field: &str, at: usize, reason: String| Error::InvalidExtendedHeader {
+                exttyp: self.header.exttyp(),
+                record,
+                field: field.to_owned(),
+                offset: 1024 + at as u64,
+                reason,
+            };
+        let size = match ext_type.record_size() {
+            Some(size) if !bytes.is_empty() => size,
+            _ => return Ok(crate::ExtHeaderData::parse(ext_type, bytes)),
+        };
+        let whole = bytes.len() - bytes.len() % size;
+        if whole != bytes.len() {
+            return Err(invalid(
+                whole / size,
+                "record",
+                whole,
+                format!("{} bytes left, a record is {size}", bytes.len() - whole),
+            ));
+        }
+        if matches!(
+            ext_type,
+            crate::ExtHeaderType::Fei1 | crate::ExtHeaderType::Fei2
+        ) {
+            for (i, r) in bytes.chunks_exact(size).enumerate() {
+                let declared = u32::from_be_bytes([r[0], r[1], r[2], r[3]]);
+                if declared as usize != size {
+                    return Err(invalid(
+                        i,
+                        "metadata_size",
+                        i * size,
+                        format!("expected {size}, got {declared}"),
+                    ));
+                }
+            }
+        }
+        Ok(crate::ExtHeaderData::parse(ext_type, bytes))
+    }
+
+    /// Split the extended header into one `record_size`-byte record per
+    /// Z-section, for layouts this crate does not parse.
+    ///
+    /// Record `z` starts at byte `z · record_size` of the extended header;
+    /// bytes past the last section's record (padding, or records for
+    /// sections a salvaged file no longer has) are not visited.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let reader = mrc::Reader::open("tilt_series.mrc")?;
+    /// // A custom 32-byte record per tilt: tilt angle as the first f32.
+    /// for (z, record) in reader.ext_records(32)?.enumerate() {
+    ///     let tilt = f32::from_le_bytes([record[0], record[1], record[2], record[3]]);
+    ///     println!("section {z}: {tilt}°");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with
+    /// [`InvalidInput`](std::io::ErrorKind::InvalidInput) if `record_size`
+    /// is 0, and [`Error::InvalidExtendedHeader`] if the extended header is
+    /// too short to hold a record for every section.
+    pub fn ext_records(
+        &self,
+        record_size: usize,
+    ) -> Result<impl ExactSizeIterator<Item = &[u8]> + '_, Error> {
+        if record_size == 0 {
+            return Err(Error::invalid_input(
+                "extended header record size must be non-zero",
+            ));
+        }
+        let bytes = self.ext_header_bytes();
+        let nz = self.shape.nz;
+        let records = bytes.len() / record_size;
+        if records < nz {
+            return Err(Error::InvalidExtendedHeader {
+                exttyp: self.header.exttyp(),
+                record: records,
+                field: "record".to_owned(),
+                offset: 1024 + (records * record_size) as u64,
+                reason: format!(
+                    "{nz} sections need {} bytes of {record_size}-byte records, extended header has {}",
+                    nz * record_size,
+                    bytes.len()
+                ),
+            });
+        }
+        Ok(bytes.chunks_exact(record_size).take(nz))
+    }
+
+    /// Parse the extended header with the [`ExtHeaderFormat`](crate::ExtHeaderFormat)
+    /// registered for this file's EXTTYP, as records of type `R`.
+    ///
+    /// Returns `None` when no format is registered for the EXTTYP, or the
+    /// registered format's record type is not `R`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use mrc::{Error, ExtHeaderFormat};
+    /// # struct AcmeCam;
+    /// # struct AcmeFrame;
+    /// # impl ExtHeaderFormat for AcmeCam {
+    /// #     type Record = AcmeFrame;
+    /// #     const EXTTYP: [u8; 4] = *b"ACME";
+    /// #     const RECORD_SIZE: usize = 8;
+    /// #     fn parse_record(_: &[u8]) -> Result<AcmeFrame, Error> { Ok(AcmeFrame) }
+    /// #     fn serialize_record(_: &AcmeFrame, _: &mut [u8]) -> Result<(), Error> { Ok(()) }
+    /// # }
+    /// # fn main() -> Result<(), Error> {
+    /// mrc::register_ext_header_format::<AcmeCam>();
+    ///
+    /// let reader = mrc::Reader::open("acme_movie.mrc")?;
+    /// if let Some(frames) = reader.ext_header_typed::<AcmeFrame>() {
+    ///     println!("{} frame records", frames?.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The inner result carries the format's parse error, e.g.
+    /// [`Error::InvalidExtendedHeader`] for a partial record.
+    pub fn ext_header_typed<R: 'static>(&self) -> Option<Result<Vec<R>, Error>> {
+        let parsed =
+            crate::header::custom::parse_registered(self.header.exttyp(), self.ext_header_bytes())?;
+        match parsed {
+            Ok(records) => records.downcast::<Vec<R>>().ok().map(|r| Ok(*r)),
+            Err(e) => Some(Err(e)),
+        }
+    }
+
     /// Parse FEI1 metadata records.
     ///
     /// # Examples
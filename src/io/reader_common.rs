@@ -57,6 +57,20 @@ where
         ))
     }
 
+    /// Iterate over slabs sized by `config`: as many whole Z-planes per step
+    /// as fit in [`chunk_bytes`](crate::StreamingConfig::chunk_bytes) of
+    /// source data while the source and converted copies together stay
+    /// under [`max_resident_bytes`](crate::StreamingConfig::max_resident_bytes)
+    /// (at least one plane).
+    pub fn chunks(&self, config: &crate::StreamingConfig) -> VoxelIter<'_, T> {
+        let s = self.reader.shape();
+        let plane = s.nx * s.ny;
+        let src = self.reader.mode().byte_size_for_count(plane).max(1);
+        let dst = plane * T::BYTE_SIZE;
+        let per_src_byte = (src + dst).div_ceil(src);
+        self.slabs(config.chunk_len(src, per_src_byte) / src)
+    }
+
     /// Iterate over 3D tiles of the given shape, auto-converting each to `T`.
     pub fn tiles(&self, tile_shape: [usize; 3]) -> Result<VoxelIter<'_, T>, Error> {
         let shape = self.reader.shape();
@@ -207,6 +221,9 @@ impl<'a, S: Stepper> Iterator for RawConvertIter<'a, S> {
 
     fn next(&mut self) -> Option<Self::Item> {
         let (offset, shape) = self.stepper.next(self.volume_shape)?;
+        if let Err(e) = self.reader.check_cancelled() {
+            return Some(Err(e));
+        }
         match self.reader.read_block_bytes_cow(offset, shape) {
             Ok(bytes) => Some(Ok((bytes, offset, shape))),
             Err(e) => Some(Err(e)),
@@ -299,63 +316,71 @@ pub(crate) fn validate_block_bounds(
     Ok(byte_len)
 }
 
-/// Gather a non-contiguous 3D block from raw data bytes.
-pub(crate) fn gather_block_bytes(
-    data: &[u8],
+/// Byte ranges of the data region that make up a block, in block order.
+///
+/// Adjacent rows are merged: rows spanning the full volume width are
+/// contiguous within a section, and sections that also span the full height
+/// are contiguous with each other, so a block yields one range per row, per
+/// section, or a single range overall. Bounds must already be validated.
+pub(crate) fn block_byte_runs(
     volume_shape: VolumeShape,
     mode: Mode,
     offset: [usize; 3],
     block_shape: [usize; 3],
-) -> Vec<u8> {
-    let [nx, ny, _nz] = [volume_shape.nx, volume_shape.ny, volume_shape.nz];
+) -> impl Iterator<Item = std::ops::Range<usize>> + Clone {
+    let [nx, ny] = [volume_shape.nx, volume_shape.ny];
     let [ox, oy, oz] = offset;
     let [sx, sy, sz] = block_shape;
 
-    if mode == Mode::Packed4Bit {
-        let vol_row_bytes = nx.div_ceil(2);
-        let block_row_bytes = sx.div_ceil(2);
-        let byte_len = block_row_bytes * sy * sz;
-        let mut dst = vec![0u8; byte_len];
-
-        if ox == 0 && sx == nx && oy == 0 && sy == ny {
-            let slice_bytes = ny * vol_row_bytes;
-            let start = oz * slice_bytes;
-            let len = sz * slice_bytes;
-            return data[start..start + len].to_vec();
-        }
-
-        for z in 0..sz {
-            for y in 0..sy {
-                let vol_row = (oz + z) * ny + (oy + y);
-                let src_start = vol_row * vol_row_bytes + ox / 2;
-                let dst_start = (y + z * sy) * block_row_bytes;
-                dst[dst_start..dst_start + block_row_bytes]
-                    .copy_from_slice(&data[src_start..src_start + block_row_bytes]);
-            }
-        }
-        return dst;
-    }
+    let (vol_row, x0, row) = if mode == Mode::Packed4Bit {
+        (nx.div_ceil(2), ox / 2, sx.div_ceil(2))
+    } else {
+        let b = mode.byte_size();
+        (nx * b, ox * b, sx * b)
+    };
+    let plane = ny * vol_row;
+    let (rows, run, sections) = if row == vol_row && sy == ny {
+        (1, plane * sz, 1)
+    } else if row == vol_row {
+        (1, vol_row * sy, sz)
+    } else {
+        (sy, row, sz)
+    };
 
-    let b = mode.byte_size();
-    let voxel_count = sx * sy * sz;
-    let byte_len = voxel_count * b;
-    let mut dst = vec![0u8; byte_len];
+    (0..sections).flat_map(move |z| {
+        (0..rows).map(move |y| {
+            let start = (oz + z) * plane + (oy + y) * vol_row + x0;
+            start..start + run
+        })
+    })
+}
 
-    if ox == 0 && sx == nx && oy == 0 && sy == ny {
-        let linear = oz * nx * ny;
-        let start = linear * b;
-        return data[start..start + byte_len].to_vec();
+/// The single byte range holding a block, if it is contiguous.
+pub(crate) fn contiguous_block_range(
+    volume_shape: VolumeShape,
+    mode: Mode,
+    offset: [usize; 3],
+    block_shape: [usize; 3],
+) -> Option<std::ops::Range<usize>> {
+    let mut runs = block_byte_runs(volume_shape, mode, offset, block_shape);
+    match (runs.next(), runs.next()) {
+        (Some(run), None) => Some(run),
+        _ => None,
     }
+}
 
-    for z in 0..sz {
-        for y in 0..sy {
-            let src_linear = ox + (oy + y) * nx + (oz + z) * nx * ny;
-            let src_start = src_linear * b;
-            let dst_linear = y * sx + z * sx * sy;
-            let dst_start = dst_linear * b;
-            dst[dst_start..dst_start + sx * b]
-                .copy_from_slice(&data[src_start..src_start + sx * b]);
-        }
+/// Gather a 3D block from raw data bytes, one copy per contiguous run.
+pub(crate) fn gather_block_bytes(
+    data: &[u8],
+    volume_shape: VolumeShape,
+    mode: Mode,
+    offset: [usize; 3],
+    block_shape: [usize; 3],
+) -> Vec<u8> {
+    let runs = block_byte_runs(volume_shape, mode, offset, block_shape);
+    let mut dst = Vec::with_capacity(runs.clone().map(|r| r.len()).sum());
+    for run in runs {
+        dst.extend_from_slice(&data[run]);
     }
     dst
 }
@@ -453,17 +478,34 @@ pub(crate) fn write_block_bytes(
     Ok(())
 }
 
-/// Parse and validate an MRC header from raw bytes.
+/// Parse and validate an MRC header from raw bytes of a file `file_len`
+/// bytes long.
+///
+/// The file length must be exactly `1024 + nsymbt + data_size`: a mismatch
+/// is a [`FileSizeMismatch`](crate::Error::FileSizeMismatch) error in
+/// strict mode and the same message as a warning in permissive mode.
 pub(crate) fn parse_header(
     header_bytes: &[u8; 1024],
+    file_len: u64,
     permissive: bool,
 ) -> Result<(crate::Header, Vec<String>, crate::FileEndian, usize), crate::Error> {
     let (header, endian_warning) = crate::Header::decode_from_bytes_with_info(header_bytes);
+    // Non-IEEE floats would decode to silently wrong values, even in
+    // permissive mode.
+    crate::MachineStamp::parse(header.machst)?;
+    let record_warning = check_nsymbt(&header, file_len, permissive)?;
     let mut warnings = if permissive {
         header
             .validate_permissive()
             .map_err(crate::Error::InvalidHeaderDetailed)?
     } else {
+        let stamp = crate::MachineStamp::parse(header.machst)?;
+        if header.map != *b"MAP " || stamp.endian().is_none() {
+            return Err(crate::Error::NotAnMrcFile {
+                map: header.map,
+                machst: header.machst,
+            });
+        }
         header
             .validate_detailed()
             .map_err(crate::Error::InvalidHeaderDetailed)?;
@@ -472,23 +514,87 @@ pub(crate) fn parse_header(
     if let Some(w) = endian_warning {
         warnings.push(w.to_string());
     }
-    let data_size = header.data_size().ok_or(crate::Error::InvalidHeader)?;
+    warnings.extend(record_warning);
+    // Sized in u64 so 32-bit targets report a specific error rather than
+    // a generic invalid header when the file is merely too large.
+    let data_size = header.data_size_u64().ok_or(crate::Error::InvalidHeader)?;
+    let total = (header.data_offset() as u64)
+        .checked_add(data_size)
+        .ok_or(crate::Error::InvalidHeader)?;
+    let expected = crate::Error::addressable(total)?;
+    let data_size = crate::Error::addressable(data_size)?;
+    if file_len != total {
+        let mismatch = crate::Error::FileSizeMismatch {
+            expected,
+            actual: usize::try_from(file_len).unwrap_or(usize::MAX),
+        };
+        if !permissive {
+            return Err(mismatch);
+        }
+        warnings.push(mismatch.to_string());
+    }
     let endian = header.detect_endian();
     Ok((header, warnings, endian, data_size))
 }
 
+/// Reject an `nsymbt` that would send reads past the end of the file.
+///
+/// A negative value or one that is not a whole number of EXTTYP records is
+/// an error in strict mode; in permissive mode the former is already a
+/// validation warning and the latter is returned as one.
+fn check_nsymbt(
+    header: &crate::Header,
+    file_len: u64,
+    permissive: bool,
+) -> Result<Option<String>, crate::Error> {
+    let nsymbt = header.nsymbt;
+    let invalid = |reason: String| crate::Error::InvalidExtendedHeaderSize { nsymbt, reason };
+    if nsymbt < 0 {
+        return if permissive {
+            Ok(None)
+        } else {
+            Err(invalid("negative".into()))
+        };
+    }
+    if 1024 + nsymbt as u64 > file_len {
+        return Err(invalid(format!(
+            "extended header ends past the end of the {file_len}-byte file"
+        )));
+    }
+    let ext_type = crate::ExtHeaderType::from_header(header);
+    match ext_type.record_size() {
+        Some(size) if nsymbt as usize % size != 0 => {
+            let reason = format!(
+                "not a multiple of the {size}-byte {} record",
+                String::from_utf8_lossy(&header.exttyp())
+            );
+            if permissive {
+                Ok(Some(format!("NSYMBT {nsymbt} is {reason}")))
+            } else {
+                Err(invalid(reason))
+            }
+        }
+        _ => Ok(None),
+    }
+}
+
 /// Default maximum decompressed bytes for compressed MRC files (256 GiB).
 pub const DEFAULT_MAX_DECOMPRESSED_BYTES: u64 = 256 * 1024 * 1024 * 1024;
 
 /// Components of a decompressed MRC file.
+#[cfg_attr(
+    not(any(feature = "gzip", feature = "bzip2", feature = "tiff")),
+    allow(dead_code)
+)]
 pub(crate) struct DecompressedMrc {
     pub header: crate::Header,
     pub ext_header: Vec<u8>,
-    pub data: Vec<u8>,
+    pub data: crate::AlignedBuf,
     pub warnings: Vec<String>,
 }
 
 /// Open a compressed MRC file from a decoder.
+#[cfg_attr(not(any(feature = "gzip", feature = "bzip2")), allow(dead_code))]
 pub(crate) fn open_compressed<D: std::io::Read>(
     mut decoder: D,
     permissive: bool,
@@ -511,31 +617,13 @@ pub(crate) fn open_compressed<D: std::io::Read>(
 
     let mut header_bytes = [0u8; 1024];
     header_bytes.copy_from_slice(&buf[..1024]);
-    let (header, mut warnings, _endian, data_size) = parse_header(&header_bytes, permissive)?;
-    let ext_size = header.nsymbt as usize;
-
-    if !permissive {
-        if buf.len() != 1024 + ext_size + data_size {
-            return Err(crate::Error::FileSizeMismatch {
-                expected: 1024 + ext_size + data_size,
-                actual: buf.len(),
-            });
-        }
-    } else if buf.len() != 1024 + ext_size + data_size {
-        warnings.push(format!(
-            "File size mismatch: expected {} bytes, got {}",
-            1024 + ext_size + data_size,
-            buf.len(),
-        ));
-    }
+    let (header, mut warnings, _endian, _data_size) =
+        parse_header(&header_bytes, buf.len() as u64, permissive)?;
+    let ext_size = header.nsymbt.max(0) as usize;
 
     let ext_end = (1024 + ext_size).min(buf.len());
     let ext_header = buf[1024..ext_end].to_vec();
-    let data = if ext_end < buf.len() {
-        buf[ext_end..].to_vec()
-    } else {
-        Vec::new()
-    };
+    let data = crate::AlignedBuf::from(&buf[ext_end..]);
 
     if let Some(mode) = Mode::from_i32(header.mode) {
         if mode == Mode::Int8 {
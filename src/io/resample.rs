@@ -0,0 +1,145 @@
+//! Resampling a map onto another map's grid.
+//!
+//! Maps from different pipelines rarely share a grid: pixel sizes, box
+//! sizes and origins all differ. [`Reader::resample_onto`] interpolates a
+//! map at the voxel centres of a reference header so the two can then be
+//! compared or combined voxel by voxel.
+//!
+//! The physical position of voxel index `i` along an axis is
+//! `origin + i · apix` when the header's origin is set (any component
+//! non-zero), and `(nstart + i) · apix` otherwise, in Å — the rule UCSF
+//! ChimeraX applies when opening MRC files.
+//!
+//! [`Reader::resample_onto`]: crate::Reader::resample_onto
+
+use crate::{Error, Header, VoxelBlock};
+
+impl crate::Reader {
+    /// Trilinearly interpolate this map onto the grid of `reference`.
+    ///
+    /// The result has `reference`'s dimensions, voxel size and origin;
+    /// positions that fall outside this map are 0. The returned header is
+    /// `reference` with mode 2, no extended header and the density
+    /// statistics of the resampled data.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let ours = mrc::Reader::open("ours_1.06A.mrc")?;
+    /// let theirs = mrc::Reader::open("theirs_0.83A.mrc")?;
+    /// let (header, data) = ours.resample_onto(theirs.header())?;
+    /// assert_eq!(header.voxel_size(), theirs.header().voxel_size());
+    /// # let _ = data;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with
+    /// [`InvalidInput`](std::io::ErrorKind::InvalidInput) if either header
+    /// has a zero voxel size or an axis order other than X, Y, Z
+    /// (`mapc/mapr/maps = 1/2/3`), and propagates any error from reading
+    /// this map.
+    pub fn resample_onto(&self, reference: &Header) -> Result<(Header, VoxelBlock<f32>), Error> {
+        let from = Grid::of(self.header())?;
+        let to = Grid::of(reference)?;
+        let s = self.shape();
+        let src = self.convert::<f32>().read_volume()?.data;
+        let dims = [s.nx, s.ny, s.nz];
+
+        let out_dims = [reference.nx, reference.ny, reference.nz].map(|n| n.max(0) as usize);
+        let mut data = Vec::with_capacity(out_dims.iter().product());
+        for k in 0..out_dims[2] {
+            for j in 0..out_dims[1] {
+                for i in 0..out_dims[0] {
+                    let p = from.index_of(to.position_of([i, j, k]));
+                    data.push(trilinear(&src, dims, p));
+                }
+            }
+        }
+
+        let mut header = *reference;
+        header.mode = crate::Mode::Float32.as_i32();
+        header.nsymbt = 0;
+        (header.dmin, header.dmax, header.dmean, header.rms) = super::compare::summary(&data);
+        Ok((header, VoxelBlock::new([0, 0, 0], out_dims, data)?))
+    }
+}
+
+/// Index-to-Å mapping of one header's grid.
+struct Grid {
+    start: [f64; 3],
+    apix: [f64; 3],
+}
+
+impl Grid {
+    fn of(h: &Header) -> Result<Self, Error> {
+        if (h.mapc, h.mapr, h.maps) != (1, 2, 3) {
+            return Err(Error::invalid_input(format!(
+                "resampling needs X, Y, Z axis order, got mapc/mapr/maps = {}/{}/{}",
+                h.mapc, h.mapr, h.maps
+            )));
+        }
+        let apix = h.voxel_size().map(f64::from);
+        if apix.iter().any(|&a| !(a.is_finite() && a > 0.0)) {
+            return Err(Error::invalid_input(format!(
+                "resampling needs a positive voxel size, got {apix:?}"
+            )));
+        }
+        let start = if h.origin != [0.0; 3] {
+            h.origin.map(f64::from)
+        } else {
+            let n = h.nstart();
+            [0, 1, 2].map(|a| f64::from(n[a]) * apix[a])
+        };
+        Ok(Self { start, apix })
+    }
+
+    fn position_of(&self, index: [usize; 3]) -> [f64; 3] {
+        [0, 1, 2].map(|a| self.start[a] + index[a] as f64 * self.apix[a])
+    }
+
+    fn index_of(&self, position: [f64; 3]) -> [f64; 3] {
+        [0, 1, 2].map(|a| (position[a] - self.start[a]) / self.apix[a])
+    }
+}
+
+/// How far outside the grid, in voxels, a position may fall and still be
+/// read from the edge voxel.
+const EDGE_SLACK: f64 = 1e-6;
+
+/// Trilinear interpolation at fractional index `p`; 0 outside the volume.
+fn trilinear(src: &[f32], [nx, ny, nz]: [usize; 3], p: [f64; 3]) -> f32 {
+    let n = [nx, ny, nz];
+    let mut base = [0usize; 3];
+    let mut frac = [0f64; 3];
+    for a in 0..3 {
+        let last = (n[a] - 1) as f64;
+        // Allow for rounding in the index arithmetic at the edges.
+        if !(-EDGE_SLACK..=last + EDGE_SLACK).contains(&p[a]) {
+            return 0.0;
+        }
+        let q = p[a].clamp(0.0, last);
+        // Step back from the last voxel so `base + 1` stays in range.
+        let b = q.floor().min((last - 1.0).max(0.0));
+        base[a] = b as usize;
+        frac[a] = q - b;
+    }
+    let at = |x: usize, y: usize, z: usize| {
+        let (x, y, z) = (x.min(nx - 1), y.min(ny - 1), z.min(nz - 1));
+        f64::from(src[(z * ny + y) * nx + x])
+    };
+    let [x, y, z] = base;
+    let [fx, fy, fz] = frac;
+    let lerp = |a: f64, b: f64, t: f64| a + (b - a) * t;
+    let plane = |z| {
+        lerp(
+            lerp(at(x, y, z), at(x + 1, y, z), fx),
+            lerp(at(x, y + 1, z), at(x + 1, y + 1, z), fx),
+            fy,
+        )
+    };
+    lerp(plane(z), plane(z + 1), fz) as f32
+}
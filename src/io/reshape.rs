@@ -0,0 +1,308 @@
+//! Changing the box an in-memory volume is stored in.
+//!
+//! Single-particle pipelines move maps between box sizes all the time, and
+//! each move has to keep the map where it was in space: the voxel at the
+//! centre of the old box (index `n / 2` along each axis, the Fourier
+//! convention) ends up at the centre of the new one, and the header's
+//! `nxstart`/`nystart`/`nzstart` and origin are shifted to match.
+//! [`MrcBuf::flip`] and [`MrcBuf::rotate90`] reorient a map within its
+//! box the same way, and [`MrcBuf::to_canonical_order`] undoes a
+//! non-standard axis order.
+
+use crate::engine::convert::{pack_u8_to_u4_bytes, unpack_u4_bytes_to_u8};
+use crate::{Error, MrcBuf, OwnedData};
+
+impl MrcBuf {
+    /// A copy enlarged to `shape` (`[nx, ny, nz]`), centred, with the new
+    /// voxels set to `fill` (converted as by [`OwnedData::fill`]).
+    ///
+    /// The voxel size is kept: `mx`/`my`/`mz` and the cell grow with the
+    /// box where they matched it. Density statistics are recomputed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrc::{Header, MrcBuf};
+    ///
+    /// let mut header = Header::new();
+    /// (header.nx, header.ny, header.nz) = (2, 2, 2);
+    /// (header.mx, header.my, header.mz) = (2, 2, 2);
+    /// (header.xlen, header.ylen, header.zlen) = (2.0, 2.0, 2.0);
+    /// let buf = MrcBuf::new(header)?;
+    ///
+    /// let padded = buf.pad_to([4, 4, 4], 0.0)?;
+    /// assert_eq!(padded.header().voxel_size(), [1.0; 3]);
+    /// assert_eq!(padded.header().nxstart, -1);
+    /// # Ok::<(), mrc::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] of kind `InvalidInput` if `shape` is smaller
+    /// than the current box along any axis.
+    pub fn pad_to(&self, shape: [usize; 3], fill: f32) -> Result<MrcBuf, Error> {
+        let old = self.dims();
+        if (0..3).any(|i| shape[i] < old[i]) {
+            return Err(Error::invalid_input(format!(
+                "cannot pad a {old:?} box to the smaller {shape:?}"
+            )));
+        }
+        Ok(self.recentre(shape, fill))
+    }
+
+    /// A copy cut down to the central `shape` (`[nx, ny, nz]`).
+    ///
+    /// The header is updated as by [`pad_to`](Self::pad_to).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] of kind `InvalidInput` if `shape` is zero or
+    /// larger than the current box along any axis.
+    pub fn crop_center(&self, shape: [usize; 3]) -> Result<MrcBuf, Error> {
+        let old = self.dims();
+        if (0..3).any(|i| shape[i] == 0 || shape[i] > old[i]) {
+            return Err(Error::invalid_input(format!(
+                "cannot crop a {old:?} box to {shape:?}"
+            )));
+        }
+        Ok(self.recentre(shape, 0.0))
+    }
+
+    /// A copy mirrored along `axis`, in place in its box.
+    ///
+    /// The header geometry is unchanged, but the map's handedness is
+    /// inverted: flipping is how a reconstruction of the wrong hand is
+    /// corrected, and flipping a correct one breaks it.
+    pub fn flip(&self, axis: crate::Axis) -> MrcBuf {
+        let (a, n) = (axis.index(), self.dims()[axis.index()]);
+        let data = self.remap(self.dims(), 0.0, |mut p| {
+            p[a] = n - 1 - p[a];
+            Some(p)
+        });
+        MrcBuf::from_parts(*self.header(), self.ext_header().to_vec(), data)
+    }
+
+    /// A copy turned `quarter_turns` × 90° about `axis`, right-handed
+    /// (a quarter turn about Z takes X to Y), around the centre of the box.
+    ///
+    /// Negative counts turn the other way. Handedness is preserved. An odd
+    /// number of turns exchanges the box's extent, sampling, cell length
+    /// and voxel size between the other two axes; `n*start` and the origin
+    /// are moved so the box keeps its centre, to the nearest voxel for
+    /// `n*start` when the two extents differ in parity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrc::{Axis, Header, MrcBuf};
+    ///
+    /// let mut header = Header::new();
+    /// (header.nx, header.ny, header.nz) = (2, 1, 1);
+    /// (header.mx, header.my, header.mz) = (2, 1, 1);
+    /// let mut buf = MrcBuf::new(header)?;
+    /// buf.data_as_mut::<f32>()?.copy_from_slice(&[1.0, 2.0]);
+    ///
+    /// let turned = buf.rotate90(Axis::Z, 1);
+    /// assert_eq!(turned.shape().ny, 2);
+    /// assert_eq!(turned.data_as::<f32>()?, &[1.0, 2.0]);
+    /// assert_eq!(buf.rotate90(Axis::Z, 2).data_as::<f32>()?, &[2.0, 1.0]);
+    /// # Ok::<(), mrc::Error>(())
+    /// ```
+    pub fn rotate90(&self, axis: crate::Axis, quarter_turns: i32) -> MrcBuf {
+        // The two axes turned, in right-handed order.
+        let (b, c) = match axis {
+            crate::Axis::X => (1, 2),
+            crate::Axis::Y => (2, 0),
+            crate::Axis::Z => (0, 1),
+        };
+        let old = self.dims();
+        let (nb, nc) = (old[b], old[c]);
+        let turns = quarter_turns.rem_euclid(4);
+        let mut shape = old;
+        if turns % 2 == 1 {
+            shape.swap(b, c);
+        }
+        let data = self.remap(shape, 0.0, |p| {
+            let mut q = p;
+            match turns {
+                1 => (q[b], q[c]) = (p[c], nc - 1 - p[b]),
+                2 => (q[b], q[c]) = (nb - 1 - p[b], nc - 1 - p[c]),
+                3 => (q[b], q[c]) = (nb - 1 - p[c], p[b]),
+                _ => {}
+            }
+            Some(q)
+        });
+
+        let mut h = *self.header();
+        if turns % 2 == 1 {
+            let axes = h.storage_axes();
+            let (ab, ac) = (axes[b], axes[c]);
+            let apix = h.voxel_size();
+            let mut sampling = [h.mx, h.my, h.mz];
+            let mut cell = [h.xlen, h.ylen, h.zlen];
+            let mut start = [h.nxstart, h.nystart, h.nzstart];
+            sampling.swap(ab, ac);
+            cell.swap(ab, ac);
+            let half_diff = (nb as i32 - nc as i32).div_euclid(2);
+            start[b] += half_diff;
+            start[c] -= half_diff;
+            if h.origin != [0.0; 3] {
+                let centre = |k: usize, n: usize| h.origin[k] + (n as f32 - 1.0) / 2.0 * apix[k];
+                let (cb, cc) = (centre(ab, nb), centre(ac, nc));
+                h.origin[ab] = cb - (nc as f32 - 1.0) / 2.0 * apix[ac];
+                h.origin[ac] = cc - (nb as f32 - 1.0) / 2.0 * apix[ab];
+            }
+            [h.nx, h.ny, h.nz] = shape.map(|n| n as i32);
+            [h.mx, h.my, h.mz] = sampling;
+            [h.xlen, h.ylen, h.zlen] = cell;
+            [h.nxstart, h.nystart, h.nzstart] = start;
+        }
+        MrcBuf::from_parts(h, self.ext_header().to_vec(), data)
+    }
+
+    /// A copy stored X fastest, then Y, then Z, with `mapc/mapr/maps`
+    /// reset to 1/2/3.
+    ///
+    /// [`Volume::get_logical`](crate::Volume::get_logical) reads a
+    /// permuted file in spatial coordinates without moving anything; this
+    /// moves the voxels once so later code can assume the usual layout.
+    /// Dimensions and `n*start` follow their axes; the cell, sampling and
+    /// origin are already per spatial axis and stay as they are. A header
+    /// whose mapping is already 1/2/3, or is not a permutation of 1, 2, 3,
+    /// is returned unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let reader = mrc::Reader::open("zyx_ordered.map")?;
+    /// let buf = mrc::MrcBuf::from_reader(&reader)?.to_canonical_order();
+    /// assert_eq!([buf.header().mapc, buf.header().mapr, buf.header().maps], [1, 2, 3]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_canonical_order(&self) -> MrcBuf {
+        let axes = self.header().storage_axes();
+        if axes == [0, 1, 2] {
+            return self.clone();
+        }
+        let old = self.dims();
+        let mut shape = [0; 3];
+        let mut h = *self.header();
+        let old_start = [h.nxstart, h.nystart, h.nzstart];
+        let mut start = [0; 3];
+        for (i, &a) in axes.iter().enumerate() {
+            shape[a] = old[i];
+            start[a] = old_start[i];
+        }
+        let data = self.remap(shape, 0.0, |p| Some(axes.map(|a| p[a])));
+        [h.nx, h.ny, h.nz] = shape.map(|n| n as i32);
+        [h.nxstart, h.nystart, h.nzstart] = start;
+        [h.mapc, h.mapr, h.maps] = [1, 2, 3];
+        MrcBuf::from_parts(h, self.ext_header().to_vec(), data)
+    }
+
+    /// `[nx, ny, nz]`.
+    fn dims(&self) -> [usize; 3] {
+        let s = self.shape();
+        [s.nx, s.ny, s.nz]
+    }
+
+    /// Move the data into a `shape` box keeping the `n / 2` voxel at the
+    /// centre, and shift the header geometry with it.
+    fn recentre(&self, shape: [usize; 3], fill: f32) -> MrcBuf {
+        let old = self.dims();
+        let shift: [isize; 3] =
+            std::array::from_fn(|i| (shape[i] / 2) as isize - (old[i] / 2) as isize);
+        let data = self.remap(shape, fill, |p| {
+            let q: [isize; 3] = std::array::from_fn(|i| p[i] as isize - shift[i]);
+            (0..3)
+                .all(|i| (0..old[i] as isize).contains(&q[i]))
+                .then(|| q.map(|v| v as usize))
+        });
+
+        let mut h = *self.header();
+        let axes = h.storage_axes();
+        let apix = h.voxel_size();
+        let origin_set = h.origin != [0.0; 3];
+        let mut sampling = [h.mx, h.my, h.mz];
+        let mut cell = [h.xlen, h.ylen, h.zlen];
+        let mut start = [h.nxstart, h.nystart, h.nzstart];
+        for (i, &a) in axes.iter().enumerate() {
+            if sampling[a] == old[i] as i32 {
+                sampling[a] = shape[i] as i32;
+                cell[a] = apix[a] * shape[i] as f32;
+            }
+            start[i] -= shift[i] as i32;
+            if origin_set {
+                h.origin[a] -= shift[i] as f32 * apix[a];
+            }
+        }
+        [h.nx, h.ny, h.nz] = shape.map(|n| n as i32);
+        [h.mx, h.my, h.mz] = sampling;
+        [h.xlen, h.ylen, h.zlen] = cell;
+        [h.nxstart, h.nystart, h.nzstart] = start;
+        let mut buf = MrcBuf::from_parts(h, self.ext_header().to_vec(), data);
+        buf.update_header_stats();
+        buf
+    }
+
+    /// Data for a `shape` box whose voxel `[x, y, z]` is copied from
+    /// `source([x, y, z])` in this one, or set to `fill` where that is
+    /// `None`.
+    pub(crate) fn remap(
+        &self,
+        shape: [usize; 3],
+        fill: f32,
+        source: impl Fn([usize; 3]) -> Option<[usize; 3]>,
+    ) -> OwnedData {
+        let old = self.dims();
+        let n: usize = shape.iter().product();
+        let index = |o: usize| {
+            let p = [
+                o % shape[0],
+                o / shape[0] % shape[1],
+                o / (shape[0] * shape[1]),
+            ];
+            source(p).map(|[x, y, z]| (z * old[1] + y) * old[0] + x)
+        };
+        fn gather<T: Copy>(src: &[T], dst: &mut [T], index: impl Fn(usize) -> Option<usize>) {
+            for (o, d) in dst.iter_mut().enumerate() {
+                if let Some(i) = index(o) {
+                    *d = src[i];
+                }
+            }
+        }
+
+        if let OwnedData::Packed4Bit(v) = self.owned_data() {
+            let src = unpack_u4_bytes_to_u8(v, old[0], old[1] * old[2]);
+            let mut dst = vec![(fill as u8).min(15); n];
+            gather(&src, &mut dst, index);
+            return OwnedData::Packed4Bit(pack_u8_to_u4_bytes(&dst, shape[0], shape[1] * shape[2]));
+        }
+        let mut out = match self.owned_data() {
+            OwnedData::Int8(_) => OwnedData::Int8(vec![0; n]),
+            OwnedData::Int16(_) => OwnedData::Int16(vec![0; n]),
+            OwnedData::Float32(_) => OwnedData::Float32(vec![0.0; n]),
+            OwnedData::Int16Complex(_) => OwnedData::Int16Complex(vec![Default::default(); n]),
+            OwnedData::Float32Complex(_) => OwnedData::Float32Complex(vec![Default::default(); n]),
+            OwnedData::Uint16(_) => OwnedData::Uint16(vec![0; n]),
+            #[cfg(feature = "f16")]
+            OwnedData::Float16(_) => OwnedData::Float16(vec![crate::f16::ZERO; n]),
+            OwnedData::Packed4Bit(_) => OwnedData::Packed4Bit(Vec::new()),
+        };
+        out.fill(fill);
+        match (self.owned_data(), &mut out) {
+            (OwnedData::Int8(s), OwnedData::Int8(d)) => gather(s, d, index),
+            (OwnedData::Int16(s), OwnedData::Int16(d)) => gather(s, d, index),
+            (OwnedData::Float32(s), OwnedData::Float32(d)) => gather(s, d, index),
+            (OwnedData::Int16Complex(s), OwnedData::Int16Complex(d)) => gather(s, d, index),
+            (OwnedData::Float32Complex(s), OwnedData::Float32Complex(d)) => gather(s, d, index),
+            (OwnedData::Uint16(s), OwnedData::Uint16(d)) => gather(s, d, index),
+            #[cfg(feature = "f16")]
+            (OwnedData::Float16(s), OwnedData::Float16(d)) => gather(s, d, index),
+            _ => {}
+        }
+        out
+    }
+}
@@ -0,0 +1,129 @@
+//! Sharing one decoded volume between processes through shared memory.
+//!
+//! A pipeline where one process decompresses or converts a large volume
+//! and several others analyse it would otherwise hold one private copy per
+//! process. [`Reader::share`] writes the volume once into POSIX shared
+//! memory as a complete, native-endian MRC image, and
+//! [`Reader::open_shared`] maps that image by name in any process, so every
+//! reader borrows the same physical pages without copying or byte-swapping.
+//!
+//! Segments live in `/dev/shm`, which is where `shm_open` keeps them on
+//! Linux, so they can also be inspected or removed with ordinary file
+//! tools. They outlive the processes that created them until
+//! [`remove_shared`] is called or the machine reboots; readers that already
+//! have a segment open keep their mapping after it is removed.
+//!
+//! Requires the `shm` feature (disabled by default) and Linux.
+//!
+//! [`Reader::share`]: crate::Reader::share
+//! [`Reader::open_shared`]: crate::Reader::open_shared
+
+use crate::{Error, FileEndian, Reader};
+
+use std::path::PathBuf;
+
+/// Directory that backs POSIX shared memory on Linux.
+const SHM_DIR: &str = "/dev/shm";
+
+/// Path of the segment called `name`, after checking that the name is a
+/// single non-empty path component, as `shm_open` requires.
+fn segment_path(name: &str) -> Result<PathBuf, Error> {
+    let valid = !name.is_empty()
+        && name != "."
+        && name != ".."
+        && !name.contains('/')
+        && !name.contains('\0');
+    if !valid {
+        return Err(Error::invalid_input(format!(
+            "invalid shared-memory name {name:?}"
+        )));
+    }
+    Ok(PathBuf::from(SHM_DIR).join(name))
+}
+
+impl Reader {
+    /// Publish this volume in shared memory under `name`.
+    ///
+    /// The header, extended header and voxel data are written as they
+    /// would be by [`encoded`](Self::encoded) in the host's byte order, so
+    /// readers of the segment get zero-copy typed views. The image is
+    /// written under a temporary name and renamed into place, so
+    /// [`open_shared`](Self::open_shared) never sees a partial volume; an
+    /// existing segment of the same name is replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// // Decoder process: decompress once.
+    /// mrc::Reader::open("tomogram.mrc.gz")?.share("tomo-42")?;
+    ///
+    /// // Any number of analysis processes.
+    /// let reader = mrc::Reader::open_shared("tomo-42")?;
+    /// let vol = reader.volume::<f32>()?;
+    /// # let _ = vol;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with
+    /// [`InvalidInput`](std::io::ErrorKind::InvalidInput) if `name` is
+    /// empty or contains `/`, and any error from writing the segment, such
+    /// as running out of space in `/dev/shm`.
+    pub fn share(&self, name: &str) -> Result<(), Error> {
+        let path = segment_path(name)?;
+        let tmp = segment_path(&format!(".{name}.{}.tmp", std::process::id()))?;
+        let written = std::fs::File::create(&tmp).and_then(|mut file| {
+            std::io::copy(&mut self.encoded(FileEndian::native()), &mut file)?;
+            std::fs::rename(&tmp, &path)
+        });
+        if written.is_err() {
+            let _ = std::fs::remove_file(&tmp);
+        }
+        Ok(written?)
+    }
+
+    /// Open the volume another process published with
+    /// [`share`](Self::share), by memory-mapping its segment.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with
+    /// [`NotFound`](std::io::ErrorKind::NotFound) when no segment has that
+    /// name, and otherwise the errors of [`open`](Self::open).
+    pub fn open_shared(name: &str) -> Result<Reader, Error> {
+        crate::OpenOptions::new()
+            .mmap(true)
+            .open(segment_path(name)?)
+    }
+}
+
+/// Remove the shared-memory segment `name`, like `shm_unlink`.
+///
+/// Processes that have it open keep reading it; the memory is released
+/// when the last of them drops its reader.
+///
+/// # Errors
+///
+/// Returns [`Error::Io`] if `name` is invalid or no segment has that name.
+pub fn remove_shared(name: &str) -> Result<(), Error> {
+    Ok(std::fs::remove_file(segment_path(name)?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_names_that_are_not_one_component() {
+        for name in ["", ".", "..", "a/b", "/abs"] {
+            assert!(segment_path(name).is_err(), "{name:?}");
+        }
+        assert_eq!(
+            segment_path("tomo-42").unwrap(),
+            PathBuf::from("/dev/shm/tomo-42")
+        );
+    }
+}
@@ -0,0 +1,199 @@
+//! Backend-independent read access.
+//!
+//! [`MrcSource`] is the read surface shared by every way of holding an MRC
+//! file. [`Reader`](crate::Reader) implements it for all of its backends —
+//! memory-mapped files, buffered files, decompressed gzip/bzip2 streams and
+//! in-memory buffers — and so does [`MrcBuf`](crate::MrcBuf) for volumes
+//! built in memory. Smart pointers to a source are sources too, so pipeline
+//! code can be written once against `&dyn MrcSource` or a generic bound. Third-party backends (object stores, caches) can implement it as
+//! well.
+//!
+//! ```
+//! use mrc::{MrcSource, DataView};
+//!
+//! fn first_voxel(src: &dyn MrcSource) -> Result<f32, mrc::Error> {
+//!     match src.read_section(0)?.data() {
+//!         DataView::Float32(v) => Ok(v[0]),
+//!         _ => Err(mrc::Error::UnsupportedMode),
+//!     }
+//! }
+//! # let mut h = mrc::Header::new();
+//! # h.nx = 2; h.ny = 1; h.nz = 1;
+//! # h.mx = 2; h.my = 1; h.mz = 1;
+//! # let mut raw = [0u8; 1024];
+//! # h.encode_to_bytes(&mut raw);
+//! # let buf: Vec<u8> = raw.into_iter().chain(1.5f32.to_le_bytes()).chain(2.5f32.to_le_bytes()).collect();
+//! # let reader = mrc::Reader::from_bytes(buf)?;
+//! assert_eq!(first_voxel(&reader)?, 1.5);
+//! # Ok::<_, mrc::Error>(())
+//! ```
+
+use crate::{DataBlock, Error, Header, Mode, VolumeShape};
+
+use std::sync::Arc;
+
+/// Read access to an MRC volume, independent of the storage backend.
+///
+/// Implementors provide [`header`](Self::header) and
+/// [`read_view`](Self::read_view); the remaining methods have default
+/// implementations derived from the header.
+pub trait MrcSource {
+    /// The parsed header.
+    fn header(&self) -> &Header;
+
+    /// Read the sub-block at `offset` with extent `shape` (both `[x, y, z]`),
+    /// decoded into the file's own mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::BoundsError`] if the block lies outside the volume,
+    /// or any backend error.
+    fn read_view(&self, offset: [usize; 3], shape: [usize; 3]) -> Result<DataBlock<'_>, Error>;
+
+    /// Read Z-section `z` in full.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::BoundsError`] if `z` is past the last section.
+    fn read_section(&self, z: usize) -> Result<DataBlock<'_>, Error> {
+        let s = self.shape();
+        self.read_view([0, 0, z], [s.nx, s.ny, 1])
+    }
+
+    /// Volume dimensions from the header.
+    fn shape(&self) -> VolumeShape {
+        let h = self.header();
+        VolumeShape::new(
+            h.nx.max(0) as usize,
+            h.ny.max(0) as usize,
+            h.nz.max(0) as usize,
+        )
+    }
+
+    /// Voxel mode from the header, or `None` if it is not a known mode.
+    fn mode(&self) -> Option<Mode> {
+        Mode::from_i32(self.header().mode)
+    }
+
+    /// Number of voxels (`nx * ny * nz`, saturating).
+    fn len(&self) -> usize {
+        let s = self.shape();
+        s.nx.saturating_mul(s.ny).saturating_mul(s.nz)
+    }
+
+    /// `true` if the volume has no voxels.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl MrcSource for crate::Reader {
+    #[inline]
+    fn header(&self) -> &Header {
+        &self.header
+    }
+
+    #[inline]
+    fn read_view(&self, offset: [usize; 3], shape: [usize; 3]) -> Result<DataBlock<'_>, Error> {
+        self.subregion(offset, shape)
+    }
+
+    #[inline]
+    fn shape(&self) -> VolumeShape {
+        self.shape
+    }
+
+    #[inline]
+    fn mode(&self) -> Option<Mode> {
+        Some(self.mode)
+    }
+}
+
+impl MrcSource for crate::MrcBuf {
+    #[inline]
+    fn header(&self) -> &Header {
+        crate::MrcBuf::header(self)
+    }
+
+    /// Whole XY sections borrow the buffer; other blocks are copied out.
+    fn read_view(&self, offset: [usize; 3], shape: [usize; 3]) -> Result<DataBlock<'_>, Error> {
+        let s = crate::MrcBuf::shape(self);
+        let volume = [s.nx, s.ny, s.nz];
+        if (0..3).any(|i| {
+            offset[i]
+                .checked_add(shape[i])
+                .is_none_or(|end| end > volume[i])
+        }) {
+            return Err(Error::BoundsError {
+                offset: Some(offset),
+                shape: Some(shape),
+                volume: Some(volume),
+            });
+        }
+        if offset[..2] == [0, 0] && shape[..2] == volume[..2] {
+            return Ok(DataBlock::Borrowed {
+                offset,
+                shape,
+                data: self.sections(offset[2]..offset[2] + shape[2]),
+            });
+        }
+        let [ox, oy, oz] = offset;
+        Ok(DataBlock::Owned {
+            offset,
+            shape,
+            data: self.remap(shape, 0.0, |[x, y, z]| Some([x + ox, y + oy, z + oz])),
+        })
+    }
+
+    #[inline]
+    fn shape(&self) -> VolumeShape {
+        crate::MrcBuf::shape(self)
+    }
+
+    #[inline]
+    fn mode(&self) -> Option<Mode> {
+        Some(crate::MrcBuf::mode(self))
+    }
+}
+
+macro_rules! forward_source {
+    ($($ptr:ty),*) => {$(
+        impl<S: MrcSource + ?Sized> MrcSource for $ptr {
+            #[inline]
+            fn header(&self) -> &Header {
+                (**self).header()
+            }
+
+            #[inline]
+            fn read_view(
+                &self,
+                offset: [usize; 3],
+                shape: [usize; 3],
+            ) -> Result<DataBlock<'_>, Error> {
+                (**self).read_view(offset, shape)
+            }
+
+            #[inline]
+            fn read_section(&self, z: usize) -> Result<DataBlock<'_>, Error> {
+                (**self).read_section(z)
+            }
+
+            #[inline]
+            fn shape(&self) -> VolumeShape {
+                (**self).shape()
+            }
+
+            #[inline]
+            fn mode(&self) -> Option<Mode> {
+                (**self).mode()
+            }
+
+            #[inline]
+            fn len(&self) -> usize {
+                (**self).len()
+            }
+        }
+    )*};
+}
+
+forward_source!(&S, Box<S>, Arc<S>);
@@ -0,0 +1,117 @@
+//! Splitting a volume stack into its volumes.
+//!
+//! A volume stack (`ispg` 401–630) stores several 3D volumes of `mz`
+//! sections each, one after another along Z, so the file's own header
+//! describes a single `nx × ny × nz` block. [`Reader::stack_volume`] and
+//! [`Reader::stack_volumes`] hand out the volumes one at a time, each with a
+//! header that describes that volume alone.
+//!
+//! [`Reader::stack_volume`]: crate::Reader::stack_volume
+//! [`Reader::stack_volumes`]: crate::Reader::stack_volumes
+
+use crate::{DataBlock, Error, Header};
+
+/// One volume of a volume stack.
+#[derive(Debug)]
+pub struct StackVolume<'a> {
+    /// Position of the volume in the stack, from 0.
+    pub index: usize,
+    /// Header for this volume on its own.
+    ///
+    /// `nz` and `mz` are the stack's `mz`, `ispg` is the per-volume space
+    /// group (`ispg - 400`, at least 1) and `nsymbt` is 0. Cell, origin,
+    /// axis mapping, labels and byte order are copied from the stack. The
+    /// density statistics describe the whole stack, so they are reset to
+    /// the "not computed" values (`dmax < dmin`, `dmean < both`, `rms < 0`).
+    pub header: Header,
+    /// The voxel data. Offsets are in stack coordinates, so Z starts at
+    /// `index * mz`.
+    pub data: DataBlock<'a>,
+}
+
+impl crate::Reader {
+    /// Read volume `index` of a volume stack.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let reader = mrc::Reader::open("subtomograms.mrc")?;
+    /// let third = reader.stack_volume(2)?;
+    /// assert_eq!(third.header.nz, reader.header().mz);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NotAVolumeStack`] if the file is not a volume stack
+    /// and [`Error::BoundsError`] if `index` is not below the number of
+    /// volumes (`logical_shape()[0]`).
+    pub fn stack_volume(&self, index: usize) -> Result<StackVolume<'_>, Error> {
+        let (count, mz) = self.stack_layout()?;
+        if index >= count {
+            return Err(Error::bounds_err());
+        }
+        let s = self.shape();
+        let data = self.subregion([0, 0, index * mz], [s.nx, s.ny, mz])?;
+        Ok(StackVolume {
+            index,
+            header: volume_header(self.header(), mz),
+            data,
+        })
+    }
+
+    /// Iterate over the volumes of a volume stack, each with its own header.
+    ///
+    /// Unlike [`volumes`](Self::volumes), sections left over when `nz` is
+    /// not a multiple of `mz` are not returned as a short final volume.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let reader = mrc::Reader::open("subtomograms.mrc")?;
+    /// for vol in reader.stack_volumes()? {
+    ///     let vol = vol?;
+    ///     println!("volume {}: {} sections", vol.index, vol.header.nz);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NotAVolumeStack`] if the file is not a volume stack.
+    /// Read errors are reported per volume by the iterator.
+    pub fn stack_volumes(
+        &self,
+    ) -> Result<impl Iterator<Item = Result<StackVolume<'_>, Error>> + '_, Error> {
+        let (count, _) = self.stack_layout()?;
+        Ok((0..count).map(move |i| self.stack_volume(i)))
+    }
+
+    /// `(number of volumes, sections per volume)`.
+    fn stack_layout(&self) -> Result<(usize, usize), Error> {
+        let h = self.header();
+        if !h.is_volume_stack() || h.mz <= 0 {
+            return Err(Error::NotAVolumeStack {
+                ispg: h.ispg,
+                mz: h.mz,
+            });
+        }
+        let [count, mz, _, _] = h.logical_shape();
+        Ok((count, mz))
+    }
+}
+
+fn volume_header(stack: &Header, mz: usize) -> Header {
+    let mut h = *stack;
+    h.nz = mz as i32;
+    h.mz = mz as i32;
+    // 400 is not a real stack space group; treat it as P1.
+    h.ispg = (h.ispg - 400).max(1);
+    h.nsymbt = 0;
+    (h.dmin, h.dmax, h.dmean, h.rms) = (0.0, -1.0, -2.0, -1.0);
+    h
+}
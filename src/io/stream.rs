@@ -0,0 +1,118 @@
+//! Streaming serialization through [`std::io::Read`].
+//!
+//! [`Reader::encoded`](crate::Reader::encoded) returns an [`EncodedStream`]
+//! that produces the bytes of a complete MRC file — header, extended header,
+//! then voxel data — as they are read, in a chosen byte order. It can be
+//! handed to [`std::io::copy`], an HTTP client body or a compressor without
+//! first building the whole file image in memory (compare
+//! [`Reader::to_bytes`](crate::Reader::to_bytes)).
+
+use crate::{FileEndian, Mode};
+
+use std::io::Read;
+
+/// A [`Read`] adapter yielding an encoded MRC file.
+///
+/// Created by [`Reader::encoded`](crate::Reader::encoded).
+#[derive(Debug, Clone)]
+pub struct EncodedStream<'a> {
+    /// Encoded header followed by the extended header.
+    head: Vec<u8>,
+    data: &'a [u8],
+    /// Bytes per swapped word, or 1 when the data is copied as stored.
+    swap_unit: usize,
+    pos: usize,
+}
+
+impl EncodedStream<'_> {
+    /// Total number of bytes the stream produces.
+    pub fn byte_len(&self) -> usize {
+        self.head.len() + self.data.len()
+    }
+
+    /// Number of bytes not yet read.
+    pub fn remaining(&self) -> usize {
+        self.byte_len() - self.pos
+    }
+}
+
+impl Read for EncodedStream<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.pos < self.head.len() {
+            let n = buf.len().min(self.head.len() - self.pos);
+            buf[..n].copy_from_slice(&self.head[self.pos..self.pos + n]);
+            self.pos += n;
+            return Ok(n);
+        }
+        let start = self.pos - self.head.len();
+        let n = buf.len().min(self.data.len() - start);
+        let src = &self.data[start..start + n];
+        if self.swap_unit <= 1 {
+            buf[..n].copy_from_slice(src);
+        } else {
+            // Byte `i` of the output comes from the mirrored position within
+            // its word; words may straddle successive `read` calls.
+            let u = self.swap_unit;
+            for (k, out) in buf[..n].iter_mut().enumerate() {
+                let i = start + k;
+                let j = i - i % u + (u - 1 - i % u);
+                *out = self.data.get(j).copied().unwrap_or(self.data[i]);
+            }
+        }
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+/// Size of the words that must be reversed to change the byte order of `mode`.
+fn swap_unit(mode: Mode) -> usize {
+    if mode == Mode::Packed4Bit {
+        1
+    } else if mode.is_complex() {
+        mode.byte_size() / 2
+    } else {
+        mode.byte_size()
+    }
+}
+
+impl crate::Reader {
+    /// Stream the file image in `endian` byte order.
+    ///
+    /// The header is re-stamped for `endian` and voxel data is byte-swapped
+    /// on the fly when it differs from the file's own order; otherwise the
+    /// output matches [`to_bytes`](Self::to_bytes). The extended header is
+    /// opaque and copied unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use mrc::FileEndian;
+    ///
+    /// let reader = mrc::Reader::open("density.mrc")?;
+    /// let mut out = std::fs::File::create("density_be.mrc")?;
+    /// std::io::copy(&mut reader.encoded(FileEndian::BigEndian), &mut out)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn encoded(&self, endian: FileEndian) -> EncodedStream<'_> {
+        let mut header = self.header;
+        header.set_file_endian(endian);
+        let mut raw = [0u8; 1024];
+        header.encode_to_bytes(&mut raw);
+        let ext = self.ext_header_bytes();
+        let mut head = Vec::with_capacity(1024 + ext.len());
+        head.extend_from_slice(&raw);
+        head.extend_from_slice(ext);
+        EncodedStream {
+            head,
+            data: self._source_data(),
+            swap_unit: if endian == self.endian {
+                1
+            } else {
+                swap_unit(self.mode)
+            },
+            pos: 0,
+        }
+    }
+}
@@ -0,0 +1,73 @@
+//! Memory tuning for chunked whole-volume routines.
+//!
+//! Routines that walk an entire volume — [`Reader::data_stats_with`],
+//! [`Writer::update_header_stats_with`],
+//! [`ConvertReader::chunks`](crate::ConvertReader::chunks) and
+//! [`ChunkedProcessor`](crate::ChunkedProcessor) — process it a
+//! chunk of whole Z-sections (or voxels) at a time. [`StreamingConfig`]
+//! sets how large those chunks are and caps the memory they keep resident,
+//! so the same code can run on a small workstation and a large node.
+//!
+//! [`Reader::data_stats_with`]: crate::Reader::data_stats_with
+//! [`Writer::update_header_stats_with`]: crate::Writer::update_header_stats_with
+
+/// Chunk size and memory ceiling for streaming routines.
+///
+/// ```
+/// use mrc::StreamingConfig;
+///
+/// let small = StreamingConfig {
+///     chunk_bytes: 8 << 20,
+///     max_resident_bytes: 32 << 20,
+/// };
+/// let default = StreamingConfig::default();
+/// assert!(small.chunk_bytes < default.chunk_bytes);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamingConfig {
+    /// Preferred amount of raw file data handled per step, in bytes.
+    pub chunk_bytes: usize,
+    /// Upper bound on the buffers a routine keeps alive at once — raw
+    /// chunk plus decoded copy — in bytes. Chunks shrink to fit; a single
+    /// voxel row or section that is larger on its own is still processed
+    /// whole.
+    pub max_resident_bytes: usize,
+}
+
+impl StreamingConfig {
+    /// Default [`chunk_bytes`](Self::chunk_bytes): 64 MiB.
+    pub const DEFAULT_CHUNK_BYTES: usize = 64 << 20;
+    /// Default [`max_resident_bytes`](Self::max_resident_bytes): 256 MiB.
+    pub const DEFAULT_MAX_RESIDENT_BYTES: usize = 256 << 20;
+
+    /// Chunk length in bytes for a routine that needs `resident_per_byte`
+    /// bytes of memory per raw byte, rounded down to a multiple of `unit`
+    /// and never below one unit.
+    pub(crate) fn chunk_len(&self, unit: usize, resident_per_byte: usize) -> usize {
+        let unit = unit.max(1);
+        let cap = self.max_resident_bytes / resident_per_byte.max(1);
+        let len = self.chunk_bytes.min(cap);
+        (len / unit).max(1) * unit
+    }
+}
+
+impl Default for StreamingConfig {
+    fn default() -> Self {
+        Self {
+            chunk_bytes: Self::DEFAULT_CHUNK_BYTES,
+            max_resident_bytes: Self::DEFAULT_MAX_RESIDENT_BYTES,
+        }
+    }
+}
+
+/// Chunk length for statistics: whole voxels (whole rows for 4-bit data),
+/// sized for the raw chunk plus its decoded copy.
+pub(crate) fn stats_chunk_len(config: &StreamingConfig, mode: crate::Mode, nx: usize) -> usize {
+    match mode {
+        // Unpacked to one byte per voxel.
+        crate::Mode::Packed4Bit => config.chunk_len(nx.div_ceil(2), 3),
+        // Decoded to f16, then widened to f32.
+        crate::Mode::Float16 => config.chunk_len(2, 4),
+        m => config.chunk_len(m.byte_size(), 2),
+    }
+}
@@ -0,0 +1,57 @@
+//! New files shaped like an existing one.
+//!
+//! Filtering, masking or segmenting a map usually produces a result with
+//! the input's dimensions, mode, voxel size, origin, symmetry and extended
+//! header. [`Reader::clone_structure_to`] creates that file in one call,
+//! with an all-zero data block ready to be filled.
+//!
+//! [`Reader::clone_structure_to`]: crate::Reader::clone_structure_to
+
+use crate::{Error, Reader, Writer};
+
+impl Reader {
+    /// Create a file at `path` with this reader's header and extended
+    /// header and a zero-filled data block of the full size, and return a
+    /// [`Writer`] on it.
+    ///
+    /// The data block is made by extending the file rather than writing
+    /// zeros, so on filesystems that support it the file is sparse and
+    /// creating it is instant whatever its size. The new file keeps this
+    /// file's byte order and header as is, statistics and labels included;
+    /// call [`update_header_stats`](Writer::update_header_stats) after
+    /// writing new data, and [`finalize`](Writer::finalize) in any case.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let input = mrc::Reader::open("map.mrc")?;
+    /// let mut output = input.clone_structure_to("masked.mrc")?;
+    /// for slice in input.convert::<f32>().slices() {
+    ///     let mut block = slice?;
+    ///     block.data.iter_mut().for_each(|v| *v = v.max(0.0));
+    ///     output.write_block_as(&block)?;
+    /// }
+    /// output.update_header_stats()?;
+    /// output.finalize()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidHeaderDetailed`] if the header does not pass
+    /// validation for writing, and [`Error::Io`] if the file cannot be
+    /// created or extended.
+    pub fn clone_structure_to<P: AsRef<std::path::Path>>(&self, path: P) -> Result<Writer, Error> {
+        let path = path.as_ref();
+        let data_size = self.header.data_size_u64().ok_or(Error::InvalidHeader)?;
+        let writer = Writer::create(path, self.header, self.ext_header_bytes(), self.endian)?;
+        let len = self.header.data_offset() as u64 + data_size;
+        std::fs::OpenOptions::new()
+            .write(true)
+            .open(path)?
+            .set_len(len)?;
+        Ok(writer)
+    }
+}
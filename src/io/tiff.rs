@@ -0,0 +1,192 @@
+//! Multi-page TIFF import.
+//!
+//! Camera software commonly writes movies and image stacks as multi-page
+//! grayscale TIFF. [`Reader::open_tiff`](crate::Reader::open_tiff) decodes
+//! every page into memory and presents the result as an in-memory MRC image
+//! stack (`ispg = 0`, one page per section) with a constructed header, so
+//! the usual iteration and conversion APIs apply unchanged.
+//!
+//! | TIFF sample type | MRC mode |
+//! |------------------|----------|
+//! | `u8` | [`Mode::Uint16`] (widened, lossless) |
+//! | `i8` | [`Mode::Int8`] |
+//! | `i16` | [`Mode::Int16`] |
+//! | `u16` | [`Mode::Uint16`] |
+//! | `f16` | [`Mode::Float16`] (or [`Mode::Float32`] without the `f16` feature) |
+//! | `f32` | [`Mode::Float32`] |
+//!
+//! Colour, palette and wider integer/float pages are rejected with
+//! [`Error::UnsupportedMode`].
+//!
+//! Requires the `tiff` feature (disabled by default).
+
+use crate::io::reader_common::DecompressedMrc;
+use crate::{Error, FileEndian, Header, Mode};
+
+use std::fs::File;
+use std::io::{BufReader, Read, Seek};
+use std::path::Path;
+
+use tiff::ColorType;
+use tiff::decoder::{Decoder, DecodingResult};
+
+impl crate::Reader {
+    /// Import a multi-page grayscale TIFF as an in-memory MRC image stack.
+    ///
+    /// Every page must share the same dimensions and sample type. The
+    /// constructed header has `nx`/`ny` from the page size, `nz` equal to
+    /// the page count, a 1 Å/pixel cell, and density statistics computed
+    /// from the decoded data.
+    ///
+    /// Requires the `tiff` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the file cannot be read or is not a valid
+    /// TIFF, [`Error::UnsupportedMode`] for non-grayscale or unsupported
+    /// sample types, [`Error::BlockShapeMismatch`] if pages disagree in size,
+    /// and [`Error::ModeMismatch`] if a page's sample type maps to a
+    /// different mode than the first page's (`offset` gives the page).
+    pub fn open_tiff<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::from_tiff_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Import a multi-page grayscale TIFF from any seekable stream.
+    ///
+    /// See [`open_tiff`](Self::open_tiff) for the header conventions and
+    /// supported sample types.
+    pub fn from_tiff_reader<R: Read + Seek>(reader: R) -> Result<Self, Error> {
+        let mut decoder = Decoder::new(reader).map_err(tiff_err)?;
+        let (nx, ny) = decoder.dimensions().map_err(tiff_err)?;
+        let (nx, ny) = (nx as usize, ny as usize);
+
+        let mut mode = None;
+        let mut data = Vec::new();
+        let mut nz = 0usize;
+        loop {
+            if !matches!(decoder.colortype().map_err(tiff_err)?, ColorType::Gray(_)) {
+                return Err(Error::UnsupportedMode);
+            }
+            let (w, h) = decoder.dimensions().map_err(tiff_err)?;
+            if (w as usize, h as usize) != (nx, ny) {
+                return Err(Error::BlockShapeMismatch {
+                    expected: nx * ny,
+                    actual: w as usize * h as usize,
+                });
+            }
+
+            let page_mode = append_page(decoder.read_image().map_err(tiff_err)?, &mut data)?;
+            match mode {
+                None => mode = Some(page_mode),
+                Some(m) if m != page_mode => {
+                    return Err(Error::ModeMismatch {
+                        file_mode: m,
+                        requested_mode: page_mode,
+                        offset: Some([0, 0, nz]),
+                    });
+                }
+                Some(_) => {}
+            }
+            nz += 1;
+
+            if !decoder.more_images() {
+                break;
+            }
+            decoder.next_image().map_err(tiff_err)?;
+        }
+        let mode = mode.ok_or(Error::InvalidHeader)?;
+
+        let mut header = Header::new();
+        header.nx = to_i32(nx)?;
+        header.ny = to_i32(ny)?;
+        header.nz = to_i32(nz)?;
+        header.mx = header.nx;
+        header.my = header.ny;
+        header.mz = header.nz;
+        header.mode = mode.as_i32();
+        header.xlen = nx as f32;
+        header.ylen = ny as f32;
+        header.zlen = nz as f32;
+        header.set_image_stack();
+        let (dmin, dmax, dmean, rms) = crate::engine::stats::compute_stats(
+            &data,
+            mode,
+            FileEndian::LittleEndian,
+            nx,
+            ny * nz,
+        )?;
+        header.dmin = dmin;
+        header.dmax = dmax;
+        header.dmean = dmean;
+        header.rms = rms;
+        header.add_label("Imported from multi-page TIFF");
+        header
+            .validate_detailed()
+            .map_err(Error::InvalidHeaderDetailed)?;
+
+        Self::_from_decompressed(DecompressedMrc {
+            header,
+            ext_header: Vec::new(),
+            data: data.into(),
+            warnings: Vec::new(),
+        })
+        .map(|(r, _)| r)
+    }
+}
+
+/// Append one decoded page to `out` as little-endian MRC voxel bytes and
+/// return the MRC mode it maps to.
+fn append_page(page: DecodingResult, out: &mut Vec<u8>) -> Result<Mode, Error> {
+    Ok(match page {
+        DecodingResult::U8(v) => {
+            out.extend(v.iter().flat_map(|&b| u16::from(b).to_le_bytes()));
+            Mode::Uint16
+        }
+        DecodingResult::I8(v) => {
+            out.extend(v.iter().flat_map(|&b| b.to_le_bytes()));
+            Mode::Int8
+        }
+        DecodingResult::I16(v) => {
+            out.extend(v.iter().flat_map(|&x| x.to_le_bytes()));
+            Mode::Int16
+        }
+        DecodingResult::U16(v) => {
+            out.extend(v.iter().flat_map(|&x| x.to_le_bytes()));
+            Mode::Uint16
+        }
+        #[cfg(feature = "f16")]
+        DecodingResult::F16(v) => {
+            out.extend(v.iter().flat_map(|x| x.to_bits().to_le_bytes()));
+            Mode::Float16
+        }
+        #[cfg(not(feature = "f16"))]
+        DecodingResult::F16(v) => {
+            out.extend(v.iter().flat_map(|x| x.to_f32().to_le_bytes()));
+            Mode::Float32
+        }
+        DecodingResult::F32(v) => {
+            out.extend(v.iter().flat_map(|&x| x.to_le_bytes()));
+            Mode::Float32
+        }
+        _ => return Err(Error::UnsupportedMode),
+    })
+}
+
+#[inline]
+fn to_i32(n: usize) -> Result<i32, Error> {
+    i32::try_from(n).map_err(|_| Error::ValueOutOfRange {
+        value: n as u64,
+        max: i32::MAX as u64,
+    })
+}
+
+#[cold]
+fn tiff_err(e: tiff::TiffError) -> Error {
+    match e {
+        tiff::TiffError::IoError(e) => Error::Io(e),
+        other => Error::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            other.to_string(),
+        )),
+    }
+}
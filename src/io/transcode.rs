@@ -0,0 +1,178 @@
+//! Converting an in-memory volume to another mode.
+//!
+//! Storing a float map as 16-bit integers halves its size but needs a
+//! scale to keep the dynamic range, and reading it back as floats means
+//! knowing what that scale was. [`MrcBuf::convert_to`] applies a
+//! [`ScalingPolicy`] on the way to the new mode and records the mapping
+//! in the header labels.
+
+use crate::engine::convert::{pack_u8_to_u4_bytes, unpack_u4_bytes_to_u8};
+use crate::{ComplexToRealStrategy, Error, Mode, MrcBuf, OwnedData};
+
+/// How values are mapped when [`MrcBuf::convert_to`] changes mode.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScalingPolicy {
+    /// Keep values as they are, saturating at the limits of the new mode.
+    None,
+    /// `new = old · scale + offset`.
+    Linear {
+        /// Multiplier applied first.
+        scale: f32,
+        /// Added after scaling.
+        offset: f32,
+    },
+    /// Map the data's `[min, max]` onto the full range of an integer mode
+    /// (`0..=15` for Mode 101). Float modes keep their values.
+    FitRange,
+}
+
+impl MrcBuf {
+    /// A copy of this volume in `mode`, with values mapped by `policy`.
+    ///
+    /// Values are read as by [`Reader::convert::<f32>`](crate::Reader::convert):
+    /// complex voxels as their magnitudes and IMOD's unsigned Mode 0 bytes
+    /// as `0..=255`. Integer targets round to nearest and saturate. The new
+    /// header has the target mode, the density statistics of the converted
+    /// data and, unless the policy left values unchanged, a label such as
+    /// `mrc: mode 2 -> 1, x * 12.5 -3` recording the mapping.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrc::{Header, Mode, MrcBuf, ScalingPolicy};
+    ///
+    /// let mut header = Header::new();
+    /// (header.nx, header.ny, header.nz) = (2, 1, 1);
+    /// (header.mx, header.my, header.mz) = (2, 1, 1);
+    /// let mut buf = MrcBuf::new(header)?;
+    /// buf.data_as_mut::<f32>()?.copy_from_slice(&[-1.0, 1.0]);
+    ///
+    /// let ints = buf.convert_to(Mode::Int16, ScalingPolicy::FitRange)?;
+    /// assert_eq!(ints.data_as::<i16>()?, &[i16::MIN, i16::MAX]);
+    /// # Ok::<(), mrc::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedMode`] for complex target modes, and for
+    /// Mode 12 without the `f16` feature.
+    pub fn convert_to(&self, mode: Mode, policy: ScalingPolicy) -> Result<MrcBuf, Error> {
+        let values = self.values_f32();
+        let (scale, offset) = match policy {
+            ScalingPolicy::None => (1.0, 0.0),
+            ScalingPolicy::Linear { scale, offset } => (scale, offset),
+            ScalingPolicy::FitRange => fit_range(&values, mode),
+        };
+        let s = self.shape();
+        let map = |v: f32| v * scale + offset;
+        let data = match mode {
+            Mode::Int8 => OwnedData::Int8(map_values(&values, |v| map(v).round() as i8)),
+            Mode::Int16 => OwnedData::Int16(map_values(&values, |v| map(v).round() as i16)),
+            Mode::Uint16 => OwnedData::Uint16(map_values(&values, |v| map(v).round() as u16)),
+            Mode::Float32 => OwnedData::Float32(map_values(&values, map)),
+            #[cfg(feature = "f16")]
+            Mode::Float16 => {
+                OwnedData::Float16(map_values(&values, |v| crate::f16::from_f32(map(v))))
+            }
+            Mode::Packed4Bit => {
+                let nibbles = map_values(&values, |v| map(v).round().clamp(0.0, 15.0) as u8);
+                OwnedData::Packed4Bit(pack_u8_to_u4_bytes(&nibbles, s.nx, s.ny * s.nz))
+            }
+            _ => return Err(Error::UnsupportedMode),
+        };
+
+        let mut header = *self.header();
+        header.mode = mode.as_i32();
+        if mode == Mode::Int8 && header.detect_imod().is_some() {
+            // The bytes written are signed; say so to IMOD.
+            header.extra[60] |= 1;
+        }
+        if (scale, offset) != (1.0, 0.0) {
+            header.add_label(&format!(
+                "mrc: mode {} -> {}, x * {scale} {offset:+}",
+                self.mode().as_i32(),
+                mode.as_i32()
+            ));
+        }
+        let mut buf = MrcBuf::from_parts(header, self.ext_header().to_vec(), data);
+        buf.update_header_stats();
+        Ok(buf)
+    }
+
+    /// Every voxel as `f32`, in storage order.
+    fn values_f32(&self) -> Vec<f32> {
+        let magnitude = ComplexToRealStrategy::Magnitude;
+        match self.data() {
+            crate::DataView::Int8(v) => {
+                let unsigned = self
+                    .header()
+                    .detect_imod()
+                    .is_some_and(|imod| !imod.bytes_are_signed);
+                if unsigned {
+                    v.iter().map(|&b| f32::from(b as u8)).collect()
+                } else {
+                    v.iter().map(|&b| f32::from(b)).collect()
+                }
+            }
+            crate::DataView::Int16(v) => v.iter().map(|&x| f32::from(x)).collect(),
+            crate::DataView::Float32(v) => v.to_vec(),
+            crate::DataView::Int16Complex(v) => v.iter().map(|c| c.to_real(magnitude)).collect(),
+            crate::DataView::Float32Complex(v) => v.iter().map(|c| c.to_real(magnitude)).collect(),
+            crate::DataView::Uint16(v) => v.iter().map(|&x| f32::from(x)).collect(),
+            #[cfg(feature = "f16")]
+            crate::DataView::Float16(v) => v.iter().map(|h| h.to_f32()).collect(),
+            crate::DataView::Packed4Bit(v) => {
+                let s = self.shape();
+                unpack_u4_bytes_to_u8(v, s.nx, s.ny * s.nz)
+                    .into_iter()
+                    .map(f32::from)
+                    .collect()
+            }
+        }
+    }
+}
+
+/// `f` applied to each value, across the rayon pool with the `parallel`
+/// feature.
+fn map_values<T: Send>(values: &[f32], f: impl Fn(f32) -> T + Sync + Send) -> Vec<T> {
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        const CHUNK_VOXELS: usize = 262_144;
+        values
+            .par_iter()
+            .with_min_len(CHUNK_VOXELS)
+            .map(|&v| f(v))
+            .collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        values.iter().map(|&v| f(v)).collect()
+    }
+}
+
+/// `(scale, offset)` taking the finite range of `values` onto that of
+/// `mode`, or the identity for float modes and empty data.
+fn fit_range(values: &[f32], mode: Mode) -> (f32, f32) {
+    let (lo, hi) = match mode {
+        Mode::Int8 => (f64::from(i8::MIN), f64::from(i8::MAX)),
+        Mode::Int16 => (f64::from(i16::MIN), f64::from(i16::MAX)),
+        Mode::Uint16 => (0.0, f64::from(u16::MAX)),
+        Mode::Packed4Bit => (0.0, 15.0),
+        _ => return (1.0, 0.0),
+    };
+    let (min, max) = values
+        .iter()
+        .filter(|v| v.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(a, b), &v| {
+            (a.min(f64::from(v)), b.max(f64::from(v)))
+        });
+    if min > max {
+        return (1.0, 0.0);
+    }
+    if min == max {
+        return (1.0, (lo - min) as f32);
+    }
+    let scale = (hi - lo) / (max - min);
+    (scale as f32, (lo - min * scale) as f32)
+}
@@ -0,0 +1,174 @@
+//! Following a file while it is being written.
+//!
+//! During live acquisition, camera and tomography software append sections
+//! to an MRC file one at a time. [`Reader::watch`](crate::Reader::watch)
+//! subscribes to filesystem change notifications for the file and, each
+//! time more complete sections are on disk, sends a [`SectionUpdate`] with
+//! a fresh reader and the range of sections that just became readable.
+//!
+//! A section counts as available once all of its bytes are present and it
+//! lies within the header's `nz`, so writers that grow `nz` as they go and
+//! writers that pre-allocate the header both work. Watching the parent
+//! directory means a file that is replaced (written to a temporary name
+//! and renamed) is picked up as well.
+//!
+//! Requires the `notify` feature (disabled by default).
+
+use crate::{Error, Mode, Reader};
+
+use notify::{EventKind, RecursiveMode, Watcher as _};
+use std::ops::Range;
+use std::path::Path;
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Sections that became readable since the previous update.
+#[derive(Debug)]
+pub struct SectionUpdate {
+    /// Newly available Z-sections, `start..end`.
+    pub sections: Range<usize>,
+    /// The file reopened at the time of the change; reads of `sections`
+    /// through it see the new data.
+    pub reader: Reader,
+}
+
+/// Live feed of new sections, returned by [`Reader::watch`].
+///
+/// Notifications stop when this is dropped.
+pub struct SectionWatcher {
+    rx: mpsc::Receiver<Result<SectionUpdate, Error>>,
+    _watcher: notify::RecommendedWatcher,
+}
+
+impl std::fmt::Debug for SectionWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SectionWatcher").finish_non_exhaustive()
+    }
+}
+
+impl SectionWatcher {
+    /// Block until the next update.
+    ///
+    /// Returns `None` once the underlying filesystem watcher has shut down.
+    pub fn recv(&self) -> Option<Result<SectionUpdate, Error>> {
+        self.rx.recv().ok()
+    }
+
+    /// Wait up to `timeout` for the next update.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<Result<SectionUpdate, Error>> {
+        self.rx.recv_timeout(timeout).ok()
+    }
+
+    /// Return an update if one is pending, without blocking.
+    pub fn try_recv(&self) -> Option<Result<SectionUpdate, Error>> {
+        self.rx.try_recv().ok()
+    }
+}
+
+impl crate::Reader {
+    /// Watch the file this reader was opened from for newly written
+    /// sections.
+    ///
+    /// Sections already readable through `self` are not reported; the first
+    /// update starts where this reader ends. The file is reopened with the
+    /// reader's [`OpenOptions`](crate::OpenOptions), except that validation
+    /// is always [`permissive`](crate::OpenOptions::permissive) — a file in
+    /// the middle of being written is shorter than its header says.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let reader = mrc::OpenOptions::new()
+    ///     .permissive(true)
+    ///     .open("live_tilt_series.mrc")?;
+    /// let watcher = reader.watch()?;
+    /// while let Some(update) = watcher.recv() {
+    ///     let update = update?;
+    ///     for z in update.sections.clone() {
+    ///         let s = update.reader.shape();
+    ///         let section = update.reader.subregion([0, 0, z], [s.nx, s.ny, 1])?;
+    ///         println!("section {z} arrived ({:?})", section.data().mode());
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with [`Unsupported`](std::io::ErrorKind::Unsupported)
+    /// for readers not opened from a path, and [`Error::Io`] if the
+    /// filesystem watch cannot be set up. Errors reopening the file later
+    /// are delivered through the watcher.
+    pub fn watch(&self) -> Result<SectionWatcher, Error> {
+        let Some((path, options)) = &self.origin else {
+            return Err(Error::Io(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "reader was not opened from a path",
+            )));
+        };
+        let path = std::fs::canonicalize(path)?;
+        let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        let options = options.clone().permissive(true);
+        let mut seen = self.available_sections();
+
+        let (tx, rx) = mpsc::channel();
+        let target = path;
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                let event = match event {
+                    Ok(e) => e,
+                    Err(e) => {
+                        let _ = tx.send(Err(Error::Io(std::io::Error::other(e))));
+                        return;
+                    }
+                };
+                if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+                    || !event.paths.iter().any(|p| p == &target)
+                {
+                    return;
+                }
+                let reader = match options.open(&target) {
+                    Ok(r) => r,
+                    // Caught mid-write before the header is complete.
+                    Err(Error::InvalidHeader | Error::FileSizeMismatch { .. }) => return,
+                    Err(e) => {
+                        let _ = tx.send(Err(e));
+                        return;
+                    }
+                };
+                let now = reader.available_sections();
+                if now > seen {
+                    let update = SectionUpdate {
+                        sections: seen..now,
+                        reader,
+                    };
+                    let _ = tx.send(Ok(update));
+                }
+                seen = now;
+            })
+            .map_err(|e| Error::Io(std::io::Error::other(e)))?;
+        watcher
+            .watch(&dir, RecursiveMode::NonRecursive)
+            .map_err(|e| Error::Io(std::io::Error::other(e)))?;
+
+        Ok(SectionWatcher {
+            rx,
+            _watcher: watcher,
+        })
+    }
+
+    /// Number of leading sections whose bytes are all present.
+    fn available_sections(&self) -> usize {
+        let s = self.shape();
+        let section_bytes = match self.mode() {
+            Mode::Packed4Bit => s.ny * s.nx.div_ceil(2),
+            m => s.nx * s.ny * m.byte_size(),
+        };
+        if section_bytes == 0 {
+            return s.nz;
+        }
+        (self._source_data().len() / section_bytes).min(s.nz)
+    }
+}
@@ -138,6 +138,7 @@ use crate::engine::block::{VolumeShape, VoxelBlock};
 use crate::engine::codec::encode_block_parallel;
 use crate::engine::codec::encode_slice;
 use crate::engine::endian::FileEndian;
+use crate::engine::stats::StatsAccumulator;
 use crate::mode::Voxel;
 use crate::{Error, Header, Mode};
 
@@ -152,6 +153,7 @@ enum DataSink {
     #[cfg(feature = "mmap")]
     Mmap(memmap2::MmapMut),
     /// Buffered in memory; compressed and written to disk on finalize.
+    #[cfg_attr(not(any(feature = "gzip", feature = "bzip2")), allow(dead_code))]
     Compressed {
         buf: Vec<u8>,
         path: std::path::PathBuf,
@@ -160,6 +162,45 @@ enum DataSink {
     },
 }
 
+/// Statistics folded in while whole Z-sections are written front to back.
+///
+/// Tracking ends (the writer drops this) at the first write that is not the
+/// next run of whole sections; statistics then need a read-back pass.
+struct SectionStats {
+    acc: StatsAccumulator,
+    /// First section not yet written.
+    next_z: usize,
+}
+
+impl SectionStats {
+    fn new(mode: Mode, endian: FileEndian, nx: usize) -> Self {
+        Self {
+            acc: StatsAccumulator::new(mode, endian, nx),
+            next_z: 0,
+        }
+    }
+
+    /// Accept a block if it continues the in-order section run, returning
+    /// the accumulator to push its encoded bytes into; otherwise stop
+    /// tracking.
+    fn claim(
+        stats: &mut Option<Self>,
+        volume: VolumeShape,
+        offset: [usize; 3],
+        shape: [usize; 3],
+    ) -> Option<&mut StatsAccumulator> {
+        let whole_sections =
+            offset[0] == 0 && offset[1] == 0 && shape[0] == volume.nx && shape[1] == volume.ny;
+        if !(whole_sections && stats.as_ref().is_some_and(|s| s.next_z == offset[2])) {
+            *stats = None;
+            return None;
+        }
+        let s = stats.as_mut()?;
+        s.next_z += shape[2];
+        Some(&mut s.acc)
+    }
+}
+
 /// Trait alias for types that support read, write, and seek simultaneously.
 ///
 /// Required by [`Writer`] which needs random-access read-back for
@@ -429,6 +470,26 @@ macro_rules! builder_setters {
             self.header.add_label(text);
             self
         }
+
+        /// Apply the Python `mrcfile` conventions for new files.
+        ///
+        /// Resets everything except dimensions, mode, sampling and space
+        /// group to `mrcfile`'s defaults and stamps its creation label, so
+        /// call this after the shape and layout setters. See
+        /// [`mrcfile_compat`](crate::mrcfile_compat).
+        ///
+        /// # Examples
+        /// ```
+        /// use mrc::create;
+        /// let builder = create("output.mrc")
+        ///     .shape([8, 8, 4])
+        ///     .mrcfile_conventions(std::time::UNIX_EPOCH);
+        /// ```
+        #[must_use]
+        pub fn mrcfile_conventions(mut self, created: std::time::SystemTime) -> Self {
+            crate::mrcfile_compat::apply_conventions(&mut self.header, created);
+            self
+        }
     };
 }
 
@@ -456,9 +517,12 @@ macro_rules! builder_setters {
 #[derive(Debug)]
 pub struct WriterBuilder {
     path: PathBuf,
-    header: Header,
+    pub(crate) header: Header,
     ext_header: Vec<u8>,
     compression: CompressionLevel,
+    provenance: bool,
+    endian: FileEndian,
+    mrc2000: bool,
 }
 
 impl WriterBuilder {
@@ -478,6 +542,9 @@ impl WriterBuilder {
             header: Header::new(),
             ext_header: Vec::new(),
             compression: CompressionLevel::Balanced,
+            provenance: false,
+            endian: FileEndian::LittleEndian,
+            mrc2000: false,
         }
     }
 
@@ -508,6 +575,99 @@ impl WriterBuilder {
 
     builder_setters!();
 
+    /// Stamp a provenance label when the file is saved.
+    ///
+    /// On [`finalize`](Writer::finalize) the writer appends
+    /// `Created by mrc-rs <version> on <YYYY-MM-DD HH:MM:SS>` (UTC) to the
+    /// labels, the way CCP4 programs and `mrcfile` record who wrote a file.
+    /// `nlabl` is updated, and when all ten slots are taken the oldest label
+    /// is dropped to make room. Default: off.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use mrc::WriterBuilder;
+    /// let mut writer = WriterBuilder::new("output.mrc")
+    ///     .shape([64, 64, 1])
+    ///     .mode::<f32>()
+    ///     .provenance_label(true)
+    ///     .finish()?;
+    /// writer.finalize()?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn provenance_label(mut self, enabled: bool) -> Self {
+        self.provenance = enabled;
+        self
+    }
+
+    /// Byte order of the file to write.
+    ///
+    /// New files are little-endian by default, which every current program
+    /// reads. Choose [`FileEndian::BigEndian`] for legacy software that
+    /// only reads big-endian files: the header words and all voxel data are
+    /// then written big-endian and the machine stamp is set to
+    /// `0x11 0x11 0x00 0x00`. The extended header is written as given.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use mrc::{FileEndian, WriterBuilder};
+    /// let mut writer = WriterBuilder::new("legacy.mrc")
+    ///     .shape([64, 64, 1])
+    ///     .mode::<f32>()
+    ///     .endian(FileEndian::BigEndian)
+    ///     .finish()?;
+    /// writer.finalize()?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn endian(mut self, endian: FileEndian) -> Self {
+        self.endian = endian;
+        self
+    }
+
+    /// Write conservative MRC2000-style output for very old readers.
+    ///
+    /// Some pre-2014 programs reject files with a non-zero `extra` block,
+    /// which is where MRC2014 keeps EXTTYP and NVERSION. With this enabled
+    /// the whole block is zeroed when the file is created and again on
+    /// [`finalize`](Writer::finalize), and only modes 0–4 (the modes
+    /// MRC2000 defines) are accepted.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use mrc::WriterBuilder;
+    /// let mut writer = WriterBuilder::new("for_old_tools.mrc")
+    ///     .shape([64, 64, 1])
+    ///     .mode::<i16>()
+    ///     .mrc2000(true)
+    ///     .finish()?;
+    /// writer.finalize()?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn mrc2000(mut self, enabled: bool) -> Self {
+        self.mrc2000 = enabled;
+        self
+    }
+
+    /// The header to create the file with, after the MRC2000 restrictions.
+    fn output_header(&self) -> Result<Header, Error> {
+        let mut header = self.header;
+        if self.mrc2000 {
+            if !(0..=4).contains(&header.mode) {
+                return Err(Error::invalid_input(format!(
+                    "MRC2000 output supports modes 0-4, got mode {}",
+                    header.mode
+                )));
+            }
+            header.extra = [0; 100];
+        }
+        Ok(header)
+    }
+
     /// Set the extended header bytes.
     ///
     /// When provided, `nsymbt` is automatically updated to match the byte
@@ -553,7 +713,9 @@ impl WriterBuilder {
     /// # Ok(()) }
     /// ```
     pub fn finish(self) -> Result<Writer, Error> {
-        Writer::create(self.path, self.header, &self.ext_header)
+        let header = self.output_header()?;
+        Writer::create(self.path, header, &self.ext_header, self.endian)
+            .map(|w| w.with_flags(self.provenance, self.mrc2000))
     }
 
     /// Build a memory-mapped writer.
@@ -573,7 +735,9 @@ impl WriterBuilder {
     /// ```
     #[cfg(feature = "mmap")]
     pub fn finish_mmap(self) -> Result<Writer, Error> {
-        Writer::create_mmap(self.path, self.header, &self.ext_header)
+        let header = self.output_header()?;
+        Writer::create_mmap(self.path, header, &self.ext_header, self.endian)
+            .map(|w| w.with_flags(self.provenance, self.mrc2000))
     }
 
     /// Build a gzip-compressed writer.
@@ -594,13 +758,16 @@ impl WriterBuilder {
     /// ```
     #[cfg(feature = "gzip")]
     pub fn finish_gzip(self) -> Result<Writer, Error> {
+        let header = self.output_header()?;
         Writer::create_compressed(
             self.path,
-            self.header,
+            header,
             &self.ext_header,
             self.compression,
             true,
+            self.endian,
         )
+        .map(|w| w.with_flags(self.provenance, self.mrc2000))
     }
 
     /// Build a bzip2-compressed writer.
@@ -621,13 +788,16 @@ impl WriterBuilder {
     /// ```
     #[cfg(feature = "bzip2")]
     pub fn finish_bzip2(self) -> Result<Writer, Error> {
+        let header = self.output_header()?;
         Writer::create_compressed(
             self.path,
-            self.header,
+            header,
             &self.ext_header,
             self.compression,
             false,
+            self.endian,
         )
+        .map(|w| w.with_flags(self.provenance, self.mrc2000))
     }
 
     /// Build an in-memory writer backed by a [`Cursor<Vec<u8>>`](std::io::Cursor).
@@ -650,9 +820,15 @@ impl WriterBuilder {
     /// writer.finalize().unwrap();
     /// ```
     pub fn finish_buffer(self) -> Result<Writer, Error> {
-        let header = self.header;
+        let header = self.output_header()?;
         let ext_header = self.ext_header;
-        Writer::from_writer(std::io::Cursor::new(Vec::new()), header, &ext_header)
+        Writer::_create(
+            Box::new(std::io::Cursor::new(Vec::new())),
+            header,
+            &ext_header,
+            self.endian,
+        )
+        .map(|w| w.with_flags(self.provenance, self.mrc2000))
     }
 }
 
@@ -688,6 +864,11 @@ pub struct Writer {
     shape: VolumeShape,
     sink: DataSink,
     finalized: bool,
+    section_stats: Option<SectionStats>,
+    /// Append a provenance label on the next finalize.
+    provenance: bool,
+    /// Keep the `extra` block zeroed for MRC2000 readers.
+    mrc2000: bool,
 }
 
 impl std::fmt::Debug for Writer {
@@ -733,7 +914,12 @@ impl Writer {
         ext_header: &[u8],
     ) -> Result<Self, Error> {
         // New files are always little-endian per crate policy
-        Self::_create(Box::new(writer), header, ext_header)
+        Self::_create(
+            Box::new(writer),
+            header,
+            ext_header,
+            FileEndian::LittleEndian,
+        )
     }
 
     /// Create a memory-mapped writer from a [`Header`] directly.
@@ -758,7 +944,7 @@ impl Writer {
         header: Header,
         ext_header: &[u8],
     ) -> Result<Self, Error> {
-        Self::create_mmap(path, header, ext_header)
+        Self::create_mmap(path, header, ext_header, FileEndian::LittleEndian)
     }
 
     /// Create a gzip-compressed writer from a [`Header`] directly.
@@ -782,7 +968,14 @@ impl Writer {
         ext_header: &[u8],
         compression: CompressionLevel,
     ) -> Result<Self, Error> {
-        Self::create_compressed(path, header, ext_header, compression, true)
+        Self::create_compressed(
+            path,
+            header,
+            ext_header,
+            compression,
+            true,
+            FileEndian::LittleEndian,
+        )
     }
 
     /// Create a bzip2-compressed writer from a [`Header`] directly.
@@ -806,13 +999,21 @@ impl Writer {
         ext_header: &[u8],
         compression: CompressionLevel,
     ) -> Result<Self, Error> {
-        Self::create_compressed(path, header, ext_header, compression, false)
+        Self::create_compressed(
+            path,
+            header,
+            ext_header,
+            compression,
+            false,
+            FileEndian::LittleEndian,
+        )
     }
 
     pub(crate) fn create<P: AsRef<std::path::Path>>(
         path: P,
         header: Header,
         ext_header: &[u8],
+        endian: FileEndian,
     ) -> Result<Self, Error> {
         let file = std::fs::OpenOptions::new()
             .read(true)
@@ -820,16 +1021,16 @@ impl Writer {
             .create(true)
             .truncate(true)
             .open(path)?;
-        Self::_create(Box::new(file), header, ext_header)
+        Self::_create(Box::new(file), header, ext_header, endian)
     }
 
     fn _create(
         mut io: Box<dyn ReadWriteSeek + 'static>,
         mut header: Header,
         ext_header: &[u8],
+        endian: FileEndian,
     ) -> Result<Self, Error> {
-        // New files are always little-endian per crate policy
-        header.set_file_endian(FileEndian::LittleEndian);
+        header.set_file_endian(endian);
 
         header.validate_detailed()?;
 
@@ -869,6 +1070,9 @@ impl Writer {
             shape,
             sink: DataSink::File(io),
             finalized: false,
+            section_stats: Some(SectionStats::new(mode, header.detect_endian(), shape.nx)),
+            provenance: false,
+            mrc2000: false,
         })
     }
 
@@ -878,13 +1082,15 @@ impl Writer {
         path: P,
         mut header: Header,
         ext_header: &[u8],
+        endian: FileEndian,
     ) -> Result<Self, Error> {
-        header.set_file_endian(FileEndian::LittleEndian);
+        header.set_file_endian(endian);
         header.validate_detailed()?;
-        let total_size = header
-            .data_offset()
-            .checked_add(header.data_size().ok_or(Error::InvalidHeader)?)
+        // The whole file is mapped, so it must fit the address space.
+        let total_size = (header.data_offset() as u64)
+            .checked_add(header.data_size_u64().ok_or(Error::InvalidHeader)?)
             .ok_or(Error::InvalidHeader)?;
+        Error::addressable(total_size)?;
         let mmap = {
             use std::fs::OpenOptions;
             use std::io::Write;
@@ -894,7 +1100,7 @@ impl Writer {
                 .create(true)
                 .truncate(true)
                 .open(path)?;
-            file.set_len(total_size as u64)?;
+            file.set_len(total_size)?;
             let mut hb = [0u8; 1024];
             header.encode_to_bytes(&mut hb);
             (&file).write_all(&hb)?;
@@ -931,6 +1137,9 @@ impl Writer {
             shape,
             sink: DataSink::Mmap(mmap),
             finalized: false,
+            section_stats: Some(SectionStats::new(mode, header.detect_endian(), shape.nx)),
+            provenance: false,
+            mrc2000: false,
         })
     }
 
@@ -942,8 +1151,9 @@ impl Writer {
         ext_header: &[u8],
         compression: CompressionLevel,
         is_gzip: bool,
+        endian: FileEndian,
     ) -> Result<Self, Error> {
-        header.set_file_endian(FileEndian::LittleEndian);
+        header.set_file_endian(endian);
         if !ext_header.is_empty() {
             header.nsymbt = ext_header.len() as i32;
         }
@@ -956,7 +1166,7 @@ impl Writer {
             v.resize(ext_size, 0);
             v
         };
-        let data_size = header.data_size().ok_or(Error::InvalidHeader)?;
+        let data_size = Error::addressable(header.data_size_u64().ok_or(Error::InvalidHeader)?)?;
         let off = header.data_offset();
         let mut buf = vec![0u8; off + data_size];
         let mut hb = [0u8; 1024];
@@ -987,9 +1197,18 @@ impl Writer {
                 is_gzip,
             },
             finalized: false,
+            section_stats: Some(SectionStats::new(mode, header.detect_endian(), shape.nx)),
+            provenance: false,
+            mrc2000: false,
         })
     }
 
+    fn with_flags(mut self, provenance: bool, mrc2000: bool) -> Self {
+        self.provenance = provenance;
+        self.mrc2000 = mrc2000;
+        self
+    }
+
     /// Volume dimensions for this writer.
     ///
     /// # Examples
@@ -1174,14 +1393,22 @@ impl Writer {
                     encode_slice(data, &mut buffer, file_endian)?;
                     io.seek(SeekFrom::Start(start_offset))?;
                     io.write_all(&buffer)?;
+                    if let Some(acc) =
+                        SectionStats::claim(&mut self.section_stats, self.shape, offset, shape)
+                    {
+                        acc.push(&buffer)?;
+                    }
                     return Ok(());
                 }
 
+                self.section_stats = None;
                 let mut row_bytes = vec![0u8; sx * b];
                 for z in 0..sz {
                     for y in 0..sy {
-                        let file_linear = ox + (oy + y) * nx + (oz + z) * nx * ny;
-                        let file_offset = self.data_offset + (file_linear as u64) * (b as u64);
+                        let file_linear = (ox as u64)
+                            + ((oy + y) as u64) * (nx as u64)
+                            + ((oz + z) as u64) * (nx as u64) * (ny as u64);
+                        let file_offset = self.data_offset + file_linear * (b as u64);
                         let block_idx = y * sx + z * sx * sy;
                         if block_idx + sx > data.len() {
                             return Err(Error::bounds_err());
@@ -1192,7 +1419,7 @@ impl Writer {
                         io.write_all(&row_bytes)?;
                     }
                 }
-                Ok(())
+                return Ok(());
             }
             #[cfg(feature = "mmap")]
             DataSink::Mmap(mmap) => {
@@ -1208,7 +1435,7 @@ impl Writer {
                     file_endian,
                     self.data_offset as usize,
                     mmap,
-                )
+                )?;
             }
             DataSink::Compressed { buf, .. } => {
                 let block = VoxelBlock {
@@ -1223,9 +1450,41 @@ impl Writer {
                     file_endian,
                     self.data_offset as usize,
                     buf,
-                )
+                )?;
             }
         }
+        self.track_buffered_sections(offset, shape)
+    }
+
+    /// After a write to an in-memory sink, fold the written sections into
+    /// the running statistics straight from the buffer.
+    fn track_buffered_sections(
+        &mut self,
+        offset: [usize; 3],
+        shape: [usize; 3],
+    ) -> Result<(), Error> {
+        let section = self.section_bytes();
+        let start = self.data_offset as usize + offset[2] * section;
+        let end = start + shape[2] * section;
+        let Some(acc) = SectionStats::claim(&mut self.section_stats, self.shape, offset, shape)
+        else {
+            return Ok(());
+        };
+        let buf: &[u8] = match &self.sink {
+            DataSink::File(_) => return Ok(()),
+            #[cfg(feature = "mmap")]
+            DataSink::Mmap(mmap) => mmap,
+            DataSink::Compressed { buf, .. } => buf,
+        };
+        acc.push(buf.get(start..end).ok_or_else(Error::bounds_err)?)
+    }
+
+    /// Encoded size of one Z-section in bytes.
+    fn section_bytes(&self) -> usize {
+        match self.mode {
+            Mode::Packed4Bit => self.shape.ny * self.shape.nx.div_ceil(2),
+            _ => self.shape.nx * self.shape.ny * self.bytes_per_voxel,
+        }
     }
 
     /// Write a block of `u8` data by automatically widening to `u16` (Mode 6).
@@ -1350,11 +1609,21 @@ impl Writer {
         let file_endian = self.header.detect_endian();
         let encoded_chunks = encode_block_parallel(&block.data, chunk_size, file_endian);
 
-        for (chunk_idx, encoded) in encoded_chunks {
+        for (chunk_idx, encoded) in &encoded_chunks {
             let offset = base_offset
-                + (chunk_idx as u64) * (chunk_size as u64) * (self.bytes_per_voxel as u64);
+                + (*chunk_idx as u64) * (chunk_size as u64) * (self.bytes_per_voxel as u64);
             io.seek(SeekFrom::Start(offset))?;
-            io.write_all(&encoded)?;
+            io.write_all(encoded)?;
+        }
+        if let Some(acc) = SectionStats::claim(
+            &mut self.section_stats,
+            self.shape,
+            block.offset,
+            block.shape,
+        ) {
+            for (_, encoded) in &encoded_chunks {
+                acc.push(encoded)?;
+            }
         }
         Ok(())
     }
@@ -1405,26 +1674,32 @@ impl Writer {
                 }
                 if sx == nx && oy == 0 && sy == ny {
                     let slice_bytes = ny * file_row_bytes;
-                    let start_offset = (self.data_offset as usize) + oz * slice_bytes;
+                    let start_offset = self.data_offset + (oz as u64) * (slice_bytes as u64);
                     let byte_len = sz * slice_bytes;
-                    io.seek(SeekFrom::Start(start_offset as u64))?;
+                    io.seek(SeekFrom::Start(start_offset))?;
                     io.write_all(&packed[..byte_len])?;
+                    if let Some(acc) =
+                        SectionStats::claim(&mut self.section_stats, self.shape, offset, shape)
+                    {
+                        acc.push(&packed[..byte_len])?;
+                    }
                     return Ok(());
                 }
+                self.section_stats = None;
                 for z in 0..sz {
                     for y in 0..sy {
-                        let vol_row = (oz + z) * ny + (oy + y);
-                        let file_offset = (self.data_offset as usize) + vol_row * file_row_bytes;
+                        let vol_row = ((oz + z) as u64) * (ny as u64) + (oy + y) as u64;
+                        let file_offset = self.data_offset + vol_row * (file_row_bytes as u64);
                         let packed_start = (y + z * sy) * block_row_bytes;
                         let packed_end = packed_start + block_row_bytes;
                         if packed_end > packed.len() {
                             return Err(Error::bounds_err());
                         }
-                        io.seek(SeekFrom::Start(file_offset as u64))?;
+                        io.seek(SeekFrom::Start(file_offset))?;
                         io.write_all(&packed[packed_start..packed_end])?;
                     }
                 }
-                Ok(())
+                return Ok(());
             }
             #[cfg(feature = "mmap")]
             DataSink::Mmap(mmap) => crate::io::reader_common::write_block_bytes(
@@ -1434,7 +1709,7 @@ impl Writer {
                 shape,
                 self.data_offset as usize,
                 mmap,
-            ),
+            )?,
             DataSink::Compressed { buf, .. } => crate::io::reader_common::write_block_bytes(
                 packed,
                 self.shape,
@@ -1442,12 +1717,16 @@ impl Writer {
                 shape,
                 self.data_offset as usize,
                 buf,
-            ),
+            )?,
         }
+        self.track_buffered_sections(offset, shape)
     }
 
     /// Finalize the MRC file by rewriting the header.
     ///
+    /// If [`provenance_label`](WriterBuilder::provenance_label) was enabled,
+    /// the label is appended first (once, however often this is called).
+    ///
     /// # Examples
     /// ```no_run
     /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -1462,6 +1741,13 @@ impl Writer {
     /// # Ok(()) }
     /// ```
     pub fn finalize(&mut self) -> Result<(), Error> {
+        if std::mem::take(&mut self.provenance) {
+            self.header
+                .add_label(&provenance_label(std::time::SystemTime::now()));
+        }
+        if self.mrc2000 {
+            self.header.extra = [0; 100];
+        }
         let mut header_bytes = [0u8; 1024];
         self.header.encode_to_bytes(&mut header_bytes);
 
@@ -1495,7 +1781,16 @@ impl Writer {
         result
     }
 
-    /// Scan the written data block and update header statistics.
+    /// Update header statistics (`dmin`, `dmax`, `dmean`, `rms`) from the
+    /// written data.
+    ///
+    /// While every write so far has covered whole Z-sections in order —
+    /// section by section, slab by slab, or the whole volume at once — the
+    /// writer folds each one into running statistics as it goes. Once the
+    /// last section is in, this call uses them directly and never reads the
+    /// data back. Any other write pattern (sub-section blocks, skipping
+    /// ahead, rewriting a section) falls back to one streaming read-back
+    /// pass, as in [`update_header_stats_with`](Self::update_header_stats_with).
     ///
     /// # Examples
     /// ```no_run
@@ -1512,36 +1807,76 @@ impl Writer {
     /// # Ok(()) }
     /// ```
     pub fn update_header_stats(&mut self) -> Result<(), Error> {
-        let (data_offset, data_size) = {
-            let ds = self.header.data_size().ok_or(Error::InvalidHeader)?;
-            (self.header.data_offset(), ds)
-        };
+        match &self.section_stats {
+            Some(s) if s.next_z == self.shape.nz => {
+                let stats = s.acc.finish();
+                self.set_header_stats(stats);
+                Ok(())
+            }
+            _ => self.update_header_stats_with(&crate::StreamingConfig::default()),
+        }
+    }
+
+    /// [`update_header_stats`](Self::update_header_stats) with explicit
+    /// chunking: the data is read back and decoded one chunk at a time, so
+    /// memory use stays within `config` however large the volume is.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if reading the data back fails and
+    /// [`Error::BoundsError`] if less data has been written than the header
+    /// declares (memory-mapped and compressed sinks).
+    pub fn update_header_stats_with(
+        &mut self,
+        config: &crate::StreamingConfig,
+    ) -> Result<(), Error> {
+        let data_size = self.header.data_size_u64().ok_or(Error::InvalidHeader)?;
+        let nx = self.shape.nx;
+        let chunk = crate::io::streaming::stats_chunk_len(config, self.mode, nx);
+        let mut acc =
+            crate::engine::stats::StatsAccumulator::new(self.mode, self.header.detect_endian(), nx);
         match &mut self.sink {
             DataSink::File(io) => {
-                let mut buf = vec![0u8; data_size];
                 io.seek(SeekFrom::Start(self.data_offset))?;
-                io.read_exact(&mut buf)?;
-                update_header_stats_from_bytes(&mut self.header, &buf)
+                let mut buf = vec![0u8; (chunk as u64).min(data_size) as usize];
+                let mut left = data_size;
+                while left > 0 {
+                    let n = left.min(buf.len() as u64) as usize;
+                    io.read_exact(&mut buf[..n])?;
+                    acc.push(&buf[..n])?;
+                    left -= n as u64;
+                }
             }
             #[cfg(feature = "mmap")]
             DataSink::Mmap(mmap) => {
-                let end = self.data_offset as usize + data_size;
+                let end = self.data_offset as usize + Error::addressable(data_size)?;
                 if end > mmap.len() {
                     return Err(Error::bounds_err());
                 }
-                update_header_stats_from_bytes(
-                    &mut self.header,
-                    &mmap[self.data_offset as usize..end],
-                )
+                for part in mmap[self.data_offset as usize..end].chunks(chunk) {
+                    acc.push(part)?;
+                }
             }
             DataSink::Compressed { buf, .. } => {
-                let end = data_offset + data_size;
+                let data_offset = self.data_offset as usize;
+                let end = data_offset + Error::addressable(data_size)?;
                 if end > buf.len() {
                     return Err(Error::bounds_err());
                 }
-                update_header_stats_from_bytes(&mut self.header, &buf[data_offset..end])
+                for part in buf[data_offset..end].chunks(chunk) {
+                    acc.push(part)?;
+                }
             }
         }
+        self.set_header_stats(acc.finish());
+        Ok(())
+    }
+
+    fn set_header_stats(&mut self, (dmin, dmax, dmean, rms): (f32, f32, f32, f32)) {
+        self.header.dmin = dmin;
+        self.header.dmax = dmax;
+        self.header.dmean = dmean;
+        self.header.rms = rms;
     }
 }
 
@@ -1549,8 +1884,17 @@ impl Writer {
 // Stats helpers and compression
 // ============================================================================
 
+/// The label [`WriterBuilder::provenance_label`] stamps on save.
+fn provenance_label(time: std::time::SystemTime) -> String {
+    format!(
+        "Created by mrc-rs {} on {}",
+        env!("CARGO_PKG_VERSION"),
+        crate::mrcfile_compat::format_utc(time)
+    )
+}
+
 /// Compress MRC data using the appropriate algorithm based on compression level.
-#[cfg(any(feature = "gzip", feature = "bzip2"))]
+#[cfg_attr(not(any(feature = "gzip", feature = "bzip2")), allow(unused_variables))]
 fn compress_data(
     data: &[u8],
     compression: CompressionLevel,
@@ -1570,18 +1914,3 @@ fn compress_data(
     }
     Err(Error::UnsupportedMode)
 }
-
-fn update_header_stats_from_bytes(header: &mut Header, bytes: &[u8]) -> Result<(), Error> {
-    let endian = header.detect_endian();
-    let mode = Mode::from_i32(header.mode).ok_or(Error::UnsupportedMode)?;
-    let nx = header.nx.max(0) as usize;
-    let ny = header.ny.max(0) as usize;
-    let nz = header.nz.max(0) as usize;
-    let (dmin, dmax, dmean, rms) =
-        crate::engine::stats::compute_stats(bytes, mode, endian, nx, ny * nz)?;
-    header.dmin = dmin;
-    header.dmax = dmax;
-    header.dmean = dmean;
-    header.rms = rms;
-    Ok(())
-}
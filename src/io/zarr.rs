@@ -0,0 +1,210 @@
+//! OME-Zarr export.
+//!
+//! [`Reader::export_ome_zarr`](crate::Reader::export_ome_zarr) writes the
+//! volume as an [OME-NGFF 0.4](https://ngff.openmicroscopy.org/0.4/) image:
+//! a Zarr v2 group holding one uncompressed little-endian `float32` array per
+//! pyramid level (`0`, `1`, …), each downsampled 2× from the previous by
+//! mean binning, with `multiscales` metadata carrying the voxel size in Å.
+//!
+//! ```text
+//! out.zarr/
+//! ├── .zgroup
+//! ├── .zattrs          # multiscales: axes z/y/x, per-level scale
+//! ├── 0/.zarray        # full resolution
+//! ├── 0/<z>/<y>/<x>    # chunk files ("/" dimension separator)
+//! └── 1/...            # 2× binned, and so on
+//! ```
+//!
+//! Image stacks (`ispg = 0`) are binned in X and Y only, so sections are
+//! never averaged together. An axis that has shrunk to one voxel stops
+//! being binned, and its scale in the metadata stops growing with it. All modes are exported as `float32` using the
+//! default [`ConvertReader`](crate::ConvertReader) conversions.
+//!
+//! Requires the `zarr` feature (disabled by default).
+
+use crate::{Error, VolumeShape};
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+impl crate::Reader {
+    /// Export the volume as a chunked OME-Zarr multiscale image.
+    ///
+    /// `chunk` is the chunk shape in `[x, y, z]` order (each entry is
+    /// clamped to at least 1). `levels` is the number of pyramid levels
+    /// including full resolution; fewer are written if a level would shrink
+    /// to a single voxel along every binned axis. The target directory is
+    /// created if needed; existing files inside it are overwritten.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// let reader = mrc::Reader::open("tomogram.mrc")?;
+    /// reader.export_ome_zarr("tomogram.zarr", [64, 64, 64], 3)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] on filesystem errors and propagates any
+    /// decoding error from reading the volume.
+    pub fn export_ome_zarr<P: AsRef<Path>>(
+        &self,
+        path: P,
+        chunk: [usize; 3],
+        levels: usize,
+    ) -> Result<(), Error> {
+        let root = path.as_ref();
+        fs::create_dir_all(root)?;
+
+        let chunk = chunk.map(|c| c.max(1));
+        let bin_z = !self.is_image_stack();
+        let mut shape = self.shape();
+        let mut data = self.convert::<f32>().read_volume()?.data;
+        let base = self
+            .header
+            .voxel_size()
+            .map(|v| if v > 0.0 { v } else { 1.0 });
+
+        // Product of the bin widths actually applied to each axis so far;
+        // an axis that has reached one voxel stops growing its scale.
+        let mut binned = [1usize; 3];
+        let mut scales = Vec::new();
+        for level in 0..levels.max(1) {
+            if level > 0 {
+                let factor = [2, 2, if bin_z { 2 } else { 1 }];
+                if shape.nx < 2 && shape.ny < 2 && (!bin_z || shape.nz < 2) {
+                    break;
+                }
+                let applied;
+                (data, shape, applied) = bin_mean(&data, shape, factor);
+                for (b, a) in binned.iter_mut().zip(applied) {
+                    *b *= a;
+                }
+            }
+            scales.push([
+                base[2] * binned[2] as f32,
+                base[1] * binned[1] as f32,
+                base[0] * binned[0] as f32,
+            ]);
+            write_array(&root.join(level.to_string()), &data, shape, chunk)?;
+        }
+
+        fs::write(root.join(".zgroup"), "{\"zarr_format\":2}\n")?;
+        fs::write(root.join(".zattrs"), multiscales_json(&scales))?;
+        Ok(())
+    }
+}
+
+/// Downsample by averaging `factor` voxels per axis (`[x, y, z]`). Edge
+/// voxels that do not fill a whole bin are dropped, except that an axis
+/// never shrinks below one voxel. Also returns the bin width used on each
+/// axis, which is less than `factor` where the axis was already shorter.
+fn bin_mean(
+    data: &[f32],
+    s: VolumeShape,
+    factor: [usize; 3],
+) -> (Vec<f32>, VolumeShape, [usize; 3]) {
+    let out = VolumeShape::new(
+        (s.nx / factor[0]).max(1),
+        (s.ny / factor[1]).max(1),
+        (s.nz / factor[2]).max(1),
+    );
+    let fx = factor[0].min(s.nx);
+    let fy = factor[1].min(s.ny);
+    let fz = factor[2].min(s.nz);
+    let norm = (fx * fy * fz) as f32;
+    let bin_section = |z: usize, section: &mut [f32]| {
+        for y in 0..out.ny {
+            for x in 0..out.nx {
+                let mut sum = 0.0f32;
+                for dz in 0..fz {
+                    for dy in 0..fy {
+                        let row = ((z * fz + dz) * s.ny + y * fy + dy) * s.nx + x * fx;
+                        sum += data[row..row + fx].iter().sum::<f32>();
+                    }
+                }
+                section[y * out.nx + x] = sum / norm;
+            }
+        }
+    };
+    let mut binned = vec![0.0f32; out.nx * out.ny * out.nz];
+    // Each output section reads its own slab of input, so sections bin
+    // independently.
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        binned
+            .par_chunks_mut(out.nx * out.ny)
+            .enumerate()
+            .for_each(|(z, section)| bin_section(z, section));
+    }
+    #[cfg(not(feature = "parallel"))]
+    for (z, section) in binned.chunks_mut(out.nx * out.ny).enumerate() {
+        bin_section(z, section);
+    }
+    (binned, out, [fx, fy, fz])
+}
+
+/// Write one Zarr v2 array (`.zarray` plus padded chunk files).
+fn write_array(dir: &Path, data: &[f32], s: VolumeShape, chunk: [usize; 3]) -> Result<(), Error> {
+    let [cx, cy, cz] = chunk;
+    fs::create_dir_all(dir)?;
+    fs::write(
+        dir.join(".zarray"),
+        format!(
+            "{{\"zarr_format\":2,\"shape\":[{},{},{}],\"chunks\":[{cz},{cy},{cx}],\
+             \"dtype\":\"<f4\",\"compressor\":null,\"fill_value\":0.0,\"order\":\"C\",\
+             \"filters\":null,\"dimension_separator\":\"/\"}}\n",
+            s.nz, s.ny, s.nx
+        ),
+    )?;
+
+    let mut buf = vec![0u8; cx * cy * cz * 4];
+    for kz in 0..s.nz.div_ceil(cz) {
+        for ky in 0..s.ny.div_ceil(cy) {
+            let chunk_dir = dir.join(kz.to_string()).join(ky.to_string());
+            fs::create_dir_all(&chunk_dir)?;
+            for kx in 0..s.nx.div_ceil(cx) {
+                buf.fill(0);
+                let (x0, y0, z0) = (kx * cx, ky * cy, kz * cz);
+                let w = cx.min(s.nx - x0);
+                for dz in 0..cz.min(s.nz - z0) {
+                    for dy in 0..cy.min(s.ny - y0) {
+                        let src = ((z0 + dz) * s.ny + y0 + dy) * s.nx + x0;
+                        let dst = (dz * cy + dy) * cx * 4;
+                        for (i, v) in data[src..src + w].iter().enumerate() {
+                            buf[dst + i * 4..dst + i * 4 + 4].copy_from_slice(&v.to_le_bytes());
+                        }
+                    }
+                }
+                fs::write(chunk_dir.join(kx.to_string()), &buf)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// OME-NGFF 0.4 `.zattrs` with one scale transform per level (`[z, y, x]`).
+fn multiscales_json(scales: &[[f32; 3]]) -> String {
+    let mut datasets = String::new();
+    for (i, [sz, sy, sx]) in scales.iter().enumerate() {
+        if i > 0 {
+            datasets.push(',');
+        }
+        let _ = write!(
+            datasets,
+            "{{\"path\":\"{i}\",\"coordinateTransformations\":\
+             [{{\"type\":\"scale\",\"scale\":[{sz:?},{sy:?},{sx:?}]}}]}}"
+        );
+    }
+    let axes = ["z", "y", "x"]
+        .map(|a| format!("{{\"name\":\"{a}\",\"type\":\"space\",\"unit\":\"angstrom\"}}"))
+        .join(",");
+    format!(
+        "{{\"multiscales\":[{{\"version\":\"0.4\",\"axes\":[{axes}],\"datasets\":[{datasets}]}}]}}\n"
+    )
+}
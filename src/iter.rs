@@ -144,59 +144,30 @@ impl<'a, S> RegionIter<'a, S> {
 
     /// Try zero-copy reinterpretation for a native-endian contiguous slab.
     /// Returns `Some(DataView)` on success, `None` if the block cannot be
-    /// zero-copied (non-contiguous, endian mismatch, or alignment issue).
+    /// zero-copied (misaligned for the voxel type, or not a whole number of
+    /// voxels); callers then decode into an owned copy instead.
     pub(crate) fn try_zero_copy<'b>(bytes: &'b [u8], mode: Mode) -> Option<DataView<'b>> {
+        fn cast<T: bytemuck::Pod>(bytes: &[u8]) -> Option<&[T]> {
+            bytemuck::try_cast_slice(bytes).ok()
+        }
         Some(match mode {
-            Mode::Int8 => {
-                let (prefix, data, _suffix) = unsafe { bytes.align_to::<i8>() };
-                if !prefix.is_empty() {
-                    return None;
-                }
-                DataView::Int8(data)
-            }
-            Mode::Int16 => {
-                let (prefix, data, _suffix) = unsafe { bytes.align_to::<i16>() };
-                if !prefix.is_empty() {
-                    return None;
-                }
-                DataView::Int16(data)
-            }
-            Mode::Float32 => {
-                let (prefix, data, _suffix) = unsafe { bytes.align_to::<f32>() };
-                if !prefix.is_empty() {
-                    return None;
-                }
-                DataView::Float32(data)
-            }
-            Mode::Int16Complex => {
-                let (prefix, data, _suffix) = unsafe { bytes.align_to::<crate::Int16Complex>() };
-                if !prefix.is_empty() {
-                    return None;
-                }
-                DataView::Int16Complex(data)
-            }
-            Mode::Float32Complex => {
-                let (prefix, data, _suffix) = unsafe { bytes.align_to::<crate::Float32Complex>() };
-                if !prefix.is_empty() {
-                    return None;
-                }
-                DataView::Float32Complex(data)
-            }
-            Mode::Uint16 => {
-                let (prefix, data, _suffix) = unsafe { bytes.align_to::<u16>() };
-                if !prefix.is_empty() {
-                    return None;
-                }
-                DataView::Uint16(data)
-            }
+            Mode::Int8 => DataView::Int8(cast(bytes)?),
+            Mode::Int16 => DataView::Int16(cast(bytes)?),
+            Mode::Float32 => DataView::Float32(cast(bytes)?),
+            Mode::Int16Complex => DataView::Int16Complex(cast(bytes)?),
+            Mode::Float32Complex => DataView::Float32Complex(cast(bytes)?),
+            Mode::Uint16 => DataView::Uint16(cast(bytes)?),
             #[cfg(feature = "f16")]
             Mode::Float16 => {
-                let (prefix, data, _suffix) = unsafe { bytes.align_to::<crate::f16>() };
-                if !prefix.is_empty() {
-                    return None;
-                }
-                DataView::Float16(data)
+                let bits: &[u16] = cast(bytes)?;
+                // SAFETY: `f16` is `repr(transparent)` over `u16`, and `bits`
+                // is already checked for `u16` alignment and length.
+                DataView::Float16(unsafe {
+                    core::slice::from_raw_parts(bits.as_ptr().cast::<crate::f16>(), bits.len())
+                })
             }
+            #[cfg(not(feature = "f16"))]
+            Mode::Float16 => return None,
             Mode::Packed4Bit => DataView::Packed4Bit(bytes),
         })
     }
@@ -207,6 +178,9 @@ impl<'a, S: Stepper> Iterator for RegionIter<'a, S> {
 
     fn next(&mut self) -> Option<Self::Item> {
         let (offset, shape) = self.stepper.next(self.volume_shape)?;
+        if let Err(e) = self.reader.check_cancelled() {
+            return Some(Err(e));
+        }
         let bytes = match self.reader.read_block_bytes_cow(offset, shape) {
             Ok(b) => b,
             Err(e) => return Some(Err(e)),
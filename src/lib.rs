@@ -120,6 +120,20 @@
 //! [`Reader::from_bytes_permissive`] accept non-critical header issues as
 //! warnings without failing, mirroring [`Reader::open_permissive`].
 //!
+//! ### WebAssembly
+//!
+//! [`Reader::from_slice`] parses a whole file (plain, or gzip/bzip2 when the
+//! feature is enabled) from a borrowed buffer without touching the
+//! filesystem. It works on `wasm32-unknown-unknown` with the file-backed
+//! features disabled:
+//!
+//! ```toml
+//! mrc = { version = "0.7", default-features = false, features = ["f16", "gzip"] }
+//! ```
+//!
+//! `mmap` needs an OS and `parallel` needs threads, so leave both off for
+//! browser builds.
+//!
 //! ### Large files
 //!
 //! When the file does not fit in RAM, [`Reader::open`] automatically uses
@@ -259,7 +273,7 @@
 //! | [`Int16Complex`](Mode::Int16Complex) (3) | [`Int16Complex`] | Complex data (i16 real + i16 imag) |
 //! | [`Float32Complex`](Mode::Float32Complex) (4) | [`Float32Complex`] | Complex data (f32 real + f32 imag) |
 //! | [`Uint16`](Mode::Uint16) (6) | `u16` | Segmentation labels |
-//! | [`Float16`](Mode::Float16) (12) | `f16` | Half-precision storage (feature `f16`) |
+//! | [`Float16`](Mode::Float16) (12) | [`f16`] | Half-precision storage (feature `f16`); read with `volume::<f16>()` and widen with `iter_as_f32()` |
 //! | [`Packed4Bit`](Mode::Packed4Bit) (101) | `u8` via [`slices_u8`](Reader::slices_u8) | 4-bit packed data; no `Voxel` impl |
 //!
 //! Packed 4-bit data is handled transparently by the unified API:
@@ -278,11 +292,23 @@
 //! | `mmap` | Memory-mapped readers and writers | ✅ |
 //! | `f16` | Half-precision float via the `half` crate | ✅ |
 //! | `simd` | AVX2 / NEON acceleration for integer↔f32, f16↔f32, byte-swap, stats, and f32→integer clamping | ✅ |
-//! | `parallel` | Parallel encoding via `rayon` | ✅ |
+//! | `parallel` | Parallel encoding, statistics, conversion, [`Reader::par_sections`] and [`MrcBuf::par_map_voxels`] via `rayon` | ✅ |
 //! | `gzip` | Gzip-compressed I/O | ✅ |
 //! | `bzip2` | Bzip2-compressed I/O | ❌ |
 //! | `ndarray` | Return volumes as `ndarray::Array3<T>` via `to_ndarray()` | ❌ |
 //! | `serde` | Serialize/Deserialize support via `serde` | ❌ |
+//! | `tiff` | Import multi-page TIFF stacks via [`Reader::open_tiff`] | ❌ |
+//! | `capi` | C ABI (`mrc_open`, `mrc_read_section`, …) in the `capi` module | ❌ |
+//! | `zarr` | OME-Zarr multiscale export via [`Reader::export_ome_zarr`] | ❌ |
+//! | `fft` | [`Reader::fft3`] / [`Reader::ifft3`] and dose-weighted frame averaging via [`Reader::dose_weight`] (`rustfft`) | ❌ |
+//! | `notify` | Follow files during live acquisition via [`Reader::watch`] | ❌ |
+//! | `arbitrary` | `arbitrary::Arbitrary` for [`Header`] (any bytes), `ValidHeader` and `SmallVolume`, for fuzzing | ❌ |
+//! | `shm` | Share one decoded volume between processes via [`Reader::share`] / [`Reader::open_shared`] (Linux) | ❌ |
+//! | `hash` | BLAKE3 content hashes for deduplication and transfer checks via `Reader::content_hash` | ❌ |
+//! | `wgpu` | [`Volume`] upload as a 3D texture or staging buffer, with `TextureLayout` and `texture_format` | ❌ |
+//!
+//! WASI needs no feature: when `target_os = "wasi"`, [`Reader::open`] always
+//! uses buffered `std::fs` reads and never attempts mmap.
 //!
 //! ```no_run
 //! # fn main() -> Result<(), mrc::Error> {
@@ -507,6 +533,8 @@
 //!
 //! * [`Io`](Error::Io) — the file could not be read or written
 //! * [`InvalidHeader`](Error::InvalidHeader) — not a valid MRC file
+//! * [`NotAnMrcFile`](Error::NotAnMrcFile) — no `"MAP "` tag or machine
+//!   stamp, typically a different file format
 //! * [`ModeMismatch`](Error::ModeMismatch) — writing a `VoxelBlock<i16>` to
 //!   a Float32 file; use [`write_block_as`](Writer::write_block_as) instead
 //! * [`BoundsError`](Error::BoundsError) — read or write outside the volume
@@ -517,6 +545,8 @@
 //!
 //! [`HeaderValidationError`] gives fine-grained diagnostics for header
 //! problems (bad dimensions, wrong MAP field, invalid NVERSION ...).
+//! [`Error::kind`] reduces any error to an [`ErrorKind`] with a stable
+//! numeric [`code`](ErrorKind::code), for bindings and logs.
 //!
 //! # Endianness
 //!
@@ -531,8 +561,9 @@
 //!
 //! Use [`FileEndian::native`] to query the host platform, and
 //! [`reader.endian()`](Reader::endian) to get a file's actual byte order.
-//! New files are always little-endian, matching modern hardware and the Python
-//! `mrcfile` library.
+//! New files are little-endian by default, matching modern hardware and the
+//! Python `mrcfile` library; [`WriterBuilder::endian`] writes big-endian files
+//! for software that cannot read anything else.
 //!
 //! The crate has a fallback: if the MODE field is invalid under the detected
 //! endianness, the opposite byte order is tried. This handles files with a
@@ -582,7 +613,16 @@
 //!
 //! If you already have an open [`Reader`], use
 //! [`validate_reader`](validate::validate_reader) to avoid re-opening
-//! the file.
+//! the file. For ingest gates,
+//! [`compliance_report`](validate::compliance_report) lists only the
+//! departures from MRC2014, each tagged with the [`Rule`](validate::Rule)
+//! it breaks, and [`fix_header`](validate::fix_header) corrects the ones
+//! that need no data to fix.
+//!
+//! For files exchanged with the Python `mrcfile` library,
+//! [`mrcfile_compat`] applies its stricter validation rules and its writing
+//! conventions (machine stamp, creation label, statistics), with golden test
+//! vectors in [`mrcfile_compat::vectors`].
 //!
 //! # Real-world workflows
 //!
@@ -667,6 +707,7 @@
 //! | Error | Likely cause | What to try |
 //! |---|---|---|
 //! | [`InvalidHeader`](Error::InvalidHeader) | Not an MRC file, or header corruption | Run `mrc validate file.mrc`; try [`open_permissive`](Reader::open_permissive) |
+//! | [`NotAnMrcFile`](Error::NotAnMrcFile) | Another format, or an old writer that left MAP/MACHST blank | Check the file type; legacy files open with [`open_permissive`](Reader::open_permissive) |
 //! | [`FileSizeMismatch`](Error::FileSizeMismatch) | File truncated or has trailing garbage | Re-download or check `mrc validate` output |
 //! | [`ModeMismatch`](Error::ModeMismatch) | Writing a `VoxelBlock<i16>` to an Float32 file | Use [`write_block_as`](Writer::write_block_as) — auto-converts any mode |
 //! | [`NotAVolumeStack`](Error::NotAVolumeStack) | Calling `volumes()` on a non-stack file | Check `reader.is_volume_stack()` first |
@@ -695,26 +736,46 @@ mod header;
 mod io;
 mod iter;
 mod mode;
+pub mod mrcfile_compat;
 pub mod validate;
 
+#[cfg(feature = "capi")]
+pub mod capi;
+
 #[cfg(feature = "serde")]
 mod serde_byte_array;
 
+#[cfg(feature = "arbitrary")]
+mod fuzzing;
+
 // Re-export core types
-pub use engine::block::{VolumeShape, VoxelBlock};
+/// Cache-line-aligned byte buffer backing in-memory readers.
+pub use engine::aligned::{AlignedBuf, BUF_ALIGN, PAGE_ALIGN};
+pub use engine::block::{Axis, VolumeShape, VoxelBlock};
 /// Endianness of MRC file data.
 pub use engine::endian::FileEndian;
+/// Classification of MACHST machine stamps, including legacy variants.
+pub use engine::endian::MachineStamp;
+/// Encoded voxel bytes with their mode and byte order.
+pub use engine::raw_block::RawBlock;
+/// Strided window onto a sub-box of a [`Volume`], from [`Volume::roi`].
+pub use engine::roi::RoiView;
+/// Streaming quantile estimates, from [`Reader::quantile_sketch`].
+pub use engine::sketch::QuantileSketch;
+/// Whole volume typed once against the file's mode.
+pub use engine::volume::Volume;
 
 // Re-export MRC-specific format utilities
 pub use engine::convert::{convert_u8_slice_to_u16, convert_u16_slice_to_u8, reinterpret_m0};
 
-pub use error::{Error, HeaderValidationError};
+pub use error::{Error, ErrorKind, HeaderValidationError};
 pub use header::{
-    AGAR_RECORD_SIZE, AgarRecord, CCP4_RECORD_SIZE, Ccp4Record, ExtHeaderData, ExtHeaderType,
-    FEI1_RECORD_SIZE, FEI2_RECORD_SIZE, Fei1Metadata, Fei2Metadata, Header, HeaderBuilder,
-    ImodImageType, ImodInfo, ImodMetadata, MRCO_RECORD_SIZE, MrcoRecord, SERI_RECORD_SIZE,
-    SeriRecord, parse_agar_records, parse_ccp4_records, parse_fei1_records, parse_fei2_records,
-    parse_imod_metadata, parse_mrco_records, parse_seri_records,
+    AGAR_RECORD_SIZE, AgarRecord, CCP4_RECORD_SIZE, Ccp4Record, ExtHeaderData, ExtHeaderFormat,
+    ExtHeaderType, FEI1_RECORD_SIZE, FEI2_RECORD_SIZE, Fei1Metadata, Fei2Metadata, Header,
+    HeaderBuilder, ImodImageType, ImodInfo, ImodMetadata, MRCO_RECORD_SIZE, MrcoRecord,
+    SERI_RECORD_SIZE, SeriRecord, parse_agar_records, parse_ccp4_records, parse_fei1_records,
+    parse_fei2_records, parse_imod_metadata, parse_mrco_records, parse_seri_records,
+    register_ext_header_format,
 };
 
 pub use mode::{
@@ -728,6 +789,89 @@ pub use half::f16;
 /// Consolidated MRC reader with automatic mmap/buffered backend selection.
 pub use io::reader::Reader;
 
+/// Brick-by-brick traversal returned by [`Reader::bricks`].
+pub use io::bricks::{Brick, Bricks};
+
+/// Particle boxing output and edge handling for [`Reader::extract_particles`].
+pub use io::boxing::{EdgePolicy, ParticleStack};
+
+/// Sum, mean and maximum projections, from [`Reader::project`].
+pub use io::projection::ProjectionKind;
+
+/// Configurable opening: validation, backend and byte-order policy.
+pub use io::options::{EndianPolicy, OpenOptions};
+
+/// Backend-independent read trait implemented by [`Reader`].
+pub use io::source::MrcSource;
+
+/// Vendor-neutral acquisition parameters, from [`Reader::acquisition`].
+pub use io::acquisition::Acquisition;
+
+/// Volume comparison: [`Reader::approx_eq`] reports and [`difference`] maps.
+pub use io::compare::{ComparisonReport, DifferenceMap, difference};
+
+/// Value histograms, header-or-data and per-section statistics, and header
+/// statistics checks.
+pub use io::levels::{
+    Histogram, QuickStatistics, SectionStatistics, StatCheck, StatVerdict, StatisticsReport,
+    StatsSource,
+};
+
+/// One volume of a volume stack, returned by [`Reader::stack_volume`].
+pub use io::stack::StackVolume;
+
+/// `Read` adapter returned by [`Reader::encoded`].
+pub use io::stream::EncodedStream;
+
+/// Chunk size and memory ceiling for streaming routines.
+pub use io::streaming::StreamingConfig;
+
+/// Cooperative cancellation for long-running reads.
+pub use io::cancel::CancellationToken;
+
+/// Background writer fed sections by many producer threads.
+pub use io::channel::{ChannelWriter, Section, SectionSender};
+
+/// Owned in-memory volume that can grow and be serialized.
+pub use io::buf::MrcBuf;
+
+/// Value mapping for [`MrcBuf::convert_to`].
+pub use io::transcode::ScalingPolicy;
+
+/// Free-list of voxel buffers reused across reader loads.
+pub use io::pool::BufferPool;
+
+/// Cache-line-aligned whole-volume slice, from [`Reader::data_aligned_or_copy`].
+pub use io::aligned_slice::AlignedSlice;
+/// Decode-on-access `f32` voxel reads, from [`Reader::voxel_accessor`].
+pub use io::lazy::VoxelAccessor;
+
+/// Slab-at-a-time map/reduce for files larger than memory.
+pub use io::chunked::ChunkedProcessor;
+
+/// Section writer that overlaps computation with disk writes.
+pub use io::double_buffer::DoubleBufferedWriter;
+
+/// Live section feed returned by [`Reader::watch`] (requires `notify` feature).
+#[cfg(feature = "notify")]
+pub use io::watch::{SectionUpdate, SectionWatcher};
+
+/// 3D texture upload for GPU volume rendering (requires `wgpu` feature).
+#[cfg(feature = "wgpu")]
+pub use io::gpu::{TextureLayout, texture_format};
+
+/// Removal of volumes published with [`Reader::share`] (requires `shm` feature, Linux).
+#[cfg(all(feature = "shm", target_os = "linux"))]
+pub use io::shm::remove_shared;
+
+/// Normalized content digests (requires `hash` feature).
+#[cfg(feature = "hash")]
+pub use io::hash::{ContentHash, HashOptions};
+
+/// Fuzzing inputs: well-formed headers and volumes (requires `arbitrary` feature).
+#[cfg(feature = "arbitrary")]
+pub use fuzzing::{SmallVolume, ValidHeader};
+
 /// Auto-conversion wrapper returned by [`Reader::convert`].
 pub use io::reader_common::ConvertReader;
 
@@ -749,6 +893,9 @@ pub use io::reader_common::DEFAULT_MAX_DECOMPRESSED_BYTES;
 #[doc(hidden)]
 pub use engine::codec::{decode_into, swap_bytes_in_place};
 
+/// Per-value decoding and encoding in a file's byte order.
+pub use engine::codec::{DecodeFromFile, EncodeToFile};
+
 #[doc(hidden)]
 pub use io::reader::{CompressionType, detect_compression};
 
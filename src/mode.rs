@@ -30,7 +30,19 @@ use serde::{Deserialize, Serialize};
 /// }
 /// # Ok(()) }
 /// ```
-#[derive(Debug)]
+///
+/// When the mode is known in advance, the `as_*` accessors return the slice
+/// directly, or [`Error::ModeMismatch`](crate::Error::ModeMismatch) if the
+/// view holds another type:
+///
+/// ```
+/// use mrc::DataView;
+/// let view = DataView::Float32(&[1.0, 2.0]);
+/// assert_eq!(view.as_f32()?, &[1.0, 2.0]);
+/// assert!(view.as_i16().is_err());
+/// # Ok::<(), mrc::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy)]
 pub enum DataView<'a> {
     /// Signed 8-bit integer (Mode 0).
     Int8(&'a [i8]),
@@ -74,6 +86,386 @@ pub enum OwnedData {
     Packed4Bit(Vec<u8>),
 }
 
+impl DataView<'_> {
+    /// The MRC mode of the viewed data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrc::{DataView, Mode};
+    /// assert_eq!(DataView::Int16(&[1, 2]).mode(), Mode::Int16);
+    /// ```
+    pub fn mode(&self) -> Mode {
+        match self {
+            DataView::Int8(_) => Mode::Int8,
+            DataView::Int16(_) => Mode::Int16,
+            DataView::Float32(_) => Mode::Float32,
+            DataView::Int16Complex(_) => Mode::Int16Complex,
+            DataView::Float32Complex(_) => Mode::Float32Complex,
+            DataView::Uint16(_) => Mode::Uint16,
+            #[cfg(feature = "f16")]
+            DataView::Float16(_) => Mode::Float16,
+            DataView::Packed4Bit(_) => Mode::Packed4Bit,
+        }
+    }
+
+    /// Number of elements in the view. For [`DataView::Packed4Bit`] this is
+    /// the number of packed bytes, not voxels.
+    pub fn len(&self) -> usize {
+        match self {
+            DataView::Int8(v) => v.len(),
+            DataView::Int16(v) => v.len(),
+            DataView::Float32(v) => v.len(),
+            DataView::Int16Complex(v) => v.len(),
+            DataView::Float32Complex(v) => v.len(),
+            DataView::Uint16(v) => v.len(),
+            #[cfg(feature = "f16")]
+            DataView::Float16(v) => v.len(),
+            DataView::Packed4Bit(v) => v.len(),
+        }
+    }
+
+    /// `true` if the view holds no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// `(dmin, dmax, dmean, rms)` of the viewed values in one pass, ready
+    /// for the header's density fields.
+    ///
+    /// Complex values count as their magnitudes, and `f16` values are
+    /// widened to `f32`. Every nibble of a [`DataView::Packed4Bit`] view is
+    /// counted, including the padding nibble that ends odd-width rows;
+    /// [`MrcBuf::stats`](crate::MrcBuf::stats) knows the row width and
+    /// skips it. An empty view gives the header sentinels
+    /// `(0.0, -1.0, -2.0, -1.0)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrc::{DataView, Float32Complex};
+    /// assert_eq!(DataView::Int16(&[1, 3]).stats(), (1.0, 3.0, 2.0, 1.0));
+    ///
+    /// let fft = [Float32Complex { real: 3.0, imag: 4.0 }];
+    /// assert_eq!(DataView::Float32Complex(&fft).stats(), (5.0, 5.0, 5.0, 0.0));
+    /// ```
+    pub fn stats(&self) -> (f32, f32, f32, f32) {
+        crate::engine::stats::view_stats(*self, self.len() * 2)
+    }
+    /// Indices of the NaN and infinite values, in order.
+    ///
+    /// A complex value counts when either part is non-finite. Integer and
+    /// packed modes cannot hold such values and always give an empty list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrc::DataView;
+    /// let view = DataView::Float32(&[1.0, f32::NAN, 2.0, f32::NEG_INFINITY]);
+    /// assert_eq!(view.find_non_finite(), [1, 3]);
+    /// ```
+    pub fn find_non_finite(&self) -> Vec<usize> {
+        fn positions<T>(v: &[T], bad: impl Fn(&T) -> bool) -> Vec<usize> {
+            v.iter()
+                .enumerate()
+                .filter_map(|(i, x)| bad(x).then_some(i))
+                .collect()
+        }
+        match self {
+            DataView::Float32(v) => positions(v, |x| !x.is_finite()),
+            DataView::Float32Complex(v) => {
+                positions(v, |c| !(c.real.is_finite() && c.imag.is_finite()))
+            }
+            #[cfg(feature = "f16")]
+            DataView::Float16(v) => positions(v, |x| !x.is_finite()),
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl<'a> DataView<'a> {
+    /// The viewed data as `&[T]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ModeMismatch`](crate::Error::ModeMismatch) if the
+    /// view's mode is not `T::MODE`.
+    pub fn as_slice<T: Voxel>(self) -> Result<&'a [T], crate::Error> {
+        T::from_view(self).ok_or(crate::Error::ModeMismatch {
+            file_mode: self.mode(),
+            requested_mode: T::MODE,
+            offset: None,
+        })
+    }
+
+    /// The data of a Mode 0 view.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ModeMismatch`](crate::Error::ModeMismatch) for any
+    /// other mode.
+    pub fn as_i8(self) -> Result<&'a [i8], crate::Error> {
+        self.as_slice()
+    }
+
+    /// The data of a Mode 1 view.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ModeMismatch`](crate::Error::ModeMismatch) for any
+    /// other mode.
+    pub fn as_i16(self) -> Result<&'a [i16], crate::Error> {
+        self.as_slice()
+    }
+
+    /// The data of a Mode 2 view.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ModeMismatch`](crate::Error::ModeMismatch) for any
+    /// other mode.
+    pub fn as_f32(self) -> Result<&'a [f32], crate::Error> {
+        self.as_slice()
+    }
+
+    /// The data of a Mode 3 view.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ModeMismatch`](crate::Error::ModeMismatch) for any
+    /// other mode.
+    pub fn as_int16_complex(self) -> Result<&'a [Int16Complex], crate::Error> {
+        self.as_slice()
+    }
+
+    /// The data of a Mode 4 view.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ModeMismatch`](crate::Error::ModeMismatch) for any
+    /// other mode.
+    pub fn as_float32_complex(self) -> Result<&'a [Float32Complex], crate::Error> {
+        self.as_slice()
+    }
+
+    /// The data of a Mode 6 view.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ModeMismatch`](crate::Error::ModeMismatch) for any
+    /// other mode.
+    pub fn as_u16(self) -> Result<&'a [u16], crate::Error> {
+        self.as_slice()
+    }
+
+    /// The data of a Mode 12 view (requires the `f16` feature).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ModeMismatch`](crate::Error::ModeMismatch) for any
+    /// other mode.
+    #[cfg(feature = "f16")]
+    pub fn as_f16(self) -> Result<&'a [crate::f16], crate::Error> {
+        self.as_slice()
+    }
+
+    /// The packed bytes of a Mode 101 view, two voxels per byte.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ModeMismatch`](crate::Error::ModeMismatch) for any
+    /// other mode.
+    pub fn as_packed4bit(self) -> Result<&'a [u8], crate::Error> {
+        match self {
+            DataView::Packed4Bit(v) => Ok(v),
+            other => Err(crate::Error::ModeMismatch {
+                file_mode: other.mode(),
+                requested_mode: Mode::Packed4Bit,
+                offset: None,
+            }),
+        }
+    }
+
+    /// The values of a Mode 3 or Mode 4 view as `(re, im)` pairs, in
+    /// storage order; Mode 3 components are widened to `f32`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrc::{DataView, Int16Complex};
+    /// let data = [Int16Complex { real: 3, imag: -4 }];
+    /// let pairs: Vec<_> = DataView::Int16Complex(&data).iter_complex()?.collect();
+    /// assert_eq!(pairs, [(3.0, -4.0)]);
+    /// # Ok::<(), mrc::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedMode`](crate::Error::UnsupportedMode) for
+    /// real modes.
+    pub fn iter_complex(self) -> Result<impl Iterator<Item = (f32, f32)> + 'a, crate::Error> {
+        let (ints, floats): (&[Int16Complex], &[Float32Complex]) = match self {
+            DataView::Int16Complex(v) => (v, &[]),
+            DataView::Float32Complex(v) => (&[], v),
+            _ => return Err(crate::Error::UnsupportedMode),
+        };
+        Ok(ints
+            .iter()
+            .map(|c| (f32::from(c.real), f32::from(c.imag)))
+            .chain(floats.iter().map(|c| (c.real, c.imag))))
+    }
+
+    /// [`iter_complex`](Self::iter_complex) reduced to magnitudes, as
+    /// [`ComplexToRealStrategy::Magnitude`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedMode`](crate::Error::UnsupportedMode) for
+    /// real modes.
+    pub fn iter_magnitude(self) -> Result<impl Iterator<Item = f32> + 'a, crate::Error> {
+        Ok(self
+            .iter_complex()?
+            .map(|(re, im)| (re * re + im * im).sqrt()))
+    }
+
+    /// [`iter_complex`](Self::iter_complex) reduced to phases in radians,
+    /// `atan2(im, re)` in `-π..=π`, as [`ComplexToRealStrategy::Phase`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedMode`](crate::Error::UnsupportedMode) for
+    /// real modes.
+    pub fn iter_phase(self) -> Result<impl Iterator<Item = f32> + 'a, crate::Error> {
+        Ok(self.iter_complex()?.map(|(re, im)| im.atan2(re)))
+    }
+}
+
+impl OwnedData {
+    /// The owned data as `&mut [T]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ModeMismatch`](crate::Error::ModeMismatch) if the
+    /// data's mode is not `T::MODE`.
+    pub fn as_mut_slice<T: Voxel>(&mut self) -> Result<&mut [T], crate::Error> {
+        let file_mode = DataView::from(&*self).mode();
+        T::from_owned_mut(self)
+            .map(|v| v.as_mut_slice())
+            .ok_or(crate::Error::ModeMismatch {
+                file_mode,
+                requested_mode: T::MODE,
+                offset: None,
+            })
+    }
+    /// Set every voxel to `value`, converted to the data's mode.
+    ///
+    /// Integer modes saturate and truncate toward zero, as
+    /// `f32 as i16` does; complex modes get `value` as the real part and
+    /// zero imaginary part; Mode 101 clamps to `0..=15`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrc::{DataView, OwnedData};
+    /// let mut data = OwnedData::Int16(vec![0; 4]);
+    /// data.fill(-2.7);
+    /// assert_eq!(DataView::from(&data).as_i16()?, &[-2; 4]);
+    /// # Ok::<(), mrc::Error>(())
+    /// ```
+    pub fn fill(&mut self, value: f32) {
+        let len = DataView::from(&*self).len();
+        self.fill_range(0..len, value);
+    }
+
+    /// [`fill`](Self::fill) elements `range` only; for Mode 101 the range
+    /// is in packed bytes. Out-of-range indices are ignored.
+    pub(crate) fn fill_range(&mut self, range: core::ops::Range<usize>, value: f32) {
+        fn set<T: Copy>(v: &mut [T], range: core::ops::Range<usize>, value: T) {
+            let end = range.end.min(v.len());
+            v[range.start.min(end)..end].fill(value);
+        }
+        match self {
+            OwnedData::Int8(v) => set(v, range, value as i8),
+            OwnedData::Int16(v) => set(v, range, value as i16),
+            OwnedData::Float32(v) => set(v, range, value),
+            OwnedData::Int16Complex(v) => set(
+                v,
+                range,
+                Int16Complex {
+                    real: value as i16,
+                    imag: 0,
+                },
+            ),
+            OwnedData::Float32Complex(v) => set(
+                v,
+                range,
+                Float32Complex {
+                    real: value,
+                    imag: 0.0,
+                },
+            ),
+            OwnedData::Uint16(v) => set(v, range, value as u16),
+            #[cfg(feature = "f16")]
+            OwnedData::Float16(v) => set(v, range, crate::f16::from_f32(value)),
+            OwnedData::Packed4Bit(v) => {
+                let nibble = (value as u8).min(15);
+                set(v, range, nibble | nibble << 4)
+            }
+        }
+    }
+
+    /// Overwrite every NaN and infinite value with `value`, returning how
+    /// many voxels were changed.
+    ///
+    /// Only the non-finite parts of a complex value are replaced. Integer
+    /// and packed modes are left untouched.
+    pub fn replace_non_finite(&mut self, value: f32) -> usize {
+        fn replace<T: Copy>(v: &mut [T], bad: impl Fn(T) -> bool, value: T) -> usize {
+            let mut n = 0;
+            for x in v.iter_mut().filter(|x| bad(**x)) {
+                *x = value;
+                n += 1;
+            }
+            n
+        }
+        match self {
+            OwnedData::Float32(v) => replace(v, |x| !x.is_finite(), value),
+            OwnedData::Float32Complex(v) => {
+                let mut n = 0;
+                for c in v.iter_mut() {
+                    let bad = !(c.real.is_finite() && c.imag.is_finite());
+                    for part in [&mut c.real, &mut c.imag] {
+                        if !part.is_finite() {
+                            *part = value;
+                        }
+                    }
+                    n += usize::from(bad);
+                }
+                n
+            }
+            #[cfg(feature = "f16")]
+            OwnedData::Float16(v) => replace(v, |x| !x.is_finite(), crate::f16::from_f32(value)),
+            _ => 0,
+        }
+    }
+}
+
+impl From<DataView<'_>> for OwnedData {
+    fn from(view: DataView<'_>) -> Self {
+        match view {
+            DataView::Int8(v) => OwnedData::Int8(v.to_vec()),
+            DataView::Int16(v) => OwnedData::Int16(v.to_vec()),
+            DataView::Float32(v) => OwnedData::Float32(v.to_vec()),
+            DataView::Int16Complex(v) => OwnedData::Int16Complex(v.to_vec()),
+            DataView::Float32Complex(v) => OwnedData::Float32Complex(v.to_vec()),
+            DataView::Uint16(v) => OwnedData::Uint16(v.to_vec()),
+            #[cfg(feature = "f16")]
+            DataView::Float16(v) => OwnedData::Float16(v.to_vec()),
+            DataView::Packed4Bit(v) => OwnedData::Packed4Bit(v.to_vec()),
+        }
+    }
+}
+
 impl<'a> From<&'a OwnedData> for DataView<'a> {
     fn from(owned: &'a OwnedData) -> Self {
         match owned {
@@ -115,7 +507,9 @@ impl<'a> From<&'a OwnedData> for DataView<'a> {
 /// }
 /// # Ok(()) }
 /// ```
-#[derive(Debug)]
+///
+/// `Debug` output lists the offset, shape, mode and element count rather
+/// than the voxel values, so blocks can be logged cheaply.
 pub enum DataBlock<'a> {
     /// Zero-copy variant: borrows from the reader's internal buffer.
     Borrowed {
@@ -160,20 +554,106 @@ impl<'a> DataBlock<'a> {
     #[inline]
     pub fn data(&self) -> DataView<'_> {
         match self {
-            DataBlock::Borrowed { data, .. } => match data {
-                DataView::Int8(v) => DataView::Int8(v),
-                DataView::Int16(v) => DataView::Int16(v),
-                DataView::Float32(v) => DataView::Float32(v),
-                DataView::Int16Complex(v) => DataView::Int16Complex(v),
-                DataView::Float32Complex(v) => DataView::Float32Complex(v),
-                DataView::Uint16(v) => DataView::Uint16(v),
-                #[cfg(feature = "f16")]
-                DataView::Float16(v) => DataView::Float16(v),
-                DataView::Packed4Bit(v) => DataView::Packed4Bit(v),
-            },
+            DataBlock::Borrowed { data, .. } => *data,
             DataBlock::Owned { data, .. } => data.into(),
         }
     }
+
+    /// The block's data as `&[T]`, without copying.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), mrc::Error> {
+    /// # let reader = mrc::Reader::open("density.mrc")?;
+    /// for block in reader.slices() {
+    ///     let block = block?;
+    ///     let data: &[f32] = block.data_as()?; // or block.data().as_f32()?
+    ///     println!("z={}: max {:?}", block.offset()[2], data.iter().copied().reduce(f32::max));
+    /// }
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ModeMismatch`](crate::Error::ModeMismatch) if the
+    /// block's mode is not `T::MODE`.
+    pub fn data_as<T: Voxel>(&self) -> Result<&[T], crate::Error> {
+        self.data().as_slice()
+    }
+
+    /// The block's data as `&mut [T]`.
+    ///
+    /// A borrowed block is first copied into an owned one, so edits never
+    /// reach the reader's buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ModeMismatch`](crate::Error::ModeMismatch) if the
+    /// block's mode is not `T::MODE`; the block is left unchanged.
+    pub fn data_as_mut<T: Voxel>(&mut self) -> Result<&mut [T], crate::Error> {
+        if let DataBlock::Borrowed {
+            offset,
+            shape,
+            data,
+        } = *self
+        {
+            data.as_slice::<T>()?;
+            *self = DataBlock::Owned {
+                offset,
+                shape,
+                data: data.into(),
+            };
+        }
+        match self {
+            DataBlock::Owned { data, .. } => data.as_mut_slice(),
+            DataBlock::Borrowed { .. } => unreachable!("borrowed blocks were made owned above"),
+        }
+    }
+
+    /// One-line description: offset, shape, mode, element count and whether
+    /// the data is borrowed or owned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrc::{DataBlock, OwnedData};
+    /// let block = DataBlock::Owned {
+    ///     offset: [0, 0, 3],
+    ///     shape: [2, 2, 1],
+    ///     data: OwnedData::Float32(vec![0.0; 4]),
+    /// };
+    /// assert_eq!(block.summary(), "2×2×1 Float32 block at [0, 0, 3], 4 elements (owned)");
+    /// ```
+    pub fn summary(&self) -> String {
+        let [sx, sy, sz] = self.shape();
+        let data = self.data();
+        format!(
+            "{sx}×{sy}×{sz} {:?} block at {:?}, {} elements ({})",
+            data.mode(),
+            self.offset(),
+            data.len(),
+            match self {
+                DataBlock::Borrowed { .. } => "borrowed",
+                DataBlock::Owned { .. } => "owned",
+            }
+        )
+    }
+}
+
+impl core::fmt::Debug for DataBlock<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let data = self.data();
+        f.debug_struct(match self {
+            DataBlock::Borrowed { .. } => "DataBlock::Borrowed",
+            DataBlock::Owned { .. } => "DataBlock::Owned",
+        })
+        .field("offset", &self.offset())
+        .field("shape", &self.shape())
+        .field("mode", &data.mode())
+        .field("len", &data.len())
+        .finish()
+    }
 }
 
 /// Strategy for converting complex numbers to real values.
@@ -427,6 +907,11 @@ pub struct Int16Complex {
     pub imag: i16,
 }
 
+// SAFETY: `repr(C)` pair of `i16`s, so no padding and any bit pattern is valid.
+unsafe impl bytemuck::Zeroable for Int16Complex {}
+// SAFETY: as above.
+unsafe impl bytemuck::Pod for Int16Complex {}
+
 impl Int16Complex {
     /// Convert this complex number to a real value using the given strategy.
     ///
@@ -467,6 +952,11 @@ pub struct Float32Complex {
     pub imag: f32,
 }
 
+// SAFETY: `repr(C)` pair of `f32`s, so no padding and any bit pattern is valid.
+unsafe impl bytemuck::Zeroable for Float32Complex {}
+// SAFETY: as above.
+unsafe impl bytemuck::Pod for Float32Complex {}
+
 impl Float32Complex {
     /// Convert this complex number to a real value using the given strategy.
     ///
@@ -503,33 +993,69 @@ pub trait Voxel:
 {
     /// The MRC mode constant for this voxel type
     const MODE: Mode;
-}
 
-impl Voxel for i8 {
-    const MODE: Mode = Mode::Int8;
-}
+    /// Borrow `view` as `&[Self]` when its variant holds this type.
+    #[doc(hidden)]
+    fn from_view(view: DataView<'_>) -> Option<&[Self]> {
+        let _ = view;
+        None
+    }
 
-impl Voxel for i16 {
-    const MODE: Mode = Mode::Int16;
-}
+    /// Take `data` as `Vec<Self>` when its variant holds this type.
+    #[doc(hidden)]
+    fn from_owned(data: OwnedData) -> Option<Vec<Self>> {
+        let _ = data;
+        None
+    }
 
-impl Voxel for f32 {
-    const MODE: Mode = Mode::Float32;
+    /// Borrow `data` as `&mut Vec<Self>` when its variant holds this type.
+    #[doc(hidden)]
+    fn from_owned_mut(data: &mut OwnedData) -> Option<&mut Vec<Self>> {
+        let _ = data;
+        None
+    }
 }
 
-impl Voxel for Int16Complex {
-    const MODE: Mode = Mode::Int16Complex;
-}
+macro_rules! impl_voxel {
+    ($($(#[$attr:meta])* $ty:ty => $variant:ident),* $(,)?) => {$(
+        $(#[$attr])*
+        impl Voxel for $ty {
+            const MODE: Mode = Mode::$variant;
 
-impl Voxel for Float32Complex {
-    const MODE: Mode = Mode::Float32Complex;
-}
+            #[inline]
+            fn from_view(view: DataView<'_>) -> Option<&[Self]> {
+                match view {
+                    DataView::$variant(v) => Some(v),
+                    _ => None,
+                }
+            }
 
-impl Voxel for u16 {
-    const MODE: Mode = Mode::Uint16;
+            #[inline]
+            fn from_owned(data: OwnedData) -> Option<Vec<Self>> {
+                match data {
+                    OwnedData::$variant(v) => Some(v),
+                    _ => None,
+                }
+            }
+
+            #[inline]
+            fn from_owned_mut(data: &mut OwnedData) -> Option<&mut Vec<Self>> {
+                match data {
+                    OwnedData::$variant(v) => Some(v),
+                    _ => None,
+                }
+            }
+        }
+    )*};
 }
 
-#[cfg(feature = "f16")]
-impl Voxel for crate::f16 {
-    const MODE: Mode = Mode::Float16;
+impl_voxel! {
+    i8 => Int8,
+    i16 => Int16,
+    f32 => Float32,
+    Int16Complex => Int16Complex,
+    Float32Complex => Float32Complex,
+    u16 => Uint16,
+    #[cfg(feature = "f16")]
+    crate::f16 => Float16,
 }
@@ -3,6 +3,9 @@
 //! Provides [`validate_full`] for comprehensive file validation,
 //! [`validate_reader`] for validating an already-open reader, and
 //! [`ValidationReport`] for structured results with categorized issues.
+//! [`compliance_report`] lists departures from the MRC2014 specification,
+//! each tagged with a [`Rule`], for automated ingest checks, and
+//! [`fix_header`] repairs the ones that are safe to fix.
 //!
 //! # Quick check
 //!
@@ -81,21 +84,21 @@ pub struct ValidationIssue {
 }
 
 impl ValidationIssue {
-    fn error(category: &'static str, message: String) -> Self {
+    pub(crate) fn error(category: &'static str, message: String) -> Self {
         Self {
             severity: Severity::Error,
             category,
             message,
         }
     }
-    fn warning(category: &'static str, message: String) -> Self {
+    pub(crate) fn warning(category: &'static str, message: String) -> Self {
         Self {
             severity: Severity::Warning,
             category,
             message,
         }
     }
-    fn info(category: &'static str, message: String) -> Self {
+    pub(crate) fn info(category: &'static str, message: String) -> Self {
         Self {
             severity: Severity::Info,
             category,
@@ -269,6 +272,12 @@ pub fn validate_reader(
                 HeaderValidationError::InvalidSampling { mx, my, mz } => {
                     format!("Sampling ({mx}×{my}×{mz}) must all be positive")
                 }
+                HeaderValidationError::InvalidCellLengths { xlen, ylen, zlen } => {
+                    format!("Cell lengths ({xlen}×{ylen}×{zlen} Å) must all be positive")
+                }
+                HeaderValidationError::SamplingMismatch { axis, n, m } => {
+                    format!("{axis} has {n} voxels but sampling {m}; pixel size is implausible")
+                }
                 HeaderValidationError::InvalidVolumeStack { nz, mz, ispg } => {
                     format!("Volume stack: nz={nz} not divisible by mz={mz} for ispg={ispg}")
                 }
@@ -284,8 +293,8 @@ pub fn validate_reader(
     }
 
     // ── 2. File size ──
-    if let Some(data_size) = header.data_size() {
-        let expected_total = 1024 + header.nsymbt.max(0) as usize + data_size;
+    if let Some(data_size) = header.data_size_u64() {
+        let expected_total = (header.data_offset() as u64).saturating_add(data_size);
         issues.push(ValidationIssue::info(
             "File size",
             format!("Expected {} bytes (header + ext + data)", expected_total),
@@ -519,14 +528,7 @@ pub fn validate_reader(
 /// ```
 pub fn validate_full<P: AsRef<Path>>(path: P, permissive: bool) -> Result<ValidationReport, Error> {
     let path_str = path.as_ref().to_string_lossy().into_owned();
-
-    let compression = match crate::io::reader::detect_compression(&path)? {
-        crate::io::reader::CompressionType::Plain => "plain".to_string(),
-        #[cfg(feature = "gzip")]
-        crate::io::reader::CompressionType::Gzip => "gzip".to_string(),
-        #[cfg(feature = "bzip2")]
-        crate::io::reader::CompressionType::Bzip2 => "bzip2".to_string(),
-    };
+    let compression = compression_name(path.as_ref())?;
 
     let (reader, warnings) = if permissive {
         Reader::open_permissive(&path)?
@@ -534,7 +536,494 @@ pub fn validate_full<P: AsRef<Path>>(path: P, permissive: bool) -> Result<Valida
         (Reader::open(&path)?, Vec::new())
     };
 
-    validate_reader(&reader, &path_str, &compression, &warnings)
+    validate_reader(&reader, &path_str, compression, &warnings)
+}
+
+/// Name of the compression format detected for `path`.
+pub(crate) fn compression_name(path: &Path) -> Result<&'static str, Error> {
+    Ok(match crate::io::reader::detect_compression(path)? {
+        crate::io::reader::CompressionType::Plain => "plain",
+        #[cfg(feature = "gzip")]
+        crate::io::reader::CompressionType::Gzip => "gzip",
+        #[cfg(feature = "bzip2")]
+        crate::io::reader::CompressionType::Bzip2 => "bzip2",
+    })
+}
+
+// ============================================================================
+// MRC2014 compliance
+// ============================================================================
+
+/// The MRC2014 rule a [`Finding`] is about.
+///
+/// [`id`](Self::id) gives a stable lowercase name for logs and ingest
+/// policies, e.g. to fail only on `"file_size"`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Rule {
+    /// MAP (word 53) must be `"MAP "`.
+    MapId,
+    /// MACHST (word 54) must be `44 44 00 00` or `11 11 00 00`.
+    MachineStamp,
+    /// NVERSION must be 20140 or 20141.
+    Version,
+    /// NX, NY and NZ must be positive.
+    Dimensions,
+    /// MODE must be one of 0, 1, 2, 3, 4, 6, 12 or 101; mode 3 is obsolete.
+    Mode,
+    /// MX, MY and MZ must be positive.
+    Sampling,
+    /// Cell lengths must be positive and cell angles in `(0, 180)`.
+    Cell,
+    /// MAPC, MAPR, MAPS must be a permutation of 1, 2, 3, and 1, 2, 3 for EM.
+    AxisOrder,
+    /// ISPG must be 0, 1–230 or 401–630; a volume stack's NZ a multiple of MZ.
+    SpaceGroup,
+    /// Origin must be finite.
+    Origin,
+    /// NSYMBT, EXTTYP and the extended header length must agree.
+    ExtendedHeader,
+    /// NLABL must count the filled labels, which come first.
+    Labels,
+    /// DMIN, DMAX, DMEAN and RMS must describe the data, or be unset.
+    Statistics,
+    /// The file length must be 1024 + NSYMBT + the data size.
+    FileSize,
+}
+
+impl Rule {
+    /// Stable identifier, e.g. `"machine_stamp"`.
+    pub fn id(self) -> &'static str {
+        match self {
+            Self::MapId => "map_id",
+            Self::MachineStamp => "machine_stamp",
+            Self::Version => "version",
+            Self::Dimensions => "dimensions",
+            Self::Mode => "mode",
+            Self::Sampling => "sampling",
+            Self::Cell => "cell",
+            Self::AxisOrder => "axis_order",
+            Self::SpaceGroup => "space_group",
+            Self::Origin => "origin",
+            Self::ExtendedHeader => "extended_header",
+            Self::Labels => "labels",
+            Self::Statistics => "statistics",
+            Self::FileSize => "file_size",
+        }
+    }
+}
+
+/// One departure from the MRC2014 specification.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    /// The rule that is not met.
+    pub rule: Rule,
+    /// [`Error`](Severity::Error) for a violation of the specification,
+    /// [`Warning`](Severity::Warning) for a departure from its
+    /// recommendations, [`Info`](Severity::Info) for a legal but unusual
+    /// choice.
+    pub severity: Severity,
+    /// What was found.
+    pub message: String,
+}
+
+/// Result of [`compliance_report`]: every finding, passes omitted.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComplianceReport {
+    /// Path to the checked file.
+    pub path: String,
+    /// Findings in the order the rules are checked.
+    pub findings: Vec<Finding>,
+}
+
+impl ComplianceReport {
+    /// `true` when no finding has [`Severity::Error`].
+    pub fn is_compliant(&self) -> bool {
+        !self.findings.iter().any(|f| f.severity == Severity::Error)
+    }
+
+    /// Findings for one rule.
+    pub fn for_rule(&self, rule: Rule) -> impl Iterator<Item = &Finding> {
+        self.findings.iter().filter(move |f| f.rule == rule)
+    }
+}
+
+/// Check a file against the MRC2014 specification.
+///
+/// The file is opened permissively so that every problem can be reported
+/// rather than the first. Field ranges, the axis order, the space group,
+/// NVERSION, the extended header (NSYMBT against EXTTYP and the file), the
+/// labels, the density statistics against the data, and the file length
+/// are all checked. Unlike [`validate_full`], only departures are listed,
+/// each tagged with the [`Rule`] it breaks, so the report can gate archive
+/// ingest directly.
+///
+/// # Example
+///
+/// ```no_run
+/// use mrc::validate::{compliance_report, Rule};
+///
+/// # fn main() -> Result<(), mrc::Error> {
+/// let report = compliance_report("upload.mrc")?;
+/// for f in &report.findings {
+///     println!("{:?} {}: {}", f.severity, f.rule.id(), f.message);
+/// }
+/// let size_ok = report.for_rule(Rule::FileSize).next().is_none();
+/// # let _ = size_ok;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns `Err` only when the file cannot be opened even permissively,
+/// e.g. it is shorter than a header or its extended header runs past the
+/// end.
+pub fn compliance_report<P: AsRef<Path>>(path: P) -> Result<ComplianceReport, Error> {
+    let path = path.as_ref();
+    let compression = compression_name(path)?;
+    let (reader, _) = Reader::open_permissive(path)?;
+    let file_len = if compression == "plain" {
+        Some(std::fs::metadata(path)?.len())
+    } else {
+        None
+    };
+    let h = reader.header();
+    let mut findings = Vec::new();
+    let mut add = |rule, severity, message: String| {
+        findings.push(Finding {
+            rule,
+            severity,
+            message,
+        })
+    };
+
+    if h.map != *b"MAP " {
+        add(
+            Rule::MapId,
+            Severity::Error,
+            format!(
+                "MAP is {:?}, expected \"MAP \"",
+                String::from_utf8_lossy(&h.map)
+            ),
+        );
+    }
+    match h.machst {
+        [0x44, 0x44, 0, 0] | [0x11, 0x11, 0, 0] => {}
+        [0x44, _, _, _] | [0x11, _, _, _] => add(
+            Rule::MachineStamp,
+            Severity::Warning,
+            format!("non-standard machine stamp {:02X?}", h.machst),
+        ),
+        _ => add(
+            Rule::MachineStamp,
+            Severity::Error,
+            format!("unrecognised machine stamp {:02X?}", h.machst),
+        ),
+    }
+    let nversion = h.nversion();
+    if nversion != 20140 && nversion != 20141 {
+        add(
+            Rule::Version,
+            Severity::Warning,
+            format!("NVERSION is {nversion}, expected 20140 or 20141"),
+        );
+    }
+
+    if h.nx <= 0 || h.ny <= 0 || h.nz <= 0 {
+        add(
+            Rule::Dimensions,
+            Severity::Error,
+            format!("dimensions {}×{}×{} are not all positive", h.nx, h.ny, h.nz),
+        );
+    }
+    match Mode::from_i32(h.mode) {
+        None => add(
+            Rule::Mode,
+            Severity::Error,
+            format!("mode {} is undefined", h.mode),
+        ),
+        Some(Mode::Int16Complex) => add(
+            Rule::Mode,
+            Severity::Warning,
+            "mode 3 (complex 16-bit integers) is obsolete".into(),
+        ),
+        Some(_) => {}
+    }
+    if h.mx <= 0 || h.my <= 0 || h.mz <= 0 {
+        add(
+            Rule::Sampling,
+            Severity::Error,
+            format!("sampling {}×{}×{} is not all positive", h.mx, h.my, h.mz),
+        );
+    }
+
+    let lengths = [h.xlen, h.ylen, h.zlen];
+    if !lengths.iter().all(|l| l.is_finite() && *l > 0.0) {
+        add(
+            Rule::Cell,
+            Severity::Warning,
+            format!(
+                "cell lengths {lengths:?} Å are not all positive, so the pixel size is unknown"
+            ),
+        );
+    }
+    let angles = [h.alpha, h.beta, h.gamma];
+    if !angles.iter().all(|a| *a > 0.0 && *a < 180.0) {
+        add(
+            Rule::Cell,
+            Severity::Error,
+            format!("cell angles {angles:?}° are outside (0, 180)"),
+        );
+    } else if angles != [90.0; 3] {
+        add(
+            Rule::Cell,
+            Severity::Info,
+            format!("cell angles {angles:?}° are not orthogonal"),
+        );
+    }
+
+    let axes = [h.mapc, h.mapr, h.maps];
+    let mut sorted = axes;
+    sorted.sort_unstable();
+    if sorted != [1, 2, 3] {
+        add(
+            Rule::AxisOrder,
+            Severity::Error,
+            format!("MAPC/MAPR/MAPS {axes:?} is not a permutation of 1, 2, 3"),
+        );
+    } else if axes != [1, 2, 3] {
+        add(
+            Rule::AxisOrder,
+            Severity::Warning,
+            format!("MAPC/MAPR/MAPS is {axes:?}; EM data should use 1, 2, 3"),
+        );
+    }
+    if !matches!(h.ispg, 0 | 1..=230 | 401..=630) {
+        add(
+            Rule::SpaceGroup,
+            Severity::Error,
+            format!("ISPG {} is not 0, 1–230 or 401–630", h.ispg),
+        );
+    } else if h.is_volume_stack() && h.mz > 0 && h.nz % h.mz != 0 {
+        add(
+            Rule::SpaceGroup,
+            Severity::Error,
+            format!("volume stack has NZ {} not a multiple of MZ {}", h.nz, h.mz),
+        );
+    }
+    if !h.origin.iter().all(|o| o.is_finite()) {
+        add(
+            Rule::Origin,
+            Severity::Error,
+            format!("origin {:?} is not finite", h.origin),
+        );
+    }
+
+    let ext_type = crate::ExtHeaderType::from_header(h);
+    let exttyp = String::from_utf8_lossy(&h.exttyp()).into_owned();
+    if h.nsymbt < 0 {
+        add(
+            Rule::ExtendedHeader,
+            Severity::Error,
+            format!("NSYMBT {} is negative", h.nsymbt),
+        );
+    } else if h.nsymbt > 0 {
+        match ext_type.record_size() {
+            Some(size) if h.nsymbt as usize % size != 0 => add(
+                Rule::ExtendedHeader,
+                Severity::Error,
+                format!(
+                    "NSYMBT {} is not a whole number of {size}-byte {exttyp} records",
+                    h.nsymbt
+                ),
+            ),
+            Some(_) => {}
+            None if matches!(ext_type, crate::ExtHeaderType::Unknown(_)) => add(
+                Rule::ExtendedHeader,
+                Severity::Warning,
+                format!(
+                    "NSYMBT is {} but EXTTYP {exttyp:?} is not a registered type",
+                    h.nsymbt
+                ),
+            ),
+            None => {}
+        }
+    }
+
+    let filled: Vec<bool> = h
+        .label
+        .chunks(80)
+        .map(|l| l.iter().any(|&b| b != 0 && b != b' '))
+        .collect();
+    let count = filled.iter().filter(|&&f| f).count();
+    if h.nlabl < 0 || h.nlabl as usize != count {
+        add(
+            Rule::Labels,
+            Severity::Warning,
+            format!("NLABL is {} but {count} labels contain text", h.nlabl),
+        );
+    }
+    if filled.iter().skip_while(|&&f| f).any(|&f| f) {
+        add(
+            Rule::Labels,
+            Severity::Warning,
+            "empty labels come before filled ones".into(),
+        );
+    }
+
+    // Statistics are only meaningful over the whole of the data.
+    if !reader.is_truncated() {
+        if let Err(e) = reader.validate_header_stats() {
+            add(Rule::Statistics, Severity::Warning, e.to_string());
+        }
+    }
+    let expected = h.data_offset() as u64 + h.data_size_u64().unwrap_or(0);
+    match file_len {
+        Some(len) if len != expected => add(
+            Rule::FileSize,
+            Severity::Error,
+            Error::FileSizeMismatch {
+                expected: usize::try_from(expected).unwrap_or(usize::MAX),
+                actual: usize::try_from(len).unwrap_or(usize::MAX),
+            }
+            .to_string(),
+        ),
+        None if reader.is_truncated() => add(
+            Rule::FileSize,
+            Severity::Error,
+            format!(
+                "data is truncated: {} of {} sections complete",
+                reader.complete_sections(),
+                reader.shape().nz
+            ),
+        ),
+        _ => {}
+    }
+
+    Ok(ComplianceReport {
+        path: path.to_string_lossy().into_owned(),
+        findings,
+    })
+}
+
+/// One change made by [`fix_header`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Repair {
+    /// The rule whose finding prompted the change.
+    pub rule: Rule,
+    /// The field changed, with its old and new values.
+    pub message: String,
+}
+
+/// Apply the safe corrections for `findings` to `header`, and list them.
+///
+/// Only defects that can be fixed from the header alone, without touching
+/// the data or changing where it starts, are repaired:
+///
+/// | Rule | Correction |
+/// |---|---|
+/// | [`MapId`](Rule::MapId) | MAP set to `"MAP "` |
+/// | [`MachineStamp`](Rule::MachineStamp) | MACHST set to the standard stamp for the byte order the header currently resolves to |
+/// | [`Version`](Rule::Version) | NVERSION set to 20141 |
+/// | [`Sampling`](Rule::Sampling) | non-positive MX/MY/MZ set to NX/NY/NZ (MZ of a volume stack is left alone) |
+/// | [`Cell`](Rule::Cell) | cell angles outside `(0, 180)` set to 90° |
+/// | [`Origin`](Rule::Origin) | non-finite origin components set to 0 |
+/// | [`ExtendedHeader`](Rule::ExtendedHeader) | negative NSYMBT set to 0, which is where readers already put the data |
+/// | [`Labels`](Rule::Labels) | NLABL set to the number of filled labels |
+///
+/// Other findings (dimensions, mode, axis order, space group, statistics,
+/// file size) need the data or a human and are left for the caller.
+///
+/// # Example
+///
+/// ```no_run
+/// use mrc::validate::{compliance_report, fix_header};
+///
+/// # fn main() -> Result<(), mrc::Error> {
+/// let report = compliance_report("old.mrc")?;
+/// let mut header = *mrc::Reader::open_permissive("old.mrc")?.0.header();
+/// for repair in fix_header(&mut header, &report.findings) {
+///     println!("{}: {}", repair.rule.id(), repair.message);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn fix_header(header: &mut crate::Header, findings: &[Finding]) -> Vec<Repair> {
+    let mut repairs = Vec::new();
+    let mut done = |rule, message: String| repairs.push(Repair { rule, message });
+    let mut rules: Vec<Rule> = findings.iter().map(|f| f.rule).collect();
+    rules.dedup();
+    for rule in rules {
+        let h = &mut *header;
+        match rule {
+            Rule::MapId if h.map != *b"MAP " => {
+                done(rule, format!("MAP {:02X?} → \"MAP \"", h.map));
+                h.map = *b"MAP ";
+            }
+            Rule::MachineStamp => {
+                let before = h.machst;
+                h.set_file_endian(h.detect_endian());
+                if h.machst != before {
+                    done(rule, format!("MACHST {before:02X?} → {:02X?}", h.machst));
+                }
+            }
+            Rule::Version if !matches!(h.nversion(), 20140 | 20141) => {
+                done(rule, format!("NVERSION {} → 20141", h.nversion()));
+                h.set_nversion(20141);
+            }
+            Rule::Sampling => {
+                let stack = h.is_volume_stack();
+                for (name, m, n) in [
+                    ("MX", &mut h.mx, h.nx),
+                    ("MY", &mut h.my, h.ny),
+                    ("MZ", &mut h.mz, if stack { 0 } else { h.nz }),
+                ] {
+                    if *m <= 0 && n > 0 {
+                        done(rule, format!("{name} {m} → {n}"));
+                        *m = n;
+                    }
+                }
+            }
+            Rule::Cell => {
+                for (name, a) in [
+                    ("ALPHA", &mut h.alpha),
+                    ("BETA", &mut h.beta),
+                    ("GAMMA", &mut h.gamma),
+                ] {
+                    if !(*a > 0.0 && *a < 180.0) {
+                        done(rule, format!("{name} {a} → 90"));
+                        *a = 90.0;
+                    }
+                }
+            }
+            Rule::Origin => {
+                for (i, o) in h.origin.iter_mut().enumerate() {
+                    if !o.is_finite() {
+                        done(rule, format!("ORIGIN[{i}] {o} → 0"));
+                        *o = 0.0;
+                    }
+                }
+            }
+            Rule::ExtendedHeader if h.nsymbt < 0 => {
+                done(rule, format!("NSYMBT {} → 0", h.nsymbt));
+                h.nsymbt = 0;
+            }
+            Rule::Labels => {
+                let filled = h.count_non_empty_labels() as i32;
+                if h.nlabl != filled {
+                    done(rule, format!("NLABL {} → {filled}", h.nlabl));
+                    h.nlabl = filled;
+                }
+            }
+            _ => {}
+        }
+    }
+    repairs
 }
 
 // ── Float-mode data integrity helper ──
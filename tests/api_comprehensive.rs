@@ -1,11 +1,12 @@
 //! Comprehensive public API tests — covers every method listed in APIs.md.
 //!
 //! This file is the v0.7 test plan: every public API item must be exercised.
-//! Tests use synthetic data generated programmatically (no external fixtures).
+//! Tests use synthetic data generated programmatically; the only external
+//! fixtures are the `mrcfile` golden files in `tests/data/mrcfile`.
 //! Run with: `cargo test --all-features --test api_comprehensive`
 
 use mrc::*;
-use std::io::{Cursor, Write};
+use std::io::{Cursor, Read, Write};
 
 // ── Helpers ──────────────────────────────────────────────────────────────────
 
@@ -293,6 +294,7 @@ fn reader_buffered_slab() {
     }
 }
 
+#[cfg(feature = "gzip")]
 #[test]
 fn reader_gzip_open_detect() {
     let f = TempMrc::new("gzip_detect");
@@ -868,9 +870,10 @@ fn error_invalid_header() {
     let bad = vec![0x00u8; 1024];
     std::fs::write(f.path(), &bad).unwrap();
     match Reader::open(f.path()) {
+        Err(Error::NotAnMrcFile { .. }) => {} // no MAP field or stamp
         Err(Error::InvalidHeaderDetailed(_)) => {} // validation caught it
-        Err(Error::InvalidHeader) => {}            // truly unparseable
-        other => panic!("expected InvalidHeader or InvalidHeaderDetailed, got {other:?}"),
+        Err(Error::InvalidHeader) => {}       // truly unparseable
+        other => panic!("expected NotAnMrcFile or an invalid header, got {other:?}"),
     }
 }
 
@@ -1364,3 +1367,3931 @@ fn write_as_i16_roundtrip() {
         _ => panic!("type mismatch"),
     };
 }
+
+// ── 16. TIFF import ──────────────────────────────────────────────────────────
+
+#[cfg(feature = "tiff")]
+#[test]
+fn tiff_u16_stack_import() {
+    use tiff::encoder::{TiffEncoder, colortype};
+    let (nx, ny, nz) = (6u32, 4u32, 3usize);
+    let pages: Vec<Vec<u16>> = (0..nz)
+        .map(|z| (0..nx * ny).map(|i| (z as u32 * 100 + i) as u16).collect())
+        .collect();
+    let mut buf = Cursor::new(Vec::new());
+    {
+        let mut enc = TiffEncoder::new(&mut buf).unwrap();
+        for p in &pages {
+            enc.write_image::<colortype::Gray16>(nx, ny, p).unwrap();
+        }
+    }
+    buf.set_position(0);
+
+    let r = Reader::from_tiff_reader(buf).unwrap();
+    assert_eq!(r.mode(), Mode::Uint16);
+    assert_eq!(r.shape(), VolumeShape::new(6, 4, 3));
+    assert!(r.is_image_stack());
+    assert_eq!(r.header().dmax, 223.0);
+    let block = r.read_volume().unwrap();
+    let DataView::Uint16(d) = block.data() else {
+        panic!("expected Uint16")
+    };
+    assert_eq!(d, pages.concat());
+}
+
+#[cfg(feature = "tiff")]
+#[test]
+fn tiff_f32_and_u8_import() {
+    use tiff::encoder::{TiffEncoder, colortype};
+    let mut buf = Cursor::new(Vec::new());
+    TiffEncoder::new(&mut buf)
+        .unwrap()
+        .write_image::<colortype::Gray32Float>(2, 2, &[0.5f32, -1.0, 2.0, 3.5])
+        .unwrap();
+    buf.set_position(0);
+    let r = Reader::from_tiff_reader(buf).unwrap();
+    assert_eq!(r.mode(), Mode::Float32);
+    assert_eq!(r.shape().nz, 1);
+
+    let mut buf = Cursor::new(Vec::new());
+    TiffEncoder::new(&mut buf)
+        .unwrap()
+        .write_image::<colortype::Gray8>(2, 1, &[7u8, 255])
+        .unwrap();
+    buf.set_position(0);
+    let r = Reader::from_tiff_reader(buf).unwrap();
+    assert_eq!(r.mode(), Mode::Uint16);
+    let block = r.read_volume().unwrap();
+    let DataView::Uint16(d) = block.data() else {
+        panic!("expected Uint16")
+    };
+    assert_eq!(d, vec![7u16, 255]);
+}
+
+#[cfg(feature = "tiff")]
+#[test]
+fn tiff_mixed_sample_types_rejected() {
+    use tiff::encoder::{TiffEncoder, colortype};
+    let mut buf = Cursor::new(Vec::new());
+    {
+        let mut enc = TiffEncoder::new(&mut buf).unwrap();
+        enc.write_image::<colortype::GrayI16>(2, 1, &[-1i16, 2])
+            .unwrap();
+        enc.write_image::<colortype::Gray16>(2, 1, &[1u16, 2])
+            .unwrap();
+    }
+    buf.set_position(0);
+    assert!(matches!(
+        Reader::from_tiff_reader(buf),
+        Err(Error::ModeMismatch {
+            file_mode: Mode::Int16,
+            requested_mode: Mode::Uint16,
+            offset: Some([0, 0, 1]),
+        })
+    ));
+}
+
+// ── 17. C API ────────────────────────────────────────────────────────────────
+
+#[cfg(feature = "capi")]
+#[test]
+fn capi_open_read_close() {
+    use mrc::capi::*;
+    let f = TempMrc::new("capi");
+    let data = write_f32_volume(&f, 4, 3, 2);
+    let path = std::ffi::CString::new(f.path().to_str().unwrap()).unwrap();
+
+    let mut h = std::ptr::null_mut();
+    assert_eq!(unsafe { mrc_open(path.as_ptr(), &mut h) }, MRC_OK);
+    assert_eq!(unsafe { mrc_header_get_nx(h) }, 4);
+    assert_eq!(unsafe { mrc_header_get_nz(h) }, 2);
+    assert_eq!(unsafe { mrc_header_get_mode(h) }, 2);
+
+    let mut section = vec![0f32; 12];
+    assert_eq!(
+        unsafe { mrc_read_section(h, 1, section.as_mut_ptr(), 12) },
+        MRC_OK
+    );
+    assert_eq!(section, data[12..]);
+    assert_eq!(
+        unsafe { mrc_read_section(h, 2, section.as_mut_ptr(), 12) },
+        MRC_ERR_BOUNDS
+    );
+    assert_eq!(
+        unsafe { mrc_read_section(h, 0, section.as_mut_ptr(), 5) },
+        MRC_ERR_MISMATCH
+    );
+    unsafe { mrc_close(h) };
+
+    let missing = std::ffi::CString::new("/nonexistent/x.mrc").unwrap();
+    assert_eq!(unsafe { mrc_open(missing.as_ptr(), &mut h) }, MRC_ERR_IO);
+    assert!(h.is_null());
+    let msg = unsafe { std::ffi::CStr::from_ptr(mrc_status_message(MRC_ERR_IO)) };
+    assert_eq!(msg.to_str().unwrap(), "I/O error");
+    let msg = unsafe { std::ffi::CStr::from_ptr(mrc_status_message(MRC_ERR_PANIC)) };
+    assert_eq!(msg.to_str().unwrap(), "internal panic");
+    assert_eq!(
+        unsafe { mrc_open(std::ptr::null(), &mut h) },
+        MRC_ERR_INVALID_ARGUMENT
+    );
+}
+
+// ── 18. In-memory (wasm-friendly) parsing ────────────────────────────────────
+
+#[test]
+fn reader_from_slice_plain_and_gzip() {
+    let f = TempMrc::new("from_slice");
+    let data = write_f32_volume(&f, 4, 4, 2);
+    let bytes = std::fs::read(f.path()).unwrap();
+    let r = Reader::from_slice(&bytes).unwrap();
+    assert_eq!(r.convert::<f32>().read_volume().unwrap().data, data);
+
+    #[cfg(feature = "gzip")]
+    {
+        let mut enc = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        enc.write_all(&bytes).unwrap();
+        let gz = enc.finish().unwrap();
+        let r = Reader::from_slice(&gz).unwrap();
+        assert_eq!(r.convert::<f32>().read_volume().unwrap().data, data);
+    }
+
+    let (r, _) = Reader::from_slice_permissive(&bytes[..1024 + 40]).unwrap();
+    assert!(r.is_truncated());
+}
+
+// ── 19. OME-Zarr export ──────────────────────────────────────────────────────
+
+#[cfg(feature = "zarr")]
+#[test]
+fn export_ome_zarr_pyramid() {
+    let f = TempMrc::new("zarr_src");
+    let data = write_f32_volume(&f, 6, 4, 4);
+    let r = Reader::open(f.path()).unwrap();
+    let out = std::env::temp_dir().join(format!("mrc_api_test_{}.zarr", std::process::id()));
+    let _ = std::fs::remove_dir_all(&out);
+
+    r.export_ome_zarr(&out, [4, 4, 2], 2).unwrap();
+
+    let zarray = std::fs::read_to_string(out.join("0/.zarray")).unwrap();
+    assert!(zarray.contains("\"shape\":[4,4,6]"));
+    assert!(zarray.contains("\"chunks\":[2,4,4]"));
+    let zattrs = std::fs::read_to_string(out.join(".zattrs")).unwrap();
+    assert!(zattrs.contains("\"path\":\"1\""));
+
+    // Chunk (0,0,1) holds x = 4..6, padded to 4 with zeros.
+    let chunk = std::fs::read(out.join("0/0/0/1")).unwrap();
+    assert_eq!(chunk.len(), 4 * 4 * 2 * 4);
+    let first = f32::from_le_bytes(chunk[0..4].try_into().unwrap());
+    assert_eq!(first, data[4]);
+    let pad = f32::from_le_bytes(chunk[8..12].try_into().unwrap());
+    assert_eq!(pad, 0.0);
+
+    // Level 1 is 3x2x2, each voxel the mean of a 2x2x2 block.
+    assert!(
+        std::fs::read_to_string(out.join("1/.zarray"))
+            .unwrap()
+            .contains("\"shape\":[2,2,3]")
+    );
+    let l1 = std::fs::read(out.join("1/0/0/0")).unwrap();
+    let v0 = f32::from_le_bytes(l1[0..4].try_into().unwrap());
+    let expect = [0, 1, 6, 7, 24, 25, 30, 31]
+        .iter()
+        .map(|&i| data[i])
+        .sum::<f32>()
+        / 8.0;
+    assert_eq!(v0, expect);
+    let _ = std::fs::remove_dir_all(&out);
+}
+
+#[cfg(feature = "zarr")]
+#[test]
+fn export_ome_zarr_scale_stops_on_short_axis() {
+    let f = TempMrc::new("zarr_thin");
+    write_f32_volume(&f, 16, 16, 3);
+    let r = Reader::open(f.path()).unwrap();
+    let out = std::env::temp_dir().join(format!("mrc_api_thin_{}.zarr", std::process::id()));
+    let _ = std::fs::remove_dir_all(&out);
+
+    r.export_ome_zarr(&out, [8, 8, 8], 4).unwrap();
+    let zattrs = std::fs::read_to_string(out.join(".zattrs")).unwrap();
+    // z is binned 3 -> 1 once; later levels keep its scale.
+    let [vx, vy, vz] = r
+        .header()
+        .voxel_size()
+        .map(|v| if v > 0.0 { v } else { 1.0 });
+    for [z, y, x] in [
+        [1.0, 1.0, 1.0],
+        [2.0, 2.0, 2.0],
+        [2.0, 4.0, 4.0],
+        [2.0, 8.0, 8.0],
+    ] {
+        let scale = format!("\"scale\":[{:?},{:?},{:?}]", vz * z, vy * y, vx * x);
+        assert!(zattrs.contains(&scale), "{scale} not in {zattrs}");
+    }
+    assert!(
+        std::fs::read_to_string(out.join("3/.zarray"))
+            .unwrap()
+            .contains("\"shape\":[1,2,2]")
+    );
+    let _ = std::fs::remove_dir_all(&out);
+}
+
+// ── 20. mrcfile (Python) compatibility ───────────────────────────────────────
+
+#[test]
+fn mrcfile_compat_writer_matches_golden_vectors() {
+    use mrc::mrcfile_compat::{self, vectors};
+
+    let f = TempMrc::new("mrcfile_f32");
+    let mut w = WriterBuilder::new(f.path())
+        .shape([3, 2, 2])
+        .mode::<f32>()
+        .mrcfile_conventions(vectors::created())
+        .finish()
+        .unwrap();
+    w.set_data(&(0..12).map(|v| v as f32).collect::<Vec<_>>())
+        .unwrap();
+    w.finalize().unwrap();
+    drop(w);
+    assert_eq!(std::fs::read(f.path()).unwrap(), vectors::float32_volume());
+
+    let f = TempMrc::new("mrcfile_i16");
+    let mut w = WriterBuilder::new(f.path())
+        .shape([2, 2, 3])
+        .mode::<i16>()
+        .image_stack()
+        .mrcfile_conventions(vectors::created())
+        .finish()
+        .unwrap();
+    w.set_data(&(-6..6).collect::<Vec<i16>>()).unwrap();
+    w.finalize().unwrap();
+    drop(w);
+    assert_eq!(
+        std::fs::read(f.path()).unwrap(),
+        vectors::int16_image_stack()
+    );
+
+    let report = mrcfile_compat::validate(f.path()).unwrap();
+    assert!(report.issues.is_empty(), "{:?}", report.issues);
+}
+
+#[test]
+fn mrcfile_compat_strict_validation_rules() {
+    use mrc::mrcfile_compat::{validate, validate_reader, vectors};
+
+    let mut bytes = vectors::float32_volume().to_vec();
+    let r = Reader::from_slice(&bytes).unwrap();
+    assert!(validate_reader(&r, "mem", "plain", Some(bytes.len() as u64)).is_valid());
+
+    // Unknown machine stamp, old nversion and an unset label count.
+    bytes[212] = 0x00;
+    bytes[108..112].copy_from_slice(&20000i32.to_le_bytes());
+    bytes[220..224].copy_from_slice(&0i32.to_le_bytes());
+    bytes.extend_from_slice(&[0; 4]);
+    let f = TempMrc::new("mrcfile_bad");
+    std::fs::write(f.path(), &bytes).unwrap();
+    let report = validate(f.path()).unwrap();
+    let messages: Vec<_> = report.issues.iter().map(|i| i.message.as_str()).collect();
+    assert!(
+        messages.iter().any(|m| m.contains("machine stamp")),
+        "{messages:?}"
+    );
+    assert!(messages.iter().any(|m| m.contains("MRC version 20000")));
+    assert!(messages.iter().any(|m| m.contains("nlabl is 0")));
+    assert!(messages.iter().any(|m| m.contains("File is 1076 bytes")));
+}
+
+// ── 21. Particle boxing ──────────────────────────────────────────────────────
+
+#[test]
+fn extract_particles_edge_policies_and_stack_write() {
+    use mrc::EdgePolicy;
+
+    let f = TempMrc::new("boxing_src");
+    let data = write_f32_volume(&f, 8, 8, 1);
+    let r = Reader::open(f.path()).unwrap();
+    let centers = [[4.0, 4.0, 0.0], [0.0, 0.0, 0.0], [50.0, 50.0, 0.0]];
+
+    let s = r
+        .extract_particles(&centers, [4, 4, 1], EdgePolicy::Skip)
+        .unwrap();
+    assert_eq!(s.indices, vec![0]);
+    assert_eq!(s.corners, vec![[2, 2, 0]]);
+    assert_eq!(s.particle(0).unwrap()[0], data[2 * 8 + 2]);
+
+    let s = r
+        .extract_particles(&centers, [4, 4, 1], EdgePolicy::Constant(-1.0))
+        .unwrap();
+    assert_eq!(s.indices, vec![0, 1]);
+    let p = s.particle(1).unwrap();
+    assert_eq!(p[0], -1.0);
+    assert_eq!(p[2 * 4 + 2], data[0]);
+
+    let s = r
+        .extract_particles(&centers, [4, 4, 1], EdgePolicy::Replicate)
+        .unwrap();
+    let p = s.particle(1).unwrap();
+    assert_eq!(p[0], data[0]);
+    assert_eq!(p[3], data[1]);
+    let v = s.voxel_size;
+    assert_eq!(s.origin(1), Some([-2.0 * v[0], -2.0 * v[1], 0.0]));
+
+    let out = TempMrc::new("boxing_stack");
+    s.write(out.path()).unwrap();
+    let stack = Reader::open(out.path()).unwrap();
+    assert!(stack.is_image_stack());
+    assert_eq!(stack.shape().nz, 2);
+    assert_eq!(stack.convert::<f32>().read_volume().unwrap().data, s.data);
+}
+
+#[test]
+fn extract_particles_3d_volume_stack() {
+    let f = TempMrc::new("boxing_vol");
+    let data = write_f32_volume(&f, 6, 6, 6);
+    let r = Reader::open(f.path()).unwrap();
+    let s = r
+        .extract_particles(&[[3.0, 3.0, 3.0]], [2, 2, 2], mrc::EdgePolicy::Mean)
+        .unwrap();
+    assert_eq!(s.particle(0).unwrap()[0], data[(2 * 6 + 2) * 6 + 2]);
+
+    let out = TempMrc::new("boxing_vstack");
+    s.write(out.path()).unwrap();
+    let stack = Reader::open(out.path()).unwrap();
+    assert!(stack.is_volume_stack());
+    assert_eq!(stack.header().mz, 2);
+}
+
+// ── 22. MrcSource trait ──────────────────────────────────────────────────────
+
+#[test]
+fn mrc_source_trait_objects() {
+    use mrc::MrcSource;
+    use std::sync::Arc;
+
+    fn section_sum(src: &dyn MrcSource, z: usize) -> f32 {
+        match src.read_section(z).unwrap().data() {
+            DataView::Float32(v) => v.iter().sum(),
+            _ => panic!("expected float32"),
+        }
+    }
+
+    let f = TempMrc::new("source_trait");
+    let data = write_f32_volume(&f, 4, 3, 2);
+    let reader = Reader::open(f.path()).unwrap();
+    let expected: f32 = data[12..].iter().sum();
+
+    let sources: Vec<Box<dyn MrcSource>> = vec![
+        Box::new(Reader::open(f.path()).unwrap()),
+        Box::new(Arc::new(
+            Reader::from_bytes(std::fs::read(f.path()).unwrap()).unwrap(),
+        )),
+        Box::new(&reader),
+    ];
+    for src in &sources {
+        assert_eq!(src.len(), 24);
+        assert_eq!(src.mode(), Some(Mode::Float32));
+        assert_eq!(section_sum(src.as_ref(), 1), expected);
+        assert!(src.read_section(2).is_err());
+    }
+}
+
+// ── 23. OpenOptions ──────────────────────────────────────────────────────────
+
+#[test]
+fn open_options_backends_and_endian_policy() {
+    use mrc::{EndianPolicy, OpenOptions};
+
+    let f = TempMrc::new("open_options_le");
+    let data = write_f32_volume(&f, 4, 4, 2);
+    for opts in [
+        OpenOptions::new(),
+        OpenOptions::new().eager(true),
+        OpenOptions::new().mmap(false).permissive(true),
+    ] {
+        let r = opts.open(f.path()).unwrap();
+        assert_eq!(r.convert::<f32>().read_volume().unwrap().data, data);
+    }
+
+    let be = TempMrc::new("open_options_be");
+    let mut header = HeaderBuilder::new()
+        .shape([4, 2, 1])
+        .mode::<f32>()
+        .build()
+        .unwrap();
+    header.set_file_endian(FileEndian::BigEndian);
+    let mut bytes = [0u8; 1024];
+    header.encode_to_bytes(&mut bytes);
+    let values: Vec<f32> = (0..8).map(|v| v as f32 + 0.5).collect();
+    let mut file = bytes.to_vec();
+    file.extend(values.iter().flat_map(|v| v.to_be_bytes()));
+    std::fs::write(be.path(), file).unwrap();
+
+    let swapped = OpenOptions::new().open(be.path()).unwrap();
+    assert_eq!(swapped.endian(), FileEndian::BigEndian);
+    assert_eq!(swapped.convert::<f32>().read_volume().unwrap().data, values);
+
+    let err = OpenOptions::new()
+        .endian_policy(EndianPolicy::Error)
+        .open(be.path());
+    assert!(matches!(err, Err(mrc::Error::Io(_))));
+
+    let raw = OpenOptions::new()
+        .endian_policy(EndianPolicy::Preserve)
+        .open(be.path())
+        .unwrap();
+    assert_eq!(raw.endian(), FileEndian::native());
+    assert_eq!(raw.header().detect_endian(), FileEndian::native());
+    let preserved = raw.convert::<f32>().read_volume().unwrap().data;
+    assert_eq!(preserved[1], f32::from_bits(1.5f32.to_bits().swap_bytes()));
+}
+
+// ── 24. Debug and summary formatting ─────────────────────────────────────────
+
+#[test]
+fn reader_and_block_debug_omit_voxel_data() {
+    let f = TempMrc::new("debug_summary");
+    write_f32_volume(&f, 4, 3, 2);
+    let r = Reader::open(f.path()).unwrap();
+
+    let dbg = format!("{r:?}");
+    assert!(dbg.contains("shape: [4, 3, 2]"), "{dbg}");
+    assert!(dbg.contains("data_len: 96"));
+    assert!(dbg.len() < 300);
+    let summary = r.summary();
+    assert!(
+        summary.starts_with("4×3×2 Float32, little-endian, "),
+        "{summary}"
+    );
+    assert!(summary.ends_with("ext header 0 B, data 96 B"));
+
+    let block = r.subregion([1, 0, 1], [2, 3, 1]).unwrap();
+    let dbg = format!("{block:?}");
+    assert!(
+        dbg.contains("mode: Float32") && dbg.contains("len: 6"),
+        "{dbg}"
+    );
+    assert!(
+        block
+            .summary()
+            .starts_with("2×3×1 Float32 block at [1, 0, 1], 6 elements")
+    );
+}
+
+// ── 25. Typed volume ─────────────────────────────────────────────────────────
+
+#[test]
+fn typed_volume_checks_mode_once() {
+    let f = TempMrc::new("typed_volume");
+    let data = write_f32_volume(&f, 4, 3, 2);
+    let r = Reader::open(f.path()).unwrap();
+
+    match r.volume::<i16>() {
+        Err(mrc::Error::ModeMismatch {
+            file_mode,
+            requested_mode,
+            ..
+        }) => {
+            assert_eq!(file_mode, Mode::Float32);
+            assert_eq!(requested_mode, Mode::Int16);
+        }
+        other => panic!("expected ModeMismatch, got {other:?}"),
+    }
+
+    let vol = r.volume::<f32>().unwrap();
+    assert!(vol.is_borrowed());
+    assert_eq!(vol.as_slice(), &data[..]);
+    assert_eq!(vol[(3, 2, 1)], data[23]);
+    assert_eq!(vol.get(1, 2, 0), Some(&data[9]));
+    assert_eq!(vol.get(4, 0, 0), None);
+    assert_eq!(vol.section(1), Some(&data[12..24]));
+    assert_eq!(vol.section(2), None);
+    assert_eq!(vol.sections().len(), 2);
+    assert_eq!(vol.iter().count(), 24);
+
+    let block = vol.into_block();
+    assert_eq!(block.shape, [4, 3, 2]);
+    assert_eq!(block.data, data);
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn typed_volume_index_panics_out_of_bounds() {
+    let f = TempMrc::new("typed_volume_oob");
+    write_f32_volume(&f, 2, 2, 1);
+    let r = Reader::open(f.path()).unwrap();
+    let _ = r.volume::<f32>().unwrap()[(0, 0, 1)];
+}
+
+// ── 26. Whole-file serialization ─────────────────────────────────────────────
+
+#[test]
+fn to_bytes_round_trips_file_image() {
+    let f = TempMrc::new("to_bytes");
+    let data = write_f32_volume(&f, 3, 2, 2);
+    let on_disk = std::fs::read(f.path()).unwrap();
+
+    let r = Reader::open(f.path()).unwrap();
+    let bytes = r.to_bytes();
+    assert_eq!(bytes, on_disk);
+
+    let copy = Reader::from_bytes(bytes).unwrap();
+    assert_eq!(copy.header(), r.header());
+    assert_eq!(copy.convert::<f32>().read_volume().unwrap().data, data);
+}
+
+#[test]
+fn encoded_stream_swaps_on_the_fly() {
+    let f = TempMrc::new("encoded_stream");
+    let data = write_f32_volume(&f, 3, 2, 2);
+    let r = Reader::open(f.path()).unwrap();
+
+    let mut same = Vec::new();
+    let mut stream = r.encoded(r.endian());
+    assert_eq!(stream.byte_len(), r.to_bytes().len());
+    stream.read_to_end(&mut same).unwrap();
+    assert_eq!(same, r.to_bytes());
+    assert_eq!(stream.remaining(), 0);
+
+    // Odd-sized reads split words across calls.
+    let other = match r.endian() {
+        FileEndian::LittleEndian => FileEndian::BigEndian,
+        FileEndian::BigEndian => FileEndian::LittleEndian,
+    };
+    let mut swapped = Vec::new();
+    let mut stream = r.encoded(other);
+    let mut buf = [0u8; 7];
+    loop {
+        let n = stream.read(&mut buf).unwrap();
+        if n == 0 {
+            break;
+        }
+        swapped.extend_from_slice(&buf[..n]);
+    }
+    assert_eq!(swapped.len(), same.len());
+    assert_ne!(swapped[1024..], same[1024..]);
+
+    let back = Reader::from_bytes(swapped).unwrap();
+    assert_eq!(back.endian(), other);
+    assert_eq!(back.convert::<f32>().read_volume().unwrap().data, data);
+}
+
+// ── 27. Cloning and reopening ────────────────────────────────────────────────
+
+#[test]
+fn try_clone_shares_data_across_threads() {
+    let f = TempMrc::new("try_clone");
+    let data = write_f32_volume(&f, 4, 4, 3);
+    let r = Reader::open(f.path()).unwrap();
+
+    let second = r.try_clone().unwrap();
+    let worker = std::thread::spawn(move || {
+        second
+            .convert::<f32>()
+            .subregion([0, 0, 2], [4, 4, 1])
+            .unwrap()
+            .data
+    });
+    let front = r.convert::<f32>().subregion([0, 0, 0], [4, 4, 1]).unwrap();
+    assert_eq!(front.data, data[..16]);
+    assert_eq!(worker.join().unwrap(), data[32..]);
+}
+
+#[test]
+fn reopen_uses_original_path_and_options() {
+    let f = TempMrc::new("reopen");
+    write_f32_volume(&f, 2, 2, 1);
+    let r = OpenOptions::new()
+        .endian_policy(EndianPolicy::Preserve)
+        .eager(true)
+        .open(f.path())
+        .unwrap();
+    write_f32_volume(&f, 3, 2, 1);
+
+    let again = r.reopen().unwrap();
+    assert_eq!(again.shape().nx, 3);
+    assert_eq!(r.shape().nx, 2);
+    assert_eq!(
+        again.summary().contains("buffered"),
+        r.summary().contains("buffered")
+    );
+
+    let in_memory = Reader::from_bytes(r.to_bytes()).unwrap();
+    match in_memory.reopen() {
+        Err(mrc::Error::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::Unsupported),
+        other => panic!("expected Unsupported, got {other:?}"),
+    }
+}
+
+// ── 28. Parallel sections ────────────────────────────────────────────────────
+
+#[cfg(feature = "parallel")]
+#[test]
+fn par_sections_matches_sequential() {
+    use rayon::prelude::*;
+
+    let f = TempMrc::new("par_sections");
+    write_f32_volume(&f, 5, 4, 6);
+    let r = Reader::open(f.path()).unwrap();
+
+    let sums: Vec<f32> = r
+        .par_sections()
+        .map(|s| match s.unwrap().data() {
+            DataView::Float32(v) => v.iter().sum(),
+            _ => unreachable!(),
+        })
+        .collect();
+    let expected: Vec<f32> = (0..6)
+        .map(|z| (z * 20..(z + 1) * 20).map(|v| v as f32).sum())
+        .collect();
+    assert_eq!(sums, expected);
+    assert_eq!(r.par_sections().len(), 6);
+}
+
+// ── 29. Contiguous-run block reads ───────────────────────────────────────────
+
+#[test]
+fn contiguous_blocks_are_borrowed_and_strided_blocks_gathered() {
+    let f = TempMrc::new("contiguous_runs");
+    let data = write_f32_volume(&f, 4, 3, 3);
+    let r = Reader::open(f.path()).unwrap();
+
+    // Full-width rows inside one section, and a partial single row, are one
+    // contiguous range each.
+    for (offset, shape) in [([0, 1, 2], [4, 2, 1]), ([1, 2, 0], [2, 1, 1])] {
+        let block = r.subregion(offset, shape).unwrap();
+        assert!(
+            block.summary().ends_with("(borrowed)"),
+            "{}",
+            block.summary()
+        );
+    }
+
+    // Full-width rows across sections: one run per section.
+    let rows = r.convert::<f32>().subregion([0, 1, 0], [4, 2, 3]).unwrap();
+    let expected: Vec<f32> = (0..3)
+        .flat_map(|z| data[z * 12 + 4..z * 12 + 12].to_vec())
+        .collect();
+    assert_eq!(rows.data, expected);
+
+    // Partial rows: one run per row.
+    let bytes = r.read_block_bytes([1, 0, 1], [2, 3, 2]).unwrap();
+    let got: Vec<f32> = bytes
+        .chunks_exact(4)
+        .map(|c| f32::from_le_bytes(c.try_into().unwrap()))
+        .collect();
+    let expected: Vec<f32> = [1usize, 2]
+        .iter()
+        .flat_map(|&z| (0..3).flat_map(move |y| [z * 12 + y * 4 + 1, z * 12 + y * 4 + 2]))
+        .map(|i| data[i])
+        .collect();
+    assert_eq!(got, expected);
+}
+
+// ── 30. Multi-threaded eager reads ───────────────────────────────────────────
+
+#[test]
+fn eager_open_with_read_threads_matches_mmap() {
+    let f = TempMrc::new("read_threads");
+    let data = write_f32_volume(&f, 128, 128, 40);
+    let r = OpenOptions::new()
+        .eager(true)
+        .read_threads(4)
+        .open(f.path())
+        .unwrap();
+    assert!(r.summary().contains("buffered"), "{}", r.summary());
+    assert_eq!(r.convert::<f32>().read_volume().unwrap().data, data);
+    assert_eq!(r.to_bytes(), std::fs::read(f.path()).unwrap());
+}
+
+// ── 31. Aligned in-memory buffers ────────────────────────────────────────────
+
+#[test]
+fn in_memory_readers_borrow_regardless_of_input_alignment() {
+    let f = TempMrc::new("aligned_buf");
+    write_f32_volume(&f, 4, 4, 2);
+    let bytes = std::fs::read(f.path()).unwrap();
+
+    // Shift the file image by every offset within a word so the source
+    // slice is misaligned for f32 in most iterations.
+    for shift in 0..4 {
+        let mut padded = vec![0u8; shift];
+        padded.extend_from_slice(&bytes);
+        let r = Reader::from_slice(&padded[shift..]).unwrap();
+        let section = r.subregion([0, 0, 1], [4, 4, 1]).unwrap();
+        assert!(section.summary().ends_with("(borrowed)"), "shift {shift}");
+        assert_eq!(r.raw_bytes().as_ptr() as usize % BUF_ALIGN, 0);
+    }
+}
+
+// ── 32. Prefaulting ──────────────────────────────────────────────────────────
+
+#[test]
+fn prefault_open_reads_same_data() {
+    let f = TempMrc::new("prefault");
+    let data = write_f32_volume(&f, 64, 64, 4);
+    let r = OpenOptions::new().prefault(true).open(f.path()).unwrap();
+    r.prefault();
+    assert_eq!(r.convert::<f32>().read_volume().unwrap().data, data);
+    assert!(r.reopen().is_ok());
+}
+
+// ── 33. Streaming configuration ──────────────────────────────────────────────
+
+#[test]
+fn streaming_config_bounds_chunks_without_changing_results() {
+    let f = TempMrc::new("streaming_config");
+    let data = write_f32_volume(&f, 16, 8, 10);
+    let tiny = StreamingConfig {
+        chunk_bytes: 1000,
+        max_resident_bytes: 1000,
+    };
+
+    let r = Reader::open(f.path()).unwrap();
+    let stats = r.data_stats_with(&tiny).unwrap();
+    let whole = r.data_stats_with(&StreamingConfig::default()).unwrap();
+    assert_eq!((stats.0, stats.1, stats.2), (whole.0, whole.1, whole.2));
+    assert!((stats.3 - whole.3).abs() < 1e-3);
+    assert_eq!((stats.0, stats.1), (0.0, 1279.0));
+    // Population std dev of 0..n is sqrt((n² - 1) / 12).
+    let rms = ((1280.0f64 * 1280.0 - 1.0) / 12.0).sqrt() as f32;
+    assert!(stats.2 == 639.5 && (stats.3 - rms).abs() < 1e-2);
+
+    // 512-byte f32 planes: source + converted f32 copy is 1 KiB per plane,
+    // so a 1000-byte ceiling still yields single planes.
+    let conv = r.convert::<f32>();
+    let slabs: Vec<_> = conv.chunks(&tiny).map(Result::unwrap).collect();
+    assert_eq!(slabs.len(), 10);
+    let roomy = StreamingConfig {
+        chunk_bytes: 2048,
+        max_resident_bytes: 1 << 20,
+    };
+    let slabs: Vec<_> = conv.chunks(&roomy).map(Result::unwrap).collect();
+    assert_eq!(
+        slabs.iter().map(|b| b.shape[2]).collect::<Vec<_>>(),
+        [4, 4, 2]
+    );
+    let joined: Vec<f32> = slabs.into_iter().flat_map(|b| b.data).collect();
+    assert_eq!(joined, data);
+
+    let out = TempMrc::new("streaming_config_out");
+    let mut w = create(out.path())
+        .shape([16, 8, 10])
+        .mode::<f32>()
+        .finish()
+        .unwrap();
+    w.set_data(&data).unwrap();
+    w.update_header_stats_with(&tiny).unwrap();
+    assert_eq!(w.header().dmax, 1279.0);
+    assert!((w.header().rms - rms).abs() < 1e-2);
+    w.finalize().unwrap();
+}
+
+// ── 34. Ranged byte reads ────────────────────────────────────────────────────
+
+#[test]
+fn read_bytes_maps_data_relative_ranges() {
+    let f = TempMrc::new("read_bytes");
+    let data = write_f32_volume(&f, 4, 2, 2);
+    let r = Reader::open(f.path()).unwrap();
+
+    let mut buf = [0u8; 8];
+    r.read_bytes(20..28, &mut buf).unwrap();
+    assert_eq!(f32::from_le_bytes(buf[..4].try_into().unwrap()), data[5]);
+    assert_eq!(f32::from_le_bytes(buf[4..].try_into().unwrap()), data[6]);
+
+    let mut empty = [0u8; 0];
+    r.read_bytes(64..64, &mut empty).unwrap();
+    assert!(matches!(
+        r.read_bytes(60..68, &mut buf),
+        Err(mrc::Error::BoundsError { .. })
+    ));
+    assert!(matches!(
+        r.read_bytes(0..4, &mut buf),
+        Err(mrc::Error::BlockShapeMismatch {
+            expected: 4,
+            actual: 8
+        })
+    ));
+}
+
+// ── 35. Incremental statistics while writing ─────────────────────────────────
+
+/// A write-only sink: any attempt to read the data back fails.
+struct NoReadBack(Cursor<Vec<u8>>);
+
+impl Read for NoReadBack {
+    fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+        Err(std::io::Error::other("read-back attempted"))
+    }
+}
+
+impl Write for NoReadBack {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.write(buf)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.0.flush()
+    }
+}
+
+impl std::io::Seek for NoReadBack {
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        self.0.seek(pos)
+    }
+}
+
+fn f32_header(nx: i32, ny: i32, nz: i32) -> Header {
+    let mut h = Header::new();
+    (h.nx, h.ny, h.nz) = (nx, ny, nz);
+    (h.mx, h.my, h.mz) = (nx, ny, nz);
+    h.mode = 2;
+    h
+}
+
+#[test]
+fn in_order_section_writes_need_no_read_back() {
+    let sink = NoReadBack(Cursor::new(Vec::new()));
+    let mut w = Writer::from_writer(sink, f32_header(8, 4, 6), &[]).unwrap();
+    let data: Vec<f32> = (0..8 * 4 * 6).map(|i| i as f32 - 50.0).collect();
+    // One section, then a two-section slab, then the rest.
+    for (z, n) in [(0, 1), (1, 2), (3, 3)] {
+        let part = data[z * 32..(z + n) * 32].to_vec();
+        w.write_block(&VoxelBlock::new([0, 0, z], [8, 4, n], part).unwrap())
+            .unwrap();
+    }
+    w.update_header_stats().unwrap();
+    let h = w.header();
+    assert_eq!((h.dmin, h.dmax, h.dmean), (-50.0, 141.0, 45.5));
+    let rms = ((192.0f64 * 192.0 - 1.0) / 12.0).sqrt() as f32;
+    assert!((h.rms - rms).abs() < 1e-3);
+    w.finalize().unwrap();
+}
+
+#[test]
+fn irregular_writes_fall_back_to_read_back() {
+    let data: Vec<f32> = (0..4 * 4 * 2).map(|i| i as f32).collect();
+    let section = |z: usize| VoxelBlock::new([0, 0, z], [4, 4, 1], data[z * 16..][..16].to_vec());
+
+    // Out of order: the no-read-back sink shows a second pass is attempted.
+    let sink = NoReadBack(Cursor::new(Vec::new()));
+    let mut w = Writer::from_writer(sink, f32_header(4, 4, 2), &[]).unwrap();
+    w.write_block(&section(1).unwrap()).unwrap();
+    w.write_block(&section(0).unwrap()).unwrap();
+    assert!(matches!(w.update_header_stats(), Err(mrc::Error::Io(_))));
+    w.finalize().unwrap();
+
+    // Rewriting a section after the fact still yields the final contents.
+    let mut w = Writer::from_writer(Cursor::new(Vec::new()), f32_header(4, 4, 2), &[]).unwrap();
+    w.write_block(&section(0).unwrap()).unwrap();
+    w.write_block(&section(1).unwrap()).unwrap();
+    w.write_block(&VoxelBlock::new([0, 0, 0], [1, 1, 1], vec![100.0f32]).unwrap())
+        .unwrap();
+    w.update_header_stats().unwrap();
+    assert_eq!(w.header().dmax, 100.0);
+    w.finalize().unwrap();
+}
+
+// ── 36. Dose weighting ───────────────────────────────────────────────────────
+
+#[cfg(feature = "fft")]
+#[test]
+fn dose_weight_filters_by_exposure() {
+    // Frame 0 is a Nyquist checkerboard plus an offset, frame 1 the offset
+    // alone. The offset (zero frequency) is never down-weighted.
+    let (nx, ny) = (8, 6);
+    let checker: Vec<f32> = (0..nx * ny)
+        .map(|i| {
+            if (i % nx + i / nx) % 2 == 0 {
+                1.0
+            } else {
+                -1.0
+            }
+        })
+        .collect();
+    let mut data: Vec<f32> = checker.iter().map(|v| v + 3.0).collect();
+    data.extend(std::iter::repeat_n(3.0f32, nx * ny));
+    let f = TempMrc::new("dose_weight");
+    write_as(f.path(), &data, [nx, ny, 2]).unwrap();
+    let r = Reader::open(f.path()).unwrap();
+
+    // No dose: the plain average.
+    let avg = r.dose_weight(0.0, 1.0).unwrap();
+    assert_eq!(avg.shape, [nx, ny, 1]);
+    for (v, c) in avg.data.iter().zip(&checker) {
+        assert!((v - (3.0 + 0.5 * c)).abs() < 1e-5);
+    }
+
+    // 4 e⁻/Å² per frame at 1 Å/px: the corner frequency k = √2 / 2 Å⁻¹.
+    let k = 0.5f32.sqrt();
+    let ne = 0.245 * k.powf(-1.665) + 2.81;
+    let (q0, q1) = ((-4.0 / (2.0 * ne)).exp(), (-8.0 / (2.0 * ne)).exp());
+    let amp = q0 / (q0 + q1);
+    let dw = r.dose_weight(4.0, 1.0).unwrap();
+    for (v, c) in dw.data.iter().zip(&checker) {
+        assert!(
+            (v - (3.0 + amp * c)).abs() < 1e-4,
+            "{v} vs {}",
+            3.0 + amp * c
+        );
+    }
+    assert!(amp > 0.5);
+
+    assert!(matches!(r.dose_weight(1.0, 0.0), Err(mrc::Error::Io(_))));
+    assert!(matches!(r.dose_weight(-1.0, 1.0), Err(mrc::Error::Io(_))));
+}
+
+// ── 37. Weighted frame summation ─────────────────────────────────────────────
+
+#[test]
+fn sum_frames_weighted_scales_each_frame() {
+    let frames: Vec<i16> = (0..3 * 4 * 2).map(|i| i as i16 - 5).collect();
+    let f = TempMrc::new("sum_frames_weighted");
+    write_as(f.path(), &frames, [4, 2, 3]).unwrap();
+    let r = Reader::open(f.path()).unwrap();
+
+    let sum = r.sum_frames_weighted(&[0.5, 0.0, 2.0]).unwrap();
+    assert_eq!(sum.shape, [4, 2, 1]);
+    let expected: Vec<f32> = (0..8)
+        .map(|i| 0.5 * frames[i] as f32 + 2.0 * frames[16 + i] as f32)
+        .collect();
+    assert_eq!(sum.data, expected);
+
+    assert!(matches!(
+        r.sum_frames_weighted(&[1.0; 2]),
+        Err(mrc::Error::BlockShapeMismatch {
+            expected: 3,
+            actual: 2
+        })
+    ));
+}
+
+// ── 38. Watching a growing file ──────────────────────────────────────────────
+
+#[cfg(feature = "notify")]
+#[test]
+fn watch_reports_appended_sections() {
+    use std::time::Duration;
+
+    // Header declares 3 sections of 4×2 f32; only the first is on disk.
+    let f = TempMrc::new("watch_growing");
+    let mut h = Header::new();
+    (h.nx, h.ny, h.nz) = (4, 2, 3);
+    (h.mx, h.my, h.mz) = (4, 2, 3);
+    h.mode = 2;
+    let mut raw = [0u8; 1024];
+    h.encode_to_bytes(&mut raw);
+    let section = |z: u8| -> Vec<u8> {
+        (0..8)
+            .flat_map(|i| (f32::from(z * 10 + i)).to_le_bytes())
+            .collect()
+    };
+    let mut file = std::fs::File::create(f.path()).unwrap();
+    file.write_all(&raw).unwrap();
+    file.write_all(&section(0)).unwrap();
+    file.sync_all().unwrap();
+
+    let r = OpenOptions::new().permissive(true).open(f.path()).unwrap();
+    let watcher = r.watch().unwrap();
+    assert!(watcher.try_recv().is_none());
+
+    file.write_all(&section(1)).unwrap();
+    file.write_all(&section(2)).unwrap();
+    file.sync_all().unwrap();
+
+    let mut last = 1;
+    while last < 3 {
+        let update = watcher
+            .recv_timeout(Duration::from_secs(10))
+            .expect("no update")
+            .unwrap();
+        assert_eq!(update.sections.start, last);
+        last = update.sections.end;
+        let block = update
+            .reader
+            .convert::<f32>()
+            .subregion([0, 0, last - 1], [4, 2, 1])
+            .unwrap();
+        assert_eq!(block.data[0], ((last - 1) * 10) as f32);
+    }
+
+    let buffered = Reader::from_bytes(std::fs::read(f.path()).unwrap()).unwrap();
+    assert!(matches!(buffered.watch(), Err(mrc::Error::Io(_))));
+}
+
+// ── 39. Provenance label ─────────────────────────────────────────────────────
+
+#[test]
+fn provenance_label_stamped_on_finalize() {
+    let f = TempMrc::new("provenance_label");
+    let mut builder = create(f.path())
+        .shape([2, 2, 1])
+        .mode::<f32>()
+        .provenance_label(true);
+    for i in 0..10 {
+        builder = builder.add_label(&format!("label {i}"));
+    }
+    let mut w = builder.finish().unwrap();
+    w.set_data(&[0.0f32; 4]).unwrap();
+    w.finalize().unwrap();
+    w.finalize().unwrap();
+
+    let h = *Reader::open(f.path()).unwrap().header();
+    let labels = h.get_labels();
+    assert_eq!(h.nlabl, 10);
+    assert_eq!(labels[0], "label 1");
+    let stamp = format!("Created by mrc-rs {} on ", env!("CARGO_PKG_VERSION"));
+    assert!(labels[9].starts_with(&stamp), "{}", labels[9]);
+    assert_eq!(labels[9].len(), stamp.len() + "YYYY-MM-DD HH:MM:SS".len());
+
+    let plain = TempMrc::new("provenance_label_off");
+    write_as(plain.path(), &[0.0f32; 4], [2, 2, 1]).unwrap();
+    assert_eq!(Reader::open(plain.path()).unwrap().header().nlabl, 0);
+}
+
+// ── 40. Legacy machine stamps ────────────────────────────────────────────────
+
+#[test]
+fn vax_stamped_files_are_rejected() {
+    let mut h = Header::new();
+    (h.nx, h.ny, h.nz) = (2, 2, 1);
+    (h.mx, h.my, h.mz) = (2, 2, 1);
+    h.mode = 2;
+    let mut raw = [0u8; 1024];
+    h.encode_to_bytes(&mut raw);
+    raw[212..216].copy_from_slice(&[0x22, 0x41, 0, 0]);
+    let mut file = raw.to_vec();
+    file.extend_from_slice(&[0u8; 16]);
+
+    let vax = |e: &mrc::Error| {
+        matches!(
+            e,
+            mrc::Error::UnsupportedFloatFormat {
+                machst: [0x22, 0x41, 0, 0]
+            }
+        )
+    };
+    assert!(vax(&Reader::from_bytes(file.clone()).unwrap_err()));
+    assert!(vax(&Reader::from_bytes_permissive(file).unwrap_err()));
+}
+
+// ── 41. Big-endian output ────────────────────────────────────────────────────
+
+#[test]
+fn builder_writes_big_endian_files() {
+    let f = TempMrc::new("big_endian_output");
+    let data: Vec<f32> = (0..24).map(|i| i as f32 - 4.0).collect();
+    let mut w = create(f.path())
+        .shape([4, 3, 2])
+        .mode::<f32>()
+        .endian(FileEndian::BigEndian)
+        .finish()
+        .unwrap();
+    w.set_data(&data).unwrap();
+    w.finalize().unwrap();
+
+    let raw = std::fs::read(f.path()).unwrap();
+    assert_eq!(&raw[212..216], &[0x11, 0x11, 0, 0]);
+    assert_eq!(&raw[0..4], &4i32.to_be_bytes());
+    assert_eq!(&raw[1024..1028], &(-4.0f32).to_be_bytes());
+
+    let r = Reader::open(f.path()).unwrap();
+    assert_eq!(r.endian(), FileEndian::BigEndian);
+    assert_eq!(r.convert::<f32>().read_volume().unwrap().data, data);
+    let h = r.header();
+    assert_eq!((h.dmin, h.dmax), (-4.0, 19.0));
+
+    let mut buf = create("unused.mrc")
+        .shape([2, 1, 1])
+        .mode::<i16>()
+        .endian(FileEndian::BigEndian)
+        .finish_buffer()
+        .unwrap();
+    buf.set_data(&[1i16, -2]).unwrap();
+    assert_eq!(buf.header().machst, [0x11, 0x11, 0, 0]);
+    assert_eq!((buf.header().dmin, buf.header().dmax), (-2.0, 1.0));
+}
+
+// ── 42. Header byte-order detection ──────────────────────────────────────────
+
+#[test]
+fn header_detection_reports_effective_endian() {
+    let mut h = Header::new();
+    (h.nx, h.ny, h.nz) = (5, 6, 7);
+    h.set_file_endian(FileEndian::BigEndian);
+    let mut raw = [0u8; 1024];
+    h.encode_to_bytes(&mut raw);
+
+    let (decoded, endian) = Header::from_bytes_with_detection(&raw);
+    assert_eq!(endian, FileEndian::BigEndian);
+    assert_eq!((decoded.nx, decoded.ny, decoded.nz), (5, 6, 7));
+
+    // Wrong stamp: the fields are big-endian but MACHST claims little.
+    raw[212..216].copy_from_slice(&[0x44, 0x44, 0, 0]);
+    let (decoded, endian) = Header::from_bytes_with_detection(&raw);
+    assert_eq!(endian, FileEndian::BigEndian);
+    assert_eq!(decoded.detect_endian(), FileEndian::LittleEndian);
+    assert_eq!(decoded.nz, 7);
+}
+
+// ── 43. Volume-stack splitting ───────────────────────────────────────────────
+
+#[test]
+fn stack_volumes_carry_per_volume_headers() {
+    let f = TempMrc::new("stack_volumes");
+    let data: Vec<f32> = (0..2 * 2 * 7).map(|v| v as f32).collect();
+    let mut w = create(f.path())
+        .shape([2, 2, 7])
+        .mode::<f32>()
+        .cell_lengths(2.0, 2.0, 3.0)
+        .finish()
+        .unwrap();
+    // Seven sections do not divide into volumes of three, so the stack
+    // layout is only applied after creation.
+    (w.header_mut().ispg, w.header_mut().mz) = (401, 3);
+    w.set_data(&data).unwrap();
+    w.finalize().unwrap();
+
+    let r = OpenOptions::new().permissive(true).open(f.path()).unwrap();
+    let vols: Vec<_> = r.stack_volumes().unwrap().map(|v| v.unwrap()).collect();
+    assert_eq!(
+        vols.len(),
+        2,
+        "the leftover seventh section is not a volume"
+    );
+    let second = &vols[1];
+    assert_eq!(second.index, 1);
+    assert_eq!((second.header.nz, second.header.mz), (3, 3));
+    assert_eq!(second.header.ispg, 1);
+    assert_eq!(second.header.voxel_size(), [1.0, 1.0, 1.0]);
+    assert!(second.header.dmax < second.header.dmin);
+    assert_eq!(second.data.offset(), [0, 0, 3]);
+    assert!(matches!(second.data.data(), DataView::Float32(v) if v[0] == 12.0));
+
+    assert!(matches!(
+        r.stack_volume(2),
+        Err(mrc::Error::BoundsError { .. })
+    ));
+    let plain = TempMrc::new("stack_volumes_plain");
+    write_as(plain.path(), &[0.0f32; 4], [2, 2, 1]).unwrap();
+    assert!(matches!(
+        Reader::open(plain.path())
+            .unwrap()
+            .stack_volumes()
+            .map(|_| ()),
+        Err(mrc::Error::NotAVolumeStack { .. })
+    ));
+}
+
+// ── 44. MRC2000-compatible output ────────────────────────────────────────────
+
+#[test]
+fn mrc2000_output_zeroes_extra_block() {
+    let f = TempMrc::new("mrc2000_output");
+    let mut w = create(f.path())
+        .shape([2, 2, 1])
+        .mode::<i16>()
+        .exttyp(*b"FEI1")
+        .mrc2000(true)
+        .finish()
+        .unwrap();
+    w.header_mut().set_exttyp(*b"CCP4");
+    w.set_data(&[1i16, 2, 3, 4]).unwrap();
+    w.finalize().unwrap();
+
+    let raw = std::fs::read(f.path()).unwrap();
+    assert!(raw[96..196].iter().all(|&b| b == 0));
+    assert_eq!(&raw[208..212], b"MAP ");
+    let r = Reader::open(f.path()).unwrap();
+    assert_eq!(r.header().nversion(), 0);
+    assert_eq!(r.header().dmax, 4.0);
+
+    let err = create(TempMrc::new("mrc2000_u16").path())
+        .shape([2, 2, 1])
+        .mode::<u16>()
+        .mrc2000(true)
+        .finish()
+        .unwrap_err();
+    assert!(matches!(err, mrc::Error::Io(e) if e.kind() == std::io::ErrorKind::InvalidInput));
+}
+
+// ── 45. Hermitian half-transforms ────────────────────────────────────────────
+
+/// Naive 3D DFT of a real `n[0] × n[1] × n[2]` volume.
+fn dft3(data: &[f32], n: [usize; 3]) -> Vec<Float32Complex> {
+    use std::f64::consts::TAU;
+    let mut out = Vec::with_capacity(data.len());
+    for kz in 0..n[2] {
+        for ky in 0..n[1] {
+            for kx in 0..n[0] {
+                let (mut re, mut im) = (0f64, 0f64);
+                for (i, &v) in data.iter().enumerate() {
+                    let (x, y, z) = (i % n[0], (i / n[0]) % n[1], i / (n[0] * n[1]));
+                    let phase = -TAU
+                        * ((kx * x) as f64 / n[0] as f64
+                            + (ky * y) as f64 / n[1] as f64
+                            + (kz * z) as f64 / n[2] as f64);
+                    re += v as f64 * phase.cos();
+                    im += v as f64 * phase.sin();
+                }
+                out.push(Float32Complex {
+                    real: re as f32,
+                    imag: im as f32,
+                });
+            }
+        }
+    }
+    out
+}
+
+#[test]
+fn half_transform_expands_by_hermitian_symmetry() {
+    let n = [4, 4, 3];
+    let real: Vec<f32> = (0..48).map(|i| ((i * 7) % 11) as f32 - 3.0).collect();
+    let full = dft3(&real, n);
+    let half: Vec<Float32Complex> = full.chunks(4).flat_map(|row| row[..3].to_vec()).collect();
+
+    let f = TempMrc::new("half_transform");
+    let mut w = create(f.path())
+        .shape([3, 4, 3])
+        .mode::<Float32Complex>()
+        .finish()
+        .unwrap();
+    w.set_data(&half).unwrap();
+    w.finalize().unwrap();
+
+    let r = Reader::open(f.path()).unwrap();
+    assert!(r.is_half_transform());
+    let expanded = r.expand_half_transform().unwrap();
+    assert_eq!(expanded.shape, [4, 4, 3]);
+    for (got, want) in expanded.data.iter().zip(&full) {
+        assert!((got.real - want.real).abs() < 1e-3, "{got:?} vs {want:?}");
+        assert!((got.imag - want.imag).abs() < 1e-3, "{got:?} vs {want:?}");
+    }
+
+    let plain = TempMrc::new("half_transform_real");
+    write_as(plain.path(), &real, n).unwrap();
+    let r = Reader::open(plain.path()).unwrap();
+    assert!(!r.is_half_transform());
+    assert!(matches!(
+        r.expand_half_transform(),
+        Err(mrc::Error::UnsupportedMode)
+    ));
+}
+
+// ── 46. Forward and inverse FFT ──────────────────────────────────────────────
+
+#[cfg(feature = "fft")]
+#[test]
+fn fft3_matches_dft_and_round_trips() {
+    let n = [5, 4, 3];
+    let real: Vec<f32> = (0..60).map(|i| ((i * 7) % 13) as f32 - 6.0).collect();
+    let src = TempMrc::new("fft3_source");
+    let mut w = create(src.path())
+        .shape(n)
+        .mode::<f32>()
+        .cell_lengths(10.0, 8.0, 6.0)
+        .finish()
+        .unwrap();
+    w.set_data(&real).unwrap();
+    w.finalize().unwrap();
+
+    let (header, transform) = Reader::open(src.path()).unwrap().fft3().unwrap();
+    assert_eq!(transform.shape, [3, 4, 3]);
+    assert_eq!((header.mode, header.nx, header.mx), (4, 3, 5));
+    assert_eq!(header.voxel_size(), [2.0, 2.0, 2.0]);
+    assert!(header.is_half_transform());
+    let dft = dft3(&real, n);
+    let half = dft.chunks(5).flat_map(|row| row[..3].to_vec());
+    for (got, want) in transform.data.iter().zip(half) {
+        assert!((got.real - want.real).abs() < 1e-3, "{got:?} vs {want:?}");
+        assert!((got.imag - want.imag).abs() < 1e-3, "{got:?} vs {want:?}");
+    }
+
+    let ft = TempMrc::new("fft3_transform");
+    let io = std::fs::File::options()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(ft.path())
+        .unwrap();
+    let mut w = Writer::from_writer(io, header, &[]).unwrap();
+    w.write_block(&transform).unwrap();
+    w.finalize().unwrap();
+
+    let (header, back) = Reader::open(ft.path()).unwrap().ifft3().unwrap();
+    assert_eq!((header.mode, header.nx, header.mx), (2, 5, 5));
+    assert_eq!(header.voxel_size(), [2.0, 2.0, 2.0]);
+    assert_eq!(back.shape, n);
+    for (got, want) in back.data.iter().zip(&real) {
+        assert!((got - want).abs() < 1e-4, "{got} vs {want}");
+    }
+}
+
+// ── 47. Approximate comparison ───────────────────────────────────────────────
+
+#[test]
+fn approx_eq_compares_across_modes() {
+    let values: Vec<f32> = (0..24).map(|i| i as f32 - 10.0).collect();
+    let a = TempMrc::new("approx_eq_f32");
+    write_as(a.path(), &values, [4, 3, 2]).unwrap();
+    let b = TempMrc::new("approx_eq_i16");
+    let ints: Vec<i16> = values.iter().map(|&v| v as i16).collect();
+    write_as(b.path(), &ints, [4, 3, 2]).unwrap();
+    let (a, b) = (
+        Reader::open(a.path()).unwrap(),
+        Reader::open(b.path()).unwrap(),
+    );
+
+    let report = a.approx_eq(&b, 0.0, 0.0).unwrap();
+    assert!(report.is_match());
+    assert_eq!((report.voxels, report.max_abs_diff), (24, 0.0));
+
+    let mut nudged = values.clone();
+    nudged[4 * 3 + 5] += 0.5;
+    let c = TempMrc::new("approx_eq_nudged");
+    write_as(c.path(), &nudged, [4, 3, 2]).unwrap();
+    let c = Reader::open(c.path()).unwrap();
+    let report = a.approx_eq(&c, 0.1, 0.0).unwrap();
+    assert_eq!(report.mismatches, 1);
+    assert_eq!(report.first_mismatch, Some([1, 1, 1]));
+    assert_eq!(report.max_abs_diff, 0.5);
+    assert!((report.mean_abs_diff - 0.5 / 24.0).abs() < 1e-12);
+    assert!(a.approx_eq(&c, 0.0, 0.25).unwrap().is_match());
+
+    let d = TempMrc::new("approx_eq_shape");
+    write_as(d.path(), &values, [6, 2, 2]).unwrap();
+    let err = a.approx_eq(&Reader::open(d.path()).unwrap(), 0.0, 0.0);
+    assert!(matches!(err, Err(mrc::Error::Io(e)) if e.kind() == std::io::ErrorKind::InvalidInput));
+}
+
+// ── 48. Difference maps ──────────────────────────────────────────────────────
+
+#[test]
+fn difference_map_subtracts_and_summarises() {
+    let write = |name: &str, data: &[f32], apix: f32| {
+        let f = TempMrc::new(name);
+        let mut w = create(f.path())
+            .shape([3, 2, 2])
+            .mode::<f32>()
+            .cell_lengths(3.0 * apix, 2.0 * apix, 2.0 * apix)
+            .finish()
+            .unwrap();
+        w.set_data(data).unwrap();
+        w.finalize().unwrap();
+        f
+    };
+    let a: Vec<f32> = (0..12).map(|i| i as f32).collect();
+    let b: Vec<f32> = (0..12).map(|i| (i % 2) as f32).collect();
+    let fa = write("difference_a", &a, 1.5);
+    let fb = write("difference_b", &b, 1.5);
+    let ra = Reader::open(fa.path()).unwrap();
+
+    let diff = difference(&ra, &Reader::open(fb.path()).unwrap()).unwrap();
+    let want: Vec<f32> = a.iter().zip(&b).map(|(x, y)| x - y).collect();
+    assert_eq!(diff.data.data, want);
+    assert_eq!(diff.header.mode, 2);
+    let (dmin, dmax, dmean, rms) = diff.header.density_stats();
+    assert_eq!((dmin, dmax, dmean), (0.0, 10.0, 5.0));
+    assert!((rms - (140.0f32 / 12.0).sqrt()).abs() < 1e-5, "{rms}");
+
+    let fc = write("difference_c", &b, 2.0);
+    assert!(matches!(
+        difference(&ra, &Reader::open(fc.path()).unwrap()),
+        Err(mrc::Error::Io(e)) if e.kind() == std::io::ErrorKind::InvalidInput
+    ));
+}
+
+// ── 49. Resampling onto a reference grid ─────────────────────────────────────
+
+#[test]
+fn resample_onto_reference_grid() {
+    // A linear ramp along X is reproduced exactly by trilinear interpolation.
+    let f = TempMrc::new("resample_source");
+    let data: Vec<f32> = (0..8 * 4 * 2).map(|i| (i % 8) as f32).collect();
+    let mut w = create(f.path())
+        .shape([8, 4, 2])
+        .mode::<f32>()
+        .cell_lengths(16.0, 8.0, 4.0)
+        .finish()
+        .unwrap();
+    w.set_data(&data).unwrap();
+    w.finalize().unwrap();
+
+    // Reference: 1 Å voxels starting 3 Å along X.
+    let mut reference = Header::new();
+    (reference.nx, reference.ny, reference.nz) = (16, 2, 1);
+    (reference.mx, reference.my, reference.mz) = (16, 2, 1);
+    (reference.xlen, reference.ylen, reference.zlen) = (16.0, 2.0, 1.0);
+    reference.origin = [3.0, 0.0, 0.0];
+
+    let (header, out) = Reader::open(f.path())
+        .unwrap()
+        .resample_onto(&reference)
+        .unwrap();
+    assert_eq!(out.shape, [16, 2, 1]);
+    assert_eq!(header.origin, [3.0, 0.0, 0.0]);
+    assert_eq!(header.voxel_size(), [1.0, 1.0, 1.0]);
+    // Position 3 + i Å is source index (3 + i) / 2, whose value equals it.
+    for i in 0..12 {
+        assert!((out.data[i] - (3.0 + i as f32) / 2.0).abs() < 1e-5, "{i}");
+    }
+    assert_eq!(out.data[12], 0.0, "beyond the source map");
+    assert_eq!(header.dmax, 7.0);
+
+    reference.mapc = 2;
+    reference.mapr = 1;
+    assert!(
+        Reader::open(f.path())
+            .unwrap()
+            .resample_onto(&reference)
+            .is_err()
+    );
+}
+
+// ── 50. Histogram and display range ──────────────────────────────────────────
+
+#[test]
+fn display_range_ignores_hot_pixels() {
+    let f = TempMrc::new("display_range");
+    // 0..1000 with two extreme outliers.
+    let mut data: Vec<f32> = (0..1000).map(|i| i as f32).collect();
+    data[10] = 1.0e6;
+    data[20] = -1.0e6;
+    data[30] = f32::NAN;
+    write_as(f.path(), &data, [10, 10, 10]).unwrap();
+    let reader = Reader::open(f.path()).unwrap();
+
+    let hist = reader.histogram(100).unwrap();
+    assert_eq!((hist.min, hist.max), (-1.0e6, 1.0e6));
+    assert_eq!(hist.total(), 999, "NaN is not counted");
+
+    let (black, white) = reader.display_range(1.0, 99.0).unwrap();
+    // Of the 999 finite voxels, about 10 lie below 9 and 989 below 990.
+    assert!((black - 9.0).abs() < 1.5, "{black}");
+    assert!((white - 990.0).abs() < 1.5, "{white}");
+    assert!(black < white);
+    assert_eq!(reader.display_range(0.0, 100.0).unwrap(), (-1.0e6, 1.0e6));
+
+    assert!(reader.display_range(50.0, 10.0).is_err());
+    assert!(reader.display_range(-1.0, 10.0).is_err());
+    assert!(reader.histogram(0).is_err());
+}
+
+// ── 51. Exact percentiles ────────────────────────────────────────────────────
+
+#[test]
+fn volume_percentile_matches_numpy_rule() {
+    let f = TempMrc::new("percentile");
+    // 0..=999 shuffled by a stride coprime with 1000.
+    let data: Vec<f32> = (0..1000).map(|i| ((i * 37) % 1000) as f32).collect();
+    write_as(f.path(), &data, [10, 10, 10]).unwrap();
+    let reader = Reader::open(f.path()).unwrap();
+    let vol = reader.volume::<f32>().unwrap();
+
+    assert_eq!(vol.percentile(0.0), Some(0.0));
+    assert_eq!(vol.percentile(100.0), Some(999.0));
+    // Rank 0.995 · 999 = 994.005.
+    assert!((vol.percentile(99.5).unwrap() - 994.005).abs() < 1e-9);
+    assert_eq!(vol.percentile(101.0), None);
+
+    // Every 10th voxel of the stride-37 sequence is still evenly spread.
+    let sampled = vol.percentile_sampled(50.0, 100).unwrap();
+    assert!((sampled - 499.5).abs() < 10.0, "{sampled}");
+    assert_eq!(vol.percentile_sampled(50.0, 10_000), vol.percentile(50.0));
+
+    let block = vol.into_block();
+    assert_eq!(block.percentile(50.0), Some(499.5));
+}
+
+// ── 52. Streaming quantile sketch ────────────────────────────────────────────
+
+#[test]
+fn quantile_sketch_in_one_pass() {
+    let f = TempMrc::new("quantile_sketch");
+    let data: Vec<i16> = (0..64 * 64 * 16)
+        .map(|i| ((i * 31) % 10_000) as i16)
+        .collect();
+    write_as(f.path(), &data, [64, 64, 16]).unwrap();
+    let reader = Reader::open(f.path()).unwrap();
+
+    let mut sketch = reader.quantile_sketch().unwrap();
+    assert_eq!(sketch.count(), data.len() as u64);
+    assert_eq!((sketch.min(), sketch.max()), (Some(0.0), Some(9999.0)));
+    let exact = reader.volume::<i16>().unwrap().percentile(99.5).unwrap();
+    let est = sketch.quantile(0.995).unwrap();
+    assert!((est - exact).abs() < 20.0, "{est} vs {exact}");
+
+    // Feeding sections by hand gives the same count.
+    let mut manual = QuantileSketch::default();
+    for s in reader.convert::<f32>().slices() {
+        manual.extend(&s.unwrap().data);
+    }
+    assert_eq!(manual.count(), sketch.count());
+}
+
+// ── 53. Quick statistics ─────────────────────────────────────────────────────
+
+#[test]
+fn quick_statistics_prefers_header_values() {
+    let f = TempMrc::new("quick_stats_header");
+    let mut w = create(f.path())
+        .shape([4, 4, 2])
+        .mode::<f32>()
+        .finish()
+        .unwrap();
+    w.set_data(&[0.0f32; 32]).unwrap();
+    w.finalize().unwrap();
+    let mut bytes = std::fs::read(f.path()).unwrap();
+    // Overwrite dmin/dmax/dmean (bytes 76–87) and rms (byte 216).
+    for (word, v) in [(19, -5.0f32), (20, 5.0), (21, 1.0), (54, 2.0)] {
+        bytes[word * 4..word * 4 + 4].copy_from_slice(&v.to_le_bytes());
+    }
+    let reader = Reader::from_bytes(bytes).unwrap();
+    let stats = reader.quick_statistics().unwrap();
+    assert_eq!(stats.source, StatsSource::Metadata);
+    assert_eq!(
+        (stats.dmin, stats.dmax, stats.dmean, stats.rms),
+        (-5.0, 5.0, 1.0, 2.0)
+    );
+}
+
+#[test]
+fn quick_statistics_falls_back_to_data() {
+    let f = TempMrc::new("quick_stats_data");
+    let data: Vec<f32> = (0..8).map(|i| i as f32).collect();
+    let mut header = Header::new();
+    (header.nx, header.ny, header.nz) = (8, 1, 1);
+    (header.mx, header.my, header.mz) = (8, 1, 1);
+    let mut raw = [0u8; 1024];
+    header.encode_to_bytes(&mut raw);
+    let bytes: Vec<u8> = raw
+        .into_iter()
+        .chain(data.iter().flat_map(|v| v.to_le_bytes()))
+        .collect();
+    std::fs::write(f.path(), &bytes).unwrap();
+
+    let stats = Reader::open(f.path()).unwrap().quick_statistics().unwrap();
+    assert_eq!(stats.source, StatsSource::Data);
+    assert_eq!((stats.dmin, stats.dmax, stats.dmean), (0.0, 7.0, 3.5));
+}
+
+// ── 54. Acquisition metadata ─────────────────────────────────────────────────
+
+fn fei1_record(pixel_m: f64, dose: f64, defocus_m: f64, ole_date: f64) -> Vec<u8> {
+    let mut r = vec![0u8; FEI1_RECORD_SIZE];
+    r[0..4].copy_from_slice(&(FEI1_RECORD_SIZE as u32).to_be_bytes());
+    r[12..20].copy_from_slice(&ole_date.to_be_bytes());
+    r[92..100].copy_from_slice(&dose.to_be_bytes());
+    r[156..164].copy_from_slice(&pixel_m.to_be_bytes());
+    r[220..228].copy_from_slice(&defocus_m.to_be_bytes());
+    r
+}
+
+#[test]
+fn acquisition_normalises_fei_units() {
+    let f = TempMrc::new("acquisition_fei");
+    // SI units as EPU writes them: 0.83 Å, 2e20 e⁻/m² (= 2 e⁻/Å²), -1.5 µm.
+    let mut ext = fei1_record(0.83e-10, 2.0e20, -1.5e-6, 45_000.5);
+    ext.extend(fei1_record(0.83e-10, 3.0, -1.6, 0.0));
+    let mut w = create(f.path())
+        .shape([2, 2, 2])
+        .mode::<f32>()
+        .exttyp(*b"FEI1")
+        .extended_header(ext)
+        .finish()
+        .unwrap();
+    w.set_data(&[0.0f32; 8]).unwrap();
+    w.finalize().unwrap();
+
+    let r = Reader::open(f.path()).unwrap();
+    assert!((r.pixel_size_angstrom().unwrap() - 0.83).abs() < 1e-9);
+    assert!((r.total_dose().unwrap() - 5.0).abs() < 1e-9);
+    assert!((r.defocus(0).unwrap() + 1.5).abs() < 1e-9);
+    assert_eq!(r.defocus(1), Some(-1.6), "already in µm");
+    // 45 000.5 days after 1899-12-30 is 2023-03-15 12:00 UTC.
+    assert_eq!(r.acquisition_time(0), Some(1_678_881_600.0));
+    assert_eq!(r.acquisition_time(1), None);
+    assert!(r.acquisition(2).is_none());
+}
+
+#[test]
+fn acquisition_absent_without_vendor_records() {
+    let f = TempMrc::new("acquisition_none");
+    write_as(f.path(), &[0.0f32; 4], [2, 2, 1]).unwrap();
+    let r = Reader::open(f.path()).unwrap();
+    assert!(r.acquisition(0).is_none());
+    assert_eq!(r.total_dose(), None);
+}
+
+// ── 55. Salvaging truncated files ────────────────────────────────────────────
+
+#[test]
+fn salvage_truncated_keeps_complete_sections() {
+    let f = TempMrc::new("salvage_truncated");
+    let data: Vec<f32> = (0..4 * 4 * 5).map(|i| i as f32).collect();
+    write_as(f.path(), &data, [4, 4, 5]).unwrap();
+    let mut bytes = std::fs::read(f.path()).unwrap();
+    bytes.truncate(1024 + 64 * 3 + 40); // three and a bit sections
+    std::fs::write(f.path(), &bytes).unwrap();
+
+    assert!(Reader::open(f.path()).is_err());
+    let permissive = OpenOptions::new().permissive(true).open(f.path()).unwrap();
+    assert_eq!(permissive.shape().nz, 5);
+    assert_eq!(permissive.complete_sections(), 3);
+
+    for mmap in [true, false] {
+        let (r, warnings) = OpenOptions::new()
+            .salvage_truncated(true)
+            .mmap(mmap)
+            .open_with_warnings(f.path())
+            .unwrap();
+        assert!(r.is_truncated());
+        assert_eq!(r.shape().nz, 3);
+        assert_eq!(r.header().mz, 3);
+        assert_eq!(r.header().voxel_size(), permissive.header().voxel_size());
+        assert!(
+            warnings.iter().any(|w| w.contains("kept 3 of 5")),
+            "{warnings:?}"
+        );
+        let all = r.convert::<f32>().read_volume().unwrap();
+        assert_eq!(all.data, data[..48]);
+        assert_eq!(r.slices().count(), 3);
+    }
+}
+
+#[test]
+fn salvage_truncated_rejects_file_ending_in_first_section() {
+    let f = TempMrc::new("salvage_first_section");
+    write_as(f.path(), &[1f32; 8 * 8 * 4], [8, 8, 4]).unwrap();
+    let mut bytes = std::fs::read(f.path()).unwrap();
+    bytes.truncate(1024 + 100);
+    std::fs::write(f.path(), &bytes).unwrap();
+
+    for mmap in [true, false] {
+        let err = OpenOptions::new()
+            .salvage_truncated(true)
+            .mmap(mmap)
+            .open(f.path())
+            .unwrap_err();
+        let Error::Io(e) = &err else {
+            panic!("expected Io, got {err:?}")
+        };
+        assert_eq!(e.kind(), std::io::ErrorKind::UnexpectedEof);
+        assert!(err.to_string().contains("100 of 256"), "{err}");
+    }
+}
+
+// ── 56. Rejecting non-MRC files ──────────────────────────────────────────────
+
+#[test]
+fn strict_open_rejects_files_without_map_and_stamp() {
+    let f = TempMrc::new("not_mrc");
+    // Zeros with plausible dimensions and mode: valid apart from MAP/MACHST.
+    let mut h = Header::new();
+    (h.nx, h.ny, h.nz) = (4, 4, 1);
+    (h.mx, h.my, h.mz) = (4, 4, 1);
+    h.map = [0; 4];
+    h.machst = [0; 4];
+    let mut bytes = vec![0u8; 1024 + 64];
+    let mut raw = [0u8; 1024];
+    h.encode_to_bytes(&mut raw);
+    bytes[..1024].copy_from_slice(&raw);
+    std::fs::write(f.path(), &bytes).unwrap();
+
+    match Reader::open(f.path()) {
+        Err(Error::NotAnMrcFile { map, machst }) => {
+            assert_eq!(map, [0; 4]);
+            assert_eq!(machst, [0; 4]);
+        }
+        other => panic!("expected NotAnMrcFile, got {other:?}"),
+    }
+    assert!(matches!(
+        Reader::from_bytes(bytes.clone()),
+        Err(Error::NotAnMrcFile { .. })
+    ));
+    let r = OpenOptions::new().permissive(true).open(f.path()).unwrap();
+    assert_eq!(r.shape().nx, 4);
+
+    // The legacy "MAP\0" tag is also refused in strict mode.
+    h.map = *b"MAP\0";
+    h.machst = [0x44, 0x44, 0, 0];
+    h.encode_to_bytes(&mut raw);
+    bytes[..1024].copy_from_slice(&raw);
+    assert!(matches!(
+        Reader::from_bytes(bytes),
+        Err(Error::NotAnMrcFile { .. })
+    ));
+}
+
+// ── 57. Extended header size checks ──────────────────────────────────────────
+
+#[test]
+fn pathological_nsymbt_is_rejected() {
+    let image = |nsymbt: i32, exttyp: &[u8; 4], ext_len: usize| {
+        let mut h = Header::new();
+        (h.nx, h.ny, h.nz) = (4, 4, 1);
+        (h.mx, h.my, h.mz) = (4, 4, 1);
+        h.nsymbt = nsymbt;
+        h.set_exttyp(*exttyp);
+        let mut raw = [0u8; 1024];
+        h.encode_to_bytes(&mut raw);
+        let mut bytes = raw.to_vec();
+        bytes.resize(1024 + ext_len + 64, 0);
+        bytes
+    };
+    let reason = |r: Result<Reader, Error>| match r {
+        Err(Error::InvalidExtendedHeaderSize { reason, .. }) => reason,
+        other => panic!("expected InvalidExtendedHeaderSize, got {other:?}"),
+    };
+
+    // Two gigabytes declared, 64 bytes present: refused even permissively.
+    let huge = image(i32::MAX, b"CCP4", 0);
+    assert!(reason(Reader::from_bytes(huge.clone())).contains("past the end"));
+    let f = TempMrc::new("nsymbt_huge");
+    std::fs::write(f.path(), &huge).unwrap();
+    for mmap in [true, false] {
+        let r = OpenOptions::new()
+            .permissive(true)
+            .mmap(mmap)
+            .open(f.path());
+        assert!(reason(r).contains("past the end"));
+    }
+
+    assert!(reason(Reader::from_bytes(image(-4, b"CCP4", 0))).contains("negative"));
+    let (r, _) = Reader::from_bytes_permissive(image(-4, b"CCP4", 0)).unwrap();
+    assert!(r.ext_header_bytes().is_empty());
+
+    // 1000 bytes is not a whole number of 768-byte FEI1 records.
+    let partial = image(1000, b"FEI1", 1000);
+    assert!(reason(Reader::from_bytes(partial.clone())).contains("768-byte FEI1"));
+    let (r, warnings) = Reader::from_bytes_permissive(partial).unwrap();
+    assert_eq!(r.ext_header_bytes().len(), 1000);
+    assert!(
+        warnings.iter().any(|w| w.contains("NSYMBT 1000")),
+        "{warnings:?}"
+    );
+
+    let whole = Reader::from_bytes(image(1536, b"FEI1", 1536)).unwrap();
+    assert_eq!(whole.ext_header_bytes().len(), 1536);
+}
+
+// ── 58. Sizes beyond the address space ───────────────────────────────────────
+
+#[test]
+fn oversized_declared_data_fails_without_overflow() {
+    let n = 1 << 20;
+    let mut h = Header::new();
+    (h.nx, h.ny, h.nz) = (n, n, n);
+    (h.mx, h.my, h.mz) = (n, n, n);
+    assert_eq!(h.data_size_u64(), Some(1 << 62));
+    let mut raw = [0u8; 1024];
+    h.encode_to_bytes(&mut raw);
+    let mut bytes = raw.to_vec();
+    bytes.resize(1024 + 64, 0);
+    let f = TempMrc::new("oversized");
+    std::fs::write(f.path(), &bytes).unwrap();
+
+    let results = [
+        Reader::from_bytes(bytes),
+        OpenOptions::new().mmap(true).open(f.path()),
+        OpenOptions::new().mmap(false).open(f.path()),
+        OpenOptions::new().permissive(true).open(f.path()),
+    ];
+    for r in results {
+        match r {
+            Err(Error::TooLargeForPlatform { bytes }) if cfg!(target_pointer_width = "32") => {
+                assert_eq!(bytes, 1024 + (1 << 62));
+            }
+            Err(Error::FileSizeMismatch { actual, .. }) => assert_eq!(actual, 1024 + 64),
+            Ok(r) => assert!(r.is_truncated()),
+            Err(other) => panic!("unexpected error {other:?}"),
+        }
+    }
+}
+
+// ── 59. File length cross-check ──────────────────────────────────────────────
+
+#[test]
+fn file_length_mismatch_states_missing_and_extra_bytes() {
+    let f = TempMrc::new("len_check");
+    write_as(f.path(), &[0f32; 64], [4, 4, 4]).unwrap();
+    let good = std::fs::read(f.path()).unwrap();
+
+    let mut short = good.clone();
+    short.truncate(good.len() - 100);
+    let err = Reader::from_bytes(short.clone()).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::FileSizeMismatch {
+            expected: 1280,
+            actual: 1180
+        }
+    ));
+    assert!(err.to_string().contains("100 bytes missing"), "{err}");
+
+    let mut long = good;
+    long.extend_from_slice(&[0; 12]);
+    std::fs::write(f.path(), &long).unwrap();
+    for mmap in [true, false] {
+        let err = OpenOptions::new().mmap(mmap).open(f.path()).unwrap_err();
+        assert!(err.to_string().contains("12 extra bytes"), "{err}");
+        let (r, warnings) = OpenOptions::new()
+            .mmap(mmap)
+            .permissive(true)
+            .open_with_warnings(f.path())
+            .unwrap();
+        assert!(!r.is_truncated());
+        assert!(
+            warnings
+                .iter()
+                .any(|w| w.contains("expected 1280 bytes, got 1292 bytes")),
+            "{warnings:?}"
+        );
+    }
+
+    let (_, warnings) = Reader::from_bytes_permissive(short).unwrap();
+    assert!(
+        warnings.iter().any(|w| w.contains("100 bytes missing")),
+        "{warnings:?}"
+    );
+}
+
+// ── 60. MRC2014 compliance report ────────────────────────────────────────────
+
+#[test]
+fn compliance_report_lists_departures_by_rule() {
+    use mrc::validate::{Rule, Severity, compliance_report};
+
+    let f = TempMrc::new("compliant");
+    let data: Vec<f32> = (0..64).map(|i| i as f32).collect();
+    write_as(f.path(), &data, [4, 4, 4]).unwrap();
+    let report = compliance_report(f.path()).unwrap();
+    assert!(report.is_compliant());
+    assert!(report.findings.is_empty(), "{:?}", report.findings);
+
+    let mut h = Header::new();
+    (h.nx, h.ny, h.nz) = (4, 4, 1);
+    (h.mx, h.my, h.mz) = (4, 4, 1);
+    (h.mapc, h.mapr, h.maps) = (2, 1, 3);
+    h.map = [0; 4];
+    h.nsymbt = 1000;
+    h.set_exttyp(*b"FEI1");
+    (h.dmin, h.dmax, h.dmean, h.rms) = (0.0, 100.0, 50.0, 10.0);
+    let mut raw = [0u8; 1024];
+    h.encode_to_bytes(&mut raw);
+    let mut bytes = raw.to_vec();
+    bytes.resize(1024 + 1000 + 64 + 8, 0);
+    std::fs::write(f.path(), &bytes).unwrap();
+
+    let report = compliance_report(f.path()).unwrap();
+    assert!(!report.is_compliant());
+    let severity = |rule| {
+        let found: Vec<_> = report.for_rule(rule).map(|f| f.severity).collect();
+        assert_eq!(found.len(), 1, "{rule:?}: {:?}", report.findings);
+        found[0]
+    };
+    assert_eq!(severity(Rule::MapId), Severity::Error);
+    assert_eq!(severity(Rule::AxisOrder), Severity::Warning);
+    assert_eq!(severity(Rule::ExtendedHeader), Severity::Error);
+    assert_eq!(severity(Rule::Statistics), Severity::Warning);
+    assert_eq!(severity(Rule::FileSize), Severity::Error);
+    assert!(
+        report
+            .for_rule(Rule::FileSize)
+            .all(|f| f.message.contains("8 extra bytes"))
+    );
+    assert_eq!(Rule::ExtendedHeader.id(), "extended_header");
+    assert_eq!(report.for_rule(Rule::Mode).count(), 0);
+}
+
+// ── 61. Error kinds and codes ────────────────────────────────────────────────
+
+#[test]
+fn error_kinds_have_stable_codes() {
+    use mrc::ErrorKind;
+
+    let io = Error::Io(std::io::Error::other("disk"));
+    assert_eq!((io.kind(), io.code()), (ErrorKind::Io, 2));
+    let header = Error::InvalidHeaderDetailed(mrc::HeaderValidationError::InvalidNsymbt(-1));
+    assert_eq!(
+        (header.kind(), header.code()),
+        (ErrorKind::InvalidHeader, 3)
+    );
+    let float = Error::UnsupportedFloatFormat { machst: [0x22; 4] };
+    assert_eq!(float.kind(), ErrorKind::Unsupported);
+    let stack = Error::NotAVolumeStack { ispg: 1, mz: 1 };
+    assert_eq!(
+        (stack.kind(), stack.code()),
+        (ErrorKind::NotAVolumeStack, 9)
+    );
+    let big = Error::TooLargeForPlatform { bytes: u64::MAX };
+    assert_eq!((big.kind(), big.code()), (ErrorKind::TooLarge, 11));
+
+    let r = Reader::from_bytes(vec![0; 2000]).unwrap_err();
+    assert_eq!(r.kind(), ErrorKind::InvalidHeader);
+
+    #[cfg(feature = "capi")]
+    {
+        use mrc::capi::*;
+        assert_eq!(status_code(&stack), MRC_ERR_NOT_A_VOLUME_STACK);
+        assert_eq!(status_code(&float), MRC_ERR_UNSUPPORTED_MODE);
+        assert_eq!(status_code(&big), MRC_ERR_TOO_LARGE);
+    }
+}
+
+// ── 62. Byte offsets in parse errors ─────────────────────────────────────────
+
+#[test]
+fn parse_errors_name_field_and_byte_offset() {
+    let mut h = Header::new();
+    (h.nx, h.ny, h.nz) = (4, 0, 1);
+    (h.mx, h.my, h.mz) = (4, 4, 1);
+    let mut raw = [0u8; 1024];
+    h.encode_to_bytes(&mut raw);
+    let err = Reader::from_bytes(raw.to_vec()).unwrap_err();
+    match &err {
+        Error::InvalidHeaderDetailed(e) => assert_eq!(e.field(), ("NY", 4)),
+        other => panic!("expected InvalidHeaderDetailed, got {other:?}"),
+    }
+    assert!(err.to_string().ends_with("(NY at byte 4)"), "{err}");
+
+    // Two FEI1 records, the second with a bad metadata_size.
+    let mut ext = vec![0u8; 2 * 768];
+    ext[..4].copy_from_slice(&768u32.to_be_bytes());
+    ext[768..772].copy_from_slice(&999u32.to_be_bytes());
+    let f = TempMrc::new("bad_fei");
+    let mut w = create(f.path())
+        .shape([4, 4, 1])
+        .mode::<f32>()
+        .exttyp(*b"FEI1")
+        .extended_header(ext)
+        .finish()
+        .unwrap();
+    w.set_data(&[0.0f32; 16]).unwrap();
+    w.finalize().unwrap();
+    let r = Reader::open(f.path()).unwrap();
+    assert_eq!(r.parse_extended_header(), mrc::ExtHeaderData::None);
+    match r.try_parse_extended_header().unwrap_err() {
+        Error::InvalidExtendedHeader {
+            record,
+            field,
+            offset,
+            ..
+        } => {
+            assert_eq!(
+                (record, field.as_str(), offset),
+                (1, "metadata_size", 1024 + 768)
+            );
+        }
+        other => panic!("expected InvalidExtendedHeader, got {other:?}"),
+    }
+}
+
+// ── 63. Header repair ────────────────────────────────────────────────────────
+
+#[test]
+fn fix_header_repairs_safe_defects() {
+    use mrc::validate::{Rule, compliance_report, fix_header};
+
+    let mut h = Header::new();
+    (h.nx, h.ny, h.nz) = (2, 2, 2);
+    (h.mx, h.my, h.mz) = (0, 2, 2);
+    h.map = [0; 4];
+    h.machst = [0; 4];
+    h.nlabl = 3;
+    h.nsymbt = -4;
+    let mut bytes = [0u8; 1024].to_vec();
+    h.encode_to_bytes((&mut bytes[..1024]).try_into().unwrap());
+    bytes.extend_from_slice(&[0u8; 32]);
+    let f = TempMrc::new("fix_header");
+    std::fs::write(f.path(), &bytes).unwrap();
+
+    let report = compliance_report(f.path()).unwrap();
+    let mut fixed = h;
+    let repairs = fix_header(&mut fixed, &report.findings);
+    let repaired: Vec<Rule> = repairs.iter().map(|r| r.rule).collect();
+    for rule in [
+        Rule::MapId,
+        Rule::MachineStamp,
+        Rule::Sampling,
+        Rule::Labels,
+        Rule::ExtendedHeader,
+    ] {
+        assert!(
+            repaired.contains(&rule),
+            "{rule:?} not repaired: {repairs:?}"
+        );
+    }
+    assert_eq!(
+        (fixed.map, fixed.mx, fixed.nlabl, fixed.nsymbt),
+        (*b"MAP ", 2, 0, 0)
+    );
+
+    fixed.encode_to_bytes((&mut bytes[..1024]).try_into().unwrap());
+    std::fs::write(f.path(), &bytes).unwrap();
+    let after = compliance_report(f.path()).unwrap();
+    for rule in repaired {
+        assert!(after.for_rule(rule).next().is_none(), "{rule:?}: {after:?}");
+    }
+    assert!(fix_header(&mut fixed, &after.findings).is_empty());
+}
+
+// ── 64. Page-aligned read buffers ────────────────────────────────────────────
+
+#[test]
+fn page_aligned_open_exposes_stable_raw_parts() {
+    let f = TempMrc::new("page_aligned");
+    let data: Vec<f32> = (0..5 * 3 * 2).map(|i| i as f32).collect();
+    write_as(f.path(), &data, [5, 3, 2]).unwrap();
+
+    let reader = OpenOptions::new()
+        .page_aligned(true)
+        .open(f.path())
+        .unwrap();
+    let (ptr, len) = reader.as_raw_parts();
+    assert_eq!(ptr as usize % PAGE_ALIGN, 0);
+    assert_eq!(len, data.len() * 4);
+    // SAFETY: `as_raw_parts` is valid for `len` bytes while `reader` lives.
+    let bytes = unsafe { std::slice::from_raw_parts(ptr, len) };
+    assert_eq!(&bytes[4..8], &1f32.to_le_bytes());
+
+    let clone = reader.try_clone().unwrap();
+    drop(reader);
+    assert_eq!(clone.as_raw_parts(), (ptr, len));
+    assert_eq!(clone.volume::<f32>().unwrap().as_slice(), &data[..]);
+}
+
+// ── 65. Out-of-core chunked processing ───────────────────────────────────────
+
+#[test]
+fn chunked_processor_maps_and_reduces_slabs() {
+    let src = TempMrc::new("chunked_src");
+    let dst = TempMrc::new("chunked_dst");
+    let data: Vec<i16> = (0..6 * 4 * 5).collect();
+    write_as(src.path(), &data, [6, 4, 5]).unwrap();
+    let reader = Reader::open(src.path()).unwrap();
+    // One 48-byte section per slab.
+    let config = StreamingConfig {
+        chunk_bytes: 48,
+        max_resident_bytes: 1 << 20,
+    };
+    let proc = ChunkedProcessor::new(&reader).config(config);
+
+    let (slabs, sum) = proc
+        .reduce((0, 0i64), |(n, s), slab: VoxelBlock<f32>| {
+            Ok((n + 1, s + slab.data.iter().map(|&v| v as i64).sum::<i64>()))
+        })
+        .unwrap();
+    assert_eq!((slabs, sum), (5, (0..120).sum()));
+
+    let mut w = create(dst.path())
+        .shape([6, 4, 5])
+        .mode::<f32>()
+        .finish()
+        .unwrap();
+    proc.map(&mut w, |mut slab: VoxelBlock<f32>| {
+        slab.data.iter_mut().for_each(|v| *v *= 0.5);
+        Ok(slab.data)
+    })
+    .unwrap();
+    w.finalize().unwrap();
+    let out = Reader::open(dst.path()).unwrap();
+    let vol = out.volume::<f32>().unwrap();
+    assert_eq!(vol[(5, 3, 4)], 119.0 * 0.5);
+
+    let mut wrong = create(TempMrc::new("chunked_bad").path())
+        .shape([6, 4, 4])
+        .mode::<f32>()
+        .finish()
+        .unwrap();
+    assert!(
+        proc.map(&mut wrong, |s: VoxelBlock<f32>| Ok(s.data))
+            .is_err()
+    );
+}
+
+// ── 66. Shared-memory volumes ────────────────────────────────────────────────
+
+#[cfg(all(feature = "shm", target_os = "linux"))]
+#[test]
+fn shared_volume_round_trips_by_name() {
+    let f = TempMrc::new("shm_src");
+    let data: Vec<u16> = (0..4 * 3 * 2).collect();
+    write_as(f.path(), &data, [4, 3, 2]).unwrap();
+    let name = format!("mrc-test-{}", std::process::id());
+
+    Reader::open(f.path()).unwrap().share(&name).unwrap();
+    let shared = Reader::open_shared(&name).unwrap();
+    remove_shared(&name).unwrap();
+    // The mapping outlives the name.
+    assert_eq!(shared.volume::<u16>().unwrap().as_slice(), &data[..]);
+    assert!(Reader::open_shared(&name).is_err());
+    assert!(Reader::open_shared("a/b").is_err());
+}
+
+// ── 67. Cancellation ─────────────────────────────────────────────────────────
+
+#[test]
+fn cancelled_token_stops_long_reads() {
+    let f = TempMrc::new("cancel");
+    let data: Vec<f32> = (0..8 * 8 * 4).map(|i| i as f32).collect();
+    write_as(f.path(), &data, [8, 8, 4]).unwrap();
+    let token = CancellationToken::new();
+    let mut reader = OpenOptions::new()
+        .cancellation(token.clone())
+        .open(f.path())
+        .unwrap();
+    assert!(reader.histogram(16).is_ok());
+
+    token.cancel();
+    assert!(matches!(reader.histogram(16), Err(Error::Cancelled)));
+    assert!(matches!(
+        reader.slices().next(),
+        Some(Err(Error::Cancelled))
+    ));
+    let config = StreamingConfig::default();
+    let err = reader.data_stats_with(&config).unwrap_err();
+    assert_eq!((err.kind(), err.code()), (ErrorKind::Cancelled, 12));
+    #[cfg(feature = "capi")]
+    assert_eq!(mrc::capi::status_code(&err), mrc::capi::MRC_ERR_CANCELLED);
+
+    reader.set_cancellation(None);
+    assert!(reader.data_stats_with(&config).is_ok());
+}
+
+// ── 68. Double-buffered section writer ───────────────────────────────────────
+
+#[test]
+fn double_buffered_writer_writes_sections_in_order() {
+    let f = TempMrc::new("double_buffer");
+    let mut w = create(f.path())
+        .shape([5, 3, 4])
+        .mode::<i16>()
+        .finish_double_buffered::<i16>()
+        .unwrap();
+    assert_eq!(w.shape(), [5, 3, 4]);
+    while w.next_z() < 4 {
+        let z = w.next_z() as i16;
+        for (i, v) in w.buffer().iter_mut().enumerate() {
+            *v = z * 15 + i as i16;
+        }
+        w.submit().unwrap();
+    }
+    assert!(w.submit().is_err());
+    w.finish().unwrap();
+
+    let reader = Reader::open(f.path()).unwrap();
+    let expected: Vec<i16> = (0..60).collect();
+    assert_eq!(reader.volume::<i16>().unwrap().as_slice(), &expected[..]);
+    let h = reader.header();
+    assert_eq!((h.dmin, h.dmax, h.dmean), (0.0, 59.0, 29.5));
+
+    assert!(matches!(
+        create(TempMrc::new("double_buffer_bad").path())
+            .shape([2, 2, 2])
+            .mode::<f32>()
+            .finish_double_buffered::<i16>(),
+        Err(Error::ModeMismatch { .. })
+    ));
+}
+
+// ── 69. Channel writer ───────────────────────────────────────────────────────
+
+#[test]
+fn channel_writer_orders_sections_from_many_threads() {
+    let f = TempMrc::new("channel");
+    let mut h = Header::new();
+    (h.nx, h.ny, h.nz) = (4, 2, 6);
+    (h.mx, h.my, h.mz) = (4, 2, 6);
+    h.mode = Mode::Float32.as_i32();
+    let writer = ChannelWriter::<f32>::spawn(f.path(), h).unwrap();
+    std::thread::scope(|scope| {
+        for worker in 0..3 {
+            let tx = writer.sender();
+            scope.spawn(move || {
+                // Highest z first, so most sections arrive early.
+                for z in (worker..6).step_by(3).rev() {
+                    let data = (0..8).map(|i| (z * 8 + i) as f32).collect();
+                    tx.send(Section { z, data }).unwrap();
+                }
+            });
+        }
+    });
+    writer.finish().unwrap();
+
+    let reader = Reader::open(f.path()).unwrap();
+    let expected: Vec<f32> = (0..48).map(|i| i as f32).collect();
+    assert_eq!(reader.volume::<f32>().unwrap().as_slice(), &expected[..]);
+    assert_eq!((reader.header().dmin, reader.header().dmax), (0.0, 47.0));
+
+    let g = TempMrc::new("channel_missing");
+    let writer = ChannelWriter::<f32>::spawn(g.path(), h).unwrap();
+    writer
+        .sender()
+        .send(Section {
+            z: 1,
+            data: vec![1.0; 8],
+        })
+        .unwrap();
+    assert!(writer.finish().is_err());
+    let reader = Reader::open(g.path()).unwrap();
+    assert_eq!(reader.volume::<f32>().unwrap().as_slice()[8..16], [1.0; 8]);
+
+    assert!(matches!(
+        ChannelWriter::<i16>::spawn(TempMrc::new("channel_mode").path(), h),
+        Err(Error::ModeMismatch { .. })
+    ));
+}
+
+#[test]
+fn channel_writer_finishes_with_senders_outstanding() {
+    let f = TempMrc::new("channel_outstanding");
+    let mut h = Header::new();
+    (h.nx, h.ny, h.nz) = (2, 1, 40);
+    (h.mx, h.my, h.mz) = (2, 1, 40);
+    h.mode = Mode::Int16.as_i32();
+    let writer = ChannelWriter::<i16>::spawn(f.path(), h).unwrap();
+    let tx = writer.sender();
+    // More sections than the queue holds, all from the finishing thread.
+    for z in 0..40 {
+        tx.send(Section {
+            z,
+            data: vec![z as i16; 2],
+        })
+        .unwrap();
+    }
+    writer.finish().unwrap();
+    let late = tx.send(Section {
+        z: 0,
+        data: vec![0; 2],
+    });
+    assert_eq!(late.unwrap_err().0.z, 0);
+
+    let reader = Reader::open(f.path()).unwrap();
+    assert_eq!(reader.volume::<i16>().unwrap().as_slice()[78..], [39, 39]);
+}
+
+// ── 70. Buffer pool ──────────────────────────────────────────────────────────
+
+#[test]
+fn buffer_pool_recycles_reader_buffers() {
+    let (a, b) = (TempMrc::new("pool_a"), TempMrc::new("pool_b"));
+    let data_a: Vec<f32> = (0..64).map(|i| i as f32).collect();
+    let data_b: Vec<f32> = (0..48).map(|i| -(i as f32)).collect();
+    write_as(a.path(), &data_a, [4, 4, 4]).unwrap();
+    write_as(b.path(), &data_b, [4, 4, 3]).unwrap();
+    let pool = BufferPool::new(4);
+    let options = OpenOptions::new().eager(true).buffer_pool(pool.clone());
+
+    let reader = options.open(a.path()).unwrap();
+    let clone = reader.try_clone().unwrap();
+    let ptr = reader.as_raw_parts().0;
+    drop(reader);
+    assert!(pool.is_empty(), "clone still shares the buffer");
+    drop(clone);
+    assert_eq!(pool.len(), 1);
+
+    let reader = options.open(b.path()).unwrap();
+    assert!(pool.is_empty());
+    assert_eq!(reader.as_raw_parts(), (ptr, 48 * 4));
+    assert_eq!(reader.volume::<f32>().unwrap().as_slice(), &data_b[..]);
+}
+
+// ── 71. Cloning file structure ───────────────────────────────────────────────
+
+#[test]
+fn clone_structure_copies_headers_with_zero_data() {
+    let src = TempMrc::new("structure_src");
+    let mut w = create(src.path())
+        .shape([3, 2, 2])
+        .mode::<i16>()
+        .cell_lengths(4.5, 3.0, 4.0)
+        .extended_header(vec![7u8; 64])
+        .finish()
+        .unwrap();
+    w.set_data(&[5i16; 12]).unwrap();
+    w.finalize().unwrap();
+    let input = Reader::open(src.path()).unwrap();
+
+    let dst = TempMrc::new("structure_dst");
+    let mut out = input.clone_structure_to(dst.path()).unwrap();
+    assert_eq!(std::fs::metadata(dst.path()).unwrap().len(), 1024 + 64 + 24);
+    out.write_block(&VoxelBlock::new([0, 0, 1], [3, 2, 1], vec![-1i16; 6]).unwrap())
+        .unwrap();
+    out.finalize().unwrap();
+
+    let copy = Reader::open(dst.path()).unwrap();
+    assert_eq!(copy.ext_header_bytes(), input.ext_header_bytes());
+    assert_eq!(copy.header().voxel_size(), input.header().voxel_size());
+    let mut expected = vec![0i16; 6];
+    expected.extend([-1i16; 6]);
+    assert_eq!(copy.volume::<i16>().unwrap().as_slice(), &expected[..]);
+}
+
+// ── 72. Content hashing ──────────────────────────────────────────────────────
+
+#[cfg(feature = "hash")]
+#[test]
+fn content_hash_ignores_byte_order_and_labels() {
+    let data: Vec<i16> = (0..24).collect();
+    let write = |name: &str, endian: FileEndian, label: Option<&str>, data: &[i16]| {
+        let f = TempMrc::new(name);
+        let mut w = create(f.path())
+            .shape([4, 3, 2])
+            .mode::<i16>()
+            .endian(endian)
+            .finish()
+            .unwrap();
+        if let Some(label) = label {
+            w.header_mut().add_label(label);
+        }
+        w.set_data(data).unwrap();
+        w.finalize().unwrap();
+        let reader = Reader::open(f.path()).unwrap();
+        (f, reader)
+    };
+    let (_a, le) = write("hash_le", FileEndian::LittleEndian, None, &data);
+    let (_b, be) = write("hash_be", FileEndian::BigEndian, Some("resaved"), &data);
+    let hash = le.content_hash().unwrap();
+    assert_eq!(be.content_hash().unwrap(), hash);
+    assert_eq!(hash.to_string().len(), 64);
+
+    let with_labels = HashOptions {
+        include_labels: true,
+    };
+    assert_ne!(
+        be.content_hash_with(&with_labels).unwrap(),
+        le.content_hash_with(&with_labels).unwrap()
+    );
+    let mut other = data.clone();
+    other[23] = 0;
+    let (_c, changed) = write("hash_changed", FileEndian::LittleEndian, None, &other);
+    assert_ne!(changed.content_hash().unwrap(), hash);
+}
+
+// ── 73. Verifying header statistics ──────────────────────────────────────────
+
+#[test]
+fn verify_statistics_reports_stale_fields() {
+    let f = TempMrc::new("verify_stats");
+    let data: Vec<f32> = (0..16).map(|i| i as f32).collect();
+    write_as(f.path(), &data, [4, 4, 1]).unwrap();
+    let reader = Reader::open(f.path()).unwrap();
+    let report = reader.verify_statistics().unwrap();
+    assert!(report.is_consistent(), "{report:?}");
+    assert_eq!(report.dmax.verdict, StatVerdict::Matches);
+
+    let mut h = *reader.header();
+    h.dmax = 20.0;
+    h.rms = -1.0;
+    let mut file = vec![0u8; 1024];
+    h.encode_to_bytes((&mut file[..]).try_into().unwrap());
+    file.extend(data.iter().flat_map(|v| v.to_le_bytes()));
+    let stale = Reader::from_bytes(file).unwrap();
+    let report = stale.verify_statistics().unwrap();
+    assert!(!report.is_consistent());
+    assert_eq!(
+        report.checks().map(|(_, c)| c.verdict),
+        [
+            StatVerdict::Matches,
+            StatVerdict::Differs,
+            StatVerdict::Matches,
+            StatVerdict::Unset
+        ]
+    );
+    assert_eq!((report.dmax.claimed, report.dmax.actual), (20.0, 15.0));
+    assert!(stale.verify_statistics_with(0.5).unwrap().is_consistent());
+}
+
+// ── 74. Per-section extended header records ──────────────────────────────────
+
+#[test]
+fn ext_records_yield_one_record_per_section() {
+    let f = TempMrc::new("ext_records");
+    let mut ext: Vec<u8> = (0..3u8).flat_map(|z| [z; 8]).collect();
+    ext.extend([0xff; 4]); // trailing padding
+    let mut w = create(f.path())
+        .shape([2, 2, 3])
+        .mode::<i8>()
+        .extended_header(ext)
+        .finish()
+        .unwrap();
+    w.set_data(&[0i8; 12]).unwrap();
+    w.finalize().unwrap();
+    let reader = Reader::open(f.path()).unwrap();
+
+    let records: Vec<&[u8]> = reader.ext_records(8).unwrap().collect();
+    assert_eq!(records, [[0u8; 8], [1; 8], [2; 8]]);
+    assert_eq!(reader.ext_records(4).unwrap().len(), 3);
+    assert!(matches!(
+        reader.ext_records(10),
+        Err(Error::InvalidExtendedHeader { record: 2, .. })
+    ));
+    assert!(reader.ext_records(0).is_err());
+}
+
+// ── 75. Custom extended header formats ───────────────────────────────────────
+
+struct TestCam;
+
+#[derive(Debug, PartialEq)]
+struct TestCamRecord {
+    frame: u32,
+}
+
+impl ExtHeaderFormat for TestCam {
+    type Record = TestCamRecord;
+    const EXTTYP: [u8; 4] = *b"TCAM";
+    const RECORD_SIZE: usize = 16;
+
+    fn parse_record(b: &[u8]) -> Result<TestCamRecord, Error> {
+        Ok(TestCamRecord {
+            frame: u32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+        })
+    }
+
+    fn serialize_record(r: &TestCamRecord, out: &mut [u8]) -> Result<(), Error> {
+        out[..4].copy_from_slice(&r.frame.to_le_bytes());
+        Ok(())
+    }
+}
+
+#[test]
+fn registered_ext_header_format_parses_by_exttyp() {
+    let f = TempMrc::new("custom_ext");
+    let records: Vec<TestCamRecord> = (0..3).map(|frame| TestCamRecord { frame }).collect();
+    let mut w = create(f.path())
+        .shape([2, 2, 3])
+        .mode::<i8>()
+        .exttyp(TestCam::EXTTYP)
+        .extended_header(TestCam::serialize(&records).unwrap())
+        .finish()
+        .unwrap();
+    w.set_data(&[0i8; 12]).unwrap();
+    w.finalize().unwrap();
+    let reader = Reader::open(f.path()).unwrap();
+
+    register_ext_header_format::<TestCam>();
+    let parsed = reader.ext_header_typed::<TestCamRecord>().unwrap().unwrap();
+    assert_eq!(parsed, records);
+    assert!(reader.ext_header_typed::<u32>().is_none());
+    assert!(matches!(
+        TestCam::parse(&[0u8; 20]),
+        Err(Error::InvalidExtendedHeader { record: 1, .. })
+    ));
+}
+
+// ── 76. Reader from separate parts ───────────────────────────────────────────
+
+#[test]
+fn from_parts_matches_from_bytes() {
+    let mut h = Header::new();
+    (h.nx, h.ny, h.nz) = (3, 2, 2);
+    (h.mx, h.my, h.mz) = (3, 2, 2);
+    h.mode = Mode::Int16.as_i32();
+    h.nsymbt = 8;
+    let ext = [9u8; 8];
+    let data: Vec<u8> = (0..12i16).flat_map(|v| v.to_le_bytes()).collect();
+
+    let reader = Reader::from_parts(h, &ext, &data).unwrap();
+    let mut file = vec![0u8; 1024];
+    h.encode_to_bytes((&mut file[..]).try_into().unwrap());
+    file.extend(ext);
+    file.extend(&data);
+    assert_eq!(reader.to_bytes(), file);
+    assert_eq!(
+        reader.volume::<i16>().unwrap().as_slice(),
+        (0..12).collect::<Vec<i16>>()
+    );
+
+    assert!(Reader::from_parts(h, &[], &data).is_err());
+    assert!(matches!(
+        Reader::from_parts(h, &ext, &data[2..]),
+        Err(Error::FileSizeMismatch { .. })
+    ));
+}
+
+// ── 77. Mode-checked typed accessors ─────────────────────────────────────────
+
+#[test]
+fn typed_accessors_check_mode() {
+    let tmp = TempMrc::new("typed_accessors");
+    write_as(
+        tmp.path(),
+        &(0..8).map(|v| v as f32).collect::<Vec<_>>(),
+        [2, 2, 2],
+    )
+    .unwrap();
+    let reader = Reader::open(tmp.path()).unwrap();
+
+    let mut block = reader.read_volume().unwrap();
+    assert_eq!(block.data().as_f32().unwrap()[7], 7.0);
+    assert_eq!(block.data_as::<f32>().unwrap().len(), 8);
+    assert!(matches!(
+        block.data().as_u16(),
+        Err(Error::ModeMismatch {
+            file_mode: Mode::Float32,
+            requested_mode: Mode::Uint16,
+            ..
+        })
+    ));
+    assert!(block.data().as_packed4bit().is_err());
+    assert!(block.data_as_mut::<i8>().is_err());
+
+    block.data_as_mut::<f32>().unwrap()[0] = -1.0;
+    assert_eq!(block.data_as::<f32>().unwrap()[0], -1.0);
+    assert_eq!(reader.volume::<f32>().unwrap().as_slice()[0], 0.0);
+}
+
+// ── 78. 3D voxel indexing ────────────────────────────────────────────────────
+
+#[test]
+fn volume_and_block_indexing() {
+    let data: Vec<u8> = {
+        let mut h = Header::new();
+        (h.nx, h.ny, h.nz) = (3, 2, 2);
+        (h.mx, h.my, h.mz) = (3, 2, 2);
+        h.mode = Mode::Int16.as_i32();
+        let mut file = vec![0u8; 1024];
+        h.encode_to_bytes((&mut file[..]).try_into().unwrap());
+        file.extend((0..12i16).flat_map(|v| v.to_le_bytes()));
+        file
+    };
+    let reader = Reader::from_bytes(data).unwrap();
+    let mut vol = reader.volume::<i16>().unwrap();
+    assert_eq!(vol[(2, 1, 1)], 11);
+    assert_eq!(unsafe { *vol.get_unchecked(1, 0, 1) }, 7);
+    assert!(vol.get_mut(3, 0, 0).is_none());
+
+    vol[(0, 1, 0)] = -3;
+    *vol.get_mut(2, 1, 1).unwrap() += 1;
+    assert!(!vol.is_borrowed());
+    assert_eq!((vol[(0, 1, 0)], vol[(2, 1, 1)]), (-3, 12));
+    assert_eq!(reader.volume::<i16>().unwrap()[(0, 1, 0)], 3);
+
+    let mut block = vol.into_block();
+    unsafe { *block.get_unchecked_mut(1, 1, 0) = 40 };
+    assert_eq!(unsafe { *block.get_unchecked(1, 1, 0) }, block[(1, 1, 0)]);
+    assert_eq!(block[(1, 1, 0)], 40);
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn volume_index_mut_panics_out_of_bounds() {
+    let tmp = TempMrc::new("index_mut_oob");
+    write_as(tmp.path(), &[0.0f32; 8], [2, 2, 2]).unwrap();
+    let reader = Reader::open(tmp.path()).unwrap();
+    let mut vol = reader.volume::<f32>().unwrap();
+    vol[(0, 0, 2)] = 1.0;
+}
+
+// ── 79. Block Z-sections ─────────────────────────────────────────────────────
+
+#[test]
+fn block_sections_iterate_in_z_order() {
+    let tmp = TempMrc::new("block_sections");
+    write_as(
+        tmp.path(),
+        &(0..24).map(|v| v as f32).collect::<Vec<_>>(),
+        [3, 2, 4],
+    )
+    .unwrap();
+    let reader = Reader::open(tmp.path()).unwrap();
+    let mut block = reader.volume::<f32>().unwrap().into_block();
+
+    assert_eq!(block.sections().len(), 4);
+    assert_eq!(
+        block.section(2).unwrap(),
+        &[12.0, 13.0, 14.0, 15.0, 16.0, 17.0]
+    );
+    assert!(block.section(4).is_none());
+    for (z, section) in block.sections_mut().enumerate() {
+        section[0] = -(z as f32);
+    }
+    block.section_mut(3).unwrap()[5] = 99.0;
+    assert_eq!(block[(0, 0, 3)], -3.0);
+    assert_eq!(block[(2, 1, 3)], 99.0);
+}
+
+// ── 80. Region-of-interest views ─────────────────────────────────────────────
+
+#[test]
+fn roi_view_rows_follow_volume_strides() {
+    let tmp = TempMrc::new("roi_view");
+    write_as(
+        tmp.path(),
+        &(0..60).map(|v| v as f32).collect::<Vec<_>>(),
+        [5, 4, 3],
+    )
+    .unwrap();
+    let reader = Reader::open(tmp.path()).unwrap();
+    let vol = reader.volume::<f32>().unwrap();
+
+    let roi = vol.roi([1, 2, 1], [3, 2, 2]).unwrap();
+    assert_eq!(roi.strides(), [5, 20]);
+    assert_eq!(roi.row(0, 0).unwrap(), &[31.0, 32.0, 33.0]);
+    assert_eq!(roi.rows().len(), 4);
+    let firsts: Vec<f32> = roi.rows().map(|r| r[0]).collect();
+    assert_eq!(firsts, [31.0, 36.0, 51.0, 56.0]);
+    assert_eq!(roi[(2, 1, 1)], vol[(3, 3, 2)]);
+    assert!(roi.get(3, 0, 0).is_none() && roi.row(0, 2).is_none());
+
+    let inner = roi.roi([1, 1, 1], [2, 1, 1]).unwrap();
+    assert_eq!(inner.offset(), [2, 3, 2]);
+    let block = inner.to_block();
+    assert_eq!((block.offset, block.data), ([2, 3, 2], vec![57.0, 58.0]));
+
+    assert!(matches!(
+        vol.roi([4, 0, 0], [2, 1, 1]),
+        Err(Error::BoundsError { .. })
+    ));
+    assert!(roi.roi([0, 0, 0], [3, 3, 1]).is_err());
+}
+
+// ── 81. Axis-order-aware access ──────────────────────────────────────────────
+
+#[test]
+fn get_logical_honours_axis_mapping() {
+    let tmp = TempMrc::new("get_logical");
+    // Columns run along Z, rows along X, sections along Y.
+    let (nc, nr, ns) = (4, 3, 2);
+    let mut w = create(tmp.path())
+        .shape([nc, nr, ns])
+        .mode::<f32>()
+        .axis_mapping([3, 1, 2])
+        .finish()
+        .unwrap();
+    let data: Vec<f32> = (0..nc * nr * ns).map(|i| i as f32).collect();
+    w.write_block(&VoxelBlock::new([0, 0, 0], [nc, nr, ns], data).unwrap())
+        .unwrap();
+    w.finalize().unwrap();
+
+    let reader = Reader::open(tmp.path()).unwrap();
+    let vol = reader.volume::<f32>().unwrap();
+    assert_eq!(vol.axis_order(), [2, 0, 1]);
+    assert_eq!(vol.logical_shape(), [3, 2, 4]);
+    // Spatial (x, y, z) = storage (column z, row x, section y).
+    assert_eq!(vol.get_logical(2, 1, 3), vol.get(3, 2, 1));
+    assert_eq!(vol.get_logical(2, 1, 3), Some(&23.0));
+    assert_eq!(vol.get_logical(1, 0, 0), Some(&4.0));
+    assert!(vol.get_logical(3, 0, 0).is_none());
+
+    let plain = TempMrc::new("get_logical_plain");
+    write_as(plain.path(), &[1.0f32, 2.0, 3.0, 4.0], [2, 2, 1]).unwrap();
+    let reader = Reader::open(plain.path()).unwrap();
+    let vol = reader.volume::<f32>().unwrap();
+    assert_eq!(vol.axis_order(), [0, 1, 2]);
+    assert_eq!(vol.get_logical(1, 1, 0), vol.get(1, 1, 0));
+}
+
+// ── 82. Owned in-memory volumes ──────────────────────────────────────────────
+
+#[test]
+fn mrc_buf_grows_and_round_trips() {
+    let mut h = Header::new();
+    (h.nx, h.ny, h.nz) = (3, 2, 0);
+    (h.mx, h.my, h.mz) = (3, 2, 0);
+    h.mode = Mode::Int16.as_i32();
+    h.nsymbt = 4;
+    let mut buf = MrcBuf::new(h).unwrap();
+    assert!(buf.data_as::<i16>().unwrap().is_empty());
+    assert!(buf.to_bytes().is_err());
+
+    buf.push_section(&[1i16, 2, 3, 4, 5, 6]).unwrap();
+    buf.push_section(&[7i16; 6]).unwrap();
+    assert!(matches!(
+        buf.push_section(&[0i16; 5]),
+        Err(Error::BlockShapeMismatch { .. })
+    ));
+    assert!(matches!(
+        buf.push_section(&[0f32; 6]),
+        Err(Error::ModeMismatch { .. })
+    ));
+    buf.data_as_mut::<i16>().unwrap()[1] = -2;
+    buf.set_ext_header(vec![5; 8]).unwrap();
+    assert_eq!(
+        (buf.header().nz, buf.header().mz, buf.header().nsymbt),
+        (2, 2, 8)
+    );
+
+    let tmp = TempMrc::new("mrc_buf");
+    buf.write_to(tmp.path()).unwrap();
+    let reader = Reader::open(tmp.path()).unwrap();
+    assert_eq!(reader.ext_header_bytes(), &[5; 8]);
+    assert_eq!(
+        reader.volume::<i16>().unwrap().as_slice(),
+        &[1, -2, 3, 4, 5, 6, 7, 7, 7, 7, 7, 7]
+    );
+
+    let copy = MrcBuf::from_reader(&reader).unwrap();
+    assert_eq!(copy.to_bytes().unwrap(), buf.to_bytes().unwrap());
+    let zeros = MrcBuf::new(*reader.header()).unwrap();
+    assert_eq!(zeros.data().as_i16().unwrap(), &[0; 12]);
+}
+
+#[test]
+fn mrc_buf_push_section_keeps_stack_mz() {
+    let mut h = Header::new();
+    (h.nx, h.ny, h.nz) = (2, 2, 1);
+    (h.mx, h.my, h.mz) = (2, 2, 1);
+    h.mode = Mode::Float32.as_i32();
+    h.ispg = 0;
+    let mut stack = MrcBuf::new(h).unwrap();
+    stack.push_section(&[1f32; 4]).unwrap();
+    stack.push_section(&[2f32; 4]).unwrap();
+    assert_eq!((stack.header().nz, stack.header().mz), (3, 1));
+    assert!(stack.header().is_image_stack());
+    assert!(stack.header().validate_detailed().is_ok());
+
+    h.ispg = 1;
+    let mut volume = MrcBuf::new(h).unwrap();
+    volume.push_section(&[1f32; 4]).unwrap();
+    assert_eq!((volume.header().nz, volume.header().mz), (2, 2));
+}
+
+#[test]
+fn mrc_buf_is_a_source() {
+    let h = HeaderBuilder::new()
+        .shape([3, 2, 2])
+        .mode::<i16>()
+        .build()
+        .unwrap();
+    let mut buf = MrcBuf::new(h).unwrap();
+    buf.data_as_mut::<i16>()
+        .unwrap()
+        .copy_from_slice(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]);
+
+    let src: &dyn MrcSource = &buf;
+    assert_eq!(src.mode(), Some(Mode::Int16));
+    assert_eq!(src.len(), 12);
+    let section = src.read_section(1).unwrap();
+    assert!(matches!(section, DataBlock::Borrowed { .. }));
+    assert_eq!(section.data_as::<i16>().unwrap(), &[6, 7, 8, 9, 10, 11]);
+    let column = src.read_view([1, 0, 0], [1, 2, 2]).unwrap();
+    assert_eq!(column.data_as::<i16>().unwrap(), &[1, 4, 7, 10]);
+    assert!(matches!(
+        src.read_view([2, 0, 0], [2, 1, 1]),
+        Err(Error::BoundsError { .. })
+    ));
+    assert!(src.read_section(2).is_err());
+}
+
+// ── 83. Cache-line-aligned data ──────────────────────────────────────────────
+
+#[test]
+fn data_aligned_or_copy_is_always_aligned() {
+    let data: Vec<f32> = (0..24).map(|v| v as f32).collect();
+    for nsymbt in [0usize, 4, 12] {
+        let tmp = TempMrc::new(&format!("aligned_or_copy_{nsymbt}"));
+        let mut w = create(tmp.path())
+            .shape([4, 3, 2])
+            .mode::<f32>()
+            .extended_header(vec![0; nsymbt])
+            .finish()
+            .unwrap();
+        w.write_block(&VoxelBlock::new([0, 0, 0], [4, 3, 2], data.clone()).unwrap())
+            .unwrap();
+        w.finalize().unwrap();
+
+        let reader = Reader::open(tmp.path()).unwrap();
+        let aligned = reader.data_aligned_or_copy::<f32>().unwrap();
+        assert_eq!(aligned.as_ptr() as usize % BUF_ALIGN, 0);
+        assert_eq!(&aligned[..], &data[..]);
+        if aligned.is_borrowed() {
+            assert_eq!(
+                aligned.as_ptr(),
+                reader.volume::<f32>().unwrap().as_slice().as_ptr()
+            );
+        }
+        assert!(matches!(
+            reader.data_aligned_or_copy::<i16>(),
+            Err(Error::ModeMismatch { .. })
+        ));
+    }
+}
+
+// ── 84. Checked zero-copy casts ──────────────────────────────────────────────
+
+#[cfg(feature = "mmap")]
+#[test]
+fn misaligned_data_falls_back_to_a_copy() {
+    let values: Vec<f32> = (0..12).map(|v| v as f32 * 0.5).collect();
+    // The data starts at byte 1024 + nsymbt, so each file puts it at a
+    // different alignment within the mapping.
+    for nsymbt in 0..4 {
+        let tmp = TempMrc::new(&format!("misaligned_{nsymbt}"));
+        let mut w = create(tmp.path())
+            .shape([4, 3, 1])
+            .mode::<f32>()
+            .extended_header(vec![0; nsymbt])
+            .finish()
+            .unwrap();
+        w.write_block(&VoxelBlock::new([0, 0, 0], [4, 3, 1], values.clone()).unwrap())
+            .unwrap();
+        w.finalize().unwrap();
+
+        let reader = OpenOptions::new().mmap(true).open(tmp.path()).unwrap();
+        // Whatever the backend, sections borrow exactly when the data as
+        // it sits in memory can be viewed as `f32`.
+        let data = reader.raw_data();
+        let viewable = reader.endian().is_native()
+            && data.as_ptr() as usize % std::mem::align_of::<f32>() == 0;
+        for block in reader.slices() {
+            let block = block.unwrap();
+            assert_eq!(matches!(block, DataBlock::Borrowed { .. }), viewable);
+            assert_eq!(block.data_as::<f32>().unwrap(), &values[..]);
+        }
+        assert_eq!(reader.volume::<f32>().unwrap().as_slice(), &values[..]);
+    }
+}
+
+#[cfg(feature = "f16")]
+#[test]
+fn f16_sections_view_without_copying() {
+    let tmp = TempMrc::new("f16_zero_copy");
+    let data: Vec<f16> = (0..8).map(|v| f16::from_f32(v as f32)).collect();
+    let mut w = create(tmp.path())
+        .shape([2, 2, 2])
+        .mode::<f16>()
+        .finish()
+        .unwrap();
+    w.write_block(&VoxelBlock::new([0, 0, 0], [2, 2, 2], data.clone()).unwrap())
+        .unwrap();
+    w.finalize().unwrap();
+    let reader = Reader::open(tmp.path()).unwrap();
+    let vol = reader.volume::<f16>().unwrap();
+    assert!(vol.is_borrowed());
+    assert_eq!(vol.as_slice(), &data[..]);
+}
+
+// ── 85. Decode-on-access voxels ──────────────────────────────────────────────
+
+#[test]
+fn voxel_accessor_decodes_big_endian_on_access() {
+    let mut header = HeaderBuilder::new()
+        .shape([3, 2, 2])
+        .mode::<i16>()
+        .build()
+        .unwrap();
+    header.set_file_endian(FileEndian::BigEndian);
+    let mut raw = [0u8; 1024];
+    header.encode_to_bytes(&mut raw);
+    let values: Vec<i16> = (0..12).map(|v| v * 100 - 300).collect();
+    let mut file = raw.to_vec();
+    file.extend(values.iter().flat_map(|v| v.to_be_bytes()));
+    let reader = Reader::from_bytes(file).unwrap();
+
+    let voxels = reader.voxel_accessor();
+    assert_eq!(voxels.endian(), FileEndian::BigEndian);
+    assert_eq!((voxels.len(), voxels.mode()), (12, Mode::Int16));
+    assert_eq!(voxels.get_f32(0), Some(-300.0));
+    assert_eq!(voxels.get_f32_at(2, 1, 1), Some(800.0));
+    assert_eq!(voxels.get_f32(12), None);
+    assert_eq!(voxels.get_f32_at(0, 2, 0), None);
+    assert_eq!(
+        voxels.iter_f32().collect::<Vec<_>>(),
+        reader.convert::<f32>().read_volume().unwrap().data
+    );
+}
+
+#[test]
+fn voxel_accessor_reads_packed_nibbles() {
+    let tmp = TempMrc::new("voxel_accessor_u4");
+    let nibbles: Vec<u8> = (0..9).collect();
+    let mut w = create(tmp.path())
+        .shape([3, 3, 1])
+        .mode_raw(101)
+        .finish()
+        .unwrap();
+    w.write_u4_block(&VoxelBlock::new([0, 0, 0], [3, 3, 1], nibbles.clone()).unwrap())
+        .unwrap();
+    w.finalize().unwrap();
+    let reader = Reader::open(tmp.path()).unwrap();
+    let got: Vec<f32> = reader.voxel_accessor().iter_f32().collect();
+    assert_eq!(got, nibbles.iter().map(|&v| v as f32).collect::<Vec<_>>());
+}
+
+// ── 86. f16 views widened to f32 ─────────────────────────────────────────────
+
+#[cfg(feature = "f16")]
+#[test]
+fn f16_volume_iterates_as_f32() {
+    let mut h = Header::new();
+    (h.nx, h.ny, h.nz) = (3, 1, 1);
+    (h.mx, h.my, h.mz) = (3, 1, 1);
+    h.mode = Mode::Float16.as_i32();
+    let values = [-2.5f32, 0.0, 65504.0];
+    let data: Vec<u8> = values
+        .iter()
+        .flat_map(|&v| f16::from_f32(v).to_le_bytes())
+        .collect();
+    let reader = Reader::from_parts(h, &[], &data).unwrap();
+
+    let vol = reader.volume::<f16>().unwrap();
+    assert_eq!(vol.iter_as_f32().collect::<Vec<_>>(), values);
+    assert_eq!(vol.iter_as_f32().len(), 3);
+    let block = vol.into_block();
+    assert_eq!(block.iter_as_f32().collect::<Vec<_>>(), values);
+    assert_eq!(
+        reader.voxel_accessor().iter_f32().collect::<Vec<_>>(),
+        values
+    );
+}
+
+// ── 87. Orthogonal planes ────────────────────────────────────────────────────
+
+#[test]
+fn planes_along_every_axis() {
+    let tmp = TempMrc::new("planes");
+    write_as(
+        tmp.path(),
+        &(0..24).map(|v| v as f32).collect::<Vec<_>>(),
+        [4, 3, 2],
+    )
+    .unwrap();
+    let reader = Reader::open(tmp.path()).unwrap();
+    let vol = reader.volume::<f32>().unwrap();
+
+    let yz = vol.plane(Axis::X, 1).unwrap();
+    assert_eq!((yz.offset, yz.shape), ([1, 0, 0], [1, 3, 2]));
+    assert_eq!(yz.data, [1.0, 5.0, 9.0, 13.0, 17.0, 21.0]);
+
+    let xz = vol.plane(Axis::Y, 2).unwrap();
+    assert_eq!((xz.offset, xz.shape), ([0, 2, 0], [4, 1, 2]));
+    assert_eq!(xz.data, [8.0, 9.0, 10.0, 11.0, 20.0, 21.0, 22.0, 23.0]);
+
+    let xy = vol.plane(Axis::Z, 1).unwrap();
+    assert_eq!(xy.data, vol.section(1).unwrap());
+    for (x, y, z) in [(3, 1, 0), (0, 2, 1)] {
+        assert_eq!(vol.plane(Axis::X, x).unwrap()[(0, y, z)], vol[(x, y, z)]);
+        assert_eq!(vol.plane(Axis::Y, y).unwrap()[(x, 0, z)], vol[(x, y, z)]);
+    }
+
+    assert!(matches!(
+        vol.plane(Axis::Y, 3),
+        Err(Error::BoundsError { .. })
+    ));
+    assert_eq!(Axis::Z.index(), 2);
+}
+
+// ── 88. Per-section statistics ───────────────────────────────────────────────
+
+#[test]
+fn section_stats_per_z_slice() {
+    let tmp = TempMrc::new("section_stats");
+    let mut data = vec![2.0f32; 12];
+    data.extend([1.0, 3.0, 1.0, 3.0, 1.0, 3.0]);
+    data.extend([0.0; 6]);
+    data[12] = -5.0;
+    write_as(tmp.path(), &data, [3, 2, 4]).unwrap();
+    let reader = Reader::open(tmp.path()).unwrap();
+    let stats = reader.section_stats().unwrap();
+
+    assert_eq!(stats.len(), 4);
+    for s in &stats[..2] {
+        assert_eq!((s.dmin, s.dmax, s.dmean, s.rms), (2.0, 2.0, 2.0, 0.0));
+    }
+    assert_eq!((stats[2].dmin, stats[2].dmax), (-5.0, 3.0));
+    assert_eq!(stats[2].dmean, 1.0);
+    assert_eq!(stats[3].dmean, 0.0);
+
+    let (dmin, dmax, ..) = reader.data_stats_with(&StreamingConfig::default()).unwrap();
+    assert_eq!(dmin, stats.iter().map(|s| s.dmin).fold(f32::MAX, f32::min));
+    assert_eq!(dmax, stats.iter().map(|s| s.dmax).fold(f32::MIN, f32::max));
+}
+
+#[test]
+fn section_stats_packed4bit_rows_are_padded() {
+    let tmp = TempMrc::new("section_stats_packed");
+    let nibbles = vec![1, 2, 3, 4, 5, 6, 15, 15, 15, 15, 15, 15];
+    let mut w = create(tmp.path())
+        .shape([3, 2, 2])
+        .mode_raw(101)
+        .finish()
+        .unwrap();
+    w.write_u4_block(&VoxelBlock::new([0, 0, 0], [3, 2, 2], nibbles).unwrap())
+        .unwrap();
+    w.finalize().unwrap();
+    let reader = Reader::open(tmp.path()).unwrap();
+    let stats = reader.section_stats().unwrap();
+    assert_eq!(
+        (stats[0].dmin, stats[0].dmax, stats[0].dmean),
+        (1.0, 6.0, 3.5)
+    );
+    assert_eq!((stats[1].dmin, stats[1].dmax), (15.0, 15.0));
+}
+
+// ── 89. Whole-volume statistics from typed data ──────────────────────────────
+
+#[test]
+fn data_view_stats_every_mode() {
+    assert_eq!(DataView::Int8(&[-2, 2]).stats(), (-2.0, 2.0, 0.0, 2.0));
+    assert_eq!(DataView::Uint16(&[10, 20, 30]).stats().2, 20.0);
+    assert_eq!(DataView::Float32(&[]).stats(), (0.0, -1.0, -2.0, -1.0));
+
+    let c = [
+        Int16Complex { real: 3, imag: 4 },
+        Int16Complex { real: 0, imag: -1 },
+    ];
+    assert_eq!(DataView::Int16Complex(&c).stats(), (1.0, 5.0, 3.0, 2.0));
+
+    // Two rows of three nibbles; the high nibble of each row's second byte
+    // is padding.
+    let mut header = HeaderBuilder::new()
+        .shape([3, 2, 1])
+        .mode::<f32>()
+        .build()
+        .unwrap();
+    header.mode = Mode::Packed4Bit.as_i32();
+    let mut bytes = MrcBuf::new(header).unwrap().to_bytes().unwrap();
+    let n = bytes.len();
+    bytes[n - 4..].copy_from_slice(&[0x21, 0xF3, 0x54, 0xF6]);
+    let buf = MrcBuf::from_reader(&Reader::from_bytes(bytes).unwrap()).unwrap();
+    let (dmin, dmax, dmean, rms) = buf.stats();
+    assert_eq!((dmin, dmax, dmean), (1.0, 6.0, 3.5));
+    assert!((rms - (17.5f32 / 6.0).sqrt()).abs() < 1e-6);
+    assert_eq!(buf.data().stats().1, 15.0);
+}
+
+#[cfg(feature = "f16")]
+#[test]
+fn data_view_stats_f16() {
+    let v = [f16::from_f32(1.0), f16::from_f32(-3.0)];
+    assert_eq!(DataView::Float16(&v).stats(), (-3.0, 1.0, -1.0, 2.0));
+}
+
+// ── 90. Percentiles and contour levels ───────────────────────────────────────
+
+#[test]
+fn reader_percentile_matches_exact() {
+    let tmp = TempMrc::new("reader_percentile");
+    let mut data: Vec<f32> = (0..1000).map(|v| v as f32).collect();
+    data[17] = f32::NAN;
+    write_as(tmp.path(), &data, [10, 10, 10]).unwrap();
+    let reader = Reader::open(tmp.path()).unwrap();
+    let vol = reader.volume::<f32>().unwrap();
+
+    for p in [0.0, 1.0, 50.0, 99.5, 100.0] {
+        let exact = vol.percentile(p).unwrap() as f32;
+        let approx = reader.percentile(p).unwrap();
+        assert!((approx - exact).abs() < 1.0, "p{p}: {approx} vs {exact}");
+    }
+    assert_eq!(reader.percentile(0.0).unwrap(), 0.0);
+    assert_eq!(reader.percentile(100.0).unwrap(), 999.0);
+    assert!(reader.percentile(-1.0).is_err());
+    assert!(reader.percentile(100.5).is_err());
+}
+
+#[test]
+fn suggest_contour_level_is_mean_plus_sigma_rms() {
+    let tmp = TempMrc::new("contour_level");
+    let data: Vec<f32> = (0..64)
+        .map(|i| if i % 2 == 0 { 1.0 } else { 3.0 })
+        .collect();
+    write_as(tmp.path(), &data, [4, 4, 4]).unwrap();
+    let reader = Reader::open(tmp.path()).unwrap();
+    assert_eq!(reader.suggest_contour_level(0.0).unwrap(), 2.0);
+    assert_eq!(reader.suggest_contour_level(1.5).unwrap(), 3.5);
+
+    let tmp = TempMrc::new("contour_level_complex");
+    let c = vec![
+        Float32Complex {
+            real: 1.0,
+            imag: 0.0
+        };
+        8
+    ];
+    write_as(tmp.path(), &c, [2, 2, 2]).unwrap();
+    let reader = Reader::open(tmp.path()).unwrap();
+    assert!(matches!(
+        reader.suggest_contour_level(1.0),
+        Err(Error::UnsupportedMode)
+    ));
+}
+
+// ── 91. Non-finite values ────────────────────────────────────────────────────
+
+#[test]
+fn find_and_replace_non_finite() {
+    let tmp = TempMrc::new("non_finite");
+    let data = [
+        1.0f32,
+        f32::NAN,
+        2.0,
+        f32::INFINITY,
+        -3.0,
+        f32::NEG_INFINITY,
+    ];
+    write_as(tmp.path(), &data, [3, 2, 1]).unwrap();
+    let reader = Reader::open(tmp.path()).unwrap();
+    let mut buf = MrcBuf::from_reader(&reader).unwrap();
+
+    assert_eq!(buf.data().find_non_finite(), [1, 3, 5]);
+    assert_eq!(buf.replace_non_finite(0.0), 3);
+    assert!(buf.data().find_non_finite().is_empty());
+    assert_eq!(
+        buf.data_as::<f32>().unwrap(),
+        [1.0, 0.0, 2.0, 0.0, -3.0, 0.0]
+    );
+    assert_eq!(buf.stats().0, -3.0);
+    assert_eq!(buf.replace_non_finite(0.0), 0);
+}
+
+#[test]
+fn non_finite_complex_and_integer_modes() {
+    let mut data = OwnedData::Float32Complex(vec![
+        Float32Complex {
+            real: 1.0,
+            imag: f32::NAN,
+        },
+        Float32Complex {
+            real: 2.0,
+            imag: 3.0,
+        },
+    ]);
+    assert_eq!(DataView::from(&data).find_non_finite(), [0]);
+    assert_eq!(data.replace_non_finite(-1.0), 1);
+    assert_eq!(
+        DataView::from(&data).as_float32_complex().unwrap()[0],
+        Float32Complex {
+            real: 1.0,
+            imag: -1.0
+        }
+    );
+
+    let mut ints = OwnedData::Int16(vec![1, 2]);
+    assert!(DataView::from(&ints).find_non_finite().is_empty());
+    assert_eq!(ints.replace_non_finite(0.0), 0);
+}
+
+// ── 92. Fill and clear ───────────────────────────────────────────────────────
+
+#[test]
+fn mrc_buf_fill_per_mode() {
+    let header = |mode: Mode| {
+        let mut h = HeaderBuilder::new()
+            .shape([3, 2, 2])
+            .mode::<f32>()
+            .build()
+            .unwrap();
+        h.mode = mode.as_i32();
+        h
+    };
+
+    let mut buf = MrcBuf::new(header(Mode::Uint16)).unwrap();
+    buf.fill(-5.0);
+    assert_eq!(buf.data_as::<u16>().unwrap(), [0; 12]);
+    buf.fill(70000.0);
+    assert!(buf.data_as::<u16>().unwrap().iter().all(|&v| v == u16::MAX));
+    buf.fill_section(1, 7.9).unwrap();
+    assert_eq!(buf.data_as::<u16>().unwrap()[5..7], [u16::MAX, 7]);
+    assert!(matches!(
+        buf.fill_section(2, 0.0),
+        Err(Error::BoundsError { .. })
+    ));
+    buf.zero();
+    assert_eq!(buf.stats(), (0.0, 0.0, 0.0, 0.0));
+
+    let mut buf = MrcBuf::new(header(Mode::Float32Complex)).unwrap();
+    buf.fill_section(0, 2.5).unwrap();
+    let c = buf.data_as::<Float32Complex>().unwrap();
+    assert_eq!(
+        c[5],
+        Float32Complex {
+            real: 2.5,
+            imag: 0.0
+        }
+    );
+    assert_eq!(
+        c[6],
+        Float32Complex {
+            real: 0.0,
+            imag: 0.0
+        }
+    );
+
+    let mut buf = MrcBuf::new(header(Mode::Packed4Bit)).unwrap();
+    buf.fill_section(1, 20.0).unwrap();
+    let reader = Reader::from_bytes(buf.to_bytes().unwrap()).unwrap();
+    let nibbles = reader.read_volume_u8().unwrap().data;
+    assert_eq!(nibbles[..6], [0; 6]);
+    assert_eq!(nibbles[6..], [15; 6]);
+}
+
+// ── 93. Mode conversion with scaling ─────────────────────────────────────────
+
+#[test]
+fn mrc_buf_convert_to_with_scaling() {
+    let tmp = TempMrc::new("convert_to");
+    write_as(tmp.path(), &[-2.0f32, 0.0, 1.0, 2.0], [2, 2, 1]).unwrap();
+    let buf = MrcBuf::from_reader(&Reader::open(tmp.path()).unwrap()).unwrap();
+
+    let fit = buf
+        .convert_to(Mode::Int16, ScalingPolicy::FitRange)
+        .unwrap();
+    assert_eq!(fit.mode(), Mode::Int16);
+    assert_eq!(fit.data_as::<i16>().unwrap(), [-32768, -1, 16383, 32767]);
+    assert_eq!(fit.header().dmin, -32768.0);
+    assert!(
+        fit.header()
+            .get_labels()
+            .iter()
+            .any(|l| l.starts_with("mrc: mode 2 -> 1, x * "))
+    );
+
+    let back = fit
+        .convert_to(
+            Mode::Float32,
+            ScalingPolicy::Linear {
+                scale: 2.0 / 32767.5,
+                offset: 0.0,
+            },
+        )
+        .unwrap();
+    for (a, b) in back
+        .data_as::<f32>()
+        .unwrap()
+        .iter()
+        .zip([-2.0, 0.0, 1.0, 2.0])
+    {
+        assert!((a - b).abs() < 1e-3, "{a} vs {b}");
+    }
+
+    let clamped = buf.convert_to(Mode::Uint16, ScalingPolicy::None).unwrap();
+    assert_eq!(clamped.data_as::<u16>().unwrap(), [0, 0, 1, 2]);
+    assert_eq!(
+        clamped.header().get_labels(),
+        buf.header().get_labels(),
+        "identity mapping adds no label"
+    );
+
+    let nibbles = buf
+        .convert_to(Mode::Packed4Bit, ScalingPolicy::FitRange)
+        .unwrap();
+    let reader = Reader::from_bytes(nibbles.to_bytes().unwrap()).unwrap();
+    assert_eq!(reader.read_volume_u8().unwrap().data, [0, 8, 11, 15]);
+
+    assert!(matches!(
+        buf.convert_to(Mode::Float32Complex, ScalingPolicy::None),
+        Err(Error::UnsupportedMode)
+    ));
+}
+
+#[cfg(feature = "f16")]
+#[test]
+fn mrc_buf_convert_to_f16_round_trip() {
+    let tmp = TempMrc::new("convert_to_f16");
+    write_as(tmp.path(), &[0.5f32, -1.25, 3.0, 8.0], [2, 2, 1]).unwrap();
+    let buf = MrcBuf::from_reader(&Reader::open(tmp.path()).unwrap()).unwrap();
+    let half = buf
+        .convert_to(Mode::Float16, ScalingPolicy::FitRange)
+        .unwrap();
+    assert_eq!(half.data_as::<f16>().unwrap()[1], f16::from_f32(-1.25));
+    let full = half.convert_to(Mode::Float32, ScalingPolicy::None).unwrap();
+    assert_eq!(
+        full.data_as::<f32>().unwrap(),
+        buf.data_as::<f32>().unwrap()
+    );
+}
+
+// ── 94. Pad and crop ─────────────────────────────────────────────────────────
+
+#[test]
+fn pad_and_crop_keep_geometry() {
+    let tmp = TempMrc::new("pad_crop");
+    let data: Vec<f32> = (0..8).map(|v| v as f32 + 1.0).collect();
+    let mut w = create(tmp.path())
+        .shape([2, 2, 2])
+        .mode::<f32>()
+        .cell_lengths(3.0, 3.0, 3.0)
+        .origin([10.0, 20.0, 30.0])
+        .finish()
+        .unwrap();
+    w.write_block(&VoxelBlock::new([0, 0, 0], [2, 2, 2], data).unwrap())
+        .unwrap();
+    w.finalize().unwrap();
+    let buf = MrcBuf::from_reader(&Reader::open(tmp.path()).unwrap()).unwrap();
+
+    let padded = buf.pad_to([5, 4, 2], -1.0).unwrap();
+    let h = padded.header();
+    assert_eq!((h.nx, h.ny, h.nz), (5, 4, 2));
+    assert_eq!((h.mx, h.xlen), (5, 7.5));
+    assert_eq!([h.nxstart, h.nystart, h.nzstart], [-1, -1, 0]);
+    assert_eq!(h.origin, [8.5, 18.5, 30.0]);
+    assert_eq!(h.voxel_size(), buf.header().voxel_size());
+    let p = padded.data_as::<f32>().unwrap();
+    assert_eq!(p[..5], [-1.0; 5]);
+    assert_eq!(p[5..10], [-1.0, 1.0, 2.0, -1.0, -1.0]);
+    assert_eq!(h.dmin, -1.0);
+
+    let back = padded.crop_center([2, 2, 2]).unwrap();
+    assert_eq!(
+        back.data_as::<f32>().unwrap(),
+        buf.data_as::<f32>().unwrap()
+    );
+    assert_eq!(back.header().origin, buf.header().origin);
+    assert_eq!(back.header().nxstart, 0);
+
+    let corner = buf.crop_center([1, 1, 1]).unwrap();
+    assert_eq!(corner.data_as::<f32>().unwrap(), [8.0]);
+
+    assert!(buf.pad_to([1, 2, 2], 0.0).is_err());
+    assert!(buf.crop_center([3, 2, 2]).is_err());
+    assert!(buf.crop_center([0, 2, 2]).is_err());
+}
+
+// ── 95. Flip and rotate ──────────────────────────────────────────────────────
+
+fn xyz_buf(name: &str, shape: [usize; 3]) -> (TempMrc, MrcBuf) {
+    let tmp = TempMrc::new(name);
+    let n = shape.iter().product::<usize>();
+    let data: Vec<f32> = (0..n).map(|v| v as f32).collect();
+    let mut w = create(tmp.path())
+        .shape(shape)
+        .mode::<f32>()
+        .cell_lengths(
+            shape[0] as f32,
+            2.0 * shape[1] as f32,
+            3.0 * shape[2] as f32,
+        )
+        .finish()
+        .unwrap();
+    w.write_block(&VoxelBlock::new([0, 0, 0], shape, data).unwrap())
+        .unwrap();
+    w.finalize().unwrap();
+    let buf = MrcBuf::from_reader(&Reader::open(tmp.path()).unwrap()).unwrap();
+    (tmp, buf)
+}
+
+#[test]
+fn flip_mirrors_along_each_axis() {
+    let (_tmp, buf) = xyz_buf("flip", [3, 2, 2]);
+    let v =
+        |b: &MrcBuf, x: usize, y: usize, z: usize| b.data_as::<f32>().unwrap()[(z * 2 + y) * 3 + x];
+    for (axis, f) in [
+        (Axis::X, [2, 0, 0]),
+        (Axis::Y, [0, 1, 0]),
+        (Axis::Z, [0, 0, 1]),
+    ] {
+        let flipped = buf.flip(axis);
+        assert_eq!(v(&flipped, 0, 0, 0), v(&buf, f[0], f[1], f[2]), "{axis:?}");
+        assert_eq!(
+            flipped.flip(axis).data_as::<f32>().unwrap(),
+            buf.data_as::<f32>().unwrap()
+        );
+        assert_eq!(flipped.header().nx, 3);
+    }
+}
+
+#[test]
+fn rotate90_turns_data_and_geometry() {
+    let (_tmp, buf) = xyz_buf("rotate90", [3, 2, 1]);
+    // 0 1 2      3 0
+    // 3 4 5  ->  4 1
+    //            5 2
+    let turned = buf.rotate90(Axis::Z, 1);
+    let h = turned.header();
+    assert_eq!((h.nx, h.ny, h.nz), (2, 3, 1));
+    assert_eq!(
+        turned.data_as::<f32>().unwrap(),
+        [3.0, 0.0, 4.0, 1.0, 5.0, 2.0]
+    );
+    assert_eq!((h.mx, h.my), (2, 3));
+    assert_eq!(h.voxel_size(), [2.0, 1.0, 3.0]);
+
+    assert_eq!(
+        buf.rotate90(Axis::Z, -1).data_as::<f32>().unwrap(),
+        buf.rotate90(Axis::Z, 3).data_as::<f32>().unwrap()
+    );
+    assert_eq!(
+        buf.rotate90(Axis::Z, 2).data_as::<f32>().unwrap(),
+        buf.flip(Axis::X).flip(Axis::Y).data_as::<f32>().unwrap()
+    );
+    for axis in [Axis::X, Axis::Y, Axis::Z] {
+        let mut b = buf.clone();
+        for _ in 0..4 {
+            b = b.rotate90(axis, 1);
+        }
+        assert_eq!(b.data_as::<f32>().unwrap(), buf.data_as::<f32>().unwrap());
+        assert_eq!(b.header().voxel_size(), buf.header().voxel_size());
+    }
+    let about_x = buf.rotate90(Axis::X, 1);
+    assert_eq!(about_x.shape().ny, 1);
+    assert_eq!(about_x.shape().nz, 2);
+}
+
+// ── 96. Axis projections ─────────────────────────────────────────────────────
+
+#[test]
+fn project_along_each_axis() {
+    let tmp = TempMrc::new("project");
+    let mut data: Vec<f32> = (0..12).map(|v| v as f32).collect();
+    data[1] = f32::NAN;
+    write_as(tmp.path(), &data, [3, 2, 2]).unwrap();
+    let reader = Reader::open(tmp.path()).unwrap();
+
+    let z_sum = reader.project(Axis::Z, ProjectionKind::Sum).unwrap();
+    assert_eq!((z_sum.offset, z_sum.shape), ([0, 0, 0], [3, 2, 1]));
+    assert_eq!(z_sum.data[0], 6.0);
+    assert!(z_sum.data[1].is_nan());
+    assert_eq!(z_sum.data[2..], [10.0, 12.0, 14.0, 16.0]);
+
+    let z_max = reader.project(Axis::Z, ProjectionKind::Max).unwrap();
+    assert_eq!(z_max.data, [6.0, 7.0, 8.0, 9.0, 10.0, 11.0]);
+
+    let x_mean = reader.project(Axis::X, ProjectionKind::Mean).unwrap();
+    assert_eq!(x_mean.shape, [1, 2, 2]);
+    assert_eq!(x_mean.data[1..], [4.0, 7.0, 10.0]);
+
+    let y_max = reader.project(Axis::Y, ProjectionKind::Max).unwrap();
+    assert_eq!(y_max.shape, [3, 1, 2]);
+    assert_eq!(y_max.data, [3.0, 4.0, 5.0, 9.0, 10.0, 11.0]);
+}
+
+// ── 97. Canonical axis order ─────────────────────────────────────────────────
+
+#[test]
+fn to_canonical_order_matches_get_logical() {
+    let tmp = TempMrc::new("canonical_order");
+    let (nc, nr, ns) = (4, 3, 2);
+    let mut w = create(tmp.path())
+        .shape([nc, nr, ns])
+        .mode::<f32>()
+        .axis_mapping([3, 1, 2])
+        .nstart([10, 20, 30])
+        .finish()
+        .unwrap();
+    let data: Vec<f32> = (0..nc * nr * ns).map(|i| i as f32).collect();
+    w.write_block(&VoxelBlock::new([0, 0, 0], [nc, nr, ns], data).unwrap())
+        .unwrap();
+    w.finalize().unwrap();
+
+    let reader = Reader::open(tmp.path()).unwrap();
+    let vol = reader.volume::<f32>().unwrap();
+    let canonical = MrcBuf::from_reader(&reader).unwrap().to_canonical_order();
+    let h = canonical.header();
+    assert_eq!([h.mapc, h.mapr, h.maps], [1, 2, 3]);
+    assert_eq!([h.nx, h.ny, h.nz], [3, 2, 4]);
+    assert_eq!([h.nxstart, h.nystart, h.nzstart], [20, 30, 10]);
+
+    let reread = Reader::from_bytes(canonical.to_bytes().unwrap()).unwrap();
+    let flat = reread.volume::<f32>().unwrap();
+    for (x, y, z) in [(0, 0, 0), (2, 1, 3), (1, 0, 2)] {
+        assert_eq!(flat.get(x, y, z), vol.get_logical(x, y, z));
+    }
+    let again = canonical.to_canonical_order();
+    assert_eq!(
+        again.data_as::<f32>().unwrap(),
+        canonical.data_as::<f32>().unwrap()
+    );
+}
+
+// ── 98. map_voxels ───────────────────────────────────────────────────────────
+
+#[test]
+fn map_voxels_native_and_f32() {
+    let tmp = TempMrc::new("map_voxels");
+    let data: Vec<i16> = (0..5000).map(|v| (v % 100) as i16).collect();
+    write_as(tmp.path(), &data, [50, 50, 2]).unwrap();
+    let mut buf = MrcBuf::from_reader(&Reader::open(tmp.path()).unwrap()).unwrap();
+
+    buf.map_voxels(|v: i16| v * 2).unwrap();
+    assert_eq!(buf.data_as::<i16>().unwrap()[4999], 198);
+    assert!(matches!(
+        buf.map_voxels(|v: f32| v),
+        Err(Error::ModeMismatch { .. })
+    ));
+
+    buf.map_voxels_f32(|v| v * 1000.0 - 0.6).unwrap();
+    let got = buf.data_as::<i16>().unwrap();
+    assert_eq!(got[0], -1);
+    assert_eq!(got[1], 1999);
+    assert_eq!(got[4999], i16::MAX);
+
+    let mut c = MrcBuf::new(
+        HeaderBuilder::new()
+            .shape([2, 1, 1])
+            .mode::<Float32Complex>()
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+    assert!(matches!(
+        c.map_voxels_f32(|v| v),
+        Err(Error::UnsupportedMode)
+    ));
+    c.map_voxels(|z: Float32Complex| Float32Complex {
+        real: z.real + 1.0,
+        imag: -1.0,
+    })
+    .unwrap();
+    assert_eq!(c.stats().0, 2f32.sqrt());
+}
+
+#[test]
+fn map_voxels_f32_packed4bit_keeps_row_padding() {
+    let tmp = TempMrc::new("map_voxels_packed");
+    let mut w = create(tmp.path())
+        .shape([3, 2, 1])
+        .mode_raw(101)
+        .finish()
+        .unwrap();
+    w.write_u4_block(&VoxelBlock::new([0, 0, 0], [3, 2, 1], vec![1, 2, 3, 4, 5, 6]).unwrap())
+        .unwrap();
+    w.finalize().unwrap();
+    let mut buf = MrcBuf::from_reader(&Reader::open(tmp.path()).unwrap()).unwrap();
+    buf.map_voxels_f32(|v| v * 3.0).unwrap();
+    let reader = Reader::from_bytes(buf.to_bytes().unwrap()).unwrap();
+    assert_eq!(reader.read_volume_u8().unwrap().data, [3, 6, 9, 12, 15, 15]);
+}
+
+// ── 99. Parallel voxel operations ────────────────────────────────────────────
+
+#[cfg(feature = "parallel")]
+#[test]
+fn par_map_voxels_matches_serial_across_chunks() {
+    let header = HeaderBuilder::new()
+        .shape([1000, 300, 2])
+        .mode::<i16>()
+        .build()
+        .unwrap();
+    let mut serial = MrcBuf::new(header).unwrap();
+    for (i, v) in serial.data_as_mut::<i16>().unwrap().iter_mut().enumerate() {
+        *v = (i % 20_000) as i16 - 10_000;
+    }
+    let mut parallel = serial.clone();
+
+    serial.map_voxels(|v: i16| v.saturating_mul(3)).unwrap();
+    parallel
+        .par_map_voxels(|v: i16| v.saturating_mul(3))
+        .unwrap();
+    assert_eq!(
+        serial.data_as::<i16>().unwrap(),
+        parallel.data_as::<i16>().unwrap()
+    );
+
+    serial.map_voxels_f32(|v| v * 0.5 + 7.0).unwrap();
+    parallel.par_map_voxels_f32(|v| v * 0.5 + 7.0).unwrap();
+    assert_eq!(
+        serial.data_as::<i16>().unwrap(),
+        parallel.data_as::<i16>().unwrap()
+    );
+    assert!(matches!(
+        parallel.par_map_voxels(|v: f32| v),
+        Err(Error::ModeMismatch { .. })
+    ));
+}
+
+#[test]
+fn chunked_stats_match_exact_values() {
+    // 600 000 voxels spans several statistics chunks with `parallel`.
+    let n = 600_000usize;
+    let header = HeaderBuilder::new()
+        .shape([1000, 600, 1])
+        .mode::<i16>()
+        .build()
+        .unwrap();
+    let mut buf = MrcBuf::new(header).unwrap();
+    for (i, v) in buf.data_as_mut::<i16>().unwrap().iter_mut().enumerate() {
+        *v = (i % 1000) as i16;
+    }
+    let (min, max, mean, rms) = buf.stats();
+    assert_eq!((min, max), (0.0, 999.0));
+    assert!((mean - 499.5).abs() < 1e-3);
+    let expected_rms = ((1000.0f64 * 1000.0 - 1.0) / 12.0).sqrt() as f32;
+    assert!((rms - expected_rms).abs() < 1e-3);
+
+    let floats = buf
+        .convert_to(
+            Mode::Float32,
+            ScalingPolicy::Linear {
+                scale: 2.0,
+                offset: 1.0,
+            },
+        )
+        .unwrap();
+    let data = floats.data_as::<f32>().unwrap();
+    assert_eq!(data.len(), n);
+    assert_eq!((data[0], data[999], data[n - 1]), (1.0, 1999.0, 1999.0));
+    assert_eq!(floats.header().dmax, 1999.0);
+    assert!((floats.header().dmean - 1000.0).abs() < 1e-2);
+}
+
+// ── 100. Byte swapping ───────────────────────────────────────────────────────
+
+#[test]
+fn swap_bytes_in_place_swaps_each_complex_component() {
+    let foreign = if FileEndian::native() == FileEndian::LittleEndian {
+        FileEndian::BigEndian
+    } else {
+        FileEndian::LittleEndian
+    };
+    let mut c16 = [Int16Complex {
+        real: 0x0102i16.swap_bytes(),
+        imag: 0x0304i16.swap_bytes(),
+    }];
+    swap_bytes_in_place(&mut c16, foreign);
+    assert_eq!((c16[0].real, c16[0].imag), (0x0102, 0x0304));
+
+    let mut c32 = [Float32Complex {
+        real: f32::from_bits(1.5f32.to_bits().swap_bytes()),
+        imag: f32::from_bits((-2.0f32).to_bits().swap_bytes()),
+    }];
+    swap_bytes_in_place(&mut c32, foreign);
+    assert_eq!((c32[0].real, c32[0].imag), (1.5, -2.0));
+
+    let mut floats: Vec<f32> = (0..1000)
+        .map(|i| f32::from_bits((i as f32).to_bits().swap_bytes()))
+        .collect();
+    swap_bytes_in_place(&mut floats, foreign);
+    assert!(floats.iter().enumerate().all(|(i, &v)| v == i as f32));
+
+    let mut native = [7i16, -7];
+    swap_bytes_in_place(&mut native, FileEndian::native());
+    assert_eq!(native, [7, -7]);
+}
+
+#[test]
+fn foreign_endian_complex_file_round_trips() {
+    let foreign = if FileEndian::native() == FileEndian::LittleEndian {
+        FileEndian::BigEndian
+    } else {
+        FileEndian::LittleEndian
+    };
+    let tmp = TempMrc::new("foreign_complex");
+    let data = vec![
+        Int16Complex { real: 1, imag: -2 },
+        Int16Complex { real: 300, imag: 4 },
+    ];
+    let mut w = create(tmp.path())
+        .shape([2, 1, 1])
+        .mode::<Int16Complex>()
+        .endian(foreign)
+        .finish()
+        .unwrap();
+    w.write_block(&VoxelBlock::new([0, 0, 0], [2, 1, 1], data.clone()).unwrap())
+        .unwrap();
+    w.finalize().unwrap();
+
+    let raw = std::fs::read(tmp.path()).unwrap();
+    let real = match foreign {
+        FileEndian::BigEndian => 1i16.to_be_bytes(),
+        FileEndian::LittleEndian => 1i16.to_le_bytes(),
+    };
+    assert_eq!(raw[1024..1026], real);
+
+    let reader = Reader::open(tmp.path()).unwrap();
+    assert_eq!(reader.endian(), foreign);
+    match reader.read_volume().unwrap().data() {
+        DataView::Int16Complex(v) => assert_eq!(v, &data[..]),
+        other => panic!("expected Int16Complex, got {other:?}"),
+    }
+}
+
+#[test]
+fn to_native_endian_copies_foreign_mapped_file() {
+    let foreign = if FileEndian::native() == FileEndian::LittleEndian {
+        FileEndian::BigEndian
+    } else {
+        FileEndian::LittleEndian
+    };
+    let tmp = TempMrc::new("to_native_endian");
+    let data: Vec<f32> = (0..24).map(|i| i as f32 * 0.5 - 3.0).collect();
+    let mut w = create(tmp.path())
+        .shape([4, 3, 2])
+        .mode::<f32>()
+        .endian(foreign)
+        .finish()
+        .unwrap();
+    w.write_block(&VoxelBlock::new([0, 0, 0], [4, 3, 2], data.clone()).unwrap())
+        .unwrap();
+    w.finalize().unwrap();
+
+    let reader = Reader::open(tmp.path()).unwrap();
+    let mut buf = reader.to_native_endian().unwrap();
+    assert_eq!(buf.data_as::<f32>().unwrap(), &data[..]);
+    assert_eq!(buf.header().detect_endian(), FileEndian::LittleEndian);
+
+    buf.data_as_mut::<f32>().unwrap()[0] = 100.0;
+    let back = Reader::from_bytes(buf.to_bytes().unwrap()).unwrap();
+    assert_eq!(
+        back.voxel_accessor().iter_f32().take(2).collect::<Vec<_>>(),
+        [100.0, -2.5]
+    );
+}
+
+// ── 101. Serializing to any writer ───────────────────────────────────────────
+
+#[test]
+fn write_into_streams_the_same_bytes_as_to_bytes() {
+    // Over one encode chunk, so the chunked path is covered.
+    let header = HeaderBuilder::new()
+        .shape([600, 500, 1])
+        .mode::<f32>()
+        .build()
+        .unwrap();
+    let mut buf = MrcBuf::new(header).unwrap();
+    for (i, v) in buf.data_as_mut::<f32>().unwrap().iter_mut().enumerate() {
+        *v = i as f32;
+    }
+    let mut streamed = std::io::Cursor::new(Vec::new());
+    buf.write_into(&mut streamed).unwrap();
+    let streamed = streamed.into_inner();
+    assert_eq!(streamed, buf.to_bytes().unwrap());
+
+    let reader = Reader::from_bytes(streamed.clone()).unwrap();
+    assert_eq!(reader.voxel_accessor().get_f32(299_999), Some(299_999.0));
+    let mut again = Vec::new();
+    reader.write_into(&mut again).unwrap();
+    assert_eq!(again, streamed);
+}
+
+#[test]
+fn write_into_checks_header_and_reports_writer_errors() {
+    struct Failing;
+    impl std::io::Write for Failing {
+        fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("disk full"))
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+    let header = HeaderBuilder::new()
+        .shape([2, 2, 1])
+        .mode::<i16>()
+        .build()
+        .unwrap();
+    let buf = MrcBuf::new(header).unwrap();
+    assert!(matches!(buf.write_into(&mut Failing), Err(Error::Io(_))));
+
+    let mut empty_header = Header::new();
+    (empty_header.nx, empty_header.ny, empty_header.nz) = (2, 2, 0);
+    (empty_header.mx, empty_header.my, empty_header.mz) = (2, 2, 0);
+    let empty = MrcBuf::new(empty_header).unwrap();
+    let mut out = Vec::new();
+    assert!(matches!(
+        empty.write_into(&mut out),
+        Err(Error::InvalidHeaderDetailed(_))
+    ));
+    assert!(out.is_empty());
+}
+
+// ── 102. Header statistics after edits ───────────────────────────────────────
+
+#[test]
+fn update_header_stats_follows_edits() {
+    let header = HeaderBuilder::new()
+        .shape([3, 2, 1])
+        .mode::<i16>()
+        .build()
+        .unwrap();
+    let mut buf = MrcBuf::new(header).unwrap();
+    buf.data_as_mut::<i16>()
+        .unwrap()
+        .copy_from_slice(&[1, 2, 3, 4, 5, 6]);
+    assert_ne!(buf.header().dmax, 6.0);
+
+    buf.update_header_stats();
+    let h = *buf.header();
+    assert_eq!((h.dmin, h.dmax, h.dmean), (1.0, 6.0, 3.5));
+    assert!((h.rms - (35.0f32 / 12.0).sqrt()).abs() < 1e-6);
+
+    buf.map_voxels(|v: i16| -v).unwrap();
+    assert_eq!(buf.header().dmin, 1.0);
+    buf.update_header_stats();
+    assert_eq!((buf.header().dmin, buf.header().dmax), (-6.0, -1.0));
+
+    let reader = Reader::from_bytes(buf.to_bytes().unwrap()).unwrap();
+    assert_eq!(reader.header().dmean, -3.5);
+}
+
+// ── 103. Raw blocks ──────────────────────────────────────────────────────────
+
+#[test]
+fn raw_block_decodes_and_encodes_in_its_byte_order() {
+    let mut be = RawBlock::new(vec![0; 8], Mode::Float32, FileEndian::BigEndian).unwrap();
+    assert_eq!(be.len(), 2);
+    be.set_f32(&[1.0, -0.5]).unwrap();
+    assert_eq!(be.bytes()[..4], 1.0f32.to_be_bytes());
+    assert_eq!(be.as_f32().unwrap(), [1.0, -0.5]);
+
+    let le = RawBlock::new(
+        be.as_f32()
+            .unwrap()
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect(),
+        Mode::Float32,
+        FileEndian::LittleEndian,
+    )
+    .unwrap();
+    assert_eq!(le.decode::<f32>().unwrap(), be.decode::<f32>().unwrap());
+
+    let mut complex = RawBlock::new(vec![0; 4], Mode::Int16Complex, FileEndian::BigEndian).unwrap();
+    complex
+        .encode(&[Int16Complex { real: 1, imag: -1 }])
+        .unwrap();
+    assert_eq!(complex.bytes(), [0x00, 0x01, 0xFF, 0xFF]);
+}
+
+#[test]
+fn raw_block_rejects_wrong_mode_and_length() {
+    assert!(matches!(
+        RawBlock::new(vec![0; 3], Mode::Int16, FileEndian::LittleEndian),
+        Err(Error::TypeMismatch {
+            expected: 2,
+            actual: 3
+        })
+    ));
+    let mut block = RawBlock::new(vec![0; 4], Mode::Int16, FileEndian::LittleEndian).unwrap();
+    assert!(matches!(block.as_f32(), Err(Error::ModeMismatch { .. })));
+    assert!(matches!(
+        block.set_i16(&[1, 2, 3]),
+        Err(Error::BlockShapeMismatch {
+            expected: 2,
+            actual: 3
+        })
+    ));
+
+    let packed = RawBlock::new(vec![0x21], Mode::Packed4Bit, FileEndian::LittleEndian).unwrap();
+    assert_eq!(packed.len(), 2);
+    assert_eq!(packed.into_bytes(), [0x21]);
+}
+
+// ── 104. Per-value codec traits ──────────────────────────────────────────────
+
+fn round_trip<T: Voxel + DecodeFromFile + EncodeToFile + PartialEq + std::fmt::Debug>(
+    value: T,
+    endian: FileEndian,
+) -> Vec<u8> {
+    let mut bytes = vec![0u8; std::mem::size_of::<T>()];
+    value.encode(endian, &mut bytes);
+    assert_eq!(T::decode(endian, &bytes), value);
+    bytes
+}
+
+#[test]
+fn decode_from_file_and_encode_to_file_follow_endian() {
+    assert_eq!(round_trip(-2i8, FileEndian::BigEndian), [0xFE]);
+    assert_eq!(round_trip(0x0102i16, FileEndian::BigEndian), [1, 2]);
+    assert_eq!(round_trip(0x0102u16, FileEndian::LittleEndian), [2, 1]);
+    assert_eq!(
+        round_trip(1.5f32, FileEndian::BigEndian),
+        1.5f32.to_be_bytes()
+    );
+    assert_eq!(
+        round_trip(Int16Complex { real: 1, imag: 2 }, FileEndian::BigEndian),
+        [0, 1, 0, 2]
+    );
+    #[cfg(feature = "f16")]
+    assert_eq!(
+        round_trip(mrc::f16::from_f32(1.0), FileEndian::BigEndian),
+        [0x3C, 0x00]
+    );
+
+    // Only the leading bytes are read.
+    assert_eq!(u16::decode(FileEndian::BigEndian, &[0, 7, 99]), 7);
+}
+
+// ── 105. Complex iterators ───────────────────────────────────────────────────
+
+#[test]
+fn complex_iterators_read_fft_output() {
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    let tmp = TempMrc::new("complex_iterators");
+    let data = vec![
+        Float32Complex {
+            real: 3.0,
+            imag: 4.0,
+        },
+        Float32Complex {
+            real: 0.0,
+            imag: -2.0,
+        },
+        Float32Complex {
+            real: -1.0,
+            imag: 0.0,
+        },
+    ];
+    write_as(tmp.path(), &data, [3, 1, 1]).unwrap();
+    let reader = Reader::open(tmp.path()).unwrap();
+    let block = reader.read_volume().unwrap();
+
+    let pairs: Vec<_> = block.data().iter_complex().unwrap().collect();
+    assert_eq!(pairs, [(3.0, 4.0), (0.0, -2.0), (-1.0, 0.0)]);
+    let magnitudes: Vec<_> = block.data().iter_magnitude().unwrap().collect();
+    assert_eq!(magnitudes, [5.0, 2.0, 1.0]);
+    let phases: Vec<_> = block.data().iter_phase().unwrap().collect();
+    assert_eq!(phases, [4f32.atan2(3.0), -FRAC_PI_2, PI]);
+
+    let ints = [Int16Complex { real: -3, imag: 4 }];
+    let view = DataView::Int16Complex(&ints);
+    assert_eq!(view.iter_magnitude().unwrap().collect::<Vec<_>>(), [5.0]);
+    assert!(matches!(
+        DataView::Float32(&[1.0]).iter_complex(),
+        Err(Error::UnsupportedMode)
+    ));
+}
@@ -0,0 +1,44 @@
+"""Regenerate the mrcfile golden files in this directory.
+
+Requires the Python ``mrcfile`` and ``numpy`` packages::
+
+    python tests/data/mrcfile/generate.py
+
+``mrcfile`` stamps the first label with the local time of creation; the
+clock is pinned to 2020-01-01 00:00:00 UTC (``mrc::mrcfile_compat::vectors::
+CREATED_UNIX_SECS``) so the output is reproducible.
+"""
+
+import os
+import time
+
+import mrcfile
+import numpy as np
+
+CREATED_UNIX_SECS = 1_577_836_800
+
+_strftime = time.strftime
+
+
+def _pinned_strftime(fmt, t=None):
+    return _strftime(fmt, time.gmtime(CREATED_UNIX_SECS) if t is None else t)
+
+
+def main():
+    here = os.path.dirname(os.path.abspath(__file__))
+    time.strftime = _pinned_strftime
+    try:
+        path = os.path.join(here, "float32_volume.mrc")
+        with mrcfile.new(path, overwrite=True) as m:
+            m.set_data(np.arange(12, dtype=np.float32).reshape(2, 2, 3))
+
+        path = os.path.join(here, "int16_image_stack.mrc")
+        with mrcfile.new(path, overwrite=True) as m:
+            m.set_data(np.arange(-6, 6, dtype=np.int16).reshape(3, 2, 2))
+            m.set_image_stack()
+    finally:
+        time.strftime = _strftime
+
+
+if __name__ == "__main__":
+    main()